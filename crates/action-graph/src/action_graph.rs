@@ -1,7 +1,8 @@
 use crate::action_graph_error::ActionGraphError;
-use graph_cycles::Cycles;
 use moon_action::ActionNode;
 use moon_common::{color, is_test_env};
+use moon_graph_utils::{find_elementary_cycles, format_graph_as_graphml, format_graph_as_mermaid};
+use moon_workspace_graph::WorkspaceGraph;
 use petgraph::dot::{Config, Dot};
 use petgraph::prelude::*;
 use petgraph::visit::{IntoEdgeReferences, IntoNodeReferences};
@@ -14,13 +15,19 @@ pub type GraphType = DiGraph<ActionNode, ()>;
 
 pub struct ActionGraph {
     graph: GraphType,
+
+    /// Used for locating where dependencies were declared when a cycle is detected.
+    workspace_graph: WorkspaceGraph,
 }
 
 impl ActionGraph {
-    pub fn new(graph: GraphType) -> Self {
+    pub fn new(graph: GraphType, workspace_graph: WorkspaceGraph) -> Self {
         debug!("Creating action graph");
 
-        ActionGraph { graph }
+        ActionGraph {
+            graph,
+            workspace_graph,
+        }
     }
 
     pub fn create_iter(&self, indices: Vec<NodeIndex>) -> ActionGraphIter {
@@ -53,27 +60,8 @@ impl ActionGraph {
 
     pub fn sort_topological(&self) -> miette::Result<Vec<NodeIndex>> {
         // Detect any cycles first
-        let mut cycle: Vec<NodeIndex> = vec![];
-
-        self.graph.visit_cycles(|_, c| {
-            cycle.extend(c);
-            std::ops::ControlFlow::Break(())
-        });
-
-        if !cycle.is_empty() {
-            return Err(ActionGraphError::CycleDetected(
-                cycle
-                    .into_iter()
-                    .map(|index| {
-                        self.graph
-                            .node_weight(index)
-                            .map(|n| n.label())
-                            .unwrap_or_else(|| "(unknown)".into())
-                    })
-                    .collect::<Vec<_>>()
-                    .join(" → "),
-            )
-            .into());
+        if let Some(cycle) = find_elementary_cycles(&self.graph, 1).into_iter().next() {
+            return Err(self.create_cycle_error(cycle).into());
         }
 
         // Then sort topologically
@@ -90,16 +78,77 @@ impl ActionGraph {
             }
             // For some reason the topo sort can detect a cycle,
             // that wasn't previously detected, so error...
-            Err(cycle) => Err(ActionGraphError::CycleDetected(
-                self.graph
-                    .node_weight(cycle.node_id())
-                    .map(|n| n.label())
-                    .unwrap_or_else(|| "(unknown)".into()),
-            )
+            Err(cycle) => Err(ActionGraphError::CycleDetected {
+                path: self.get_node_label(cycle.node_id()),
+                declarations: None,
+            }
             .into()),
         }
     }
 
+    fn get_node_label(&self, index: NodeIndex) -> String {
+        self.graph
+            .node_weight(index)
+            .map(|n| n.label())
+            .unwrap_or_else(|| "(unknown)".into())
+    }
+
+    fn create_cycle_error(&self, cycle: Vec<NodeIndex>) -> ActionGraphError {
+        let mut path = vec![];
+        let mut declarations = vec![];
+
+        for (i, index) in cycle.iter().enumerate() {
+            let dep_index = cycle[(i + 1) % cycle.len()];
+
+            path.push(self.get_node_label(*index));
+
+            // Only task dependencies are declared by the user in configs
+            let (Some(ActionNode::RunTask(node)), Some(ActionNode::RunTask(dep_node))) = (
+                self.graph.node_weight(*index),
+                self.graph.node_weight(dep_index),
+            ) else {
+                continue;
+            };
+
+            let Ok(edge) = self
+                .workspace_graph
+                .locate_task_dependency(&node.target, &dep_node.target)
+            else {
+                continue;
+            };
+
+            let location = match (edge.file, edge.line) {
+                (Some(file), Some(line)) => format!("{file}:{line}"),
+                (Some(file), None) => file,
+                _ => "inherited".into(),
+            };
+
+            declarations.push(format!(
+                "{} → {} ({})",
+                color::label(edge.source),
+                color::label(edge.target),
+                color::file(location)
+            ));
+        }
+
+        // Close the cycle by returning to the starting node
+        if let Some(first) = cycle.first() {
+            path.push(self.get_node_label(*first));
+        }
+
+        ActionGraphError::CycleDetected {
+            path: path.join(" → "),
+            declarations: if declarations.is_empty() {
+                None
+            } else {
+                Some(format!(
+                    "Dependencies declared at:\n  {}",
+                    declarations.join("\n  ")
+                ))
+            },
+        }
+    }
+
     pub fn to_dot(&self) -> String {
         type DotGraph = DiGraph<String, ()>;
 
@@ -136,6 +185,14 @@ impl ActionGraph {
 
         format!("{dot:?}")
     }
+
+    pub fn to_graphml(&self) -> String {
+        format_graph_as_graphml(&self.labeled_graph())
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        format_graph_as_mermaid(&self.labeled_graph())
+    }
 }
 
 pub struct ActionGraphIter<'graph> {
@@ -78,7 +78,7 @@ impl<'app> ActionGraphBuilder<'app> {
     }
 
     pub fn build(self) -> ActionGraph {
-        ActionGraph::new(self.graph)
+        ActionGraph::new(self.graph, self.workspace_graph.clone())
     }
 
     pub fn build_context(&mut self) -> ActionContext {
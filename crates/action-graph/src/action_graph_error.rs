@@ -5,6 +5,10 @@ use thiserror::Error;
 #[derive(Error, Debug, Diagnostic)]
 pub enum ActionGraphError {
     #[diagnostic(code(action_graph::cycle_detected))]
-    #[error("A dependency cycle has been detected for {}.", .0.style(Style::Label))]
-    CycleDetected(String),
+    #[error("A dependency cycle has been detected for {}.", .path.style(Style::Label))]
+    CycleDetected {
+        path: String,
+        #[help]
+        declarations: Option<String>,
+    },
 }
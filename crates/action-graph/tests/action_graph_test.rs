@@ -73,7 +73,6 @@ mod action_graph {
     use super::*;
 
     #[tokio::test]
-    #[should_panic(expected = "A dependency cycle has been detected for RunTask(deps:cycle2) →")]
     async fn errors_on_cycle() {
         let sandbox = create_sandbox("tasks");
         let container = ActionGraphContainer::new(sandbox.path()).await;
@@ -102,7 +101,12 @@ mod action_graph {
             )
             .unwrap();
 
-        builder.build().sort_topological().unwrap();
+        let error = builder.build().sort_topological().unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "A dependency cycle has been detected for RunTask(deps:cycle1) → RunTask(deps:cycle2) → RunTask(deps:cycle1)."
+        );
     }
 
     mod install_deps {
@@ -4,6 +4,7 @@ use crate::job_context::JobContext;
 use crate::job_dispatcher::JobDispatcher;
 use crate::subscribers::cleanup_subscriber::CleanupSubscriber;
 use crate::subscribers::console_subscriber::ConsoleSubscriber;
+use crate::subscribers::extensions_subscriber::{ExtensionsSubscriber, PipelineEventReceiver};
 use crate::subscribers::moonbase_subscriber::MoonbaseSubscriber;
 use crate::subscribers::remote_subscriber::RemoteSubscriber;
 use crate::subscribers::reports_subscriber::ReportsSubscriber;
@@ -14,6 +15,7 @@ use moon_action_graph::ActionGraph;
 use moon_api::Moonbase;
 use moon_app_context::AppContext;
 use moon_common::{color, is_ci, is_test_env};
+use moon_extension_plugin::ExtensionRegistry;
 use moon_toolchain_plugin::ToolchainRegistry;
 use moon_workspace_graph::WorkspaceGraph;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -41,6 +43,7 @@ pub struct ActionPipeline {
     app_context: Arc<AppContext>,
     action_context: Arc<ActionContext>,
     emitter: Arc<EventEmitter>,
+    extension_registry: Arc<ExtensionRegistry>,
     toolchain_registry: Arc<ToolchainRegistry>,
     workspace_graph: WorkspaceGraph,
 }
@@ -48,6 +51,7 @@ pub struct ActionPipeline {
 impl ActionPipeline {
     pub fn new(
         app_context: Arc<AppContext>,
+        extension_registry: Arc<ExtensionRegistry>,
         toolchain_registry: Arc<ToolchainRegistry>,
         workspace_graph: WorkspaceGraph,
     ) -> Self {
@@ -62,6 +66,7 @@ impl ActionPipeline {
             concurrency: num_cpus::get(),
             duration: None,
             emitter: Arc::new(EventEmitter::default()),
+            extension_registry,
             report_name: "runReport.json".into(),
             summarize: false,
             toolchain_registry,
@@ -357,13 +362,36 @@ impl ActionPipeline {
     }
 
     fn monitor_signals(&self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        let console = Arc::clone(&self.app_context.console);
+
         tokio::spawn(async move {
             debug!("Listening for ctrl+c signal");
 
-            if tokio::signal::ctrl_c().await.is_ok() {
-                debug!("Received ctrl+c signal, shutting down!");
+            let ctrl_c = tokio::signal::ctrl_c();
+            tokio::pin!(ctrl_c);
+
+            loop {
+                tokio::select! {
+                    result = &mut ctrl_c => {
+                        if result.is_ok() {
+                            debug!("Received ctrl+c signal, shutting down!");
 
-                cancel_token.cancel();
+                            cancel_token.cancel();
+                        }
+
+                        break;
+                    }
+                    // Reporters that own the terminal (like the TUI) receive
+                    // ctrl+c as a key press, so they request cancellation instead
+                    _ = sleep(Duration::from_millis(100)) => {
+                        if console.reporter.should_cancel() {
+                            debug!("Reporter requested cancellation, shutting down!");
+
+                            cancel_token.cancel();
+                            break;
+                        }
+                    }
+                };
             }
         })
     }
@@ -400,19 +428,38 @@ impl ActionPipeline {
 
         // For security and privacy purposes, only send webhooks from a CI environment
         if is_ci() || is_test_env() {
-            if let Some(webhook_url) = &self.app_context.workspace_config.notifier.webhook_url {
+            let webhooks = self.app_context.workspace_config.notifier.get_webhooks();
+
+            if !webhooks.is_empty() {
                 debug!(
-                    url = webhook_url,
+                    urls = ?webhooks.iter().filter_map(|hook| hook.url.as_deref()).collect::<Vec<_>>(),
                     "Subscribing webhook events ({} enabled)",
-                    color::property("notifier.webhookUrl"),
+                    color::property("notifier.webhooks"),
                 );
 
                 self.emitter
-                    .subscribe(WebhooksSubscriber::new(webhook_url))
+                    .subscribe(WebhooksSubscriber::new(webhooks))
                     .await;
             }
         }
 
+        let extension_ids = self.extension_registry.get_pipeline_subscriber_ids();
+
+        if !extension_ids.is_empty() {
+            debug!(
+                extension_ids = ?extension_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>(),
+                "Subscribing extension plugins ({} enabled)",
+                color::property("extensions.*.pipelineEvents"),
+            );
+
+            self.emitter
+                .subscribe(ExtensionsSubscriber::new(
+                    Arc::clone(&self.extension_registry) as Arc<dyn PipelineEventReceiver>,
+                    extension_ids,
+                ))
+                .await;
+        }
+
         if self.app_context.workspace_config.runner.auto_clean_cache {
             let lifetime = &self.app_context.workspace_config.runner.cache_lifetime;
 
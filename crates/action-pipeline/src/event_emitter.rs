@@ -111,6 +111,19 @@ impl Event<'_> {
             Event::WorkspaceSynced { .. } => "workspace.synced",
         }
     }
+
+    pub fn has_error(&self) -> bool {
+        match self {
+            Event::ActionCompleted { error, .. }
+            | Event::DependenciesInstalled { error, .. }
+            | Event::PipelineCompleted { error, .. }
+            | Event::ProjectSynced { error, .. }
+            | Event::TaskRan { error, .. }
+            | Event::ToolInstalled { error, .. }
+            | Event::WorkspaceSynced { error } => error.is_some(),
+            _ => false,
+        }
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,192 @@
+use crate::event_emitter::{Event, Subscriber};
+use async_trait::async_trait;
+use miette::IntoDiagnostic;
+use moon_common::color;
+use moon_extension_plugin::ExtensionRegistry;
+use moon_plugin::PluginId;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Loads extensions and delivers pipeline events to them.
+#[async_trait]
+pub trait PipelineEventReceiver: Send + Sync {
+    async fn load_extension(&self, id: &PluginId) -> miette::Result<()>;
+
+    async fn send_event(
+        &self,
+        id: &PluginId,
+        event: &str,
+        payload: &serde_json::Value,
+    ) -> miette::Result<()>;
+}
+
+#[async_trait]
+impl PipelineEventReceiver for ExtensionRegistry {
+    async fn load_extension(&self, id: &PluginId) -> miette::Result<()> {
+        self.load(id).await.map(|_| ())
+    }
+
+    async fn send_event(
+        &self,
+        id: &PluginId,
+        event: &str,
+        payload: &serde_json::Value,
+    ) -> miette::Result<()> {
+        self.load(id)
+            .await?
+            .on_pipeline_event(event, payload, self.create_context())
+            .await
+    }
+}
+
+pub struct ExtensionsSubscriber {
+    extension_ids: Vec<PluginId>,
+    receiver: Arc<dyn PipelineEventReceiver>,
+}
+
+impl ExtensionsSubscriber {
+    pub fn new(receiver: Arc<dyn PipelineEventReceiver>, extension_ids: Vec<PluginId>) -> Self {
+        Self {
+            extension_ids,
+            receiver,
+        }
+    }
+}
+
+#[async_trait]
+impl Subscriber for ExtensionsSubscriber {
+    async fn on_emit<'data>(&mut self, event: &Event<'data>) -> miette::Result<()> {
+        let event_type = event.get_type();
+        let payload = serde_json::to_value(event).into_diagnostic()?;
+        let mut failed_ids = vec![];
+
+        for id in &self.extension_ids {
+            // An extension that fails to load should not abort the pipeline,
+            // so skip it for this and all subsequent events
+            if let Err(error) = self.receiver.load_extension(id).await {
+                warn!(
+                    extension_id = id.as_str(),
+                    "Failed to load extension, it will not receive pipeline events: {error}",
+                );
+
+                failed_ids.push(id.to_owned());
+                continue;
+            }
+
+            // A failing extension should not abort the pipeline,
+            // so we only log the error and continue on
+            if let Err(error) = self.receiver.send_event(id, event_type, &payload).await {
+                warn!(
+                    extension_id = id.as_str(),
+                    "Failed to send pipeline event {} to extension: {error}",
+                    color::id(event_type),
+                );
+            }
+        }
+
+        if !failed_ids.is_empty() {
+            self.extension_ids.retain(|id| !failed_ids.contains(id));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moon_action::RunTaskNode;
+    use moon_task::Target;
+    use moon_toolchain::Runtime;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct TestReceiver {
+        events: Mutex<Vec<(String, String, serde_json::Value)>>,
+        loads: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl PipelineEventReceiver for TestReceiver {
+        async fn load_extension(&self, id: &PluginId) -> miette::Result<()> {
+            self.loads.lock().unwrap().push(id.to_string());
+
+            if id.as_str() == "broken" {
+                return Err(miette::miette!("Failed to load"));
+            }
+
+            Ok(())
+        }
+
+        async fn send_event(
+            &self,
+            id: &PluginId,
+            event: &str,
+            payload: &serde_json::Value,
+        ) -> miette::Result<()> {
+            self.events.lock().unwrap().push((
+                id.to_string(),
+                event.to_owned(),
+                payload.to_owned(),
+            ));
+
+            Ok(())
+        }
+    }
+
+    async fn emit_task_running(subscriber: &mut ExtensionsSubscriber) {
+        let target = Target::parse("app:build").unwrap();
+        let node = RunTaskNode::new(target.clone(), Runtime::system());
+
+        subscriber
+            .on_emit(&Event::TaskRunning {
+                node: &node,
+                target: &target,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sends_events_to_extensions() {
+        let receiver = Arc::new(TestReceiver::default());
+        let mut subscriber = ExtensionsSubscriber::new(
+            receiver.clone(),
+            vec![PluginId::raw("a"), PluginId::raw("b")],
+        );
+
+        emit_task_running(&mut subscriber).await;
+
+        let events = receiver.events.lock().unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].0, "a");
+        assert_eq!(events[0].1, "task.running");
+        assert_eq!(events[0].2["target"], "app:build");
+        assert_eq!(events[1].0, "b");
+    }
+
+    #[tokio::test]
+    async fn skips_extensions_that_fail_to_load() {
+        let receiver = Arc::new(TestReceiver::default());
+        let mut subscriber = ExtensionsSubscriber::new(
+            receiver.clone(),
+            vec![PluginId::raw("broken"), PluginId::raw("ok")],
+        );
+
+        emit_task_running(&mut subscriber).await;
+        emit_task_running(&mut subscriber).await;
+
+        assert_eq!(*receiver.loads.lock().unwrap(), ["broken", "ok", "ok"]);
+        assert_eq!(
+            receiver
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|event| event.0.as_str())
+                .collect::<Vec<_>>(),
+            ["ok", "ok"]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod cleanup_subscriber;
 pub mod console_subscriber;
+pub mod extensions_subscriber;
 pub mod moonbase_subscriber;
 pub mod remote_subscriber;
 pub mod reports_subscriber;
@@ -1,16 +1,23 @@
 use crate::event_emitter::{Event, Subscriber};
 use async_trait::async_trait;
+use moon_config::NotifierWebhookConfig;
 use moon_notifier::WebhooksNotifier;
+use std::time::Duration;
+use tokio::time::Instant;
 use tracing::debug;
 
+// Once the pipeline has completed, bound how long we wait on webhook
+// requests (and their retries), so that they don't stall the exit
+const COMPLETED_TIMEOUT: Duration = Duration::from_secs(15);
+
 pub struct WebhooksSubscriber {
-    notifier: WebhooksNotifier,
+    notifiers: Vec<WebhooksNotifier>,
 }
 
 impl WebhooksSubscriber {
-    pub fn new(url: &str) -> Self {
+    pub fn new(webhooks: Vec<NotifierWebhookConfig>) -> Self {
         WebhooksSubscriber {
-            notifier: WebhooksNotifier::new(url.to_owned()),
+            notifiers: webhooks.into_iter().map(WebhooksNotifier::new).collect(),
         }
     }
 }
@@ -18,12 +25,30 @@ impl WebhooksSubscriber {
 #[async_trait]
 impl Subscriber for WebhooksSubscriber {
     async fn on_emit<'data>(&mut self, event: &Event<'data>) -> miette::Result<()> {
-        self.notifier.notify(event.get_type(), event).await?;
+        let event_type = event.get_type();
+        let failed = event.has_error();
+        let completed = matches!(event, Event::PipelineCompleted { .. });
+
+        if completed {
+            let deadline = Instant::now() + COMPLETED_TIMEOUT;
+
+            for notifier in &mut self.notifiers {
+                notifier.set_deadline(deadline);
+            }
+        }
+
+        for notifier in &mut self.notifiers {
+            if notifier.should_notify(event_type, failed) {
+                notifier.notify(event_type, event).await?;
+            }
+        }
 
-        if matches!(event, Event::PipelineCompleted { .. }) {
+        if completed {
             debug!("Waiting for webhook requests to finish");
 
-            self.notifier.wait_for_requests().await;
+            for notifier in &mut self.notifiers {
+                notifier.wait_for_requests().await;
+            }
         }
 
         Ok(())
@@ -7,6 +7,7 @@ use moon_action::{Action, ActionStatus, Operation};
 use moon_action_context::ActionContext;
 use moon_app_context::AppContext;
 use moon_common::color;
+use moon_project_constraints::enforce_dependency_versions;
 use moon_remote::RemoteService;
 use moon_toolchain_plugin::ToolchainRegistry;
 use moon_workspace_graph::WorkspaceGraph;
@@ -62,6 +63,32 @@ pub async fn sync_workspace(
         }));
     }
 
+    if app_context
+        .workspace_config
+        .constraints
+        .dependency_versions
+        .enabled
+    {
+        debug!(
+            "Checking external dependency versions ({} enabled)",
+            color::property("constraints.dependencyVersions.enabled"),
+        );
+
+        let app_context = Arc::clone(&app_context);
+        let workspace_graph = workspace_graph.clone();
+
+        operation_futures.push(task::spawn(async move {
+            let op = Operation::sync_operation("Dependency versions").track(|| {
+                enforce_dependency_versions(
+                    &app_context.workspace_config.constraints.dependency_versions,
+                    workspace_graph.projects.get_all_unexpanded(),
+                )
+            })?;
+
+            Ok(vec![op])
+        }));
+    }
+
     if app_context.workspace_config.codeowners.sync_on_run {
         debug!(
             "Syncing code owners ({} enabled)",
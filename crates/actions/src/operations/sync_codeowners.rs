@@ -2,6 +2,7 @@ use moon_app_context::AppContext;
 use moon_codeowners::{CodeownersGenerator, CodeownersHash};
 use moon_config::CodeownersOrderBy;
 use moon_workspace_graph::WorkspaceGraph;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tracing::instrument;
 
@@ -28,6 +29,23 @@ pub async fn sync_codeowners(
     // Generate a hash for the codeowners file
     let mut codeowners_hash = CodeownersHash::new(&app_context.workspace_config.codeowners);
 
+    // Aggregate custom groups, which must be defined before they are used
+    let mut custom_groups = BTreeMap::from_iter(
+        app_context
+            .workspace_config
+            .codeowners
+            .custom_groups
+            .clone(),
+    );
+
+    for project in &projects {
+        if !project.config.owners.paths.is_empty() {
+            custom_groups.extend(project.config.owners.custom_groups.clone());
+        }
+    }
+
+    generator.add_custom_groups(&custom_groups)?;
+
     if !app_context
         .workspace_config
         .codeowners
@@ -17,9 +17,10 @@ use crate::commands::query::QueryCommands;
 use crate::commands::run::RunArgs;
 use crate::commands::sync::SyncCommands;
 use crate::commands::task::TaskArgs;
+use crate::commands::templates::TemplatesCommands;
 use crate::systems::bootstrap;
 use clap::builder::styling::{Color, Style, Styles};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use moon_cache::CacheMode;
 use moon_codegen::TemplatesArgs;
 use moon_common::consts::BIN_NAME;
@@ -139,7 +140,13 @@ pub enum Commands {
         name = "templates",
         about = "List all templates that are available for code generation."
     )]
-    Templates(TemplatesArgs),
+    Templates {
+        #[command(subcommand)]
+        command: Option<TemplatesCommands>,
+
+        #[command(flatten)]
+        args: TemplatesArgs,
+    },
 
     // RUNNER
 
@@ -244,6 +251,16 @@ fn create_styles() -> Styles {
         .valid(fg(ColorType::Green))
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+#[value(rename_all = "lowercase")]
+pub enum UiMode {
+    /// Stream task output sequentially to the console.
+    #[default]
+    Default,
+    /// Render an interactive terminal dashboard while running tasks.
+    Tui,
+}
+
 #[derive(Clone, Debug, Parser)]
 #[command(
     bin_name = BIN_NAME,
@@ -321,6 +338,16 @@ pub struct Cli {
     )]
     pub quiet: bool,
 
+    #[arg(
+        long,
+        global = true,
+        env = "MOON_UI",
+        value_enum,
+        help = "Interface to render task output with",
+        default_value_t
+    )]
+    pub ui: UiMode,
+
     #[command(subcommand)]
     pub command: Commands,
 }
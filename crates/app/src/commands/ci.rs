@@ -3,24 +3,38 @@ use crate::components::run_action_pipeline;
 use crate::queries::touched_files::{query_touched_files, QueryTouchedFilesOptions};
 use crate::session::CliSession;
 use ci_env::CiOutput;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use moon_action_context::ActionContext;
 use moon_action_graph::{ActionGraph, RunRequirements};
 use moon_affected::{DownstreamScope, UpstreamScope};
 use moon_common::path::WorkspaceRelativePathBuf;
 use moon_console::Console;
-use moon_task::TargetLocator;
+use moon_task::{Target, TargetLocator};
+use moon_task_runner::TaskRunCacheState;
 use moon_workspace_graph::WorkspaceGraph;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use starbase::AppResult;
 use starbase_styles::color;
+use starbase_utils::json;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 type TargetList = Vec<TargetLocator>;
 
 const HEADING_PARALLELISM: &str = "Parallelism and distribution";
 
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+#[value(rename_all = "lowercase")]
+pub enum ShardStrategy {
+    /// Split targets into equally sized batches.
+    #[default]
+    Count,
+    /// Balance targets by their durations from previous runs, and keep
+    /// targets that share dependencies in the same job.
+    Duration,
+}
+
 #[derive(Args, Clone, Debug)]
 pub struct CiArgs {
     #[arg(help = "List of targets to run")]
@@ -37,6 +51,29 @@ pub struct CiArgs {
 
     #[arg(long = "jobTotal", help = "Total amount of jobs to run", help_heading = HEADING_PARALLELISM)]
     job_total: Option<usize>,
+
+    #[arg(
+        long = "shardStrategy",
+        value_enum,
+        default_value_t,
+        help = "Strategy to use when distributing targets across jobs",
+        help_heading = HEADING_PARALLELISM
+    )]
+    shard_strategy: ShardStrategy,
+
+    #[arg(
+        long = "shardTimings",
+        help = "Path to a JSON file of target durations (in milliseconds), overriding cached durations",
+        help_heading = HEADING_PARALLELISM
+    )]
+    shard_timings: Option<PathBuf>,
+
+    #[arg(
+        long = "shardDryRun",
+        help = "Print the distribution of targets across all jobs without running tasks",
+        help_heading = HEADING_PARALLELISM
+    )]
+    shard_dry_run: bool,
 }
 
 struct CiConsole {
@@ -161,36 +198,307 @@ async fn gather_potential_targets(
     Ok(targets)
 }
 
+/// Split targets into contiguous batches of equal size.
+fn shard_targets_by_count(targets: TargetList, job_total: usize) -> Vec<TargetList> {
+    let batch_size = targets.len().div_ceil(job_total).max(1);
+    let mut shards = targets
+        .chunks(batch_size)
+        .map(|chunk| chunk.to_vec())
+        .collect::<Vec<_>>();
+
+    shards.resize_with(job_total, Vec::new);
+    shards
+}
+
+/// Distribute groups of targets across jobs, by always placing the next
+/// longest group into the job with the lowest total duration.
+fn shard_targets_by_duration(
+    groups: Vec<Vec<(TargetLocator, u128)>>,
+    job_total: usize,
+) -> Vec<TargetList> {
+    let mut shards = vec![(TargetList::new(), 0); job_total];
+    let total_duration = groups
+        .iter()
+        .flatten()
+        .map(|(_, duration)| duration)
+        .sum::<u128>();
+    let capacity = total_duration / job_total as u128;
+    let mut items = vec![];
+
+    // Keep groups together when possible, but if a group is too large
+    // to fit within a single job, split it into individual targets
+    for group in groups {
+        let duration = group.iter().map(|(_, duration)| duration).sum::<u128>();
+
+        if duration > capacity && group.len() > 1 {
+            items.extend(
+                group
+                    .into_iter()
+                    .map(|(target, duration)| (vec![target], duration)),
+            );
+        } else {
+            items.push((
+                group.into_iter().map(|(target, _)| target).collect(),
+                duration,
+            ));
+        }
+    }
+
+    // Sort is stable, so groups of equal duration retain their order
+    items.sort_by(|a, b| b.1.cmp(&a.1));
+
+    for (group, duration) in items {
+        if let Some(shard) = shards.iter_mut().min_by_key(|(_, total)| *total) {
+            shard.0.extend(group);
+            shard.1 += duration;
+        }
+    }
+
+    shards.into_iter().map(|(targets, _)| targets).collect()
+}
+
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+
+    index
+}
+
+/// Group targets that depend on the same tasks (transitively), so that shared
+/// dependencies are not ran in multiple jobs.
+fn group_targets_by_dependencies(
+    workspace_graph: &WorkspaceGraph,
+    targets: TargetList,
+) -> Vec<TargetList> {
+    let mut indexes = FxHashMap::<Target, usize>::default();
+    let mut parents = vec![];
+    let mut visited = FxHashSet::default();
+
+    let mut index_of = |target: &Target, parents: &mut Vec<usize>| -> usize {
+        *indexes.entry(target.to_owned()).or_insert_with(|| {
+            parents.push(parents.len());
+            parents.len() - 1
+        })
+    };
+
+    // Union each target with all of its dependencies
+    for locator in &targets {
+        let TargetLocator::Qualified(target) = locator else {
+            continue;
+        };
+
+        let mut stack = vec![target.to_owned()];
+
+        while let Some(target) = stack.pop() {
+            if !visited.insert(target.clone()) {
+                continue;
+            }
+
+            let Ok(task) = workspace_graph.get_task(&target) else {
+                continue;
+            };
+
+            let index = index_of(&target, &mut parents);
+
+            for dep in &task.deps {
+                let dep_index = index_of(&dep.target, &mut parents);
+                let dep_root = find_root(&mut parents, dep_index);
+                let root = find_root(&mut parents, index);
+
+                parents[dep_root] = root;
+                stack.push(dep.target.clone());
+            }
+        }
+    }
+
+    // Then group targets by their root, while preserving order
+    let mut groups = Vec::<TargetList>::new();
+    let mut group_indexes = FxHashMap::<usize, usize>::default();
+
+    for locator in targets {
+        let root = match &locator {
+            TargetLocator::Qualified(target) => {
+                let index = index_of(target, &mut parents);
+
+                Some(find_root(&mut parents, index))
+            }
+            _ => None,
+        };
+
+        if let Some(group_index) = root.and_then(|root| group_indexes.get(&root)) {
+            groups[*group_index].push(locator);
+            continue;
+        }
+
+        if let Some(root) = root {
+            group_indexes.insert(root, groups.len());
+        }
+
+        groups.push(vec![locator]);
+    }
+
+    groups
+}
+
+/// Load a mapping of target IDs to durations in milliseconds. Durations are
+/// read from the last run of each target in the cache, unless a timings file
+/// is explicitly passed, which is preferred as it's shared across all jobs.
+fn load_shard_timings(
+    session: &CliSession,
+    args: &CiArgs,
+    targets: &TargetList,
+) -> miette::Result<Option<FxHashMap<String, u128>>> {
+    if let Some(file) = &args.shard_timings {
+        let file = session.working_dir.join(file);
+
+        if file.exists() {
+            return Ok(Some(json::read_file(file)?));
+        }
+
+        warn!(
+            "Shard timings file {} does not exist, using cached durations",
+            color::path(&file)
+        );
+    }
+
+    let cache_engine = session.get_cache_engine()?;
+    let mut timings = FxHashMap::default();
+
+    for locator in targets {
+        if let TargetLocator::Qualified(target) = locator {
+            let state = cache_engine
+                .state
+                .load_target_state::<TaskRunCacheState>(target)?;
+
+            if state.data.last_run_duration > 0 {
+                timings.insert(target.as_str().to_owned(), state.data.last_run_duration);
+            }
+        }
+    }
+
+    if timings.is_empty() {
+        warn!("No durations found for the duration shard strategy, falling back to count");
+
+        return Ok(None);
+    }
+
+    Ok(Some(timings))
+}
+
+/// Map each target to its duration from the timings. Targets without
+/// a timing will use the average duration of all known targets.
+fn load_target_durations(
+    timings: &FxHashMap<String, u128>,
+    groups: Vec<TargetList>,
+) -> Vec<Vec<(TargetLocator, u128)>> {
+    let mut durations = FxHashMap::default();
+
+    for locator in groups.iter().flatten() {
+        if let TargetLocator::Qualified(target) = locator {
+            if let Some(duration) = timings.get(target.as_str()) {
+                if *duration > 0 {
+                    durations.insert(target.to_owned(), *duration);
+                }
+            }
+        }
+    }
+
+    let fallback_duration = if durations.is_empty() {
+        1
+    } else {
+        (durations.values().sum::<u128>() / durations.len() as u128).max(1)
+    };
+
+    groups
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .map(|locator| {
+                    let duration = match &locator {
+                        TargetLocator::Qualified(target) => durations.get(target).copied(),
+                        _ => None,
+                    };
+
+                    (locator, duration.unwrap_or(fallback_duration))
+                })
+                .collect()
+        })
+        .collect()
+}
+
 /// Distribute targets across jobs if parallelism is enabled.
 fn distribute_targets_across_jobs(
     console: &mut CiConsole,
+    session: &CliSession,
+    workspace_graph: &WorkspaceGraph,
     args: &CiArgs,
     targets: TargetList,
 ) -> miette::Result<TargetList> {
-    if args.job.is_none() || args.job_total.is_none() {
+    let Some(job_total) = args.job_total.or(args.shard_dry_run.then_some(1)) else {
+        return Ok(targets);
+    };
+
+    if args.job.is_none() && !args.shard_dry_run {
         return Ok(targets);
     }
 
     let job_index = args.job.unwrap_or_default();
-    let job_total = args.job_total.unwrap_or_default();
-    let batch_size = targets.len().div_ceil(job_total);
-    let batched_targets;
+    let job_total = job_total.max(1);
 
     console.print_header("Distributing targets across jobs")?;
     console.write_line(format!("Job index: {job_index}"))?;
     console.write_line(format!("Job total: {job_total}"))?;
-    console.write_line(format!("Batch size: {batch_size}"))?;
-    console.write_line("Batched targets:")?;
 
-    if job_index == 0 {
-        batched_targets = targets[0..batch_size].to_vec();
-    } else if job_index == job_total - 1 {
-        batched_targets = targets[(batch_size * job_index)..].to_vec();
-    } else {
-        batched_targets =
-            targets[(batch_size * job_index)..(batch_size * (job_index + 1))].to_vec();
+    let timings = match args.shard_strategy {
+        ShardStrategy::Count => None,
+        ShardStrategy::Duration => load_shard_timings(session, args, &targets)?,
+    };
+
+    console.write_line(format!(
+        "Shard strategy: {}",
+        if timings.is_some() {
+            "duration"
+        } else {
+            "count"
+        }
+    ))?;
+
+    let mut shards = match timings {
+        Some(timings) => shard_targets_by_duration(
+            load_target_durations(
+                &timings,
+                group_targets_by_dependencies(workspace_graph, targets),
+            ),
+            job_total,
+        ),
+        None => {
+            console.write_line(format!("Batch size: {}", targets.len().div_ceil(job_total)))?;
+
+            shard_targets_by_count(targets, job_total)
+        }
+    };
+
+    if args.shard_dry_run {
+        for (index, shard) in shards.iter().enumerate() {
+            console.write_line(format!("Job {index} targets:"))?;
+            console.print_targets(shard)?;
+        }
+
+        console.print_footer()?;
+
+        return Ok(vec![]);
     }
 
+    let batched_targets = if job_index < shards.len() {
+        shards.swap_remove(job_index)
+    } else {
+        vec![]
+    };
+
+    console.write_line("Batched targets:")?;
     console.print_targets(&batched_targets)?;
     console.print_footer()?;
 
@@ -251,7 +559,12 @@ pub async fn ci(session: CliSession, args: CiArgs) -> AppResult {
         return Ok(None);
     }
 
-    let targets = distribute_targets_across_jobs(&mut console, &args, targets)?;
+    let targets =
+        distribute_targets_across_jobs(&mut console, &session, &workspace_graph, &args, targets)?;
+
+    if args.shard_dry_run {
+        return Ok(None);
+    }
     let (action_graph, action_context) = generate_action_graph(
         &mut console,
         &session,
@@ -288,3 +601,131 @@ pub async fn ci(session: CliSession, args: CiArgs) -> AppResult {
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_targets(ids: &[&str]) -> TargetList {
+        ids.iter()
+            .map(|id| TargetLocator::parse(id).unwrap())
+            .collect()
+    }
+
+    fn create_group(items: &[(&str, u128)]) -> Vec<(TargetLocator, u128)> {
+        items
+            .iter()
+            .map(|(id, duration)| (TargetLocator::parse(id).unwrap(), *duration))
+            .collect()
+    }
+
+    #[test]
+    fn shards_by_count() {
+        let shards =
+            shard_targets_by_count(create_targets(&["a:a", "a:b", "a:c", "a:d", "a:e"]), 2);
+
+        assert_eq!(
+            shards,
+            vec![
+                create_targets(&["a:a", "a:b", "a:c"]),
+                create_targets(&["a:d", "a:e"])
+            ]
+        );
+    }
+
+    #[test]
+    fn shards_by_count_with_more_jobs_than_targets() {
+        let shards = shard_targets_by_count(create_targets(&["a:a", "a:b"]), 3);
+
+        assert_eq!(
+            shards,
+            vec![
+                create_targets(&["a:a"]),
+                create_targets(&["a:b"]),
+                create_targets(&[])
+            ]
+        );
+    }
+
+    #[test]
+    fn shards_by_duration() {
+        let shards = shard_targets_by_duration(
+            vec![
+                create_group(&[("a:e2e", 300)]),
+                create_group(&[("a:lint", 50)]),
+                create_group(&[("b:e2e", 250)]),
+                create_group(&[("b:lint", 60)]),
+                create_group(&[("c:test", 100)]),
+            ],
+            2,
+        );
+
+        assert_eq!(
+            shards,
+            vec![
+                create_targets(&["a:e2e", "b:lint"]),
+                create_targets(&["b:e2e", "c:test", "a:lint"])
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_groups_together() {
+        let shards = shard_targets_by_duration(
+            vec![
+                create_group(&[("a:build", 100), ("a:test", 100)]),
+                create_group(&[("b:build", 150)]),
+                create_group(&[("c:build", 50)]),
+            ],
+            2,
+        );
+
+        assert_eq!(
+            shards,
+            vec![
+                create_targets(&["a:build", "a:test"]),
+                create_targets(&["b:build", "c:build"])
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_groups_that_are_too_large() {
+        let shards = shard_targets_by_duration(
+            vec![
+                create_group(&[("a:build", 100), ("a:test", 300), ("a:lint", 100)]),
+                create_group(&[("b:build", 100)]),
+            ],
+            2,
+        );
+
+        assert_eq!(
+            shards,
+            vec![
+                create_targets(&["a:test"]),
+                create_targets(&["a:build", "a:lint", "b:build"])
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_durations_from_timings() {
+        let timings = FxHashMap::from_iter([("a:build".into(), 100), ("b:build".into(), 300)]);
+
+        let groups = load_target_durations(
+            &timings,
+            vec![
+                create_targets(&["a:build", "a:test"]),
+                create_targets(&["b:build"]),
+            ],
+        );
+
+        assert_eq!(
+            groups,
+            vec![
+                create_group(&[("a:build", 100), ("a:test", 200)]),
+                create_group(&[("b:build", 300)]),
+            ]
+        );
+    }
+}
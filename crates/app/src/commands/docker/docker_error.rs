@@ -12,4 +12,11 @@ pub enum AppDockerError {
         "moon docker scaffold".style(Style::Shell)
     )]
     MissingManifest,
+
+    #[diagnostic(code(app::docker::no_scaffold_projects))]
+    #[error(
+        "No projects to scaffold. Pass a list of project IDs or a {}.",
+        "--query".style(Style::Shell)
+    )]
+    NoScaffoldProjects,
 }
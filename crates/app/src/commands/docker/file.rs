@@ -1,11 +1,14 @@
 use crate::session::CliSession;
 use clap::Args;
 use moon_common::{color, Id};
+use moon_config::{NodePackageManager, ToolchainConfig};
 use moon_console::prompts::{Select, Text};
 use moon_docker::*;
 use moon_project::Project;
+use moon_task::Task;
 use starbase::AppResult;
 use starbase_utils::fs;
+use std::collections::BTreeSet;
 use tracing::{debug, instrument};
 
 #[derive(Args, Clone, Debug)]
@@ -107,14 +110,12 @@ pub async fn file(session: CliSession, args: DockerFileArgs) -> AppResult {
     };
 
     if let Some(task_id) = build_task_id {
-        let target = workspace_graph
-            .get_task_from_project(&project.id, task_id)?
-            .target
-            .to_owned();
+        let task = workspace_graph.get_task_from_project(&project.id, task_id)?;
 
-        debug!(task = target.as_str(), "Using build task");
+        debug!(task = task.target.as_str(), "Using build task");
 
-        options.build_task = Some(target);
+        options.build_outputs = get_build_outputs(&task);
+        options.build_task = Some(task.target.to_owned());
     } else {
         debug!("Not using a build task");
     }
@@ -143,18 +144,30 @@ pub async fn file(session: CliSession, args: DockerFileArgs) -> AppResult {
     };
 
     if let Some(task_id) = start_task_id {
-        let target = workspace_graph
-            .get_task_from_project(&project.id, task_id)?
-            .target
-            .to_owned();
+        let task = workspace_graph.get_task_from_project(&project.id, task_id)?;
 
-        debug!(task = target.as_str(), "Using start task");
+        debug!(task = task.target.as_str(), "Using start task");
 
-        options.start_task = Some(target);
+        options.start_command = get_start_command(&task);
+        options.start_dir = if task.options.run_from_workspace_root {
+            ".".into()
+        } else {
+            project.source.to_string()
+        };
+        options.start_task = Some(task.target.to_owned());
     } else {
         debug!("Not using a start task");
     }
 
+    // Production settings
+    options.cache_mounts = get_cache_mounts(&project, &session.toolchain_config);
+    options.runtime_image = project.config.docker.file.runtime_image.clone();
+    options.user = project.config.docker.file.user.clone();
+
+    if options.runtime_image.is_some() && options.build_outputs.is_empty() {
+        debug!("Runtime image provided but the build task has no outputs, using a full image");
+    }
+
     // Generate the file
     let out = args.dest.unwrap_or("Dockerfile".into());
     let out_file = project.root.join(&out);
@@ -175,6 +188,82 @@ pub async fn file(session: CliSession, args: DockerFileArgs) -> AppResult {
     Ok(None)
 }
 
+/// Return workspace relative paths to copy into the runtime image.
+/// Docker does not support globs, so the static directory of each
+/// output glob is copied instead.
+fn get_build_outputs(task: &Task) -> Vec<String> {
+    let mut outputs = task
+        .output_files
+        .iter()
+        .map(|file| file.to_string())
+        .collect::<BTreeSet<_>>();
+
+    for glob in &task.output_globs {
+        if glob.as_str().starts_with('!') {
+            continue;
+        }
+
+        let dir = glob
+            .as_str()
+            .split('/')
+            .take_while(|part| !part.contains(['*', '?', '[', '{']))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if !dir.is_empty() {
+            outputs.insert(dir);
+        }
+    }
+
+    outputs.into_iter().collect()
+}
+
+fn get_start_command(task: &Task) -> Vec<String> {
+    if let Some(script) = &task.script {
+        return vec!["/bin/sh".into(), "-c".into(), script.to_owned()];
+    }
+
+    let mut command = vec![task.command.clone()];
+    command.extend(task.args.clone());
+    command
+}
+
+fn get_cache_mounts(project: &Project, toolchain_config: &ToolchainConfig) -> Vec<String> {
+    let mut mounts = vec![];
+
+    for tc in &project.toolchains {
+        match tc.as_str() {
+            "bun" => mounts.push("/root/.bun/install/cache"),
+            "deno" => mounts.push("/root/.cache/deno"),
+            "node" => {
+                mounts.push(
+                    match toolchain_config
+                        .node
+                        .as_ref()
+                        .map(|node| &node.package_manager)
+                    {
+                        Some(NodePackageManager::Bun) => "/root/.bun/install/cache",
+                        Some(NodePackageManager::Pnpm) => "/root/.local/share/pnpm/store",
+                        Some(NodePackageManager::Yarn) => "/root/.yarn/berry/cache",
+                        _ => "/root/.npm",
+                    },
+                );
+            }
+            "python" => mounts.push("/root/.cache/pip"),
+            "rust" => mounts.push("/root/.cargo/registry"),
+            _ => {}
+        };
+    }
+
+    let mut mounts = mounts
+        .into_iter()
+        .map(|mount| mount.to_owned())
+        .collect::<Vec<_>>();
+
+    mounts.extend(project.config.docker.file.cache_mounts.clone());
+    mounts
+}
+
 fn get_base_image(project: &Project) -> &str {
     if let Some(tc) = project.toolchains.first() {
         return match tc.as_str() {
@@ -11,8 +11,8 @@ pub use setup::*;
 
 use clap::Subcommand;
 use moon_common::Id;
-use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Clone, Debug, Subcommand)]
 pub enum DockerCommands {
@@ -41,8 +41,19 @@ pub enum DockerCommands {
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DockerManifest {
-    pub focused_projects: FxHashSet<Id>,
-    pub unfocused_projects: FxHashSet<Id>,
+    pub focused_projects: BTreeSet<Id>,
+    pub unfocused_projects: BTreeSet<Id>,
 }
 
 pub const MANIFEST_NAME: &str = "dockerManifest.json";
+
+/// A record of every file that was scaffolded, mapped to its content hash,
+/// and a digest of all files combined.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerScaffoldManifest {
+    pub digest: String,
+    pub files: BTreeMap<String, String>,
+}
+
+pub const SCAFFOLD_MANIFEST_NAME: &str = "scaffoldManifest.json";
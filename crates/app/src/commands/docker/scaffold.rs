@@ -1,4 +1,5 @@
-use super::{DockerManifest, MANIFEST_NAME};
+use super::docker_error::AppDockerError;
+use super::{DockerManifest, DockerScaffoldManifest, MANIFEST_NAME, SCAFFOLD_MANIFEST_NAME};
 use crate::session::CliSession;
 use async_recursion::async_recursion;
 use clap::Args;
@@ -8,21 +9,28 @@ use moon_config::LanguageType;
 use moon_project_graph::{GraphConnections, ProjectGraph};
 use moon_rust_lang::cargo_toml::{CargoTomlCache, CargoTomlExt};
 use moon_toolchain::detect::detect_language_files;
-use rustc_hash::FxHashSet;
 use schematic::ConfigEnum;
+use sha2::{Digest, Sha256};
 use starbase::AppResult;
 use starbase_styles::color;
 use starbase_utils::{fs, glob, json};
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 use tracing::{debug, instrument, warn};
 
 #[derive(Args, Clone, Debug)]
 pub struct DockerScaffoldArgs {
-    #[arg(required = true, help = "List of project IDs to copy sources for")]
+    #[arg(
+        required_unless_present = "query",
+        help = "List of project IDs to copy sources for"
+    )]
     ids: Vec<Id>,
 
     #[arg(long, help = "Additional file globs to include in sources")]
     include: Vec<String>,
+
+    #[arg(long, help = "Filter projects to copy sources for using a query")]
+    query: Option<String>,
 }
 
 fn copy_files_from_paths(paths: Vec<PathBuf>, source: &Path, dest: &Path) -> AppResult {
@@ -244,10 +252,7 @@ async fn scaffold_workspace(
     );
 
     copy_files_from_paths(
-        glob::walk_files(
-            moon_dir,
-            ["*.pkl", "tasks/**/*.pkl", "*.yml", "tasks/**/*.yml"],
-        )?,
+        glob::walk_files(moon_dir, &session.config_loader.get_workspace_globs())?,
         &session.workspace_root,
         &docker_workspace_root,
     )?;
@@ -354,8 +359,8 @@ async fn scaffold_sources(
     );
 
     let mut manifest = DockerManifest {
-        focused_projects: FxHashSet::default(),
-        unfocused_projects: FxHashSet::default(),
+        focused_projects: BTreeSet::default(),
+        unfocused_projects: BTreeSet::default(),
     };
 
     // Copy all projects
@@ -408,6 +413,39 @@ async fn scaffold_sources(
     Ok(None)
 }
 
+#[instrument]
+fn create_scaffold_manifest(docker_root: &Path) -> miette::Result<DockerScaffoldManifest> {
+    let mut manifest = DockerScaffoldManifest::default();
+    let mut hasher = Sha256::default();
+
+    for file in glob::walk_files(docker_root, ["**/*"])? {
+        let rel_file = path::to_virtual_string(file.strip_prefix(docker_root).unwrap())?;
+
+        if rel_file == SCAFFOLD_MANIFEST_NAME {
+            continue;
+        }
+
+        let mut file_hasher = Sha256::default();
+        file_hasher.update(fs::read_file_bytes(&file)?);
+
+        manifest
+            .files
+            .insert(rel_file, format!("{:x}", file_hasher.finalize()));
+    }
+
+    // Files are sorted by path, so the digest is deterministic
+    for (file, hash) in &manifest.files {
+        hasher.update(file.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    manifest.digest = format!("{:x}", hasher.finalize());
+
+    Ok(manifest)
+}
+
 pub fn check_docker_ignore(workspace_root: &Path) -> miette::Result<()> {
     let ignore_file = workspace_root.join(".dockerignore");
     let mut is_ignored = false;
@@ -467,19 +505,46 @@ pub async fn scaffold(session: CliSession, args: DockerScaffoldArgs) -> AppResul
     fs::remove_dir_all(&docker_root)?;
     fs::create_dir_all(&docker_root)?;
 
-    // Create the workspace skeleton
+    // Determine the focused projects
     let project_graph = session.get_project_graph().await?;
+    let mut project_ids = args.ids.clone();
+
+    if let Some(query) = &args.query {
+        let workspace_graph = session.get_workspace_graph().await?;
+
+        for project in workspace_graph.query_projects(moon_query::build_query(query)?)? {
+            if !project_ids.contains(&project.id) {
+                project_ids.push(project.id.clone());
+            }
+        }
+    }
+
+    if project_ids.is_empty() {
+        return Err(AppDockerError::NoScaffoldProjects.into());
+    }
 
+    // Create the workspace skeleton
     scaffold_workspace(&session, &project_graph, &docker_root).await?;
 
     scaffold_sources(
         &session,
         &project_graph,
         &docker_root,
-        &args.ids,
+        &project_ids,
         &args.include,
     )
     .await?;
 
+    // Record the scaffolded files so that changes can be detected
+    let manifest = create_scaffold_manifest(&docker_root)?;
+
+    debug!(
+        digest = &manifest.digest,
+        files = manifest.files.len(),
+        "Writing scaffold manifest"
+    );
+
+    json::write_file(docker_root.join(SCAFFOLD_MANIFEST_NAME), &manifest, true)?;
+
     Ok(None)
 }
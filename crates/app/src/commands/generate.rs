@@ -1,18 +1,64 @@
+use super::run::{run_target, RunArgs};
+use super::syncs::projects::sync as sync_projects;
 use crate::helpers::create_theme;
 use crate::session::CliSession;
 use dialoguer::{Confirm, Input, Select};
 use miette::IntoDiagnostic;
-use moon_codegen::{gather_variables, CodeGenerator, FileState};
+use moon_codegen::{
+    gather_variables, gather_variables_with_values, is_interpolated_path, CodeGenerator,
+    CodegenError, FileState, Template, TemplateContext, TemplateHookAction, TemplateLock,
+};
+use moon_common::path::RelativePathBuf;
+use moon_process::Command;
+use moon_task::TargetLocator;
 use starbase::AppResult;
 use starbase_styles::color;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, instrument};
 
 pub use moon_codegen::GenerateArgs;
 
+fn resolve_dest(
+    session: &CliSession,
+    template: &mut Template,
+    relative_dest: String,
+    context: &TemplateContext,
+) -> miette::Result<(RelativePathBuf, PathBuf)> {
+    let relative_from_root = relative_dest.starts_with('/');
+    let relative_dest = template.interpolate_path(&PathBuf::from(relative_dest), context)?;
+    let dest = relative_dest.to_logical_path(if relative_from_root {
+        &session.workspace_root
+    } else {
+        &session.working_dir
+    });
+
+    Ok((relative_dest, dest))
+}
+
+fn get_relative_dest(args: &GenerateArgs, template: &Template) -> miette::Result<String> {
+    if let Some(dest) = &args.dest {
+        return Ok(dest.to_owned());
+    }
+
+    if let Some(dest) = &template.config.destination {
+        debug!(dest, "Default destination path provided by template config");
+
+        return Ok(dest.to_owned());
+    }
+
+    debug!("Destination path not provided, prompting the user");
+
+    Input::with_theme(&create_theme())
+        .with_prompt("Where to generate code to?")
+        .allow_empty(false)
+        .interact_text()
+        .into_diagnostic()
+}
+
 #[instrument(skip_all)]
-pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
+pub async fn generate(mut session: CliSession, args: GenerateArgs) -> AppResult {
     let mut generator = CodeGenerator::new(
         &session.workspace_root,
         &session.workspace_config.generator,
@@ -38,11 +84,19 @@ pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
         debug!("Running in DRY MODE");
     }
 
+    generator.set_toolchain_config(&session.toolchain_config);
     generator.load_templates().await?;
 
     // Create the template instance
     let mut template = generator.get_template(&args.name)?;
 
+    // Loading the workspace graph is expensive, so only load it when required
+    if template.uses_workspace_functions()? {
+        debug!("Template uses workspace functions, loading workspace graph");
+
+        template.set_workspace_graph(Arc::new(session.get_workspace_graph().await?));
+    }
+
     console.write_newline()?;
     console.write_line(format!(
         "{} {}",
@@ -57,37 +111,60 @@ pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
     console.write_newline()?;
     console.flush()?;
 
-    // Gather variables
-    let mut context = gather_variables(&args, &template, &session.console)?;
-    context.insert("working_dir", &session.working_dir);
-    context.insert("workspace_root", &session.workspace_root);
-
-    // Determine the destination path
-    let relative_dest = match &args.dest {
-        Some(dest) => dest.to_owned(),
-        None => {
-            if let Some(dest) = &template.config.destination {
-                debug!(dest, "Default destination path provided by template config");
+    let (mut context, relative_dest, dest, prev_lock) = if args.update {
+        // When updating, the destination must be known up-front to locate the lock.
+        // If it's interpolated, gather the variables like a fresh generate would,
+        // otherwise defer to the lock so that only new variables are prompted.
+        let relative_dest = get_relative_dest(&args, &template)?;
+        let mut dest_context = if is_interpolated_path(&relative_dest) {
+            gather_variables(&args, &template, &session.console)?
+        } else {
+            TemplateContext::new()
+        };
+        dest_context.insert("working_dir", &session.working_dir);
+        dest_context.insert("workspace_root", &session.workspace_root);
 
-                dest.to_owned()
-            } else {
-                debug!("Destination path not provided, prompting the user");
+        let (relative_dest, dest) =
+            resolve_dest(&session, &mut template, relative_dest, &dest_context)?;
 
-                Input::with_theme(&theme)
-                    .with_prompt("Where to generate code to?")
-                    .allow_empty(false)
-                    .interact_text()
-                    .into_diagnostic()?
+        let Some(lock) = TemplateLock::load(&dest, &template.id)? else {
+            return Err(CodegenError::MissingTemplateLock {
+                path: TemplateLock::get_path(&dest),
+                dest,
+                id: template.id,
             }
-        }
-    };
-    let relative_from_root = relative_dest.starts_with('/');
-    let relative_dest = template.interpolate_path(&PathBuf::from(relative_dest), &context)?;
-    let dest = relative_dest.to_logical_path(if relative_from_root {
-        &session.workspace_root
+            .into());
+        };
+
+        debug!(
+            prev_locator = ?lock.locator,
+            next_locator = ?template.locator,
+            "Updating from template lock",
+        );
+
+        // Re-use locked variables, and only prompt for new variables,
+        // but values gathered for the destination take precedence
+        let mut values = lock.to_context();
+        values.extend(dest_context);
+
+        let mut context =
+            gather_variables_with_values(&args, &template, &session.console, &values)?;
+        context.insert("working_dir", &session.working_dir);
+        context.insert("workspace_root", &session.workspace_root);
+
+        (context, relative_dest, dest, Some(lock))
     } else {
-        &session.working_dir
-    });
+        // Gather variables
+        let mut context = gather_variables(&args, &template, &session.console)?;
+        context.insert("working_dir", &session.working_dir);
+        context.insert("workspace_root", &session.workspace_root);
+
+        // Determine the destination path
+        let relative_dest = get_relative_dest(&args, &template)?;
+        let (relative_dest, dest) = resolve_dest(&session, &mut template, relative_dest, &context)?;
+
+        (context, relative_dest, dest, None)
+    };
 
     debug!(dest = ?dest, "Destination path set");
 
@@ -98,12 +175,40 @@ pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
     // Load template files and determine when to overwrite
     template.load_files(&dest, &context)?;
 
+    // Lock the rendered content before it's merged with existing files
+    let mut lock = TemplateLock::new(&template, &context, &dest)?;
+    let mut conflicts = 0;
+
     for file in template.files.values_mut() {
         if file.is_skipped() {
             file.state = FileState::Skip;
             continue;
         }
 
+        // Inject into the existing file, unless already injected
+        if file.get_inject_config().is_some() {
+            if !file.dest_path.exists() {
+                return Err(CodegenError::InjectMissingFile {
+                    path: file.dest_path.clone(),
+                }
+                .into());
+            }
+
+            file.state = if file.is_injected()? {
+                FileState::Skip
+            } else {
+                FileState::Merge
+            };
+
+            continue;
+        }
+
+        // Merge changes from the new render with the existing file
+        if let Some(prev_lock) = &prev_lock {
+            conflicts += prev_lock.update_file(file, &dest)?;
+            continue;
+        }
+
         if file.dest_path.exists() {
             if args.force || file.is_forced() {
                 file.state = FileState::Replace;
@@ -151,9 +256,21 @@ pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
         }
     }
 
+    // Only files written to the destination are owned by the template
+    let removed_files = prev_lock
+        .as_ref()
+        .map(|prev_lock| prev_lock.get_removed_files(&lock))
+        .unwrap_or_default();
+
+    lock.retain_written_files(&template, &dest, prev_lock.as_ref())?;
+
+    // Render hooks before generating, so that variables are validated
+    let hooks = template.get_after_generate_hooks(&context)?;
+
     // Generate the files in the destination and print the results
     if !args.dry_run {
         generator.generate(&template)?;
+        lock.save(&dest)?;
     }
 
     console.write_newline()?;
@@ -162,14 +279,18 @@ pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
         console.write_line(format!(
             "{} {} {}",
             match &file.state {
+                FileState::Conflict => color::failure("conflict"),
                 FileState::Create => color::success("created"),
                 FileState::Merge => color::success("merged"),
                 FileState::Replace => color::failure("replaced"),
                 FileState::Skip => color::invalid("skipped"),
+                FileState::Update => color::success("updated"),
             },
             match &file.state {
+                FileState::Conflict => color::muted("->"),
                 FileState::Merge => color::muted("--->"),
                 FileState::Replace => color::muted("->"),
+                FileState::Update => color::muted("->"),
                 _ => color::muted("-->"),
             },
             color::muted_light(
@@ -182,7 +303,89 @@ pub async fn generate(session: CliSession, args: GenerateArgs) -> AppResult {
     }
 
     console.write_newline()?;
+
+    if !removed_files.is_empty() {
+        console.write_line(format!(
+            "Found {} files that were removed from the template, delete them if no longer required:",
+            color::symbol(removed_files.len().to_string())
+        ))?;
+
+        for file in &removed_files {
+            let path = dest.join(file);
+
+            console.write_line(format!(
+                "{} {}",
+                color::muted("-"),
+                color::muted_light(
+                    path.strip_prefix(&session.working_dir)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                )
+            ))?;
+        }
+
+        console.write_newline()?;
+    }
+
+    if conflicts > 0 {
+        console.write_line(format!(
+            "Found {} merge conflicts, resolve the conflict markers in the files above.",
+            color::symbol(conflicts.to_string())
+        ))?;
+        console.write_newline()?;
+    }
+
     console.flush()?;
 
+    if args.dry_run {
+        return Ok(None);
+    }
+
+    if conflicts > 0 {
+        return Ok(Some(1));
+    }
+
+    // New projects or tasks may have been generated, so rebuild the graphs
+    session.reset_workspace_graph();
+
+    // Sync projects so that new projects are available to hooks
+    if template.config.hooks.sync_projects {
+        if let Some(code) = sync_projects(session.clone()).await? {
+            return Ok(Some(code));
+        }
+    }
+
+    for hook in hooks {
+        let result = match hook {
+            TemplateHookAction::Command(command) => {
+                debug!(command, dest = ?dest, "Running template hook command");
+
+                Command::new(command)
+                    .cwd(&dest)
+                    .with_console(session.get_console()?)
+                    .set_print_command(true)
+                    .create_async()
+                    .exec_stream_output()
+                    .await?;
+
+                None
+            }
+            TemplateHookAction::Target(target) => {
+                debug!(target, "Running template hook target");
+
+                run_target(
+                    &session,
+                    &RunArgs::default(),
+                    &[TargetLocator::from_str(&target)?],
+                )
+                .await?
+            }
+        };
+
+        if result.is_some() {
+            return Ok(result);
+        }
+    }
+
     Ok(None)
 }
@@ -1,4 +1,7 @@
-use crate::commands::graph::utils::{action_graph_repr, respond_to_request, setup_server};
+use crate::commands::graph::explorer::GraphExplorer;
+use crate::commands::graph::utils::{
+    action_graph_repr, action_graph_subjects, respond_to_request, setup_server, GraphFormat,
+};
 use crate::session::CliSession;
 use clap::Args;
 use moon_action_graph::RunRequirements;
@@ -21,6 +24,9 @@ pub struct ActionGraphArgs {
 
     #[arg(long, help = "Print the graph in JSON format")]
     json: bool,
+
+    #[arg(long, value_enum, help = "Print the graph in the provided format")]
+    format: Option<GraphFormat>,
 }
 
 #[instrument]
@@ -50,20 +56,34 @@ pub async fn action_graph(session: CliSession, args: ActionGraphArgs) -> AppResu
 
     let action_graph = action_graph_builder.build();
 
-    if args.dot {
-        println!("{}", action_graph.to_dot());
-
-        return Ok(None);
-    }
+    let format = if args.dot {
+        Some(GraphFormat::Dot)
+    } else if args.json {
+        Some(GraphFormat::Json)
+    } else {
+        args.format
+    };
 
     let graph_info = action_graph_repr(&action_graph).await;
 
-    if args.json {
-        println!("{}", json::format(&graph_info, true)?);
+    if let Some(format) = format {
+        match format {
+            GraphFormat::Dot => println!("{}", action_graph.to_dot()),
+            GraphFormat::GraphMl => println!("{}", action_graph.to_graphml()),
+            GraphFormat::Json => println!("{}", json::format(&graph_info, true)?),
+            GraphFormat::Mermaid => println!("{}", action_graph.to_mermaid()),
+        };
 
         return Ok(None);
     }
 
+    let explorer = GraphExplorer::new(
+        graph_info,
+        action_graph_subjects(&action_graph),
+        workspace_graph,
+        session.get_vcs_adapter()?,
+        "Action graph",
+    );
     let (server, mut tera) = setup_server().await?;
     let url = format!("http://{}", server.server_addr());
     let _ = open::that(&url);
@@ -71,7 +91,7 @@ pub async fn action_graph(session: CliSession, args: ActionGraphArgs) -> AppResu
     println!("Started server on {}", color::url(url));
 
     for req in server.incoming_requests() {
-        respond_to_request(req, &mut tera, &graph_info, "Action graph".to_owned())?;
+        respond_to_request(req, &mut tera, &explorer).await?;
     }
 
     Ok(None)
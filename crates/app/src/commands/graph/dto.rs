@@ -1,12 +1,17 @@
+use moon_common::Id;
+use moon_config::{InputPath, OutputPath, ProjectMetadataConfig};
+use moon_task::Target;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct GraphNodeDto {
     pub id: usize,
     pub label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affected: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GraphEdgeDto {
     pub id: String,
     pub label: String,
@@ -14,8 +19,52 @@ pub struct GraphEdgeDto {
     pub target: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GraphInfoDto {
     pub nodes: Vec<GraphNodeDto>,
     pub edges: Vec<GraphEdgeDto>,
 }
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectDetailsDto {
+    pub alias: Option<String>,
+    pub dependencies: Vec<Id>,
+    pub id: Id,
+    pub language: String,
+    pub metadata: Option<ProjectMetadataConfig>,
+    pub source: String,
+    pub stack: String,
+    pub tags: Vec<Id>,
+    pub tasks: Vec<Target>,
+    #[serde(rename = "type")]
+    pub type_of: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDetailsDto {
+    pub args: Vec<String>,
+    pub command: String,
+    pub deps: Vec<Target>,
+    pub description: Option<String>,
+    pub inputs: Vec<InputPath>,
+    pub outputs: Vec<OutputPath>,
+    pub script: Option<String>,
+    pub target: Target,
+    pub toolchains: Vec<Id>,
+    #[serde(rename = "type")]
+    pub type_of: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum GraphNodeDetailsDto {
+    Action {
+        label: String,
+        project: Option<Box<ProjectDetailsDto>>,
+        task: Option<Box<TaskDetailsDto>>,
+    },
+    Project(Box<ProjectDetailsDto>),
+    Task(Box<TaskDetailsDto>),
+}
@@ -0,0 +1,506 @@
+use super::dto::{
+    GraphInfoDto, GraphNodeDetailsDto, GraphNodeDto, ProjectDetailsDto, TaskDetailsDto,
+};
+use crate::queries::touched_files::{query_touched_files, QueryTouchedFilesOptions};
+use moon_action::ActionNode;
+use moon_affected::{Affected, AffectedTracker};
+use moon_common::{is_ci, is_test_env, Id};
+use moon_query::build_query;
+use moon_task::Target;
+use moon_vcs::BoxedVcs;
+use moon_workspace_graph::WorkspaceGraph;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use tracing::debug;
+
+/// The entity that a node in a rendered graph represents.
+pub enum GraphNodeSubject {
+    Action(ActionNode),
+    Project(Id),
+    Task(Target),
+}
+
+#[derive(Debug, Default)]
+pub struct GraphFilterParams {
+    /// Include nodes that are affected based on touched files.
+    pub affected: bool,
+
+    /// Number of edges to traverse from the focused node.
+    pub depth: usize,
+
+    /// Node to focus, and only include its neighborhood.
+    pub focus: Option<usize>,
+
+    /// MQL query to filter nodes with.
+    pub query: Option<String>,
+}
+
+/// Serves the visualizer by filtering the full graph on demand,
+/// and loading details about the subject of each node.
+pub struct GraphExplorer {
+    affected: OnceCell<Affected>,
+    graph: GraphInfoDto,
+    subjects: FxHashMap<usize, GraphNodeSubject>,
+    vcs: Arc<BoxedVcs>,
+    workspace_graph: WorkspaceGraph,
+    pub page_title: String,
+}
+
+impl GraphExplorer {
+    pub fn new(
+        graph: GraphInfoDto,
+        subjects: FxHashMap<usize, GraphNodeSubject>,
+        workspace_graph: WorkspaceGraph,
+        vcs: Arc<BoxedVcs>,
+        page_title: &str,
+    ) -> Self {
+        Self {
+            affected: OnceCell::new(),
+            graph,
+            subjects,
+            vcs,
+            workspace_graph,
+            page_title: page_title.to_owned(),
+        }
+    }
+
+    pub fn get_graph(&self) -> &GraphInfoDto {
+        &self.graph
+    }
+
+    pub async fn filter_graph(&self, params: &GraphFilterParams) -> miette::Result<GraphInfoDto> {
+        let mut included = self
+            .graph
+            .nodes
+            .iter()
+            .map(|node| node.id)
+            .collect::<FxHashSet<_>>();
+
+        if let Some(query) = params.query.as_deref().filter(|query| !query.is_empty()) {
+            let matched = self.query_nodes(query)?;
+
+            included.retain(|id| matched.contains(id));
+        }
+
+        if let Some(focus) = params.focus {
+            let neighborhood = self.get_neighborhood(focus, params.depth);
+
+            included.retain(|id| neighborhood.contains(id));
+        }
+
+        let affected = if params.affected {
+            Some(self.load_affected().await?)
+        } else {
+            None
+        };
+
+        Ok(GraphInfoDto {
+            nodes: self
+                .graph
+                .nodes
+                .iter()
+                .filter(|node| included.contains(&node.id))
+                .map(|node| GraphNodeDto {
+                    affected: affected.map(|affected| self.is_node_affected(affected, node.id)),
+                    ..node.clone()
+                })
+                .collect(),
+            edges: self
+                .graph
+                .edges
+                .iter()
+                .filter(|edge| included.contains(&edge.source) && included.contains(&edge.target))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    pub fn get_node_details(&self, id: usize) -> miette::Result<Option<GraphNodeDetailsDto>> {
+        let Some(subject) = self.subjects.get(&id) else {
+            return Ok(None);
+        };
+
+        let details = match subject {
+            GraphNodeSubject::Action(node) => GraphNodeDetailsDto::Action {
+                label: node.label(),
+                project: match node {
+                    ActionNode::InstallProjectDeps(inner) | ActionNode::SyncProject(inner) => {
+                        Some(Box::new(self.get_project_details(&inner.project)?))
+                    }
+                    _ => None,
+                },
+                task: match node {
+                    ActionNode::RunTask(inner) => {
+                        Some(Box::new(self.get_task_details(&inner.target)?))
+                    }
+                    _ => None,
+                },
+            },
+            GraphNodeSubject::Project(id) => {
+                GraphNodeDetailsDto::Project(Box::new(self.get_project_details(id)?))
+            }
+            GraphNodeSubject::Task(target) => {
+                GraphNodeDetailsDto::Task(Box::new(self.get_task_details(target)?))
+            }
+        };
+
+        Ok(Some(details))
+    }
+
+    fn get_project_details(&self, id: &Id) -> miette::Result<ProjectDetailsDto> {
+        let project = self.workspace_graph.get_project(id)?;
+
+        Ok(ProjectDetailsDto {
+            alias: project.alias.clone(),
+            dependencies: project.get_dependency_ids().into_iter().cloned().collect(),
+            id: project.id.clone(),
+            language: project.language.to_string(),
+            metadata: project.config.project.clone(),
+            source: project.source.to_string(),
+            stack: project.stack.to_string(),
+            tags: project.config.tags.clone(),
+            tasks: project.task_targets.clone(),
+            type_of: project.type_of.to_string(),
+        })
+    }
+
+    fn get_task_details(&self, target: &Target) -> miette::Result<TaskDetailsDto> {
+        let task = self.workspace_graph.get_task(target)?;
+
+        Ok(TaskDetailsDto {
+            args: task.args.clone(),
+            command: task.command.clone(),
+            deps: task.deps.iter().map(|dep| dep.target.clone()).collect(),
+            description: task.description.clone(),
+            inputs: task.inputs.clone(),
+            outputs: task.outputs.clone(),
+            script: task.script.clone(),
+            target: task.target.clone(),
+            toolchains: task.toolchains.clone(),
+            type_of: task.type_of.to_string(),
+        })
+    }
+
+    // Breadth-first traversal in both directions, so that
+    // dependencies and dependents are both included
+    fn get_neighborhood(&self, focus: usize, depth: usize) -> FxHashSet<usize> {
+        let mut adjacent: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
+
+        for edge in &self.graph.edges {
+            adjacent.entry(edge.source).or_default().push(edge.target);
+            adjacent.entry(edge.target).or_default().push(edge.source);
+        }
+
+        let mut visited = FxHashSet::from_iter([focus]);
+        let mut queue = VecDeque::from([(focus, 0)]);
+
+        while let Some((id, distance)) = queue.pop_front() {
+            if distance >= depth {
+                continue;
+            }
+
+            for next in adjacent.get(&id).into_iter().flatten() {
+                if visited.insert(*next) {
+                    queue.push_back((*next, distance + 1));
+                }
+            }
+        }
+
+        visited
+    }
+
+    fn query_nodes(&self, input: &str) -> miette::Result<FxHashSet<usize>> {
+        let query = build_query(input)?;
+        let mut matched = FxHashSet::default();
+
+        let project_ids = if self
+            .subjects
+            .values()
+            .any(|subject| matches!(subject, GraphNodeSubject::Project(_)))
+        {
+            self.workspace_graph
+                .query_projects(&query)?
+                .into_iter()
+                .map(|project| project.id.clone())
+                .collect::<FxHashSet<_>>()
+        } else {
+            FxHashSet::default()
+        };
+
+        let targets = if self
+            .subjects
+            .values()
+            .any(|subject| !matches!(subject, GraphNodeSubject::Project(_)))
+        {
+            self.workspace_graph
+                .query_tasks(&query)?
+                .into_iter()
+                .map(|task| task.target.clone())
+                .collect::<FxHashSet<_>>()
+        } else {
+            FxHashSet::default()
+        };
+
+        for (id, subject) in &self.subjects {
+            let is_match = match subject {
+                GraphNodeSubject::Project(project_id) => project_ids.contains(project_id),
+                GraphNodeSubject::Task(target) => targets.contains(target),
+                GraphNodeSubject::Action(node) => match node {
+                    ActionNode::RunTask(inner) => targets.contains(&inner.target),
+                    // Project actions are included when one of their tasks matched
+                    ActionNode::InstallProjectDeps(inner) | ActionNode::SyncProject(inner) => {
+                        targets.iter().any(|target| {
+                            target
+                                .get_project_id()
+                                .is_some_and(|project_id| project_id == &inner.project)
+                        })
+                    }
+                    _ => false,
+                },
+            };
+
+            if is_match {
+                matched.insert(*id);
+            }
+        }
+
+        Ok(matched)
+    }
+
+    fn is_node_affected(&self, affected: &Affected, id: usize) -> bool {
+        match self.subjects.get(&id) {
+            Some(GraphNodeSubject::Project(project_id)) => affected.is_project_affected(project_id),
+            Some(GraphNodeSubject::Task(target)) => affected.is_task_affected(target),
+            Some(GraphNodeSubject::Action(node)) => match node {
+                ActionNode::RunTask(inner) => affected.is_task_affected(&inner.target),
+                ActionNode::InstallProjectDeps(inner) | ActionNode::SyncProject(inner) => {
+                    affected.is_project_affected(&inner.project)
+                }
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    async fn load_affected(&self) -> miette::Result<&Affected> {
+        self.affected
+            .get_or_try_init(|| async {
+                debug!("Loading touched files to determine affected nodes");
+
+                let touched_files = if self.vcs.is_enabled() {
+                    let local = !is_ci();
+
+                    query_touched_files(
+                        &self.vcs,
+                        &QueryTouchedFilesOptions {
+                            default_branch: !local && !is_test_env(),
+                            local,
+                            ..QueryTouchedFilesOptions::default()
+                        },
+                    )
+                    .await?
+                    .files
+                } else {
+                    FxHashSet::default()
+                };
+
+                let mut tracker = AffectedTracker::new(&self.workspace_graph, &touched_files);
+                tracker.track_projects()?;
+                tracker.track_tasks()?;
+
+                Ok(tracker.build())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dto::GraphEdgeDto;
+    use super::*;
+    use moon_test_utils2::generate_workspace_graph_from_sandbox;
+    use moon_vcs::Git;
+    use starbase_sandbox::{create_empty_sandbox, Sandbox};
+
+    fn create_node(id: usize, label: &str) -> GraphNodeDto {
+        GraphNodeDto {
+            id,
+            label: label.into(),
+            affected: None,
+        }
+    }
+
+    fn create_edge(source: usize, target: usize) -> GraphEdgeDto {
+        GraphEdgeDto {
+            id: format!("{source} -> {target}"),
+            label: String::new(),
+            source,
+            target,
+        }
+    }
+
+    fn get_node_ids(graph: &GraphInfoDto) -> Vec<usize> {
+        let mut ids = graph.nodes.iter().map(|node| node.id).collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    // a <- b <- c, and d is standalone
+    async fn create_project_explorer() -> (Sandbox, GraphExplorer) {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("a/moon.yml", "tags: [frontend]\ntasks:\n  build: {}");
+        sandbox.create_file("b/moon.yml", "dependsOn: [a]\ntags: [frontend]");
+        sandbox.create_file("c/moon.yml", "dependsOn: [b]");
+        sandbox.create_file("d/moon.yml", "tags: [backend]");
+        sandbox.enable_git();
+
+        let workspace_graph = generate_workspace_graph_from_sandbox(sandbox.path()).await;
+        let vcs: Arc<BoxedVcs> =
+            Arc::new(Box::new(Git::load(sandbox.path(), "master", &[]).unwrap()));
+
+        let explorer = GraphExplorer::new(
+            GraphInfoDto {
+                nodes: vec![
+                    create_node(0, "a"),
+                    create_node(1, "b"),
+                    create_node(2, "c"),
+                    create_node(3, "d"),
+                ],
+                edges: vec![create_edge(1, 0), create_edge(2, 1)],
+            },
+            FxHashMap::from_iter([
+                (0, GraphNodeSubject::Project(Id::raw("a"))),
+                (1, GraphNodeSubject::Project(Id::raw("b"))),
+                (2, GraphNodeSubject::Project(Id::raw("c"))),
+                (3, GraphNodeSubject::Project(Id::raw("d"))),
+            ]),
+            workspace_graph,
+            vcs,
+            "Project graph",
+        );
+
+        (sandbox, explorer)
+    }
+
+    mod filter_graph {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_all_without_params() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+            let graph = explorer
+                .filter_graph(&GraphFilterParams::default())
+                .await
+                .unwrap();
+
+            assert_eq!(get_node_ids(&graph), [0, 1, 2, 3]);
+            assert_eq!(graph.edges.len(), 2);
+            assert!(graph.nodes.iter().all(|node| node.affected.is_none()));
+        }
+
+        #[tokio::test]
+        async fn filters_by_query() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+            let graph = explorer
+                .filter_graph(&GraphFilterParams {
+                    query: Some("tag=frontend".into()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(get_node_ids(&graph), [0, 1]);
+            assert_eq!(graph.edges.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn ignores_empty_query() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+            let graph = explorer
+                .filter_graph(&GraphFilterParams {
+                    query: Some(String::new()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(get_node_ids(&graph), [0, 1, 2, 3]);
+        }
+
+        #[tokio::test]
+        async fn errors_for_invalid_query() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+
+            assert!(explorer
+                .filter_graph(&GraphFilterParams {
+                    query: Some("unknown=field".into()),
+                    ..Default::default()
+                })
+                .await
+                .is_err());
+        }
+
+        #[tokio::test]
+        async fn filters_by_focus_neighborhood() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+
+            for (depth, expected) in [(0, vec![1]), (1, vec![0, 1, 2]), (5, vec![0, 1, 2])] {
+                let graph = explorer
+                    .filter_graph(&GraphFilterParams {
+                        focus: Some(1),
+                        depth,
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap();
+
+                assert_eq!(get_node_ids(&graph), expected);
+            }
+        }
+
+        #[tokio::test]
+        async fn combines_query_and_focus() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+            let graph = explorer
+                .filter_graph(&GraphFilterParams {
+                    focus: Some(2),
+                    depth: 2,
+                    query: Some("tag=frontend".into()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(get_node_ids(&graph), [0, 1]);
+            assert_eq!(graph.edges.len(), 1);
+        }
+    }
+
+    mod get_node_details {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_project_details() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+            let details = explorer.get_node_details(1).unwrap().unwrap();
+
+            let GraphNodeDetailsDto::Project(project) = details else {
+                panic!("Expected project details");
+            };
+
+            assert_eq!(project.id, "b");
+            assert_eq!(project.dependencies, [Id::raw("a")]);
+            assert_eq!(project.source, "b");
+            assert_eq!(project.tags, [Id::raw("frontend")]);
+        }
+
+        #[tokio::test]
+        async fn returns_none_for_unknown_node() {
+            let (_sandbox, explorer) = create_project_explorer().await;
+
+            assert!(explorer.get_node_details(100).unwrap().is_none());
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod action;
 mod dto;
+mod explorer;
 pub mod project;
 pub mod task;
 mod utils;
@@ -1,8 +1,12 @@
-use super::utils::{project_graph_repr, respond_to_request, setup_server};
+use super::explorer::GraphExplorer;
+use super::utils::{
+    print_dependency_cycles, project_graph_repr, project_graph_subjects, respond_to_request,
+    setup_server, GraphFormat,
+};
 use crate::session::CliSession;
 use clap::Args;
 use moon_common::Id;
-use moon_project_graph::{GraphToDot, GraphToJson};
+use moon_project_graph::{GraphToDot, GraphToGraphML, GraphToJson, GraphToMermaid};
 use starbase::AppResult;
 use starbase_styles::color;
 use std::sync::Arc;
@@ -16,15 +20,30 @@ pub struct ProjectGraphArgs {
     #[arg(long, help = "Include direct dependents of the focused project")]
     dependents: bool,
 
+    #[arg(
+        long,
+        help = "Report all project dependency cycles, with where they were declared"
+    )]
+    cycles: bool,
+
     #[arg(long, help = "Print the graph in DOT format")]
     dot: bool,
 
     #[arg(long, help = "Print the graph in JSON format")]
     json: bool,
+
+    #[arg(long, value_enum, help = "Print the graph in the provided format")]
+    format: Option<GraphFormat>,
 }
 
 #[instrument(skip_all)]
 pub async fn project_graph(session: CliSession, args: ProjectGraphArgs) -> AppResult {
+    if args.cycles {
+        let cycles = session.get_workspace_graph().await?.find_project_cycles()?;
+
+        return print_dependency_cycles(&cycles, "project");
+    }
+
     let mut project_graph = session.get_project_graph().await?;
 
     if let Some(id) = &args.id {
@@ -34,19 +53,32 @@ pub async fn project_graph(session: CliSession, args: ProjectGraphArgs) -> AppRe
     // Force expand all projects
     project_graph.get_all()?;
 
-    if args.dot {
-        println!("{}", project_graph.to_dot());
-
-        return Ok(None);
-    }
+    let format = if args.dot {
+        Some(GraphFormat::Dot)
+    } else if args.json {
+        Some(GraphFormat::Json)
+    } else {
+        args.format
+    };
 
-    if args.json {
-        println!("{}", project_graph.to_json()?);
+    if let Some(format) = format {
+        match format {
+            GraphFormat::Dot => println!("{}", project_graph.to_dot()),
+            GraphFormat::GraphMl => println!("{}", project_graph.to_graphml()),
+            GraphFormat::Json => println!("{}", project_graph.to_json()?),
+            GraphFormat::Mermaid => println!("{}", project_graph.to_mermaid()),
+        };
 
         return Ok(None);
     }
 
-    let graph_info = project_graph_repr(&project_graph).await;
+    let explorer = GraphExplorer::new(
+        project_graph_repr(&project_graph).await,
+        project_graph_subjects(&project_graph),
+        session.get_workspace_graph().await?,
+        session.get_vcs_adapter()?,
+        "Project graph",
+    );
     let (server, mut tera) = setup_server().await?;
     let url = format!("http://{}", server.server_addr());
     let _ = open::that(&url);
@@ -54,7 +86,7 @@ pub async fn project_graph(session: CliSession, args: ProjectGraphArgs) -> AppRe
     println!("Started server on {}", color::url(url));
 
     for req in server.incoming_requests() {
-        respond_to_request(req, &mut tera, &graph_info, "Project graph".to_owned())?;
+        respond_to_request(req, &mut tera, &explorer).await?;
     }
 
     Ok(None)
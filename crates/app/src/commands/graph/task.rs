@@ -1,8 +1,12 @@
-use super::utils::{respond_to_request, setup_server, task_graph_repr};
+use super::explorer::GraphExplorer;
+use super::utils::{
+    print_dependency_cycles, respond_to_request, setup_server, task_graph_repr,
+    task_graph_subjects, GraphFormat,
+};
 use crate::session::CliSession;
 use clap::Args;
 use moon_task::Target;
-use moon_task_graph::{GraphToDot, GraphToJson};
+use moon_task_graph::{GraphToDot, GraphToGraphML, GraphToJson, GraphToMermaid};
 use starbase::AppResult;
 use starbase_styles::color;
 use std::sync::Arc;
@@ -16,15 +20,30 @@ pub struct TaskGraphArgs {
     #[arg(long, help = "Include direct dependents of the focused target")]
     dependents: bool,
 
+    #[arg(
+        long,
+        help = "Report all task dependency cycles, with where they were declared"
+    )]
+    cycles: bool,
+
     #[arg(long, help = "Print the graph in DOT format")]
     dot: bool,
 
     #[arg(long, help = "Print the graph in JSON format")]
     json: bool,
+
+    #[arg(long, value_enum, help = "Print the graph in the provided format")]
+    format: Option<GraphFormat>,
 }
 
 #[instrument(skip_all)]
 pub async fn task_graph(session: CliSession, args: TaskGraphArgs) -> AppResult {
+    if args.cycles {
+        let cycles = session.get_workspace_graph().await?.find_task_cycles()?;
+
+        return print_dependency_cycles(&cycles, "task");
+    }
+
     let mut task_graph = session.get_task_graph().await?;
 
     if let Some(target) = &args.target {
@@ -34,19 +53,32 @@ pub async fn task_graph(session: CliSession, args: TaskGraphArgs) -> AppResult {
     // Force expand all tasks
     task_graph.get_all()?;
 
-    if args.dot {
-        println!("{}", task_graph.to_dot());
-
-        return Ok(None);
-    }
+    let format = if args.dot {
+        Some(GraphFormat::Dot)
+    } else if args.json {
+        Some(GraphFormat::Json)
+    } else {
+        args.format
+    };
 
-    if args.json {
-        println!("{}", task_graph.to_json()?);
+    if let Some(format) = format {
+        match format {
+            GraphFormat::Dot => println!("{}", task_graph.to_dot()),
+            GraphFormat::GraphMl => println!("{}", task_graph.to_graphml()),
+            GraphFormat::Json => println!("{}", task_graph.to_json()?),
+            GraphFormat::Mermaid => println!("{}", task_graph.to_mermaid()),
+        };
 
         return Ok(None);
     }
 
-    let graph_info = task_graph_repr(&task_graph).await;
+    let explorer = GraphExplorer::new(
+        task_graph_repr(&task_graph).await,
+        task_graph_subjects(&task_graph),
+        session.get_workspace_graph().await?,
+        session.get_vcs_adapter()?,
+        "Task graph",
+    );
     let (server, mut tera) = setup_server().await?;
     let url = format!("http://{}", server.server_addr());
     let _ = open::that(&url);
@@ -54,7 +86,7 @@ pub async fn task_graph(session: CliSession, args: TaskGraphArgs) -> AppResult {
     println!("Started server on {}", color::url(url));
 
     for req in server.incoming_requests() {
-        respond_to_request(req, &mut tera, &graph_info, "Task graph".to_owned())?;
+        respond_to_request(req, &mut tera, &explorer).await?;
     }
 
     Ok(None)
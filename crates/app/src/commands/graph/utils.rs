@@ -1,20 +1,38 @@
 use super::dto::{GraphEdgeDto, GraphInfoDto, GraphNodeDto};
+use super::explorer::{GraphExplorer, GraphFilterParams, GraphNodeSubject};
+use clap::ValueEnum;
 use miette::IntoDiagnostic;
 use moon_action_graph::ActionGraph;
-use moon_project_graph::{GraphConversions, ProjectGraph};
+use moon_project_graph::{GraphConversions, GraphData, ProjectGraph};
 use moon_task_graph::TaskGraph;
+use moon_workspace_graph::DependencyCycle;
 use petgraph::{graph::NodeIndex, Graph};
+use reqwest::Url;
 use rustc_hash::FxHashMap;
 use serde::Serialize;
 use starbase::AppResult;
+use starbase_styles::color;
 use starbase_utils::json;
 use std::env;
 use std::fmt::Display;
 use tera::{Context, Tera};
-use tiny_http::{Header, Request, Response, Server};
+use tiny_http::{Header, Request, Response, Server, StatusCode};
 
 const INDEX_HTML: &str = include_str!("graph.html.tera");
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "lowercase")]
+pub enum GraphFormat {
+    /// Graphviz DOT format.
+    Dot,
+    /// GraphML (XML) format, for analysis tools.
+    GraphMl,
+    /// JSON format.
+    Json,
+    /// Mermaid flowchart, for embedding in markdown.
+    Mermaid,
+}
+
 #[derive(Debug, Serialize)]
 pub struct RenderContext {
     pub page_title: String,
@@ -55,6 +73,7 @@ pub fn extract_nodes_and_edges_from_graph<T: Display>(
         .collect::<Vec<_>>();
 
     let get_graph_node = |ni: NodeIndex| GraphNodeDto {
+        affected: None,
         id: ni.index(),
         label: graph
             .node_weight(ni)
@@ -95,25 +114,110 @@ pub async fn action_graph_repr(action_graph: &ActionGraph) -> GraphInfoDto {
     extract_nodes_and_edges_from_graph(&labeled_graph, false)
 }
 
-pub fn respond_to_request(
+/// Map each node in the project graph to the project it represents.
+pub fn project_graph_subjects(project_graph: &ProjectGraph) -> FxHashMap<usize, GraphNodeSubject> {
+    let graph = project_graph.get_graph();
+
+    graph
+        .node_indices()
+        .map(|ni| (ni.index(), GraphNodeSubject::Project(graph[ni].id.clone())))
+        .collect()
+}
+
+/// Map each node in the task graph to the task it represents.
+pub fn task_graph_subjects(task_graph: &TaskGraph) -> FxHashMap<usize, GraphNodeSubject> {
+    let graph = task_graph.get_graph();
+
+    graph
+        .node_indices()
+        .map(|ni| (ni.index(), GraphNodeSubject::Task(graph[ni].target.clone())))
+        .collect()
+}
+
+/// Map each node in the action graph to the action it represents.
+pub fn action_graph_subjects(action_graph: &ActionGraph) -> FxHashMap<usize, GraphNodeSubject> {
+    let graph = action_graph.get_inner_graph();
+
+    graph
+        .node_indices()
+        .map(|ni| (ni.index(), GraphNodeSubject::Action(graph[ni].clone())))
+        .collect()
+}
+
+fn create_json_response(data: String, status: u16) -> Response<std::io::Cursor<Vec<u8>>> {
+    let mut response = Response::from_data(data).with_status_code(StatusCode(status));
+    response
+        .add_header(Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap());
+    response
+}
+
+fn parse_filter_params(url: &Url) -> GraphFilterParams {
+    let mut params = GraphFilterParams {
+        depth: 1,
+        ..GraphFilterParams::default()
+    };
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "affected" => params.affected = value == "true",
+            "depth" => params.depth = value.parse().unwrap_or(1),
+            "focus" => params.focus = value.parse().ok(),
+            "query" => params.query = Some(value.into_owned()),
+            _ => {}
+        };
+    }
+
+    params
+}
+
+pub async fn respond_to_request(
     req: Request,
     tera: &mut Tera,
-    graph: &GraphInfoDto,
-    page_title: String,
+    explorer: &GraphExplorer,
 ) -> AppResult {
-    let response = match req.url() {
+    let url = Url::parse(&format!("http://localhost{}", req.url())).into_diagnostic()?;
+
+    let response = match url.path() {
         "/graph-data" => {
-            let mut response = Response::from_data(json::format(graph, false)?);
-            response.add_header(
-                Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
-            );
-            response
+            // Invalid queries should not crash the server
+            match explorer.filter_graph(&parse_filter_params(&url)).await {
+                Ok(graph) => create_json_response(json::format(&graph, false)?, 200),
+                Err(error) => create_json_response(
+                    json::format(&FxHashMap::from_iter([("error", error.to_string())]), false)?,
+                    400,
+                ),
+            }
+        }
+        "/node-details" => {
+            let details = match url
+                .query_pairs()
+                .find(|(key, _)| key == "id")
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+            {
+                Some(id) => explorer.get_node_details(id),
+                None => Ok(None),
+            };
+
+            match details {
+                Ok(Some(details)) => create_json_response(json::format(&details, false)?, 200),
+                Ok(None) => create_json_response(
+                    json::format(
+                        &FxHashMap::from_iter([("error", "Node not found.".to_owned())]),
+                        false,
+                    )?,
+                    404,
+                ),
+                Err(error) => create_json_response(
+                    json::format(&FxHashMap::from_iter([("error", error.to_string())]), false)?,
+                    400,
+                ),
+            }
         }
         _ => {
-            let graph_data = json::format(graph, false)?;
+            let graph_data = json::format(explorer.get_graph(), false)?;
             let js_url = get_js_url();
             let context = RenderContext {
-                page_title,
+                page_title: explorer.page_title.clone(),
                 graph_data,
                 js_url,
             };
@@ -135,6 +239,54 @@ pub fn respond_to_request(
     Ok(None)
 }
 
+/// Print all cycles and where each dependency was declared,
+/// and return a failing exit code if any cycles were found.
+pub fn print_dependency_cycles(cycles: &[DependencyCycle], label: &str) -> AppResult {
+    if cycles.is_empty() {
+        println!("No {label} dependency cycles found");
+
+        return Ok(None);
+    }
+
+    println!(
+        "Found {} {label} dependency {}\n",
+        cycles.len(),
+        if cycles.len() == 1 { "cycle" } else { "cycles" }
+    );
+
+    for (index, cycle) in cycles.iter().enumerate() {
+        println!(
+            "{} {}",
+            color::muted_light(format!("{}.", index + 1)),
+            cycle
+                .get_path()
+                .into_iter()
+                .map(color::id)
+                .collect::<Vec<_>>()
+                .join(color::muted(" → ").as_str())
+        );
+
+        for edge in &cycle.edges {
+            let location = match (&edge.file, edge.line) {
+                (Some(file), Some(line)) => color::file(format!("{file}:{line}")),
+                (Some(file), None) => color::file(file),
+                _ => color::muted("(inferred)"),
+            };
+
+            println!(
+                "   {} → {} {}",
+                color::label(&edge.source),
+                color::label(&edge.target),
+                location
+            );
+        }
+
+        println!();
+    }
+
+    Ok(Some(1))
+}
+
 // Use the local version of the JS file when in development mode otherwise the CDN URL.
 pub fn get_js_url() -> String {
     match env::var("MOON_JS_URL") {
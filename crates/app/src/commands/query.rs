@@ -1,5 +1,6 @@
 pub use crate::queries::hash::query_hash;
 pub use crate::queries::hash_diff::query_hash_diff;
+pub use crate::queries::owners::*;
 pub use crate::queries::projects::*;
 pub use crate::queries::tasks::*;
 pub use crate::queries::touched_files::*;
@@ -32,6 +33,13 @@ pub enum QueryCommands {
     )]
     HashDiff(QueryHashDiffArgs),
 
+    #[command(
+        name = "owners",
+        about = "Query for the owners of files.",
+        long_about = "Query for the owners of files, using the same precedence rules as the generated `CODEOWNERS` file."
+    )]
+    Owners(QueryOwnersArgs),
+
     #[command(
         name = "projects",
         about = "Query for projects within the project graph.",
@@ -148,6 +156,56 @@ pub async fn hash_diff(session: CliSession, args: QueryHashDiffArgs) -> AppResul
     Ok(None)
 }
 
+#[derive(Args, Clone, Debug)]
+pub struct QueryOwnersArgs {
+    #[arg(required = true, help = "List of files to find owners for")]
+    files: Vec<String>,
+
+    #[arg(long, help = "Print the owners in JSON format")]
+    json: bool,
+}
+
+#[instrument(skip_all)]
+pub async fn owners(session: CliSession, args: QueryOwnersArgs) -> AppResult {
+    let console = &session.console;
+    let workspace_graph = session.get_workspace_graph().await?;
+
+    let result = query_owners(
+        &workspace_graph,
+        &session.workspace_config,
+        &session.workspace_root,
+        &session.working_dir,
+        &args.files,
+    )?;
+
+    // Write to stdout directly to avoid broken pipe panics
+    if args.json {
+        console.out.write_line(json::format(&result, true)?)?;
+    } else if !result.files.is_empty() {
+        console.out.write_line(
+            result
+                .files
+                .iter()
+                .map(|file| {
+                    format!(
+                        "{} | {} | {}",
+                        file.file,
+                        file.project.as_ref().map(|id| id.as_str()).unwrap_or("..."),
+                        if file.owners.is_empty() {
+                            "...".into()
+                        } else {
+                            file.owners.join(" ")
+                        }
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )?;
+    }
+
+    Ok(None)
+}
+
 #[derive(Args, Clone, Debug)]
 pub struct QueryProjectsArgs {
     #[arg(help = "Filter projects using a query (takes precedence over options)")]
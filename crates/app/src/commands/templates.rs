@@ -1,8 +1,26 @@
+use super::run::{run_target, RunArgs};
 use crate::session::CliSession;
-use moon_codegen::{templates_command, CodeGenerator, TemplatesArgs};
+use clap::Subcommand;
+use moon_codegen::{
+    diff_template_snapshot, get_template_snapshot_dir, render_template_test, templates_command,
+    CodeGenerator, TemplateSnapshotChange, TemplatesArgs, TemplatesTestArgs,
+};
+use moon_task::TargetLocator;
 use starbase::AppResult;
+use starbase_styles::color;
+use starbase_utils::fs;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{debug, instrument};
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum TemplatesCommands {
+    #[command(
+        name = "test",
+        about = "Render templates with their test variables and compare against snapshots."
+    )]
+    Test(TemplatesTestArgs),
+}
 
 #[instrument(skip_all)]
 pub async fn templates(session: CliSession, args: TemplatesArgs) -> AppResult {
@@ -14,3 +32,177 @@ pub async fn templates(session: CliSession, args: TemplatesArgs) -> AppResult {
 
     templates_command(generator, &session.console, &args).await
 }
+
+#[instrument(skip_all)]
+pub async fn test(session: CliSession, args: TemplatesTestArgs) -> AppResult {
+    let mut generator = CodeGenerator::new(
+        &session.workspace_root,
+        &session.workspace_config.generator,
+        Arc::clone(&session.moon_env),
+    );
+    generator.set_toolchain_config(&session.toolchain_config);
+    generator.set_workspace_graph(Arc::new(session.get_workspace_graph().await?));
+    generator.load_templates().await?;
+
+    let console = &session.console;
+
+    let ids = if args.ids.is_empty() {
+        generator
+            .templates
+            .iter()
+            .filter(|(_, template)| !template.config.tests.is_empty())
+            .map(|(id, _)| id.to_string())
+            .collect::<BTreeSet<_>>()
+    } else {
+        BTreeSet::from_iter(args.ids.clone())
+    };
+
+    if ids.is_empty() {
+        console
+            .err
+            .write_line("There are no templates with configured tests")?;
+
+        return Ok(Some(1));
+    }
+
+    let out = console.stdout();
+    let tests_dir = session.moon_env.temp_dir.join("template-tests");
+    let mut failed = 0;
+    let mut passed = 0;
+
+    for id in ids {
+        let template = generator.get_template(&id)?;
+
+        out.print_entry_header(&template.id)?;
+
+        if template.config.tests.is_empty() {
+            out.write_line(color::muted_light("No tests configured"))?;
+            continue;
+        }
+
+        for (name, test) in BTreeMap::from_iter(&template.config.tests) {
+            let output_dir = tests_dir.join(template.id.as_str()).join(name);
+            let snapshot_dir = get_template_snapshot_dir(&template, name);
+
+            debug!(
+                template = template.id.as_str(),
+                test = name,
+                output = ?output_dir,
+                snapshot = ?snapshot_dir,
+                "Running template test",
+            );
+
+            render_template_test(
+                &template,
+                test,
+                &output_dir,
+                &session.workspace_root,
+                console,
+            )?;
+
+            let mut errors = vec![];
+
+            if args.update {
+                if snapshot_dir.exists() {
+                    fs::remove_dir_all(&snapshot_dir)?;
+                }
+
+                fs::create_dir_all(&snapshot_dir)?;
+                fs::copy_dir_all(&output_dir, &output_dir, &snapshot_dir)?;
+            } else if !snapshot_dir.exists() {
+                errors.push(format!(
+                    "Snapshot {} does not exist, run with {} to create it",
+                    color::path(&snapshot_dir),
+                    color::shell("--update"),
+                ));
+            } else {
+                for change in diff_template_snapshot(&output_dir, &snapshot_dir)? {
+                    errors.push(match change {
+                        TemplateSnapshotChange::Added(file) => {
+                            format!("{} {}", color::success("added"), color::file(file))
+                        }
+                        TemplateSnapshotChange::Changed(file) => {
+                            format!("{} {}", color::invalid("changed"), color::file(file))
+                        }
+                        TemplateSnapshotChange::Missing(file) => {
+                            format!("{} {}", color::failure("missing"), color::file(file))
+                        }
+                    });
+                }
+            }
+
+            // Run the task through the pipeline, with the rendered
+            // output passed as an argument so that it can be verified
+            if errors.is_empty() {
+                if let Some(target) = &test.task {
+                    debug!(
+                        target = target.as_str(),
+                        output = ?output_dir,
+                        "Running template test task"
+                    );
+
+                    let result = run_target(
+                        &session,
+                        &RunArgs {
+                            force: true,
+                            passthrough: vec![output_dir.to_string_lossy().to_string()],
+                            ..RunArgs::default()
+                        },
+                        &[TargetLocator::Qualified(target.to_owned())],
+                    )
+                    .await;
+
+                    match result {
+                        Ok(None) | Ok(Some(0)) => {}
+                        Ok(Some(code)) => {
+                            errors.push(format!(
+                                "Task {} failed with exit code {code}",
+                                color::label(target)
+                            ));
+                        }
+                        Err(error) => {
+                            errors.push(format!("Task {} failed: {error}", color::label(target)));
+                        }
+                    };
+                }
+            }
+
+            if errors.is_empty() {
+                passed += 1;
+
+                out.write_line(format!(
+                    "{} {}",
+                    if args.update {
+                        color::success("updated")
+                    } else {
+                        color::success("pass")
+                    },
+                    color::label(name)
+                ))?;
+            } else {
+                failed += 1;
+
+                out.write_line(format!("{} {}", color::failure("fail"), color::label(name)))?;
+
+                for error in errors {
+                    out.write_line(format!("  {error}"))?;
+                }
+            }
+        }
+    }
+
+    out.write_newline()?;
+    out.write_line(format!(
+        "{} passed, {} failed",
+        color::symbol(passed.to_string()),
+        color::symbol(failed.to_string())
+    ))?;
+    out.write_newline()?;
+    out.flush()?;
+
+    if failed > 0 {
+        return Ok(Some(1));
+    }
+
+    Ok(None)
+}
@@ -19,9 +19,11 @@ pub async fn run_action_pipeline(
     action_graph: ActionGraph,
 ) -> miette::Result<Vec<Action>> {
     let workspace_graph = session.get_workspace_graph().await?;
+    let extension_registry = session.get_extension_registry().await?;
     let toolchain_registry = session.get_toolchain_registry().await?;
     let mut pipeline = ActionPipeline::new(
         session.get_app_context()?,
+        extension_registry,
         toolchain_registry,
         workspace_graph,
     );
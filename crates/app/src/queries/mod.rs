@@ -1,5 +1,6 @@
 pub mod hash;
 pub mod hash_diff;
+pub mod owners;
 pub mod projects;
 pub mod tasks;
 pub mod touched_files;
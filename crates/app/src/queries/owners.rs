@@ -0,0 +1,93 @@
+use moon_codeowners::{CodeownersResolver, CodeownersRule};
+use moon_common::path::{is_root_level_source, standardize_separators, WorkspaceRelativePathBuf};
+use moon_common::Id;
+use moon_config::{CodeownersOrderBy, WorkspaceConfig};
+use moon_workspace_graph::WorkspaceGraph;
+use serde::Serialize;
+use std::path::Path;
+use tracing::debug;
+
+#[derive(Serialize)]
+pub struct QueryOwnersFile {
+    pub file: WorkspaceRelativePathBuf,
+    pub owners: Vec<String>,
+    pub project: Option<Id>,
+    pub rules: Vec<CodeownersRule>,
+}
+
+#[derive(Default, Serialize)]
+pub struct QueryOwnersResult {
+    pub files: Vec<QueryOwnersFile>,
+}
+
+/// Resolve files to their project and owners, using the same
+/// precedence rules as the generated `CODEOWNERS` file.
+pub fn query_owners(
+    workspace_graph: &WorkspaceGraph,
+    workspace_config: &WorkspaceConfig,
+    workspace_root: &Path,
+    working_dir: &Path,
+    files: &[String],
+) -> miette::Result<QueryOwnersResult> {
+    debug!("Querying for owners of {} files", files.len());
+
+    // Sort the projects in the same order as the generated file
+    let mut projects = workspace_graph.projects.get_all_unexpanded();
+    let order_by = workspace_config.codeowners.order_by;
+
+    projects.sort_by(|a, d| match order_by {
+        CodeownersOrderBy::FileSource => a.source.cmp(&d.source),
+        CodeownersOrderBy::ProjectName => a.id.cmp(&d.id),
+    });
+
+    let mut resolver = CodeownersResolver::new(workspace_config.vcs.provider);
+
+    resolver.add_workspace_entries(&workspace_config.codeowners);
+
+    for project in &projects {
+        resolver.add_project_entry(&project.id, project.source.as_str(), &project.config.owners);
+    }
+
+    let mut result = QueryOwnersResult::default();
+
+    for file in files {
+        let abs_file = working_dir.join(file);
+        let rel_file = WorkspaceRelativePathBuf::from(standardize_separators(
+            abs_file
+                .strip_prefix(workspace_root)
+                .unwrap_or(Path::new(file))
+                .to_string_lossy(),
+        ));
+
+        // Find the deepest project that contains the file
+        let project = projects
+            .iter()
+            .filter(|project| {
+                is_root_level_source(project.source.as_str())
+                    || rel_file.starts_with(&project.source)
+            })
+            .max_by_key(|project| {
+                if is_root_level_source(project.source.as_str()) {
+                    0
+                } else {
+                    project.source.components().count()
+                }
+            })
+            .map(|project| project.id.clone());
+
+        let rules = resolver
+            .resolve(rel_file.as_str())?
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        result.files.push(QueryOwnersFile {
+            owners: resolver.resolve_owners(rel_file.as_str())?,
+            file: rel_file,
+            project,
+            rules,
+        });
+    }
+
+    Ok(result)
+}
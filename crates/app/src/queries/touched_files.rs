@@ -84,8 +84,9 @@ pub async fn query_touched_files(
             .await?
     } else {
         // Don't check for shallow since base is set,
-        // and we can assume the user knows what they're doing
-        if base_revision.is_none() {
+        // and we can assume the user knows what they're doing.
+        // Also don't check if history will be deepened automatically.
+        if base_revision.is_none() && !vcs.can_deepen_shallow_checkout() {
             check_shallow!(vcs);
         }
 
@@ -1,4 +1,4 @@
-use crate::app::{Cli, Commands};
+use crate::app::{Cli, Commands, UiMode};
 use crate::app_error::AppError;
 use crate::components::*;
 use crate::systems::*;
@@ -7,16 +7,18 @@ use moon_action_graph::ActionGraphBuilder;
 use moon_app_context::AppContext;
 use moon_cache::CacheEngine;
 use moon_common::{is_ci, is_test_env};
-use moon_config::{ConfigLoader, InheritedTasksManager, ToolchainConfig, WorkspaceConfig};
+use moon_config::{
+    ConfigLoader, InheritedTasksManager, ToolchainConfig, VcsManager, WorkspaceConfig,
+};
 use moon_console::Console;
-use moon_console_reporter::DefaultReporter;
+use moon_console_reporter::{DefaultReporter, TuiReporter};
 use moon_env::MoonEnvironment;
 use moon_extension_plugin::*;
 use moon_plugin::{PluginHostData, PluginId};
 use moon_project_graph::ProjectGraph;
 use moon_task_graph::TaskGraph;
 use moon_toolchain_plugin::*;
-use moon_vcs::{BoxedVcs, Git};
+use moon_vcs::{BoxedVcs, Git, Jujutsu, Sapling};
 use moon_workspace::WorkspaceBuilder;
 use moon_workspace_graph::WorkspaceGraph;
 use once_cell::sync::OnceCell;
@@ -177,13 +179,33 @@ impl CliSession {
     pub fn get_vcs_adapter(&self) -> miette::Result<Arc<BoxedVcs>> {
         let item = self.vcs_adapter.get_or_try_init(|| {
             let config = &self.workspace_config.vcs;
-            let git = Git::load(
-                &self.workspace_root,
-                &config.default_branch,
-                &config.remote_candidates,
-            )?;
+            let vcs: BoxedVcs = match config.manager {
+                VcsManager::Git => {
+                    let mut git = Git::load(
+                        &self.workspace_root,
+                        &config.default_branch,
+                        &config.remote_candidates,
+                    )?;
+
+                    if config.auto_deepen {
+                        git.deepen_limit = config.auto_deepen_limit;
+                    }
+
+                    Box::new(git)
+                }
+                VcsManager::Jujutsu => Box::new(Jujutsu::load(
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+                VcsManager::Sapling => Box::new(Sapling::load(
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+            };
 
-            Ok::<_, miette::Report>(Arc::new(Box::new(git)))
+            Ok::<_, miette::Report>(Arc::new(vcs))
         })?;
 
         Ok(Arc::clone(item))
@@ -196,6 +218,16 @@ impl CliSession {
         Ok(WorkspaceGraph::new(projects, tasks))
     }
 
+    /// Reset the lazily loaded graphs (and registries that depend on them),
+    /// so that they are rebuilt on next access. This is required when projects
+    /// or tasks have been created or changed during the session.
+    pub fn reset_workspace_graph(&mut self) {
+        self.extension_registry.take();
+        self.project_graph.take();
+        self.task_graph.take();
+        self.toolchain_registry.take();
+    }
+
     pub fn is_telemetry_enabled(&self) -> bool {
         self.workspace_config.telemetry
     }
@@ -207,6 +239,15 @@ impl CliSession {
         )
     }
 
+    pub fn should_render_tui(&self) -> bool {
+        self.cli.ui == UiMode::Tui
+            && !self.cli.quiet
+            && !is_ci()
+            && !is_test_env()
+            && self.console.out.is_terminal()
+            && matches!(self.cli.command, Commands::Check(_) | Commands::Run(_))
+    }
+
     pub fn requires_toolchain_installed(&self) -> bool {
         matches!(
             self.cli.command,
@@ -231,7 +272,11 @@ impl CliSession {
 impl AppSession for CliSession {
     /// Setup initial state for the session. Order is very important!!!
     async fn startup(&mut self) -> AppResult {
-        self.console.set_reporter(DefaultReporter::default());
+        if self.should_render_tui() {
+            self.console.set_reporter(TuiReporter::default());
+        } else {
+            self.console.set_reporter(DefaultReporter::default());
+        }
 
         // Determine paths
 
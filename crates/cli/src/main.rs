@@ -8,6 +8,7 @@ use moon_app::commands::migrate::MigrateCommands;
 use moon_app::commands::node::NodeCommands;
 use moon_app::commands::query::QueryCommands;
 use moon_app::commands::sync::SyncCommands;
+use moon_app::commands::templates::TemplatesCommands;
 use moon_app::{commands, systems::bootstrap, Cli, CliSession, Commands};
 use starbase::diagnostics::IntoDiagnostic;
 use starbase::tracing::TracingOptions;
@@ -175,6 +176,7 @@ async fn main() -> MainResult {
                     QueryCommands::HashDiff(args) => {
                         commands::query::hash_diff(session, args).await
                     }
+                    QueryCommands::Owners(args) => commands::query::owners(session, args).await,
                     QueryCommands::Projects(args) => commands::query::projects(session, args).await,
                     QueryCommands::Tasks(args) => commands::query::tasks(session, args).await,
                     QueryCommands::TouchedFiles(args) => {
@@ -199,7 +201,12 @@ async fn main() -> MainResult {
                 Commands::Task(args) => commands::task::task(session, args).await,
                 Commands::TaskGraph(args) => commands::graph::task::task_graph(session, args).await,
                 Commands::Teardown => commands::teardown::teardown().await,
-                Commands::Templates(args) => commands::templates::templates(session, args).await,
+                Commands::Templates { command, args } => match command {
+                    Some(TemplatesCommands::Test(args)) => {
+                        commands::templates::test(session, args).await
+                    }
+                    None => commands::templates::templates(session, args).await,
+                },
                 Commands::Upgrade => commands::upgrade::upgrade(session).await,
             }
         })
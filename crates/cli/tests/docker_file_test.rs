@@ -141,4 +141,26 @@ mod dockerfile {
         assert!(file.contains("moon run with-config:serve"));
         assert!(file.contains("moon docker prune"));
     }
+
+    #[test]
+    fn uses_runtime_image() {
+        let sandbox = create_moon_sandbox("dockerfile");
+
+        sandbox
+            .run_bin(|cmd| {
+                cmd.args(["docker", "file", "with-runtime", "--defaults"]);
+            })
+            .success();
+
+        let file = fs::read_to_string(sandbox.path().join("with-runtime/Dockerfile")).unwrap();
+
+        assert!(file.contains("--mount=type=cache,target=/root/.cache/custom"));
+        assert!(file.contains("FROM node:slim AS start"));
+        assert!(file
+            .contains("COPY --from=build --chown=node /app/with-runtime/dist ./with-runtime/dist"));
+        assert!(file.contains("USER node"));
+        assert!(file.contains("WORKDIR /app/with-runtime"));
+        assert!(file.contains(r#"CMD ["node","dist/index.js"]"#));
+        assert!(!file.contains("moon run with-runtime:start"));
+    }
 }
@@ -1,12 +1,13 @@
-use moon_app::commands::docker::DockerManifest;
+use moon_app::commands::docker::{DockerManifest, DockerScaffoldManifest};
 use moon_common::Id;
 use moon_config::{PartialWorkspaceConfig, PartialWorkspaceProjects};
 use moon_test_utils::{
     create_sandbox_with_config, get_cases_fixture_configs, get_node_depman_fixture_configs,
     get_node_fixture_configs, get_projects_fixture_configs, predicates::prelude::*,
 };
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::FxHashMap;
 use starbase_utils::json;
+use std::collections::BTreeSet;
 use std::{fs, path::Path};
 
 fn write_manifest(path: &Path, id: &str) {
@@ -14,8 +15,8 @@ fn write_manifest(path: &Path, id: &str) {
         path.join("dockerManifest.json"),
         json::format(
             &DockerManifest {
-                focused_projects: FxHashSet::from_iter([Id::raw(id)]),
-                unfocused_projects: FxHashSet::default(),
+                focused_projects: BTreeSet::from_iter([Id::raw(id)]),
+                unfocused_projects: BTreeSet::default(),
             },
             false,
         )
@@ -323,6 +324,96 @@ mod scaffold_sources {
         assert!(!docker.join("deps/baz").exists());
     }
 
+    #[test]
+    fn copies_projects_from_query() {
+        let (workspace_config, toolchain_config, tasks_config) = get_projects_fixture_configs();
+
+        let sandbox = create_sandbox_with_config(
+            "projects",
+            Some(workspace_config),
+            Some(toolchain_config),
+            Some(tasks_config),
+        );
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("docker")
+                .arg("scaffold")
+                .arg("--query")
+                .arg("tag=react");
+        });
+
+        let docker = sandbox.path().join(".moon/docker/sources");
+
+        assert!(docker.join("advanced").exists());
+        assert!(docker.join("deps/foo").exists());
+        assert!(docker.join("deps/bar").exists());
+        assert!(docker.join("deps/baz").exists());
+
+        // Check that some others DO NOT exist
+        assert!(!docker.join("basic").exists());
+        assert!(!docker.join("tasks").exists());
+    }
+
+    #[test]
+    fn errors_without_projects() {
+        let (workspace_config, toolchain_config, tasks_config) = get_projects_fixture_configs();
+
+        let sandbox = create_sandbox_with_config(
+            "projects",
+            Some(workspace_config),
+            Some(toolchain_config),
+            Some(tasks_config),
+        );
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("docker")
+                .arg("scaffold")
+                .arg("--query")
+                .arg("tag=unknown");
+        });
+
+        assert!(predicate::str::contains("No projects to scaffold").eval(&assert.output()));
+    }
+
+    #[test]
+    fn writes_scaffold_manifest() {
+        let (workspace_config, toolchain_config, tasks_config) = get_projects_fixture_configs();
+
+        let sandbox = create_sandbox_with_config(
+            "projects",
+            Some(workspace_config),
+            Some(toolchain_config),
+            Some(tasks_config),
+        );
+
+        let run = || {
+            sandbox.run_moon(|cmd| {
+                cmd.arg("docker").arg("scaffold").arg("basic");
+            });
+
+            json::read_file::<_, DockerScaffoldManifest>(
+                sandbox.path().join(".moon/docker/scaffoldManifest.json"),
+            )
+            .unwrap()
+        };
+
+        let manifest = run();
+
+        assert!(manifest.files.contains_key("sources/dockerManifest.json"));
+        assert!(manifest.files.contains_key("sources/basic/file.ts"));
+        assert!(manifest.files.contains_key("workspace/dockerManifest.json"));
+        assert!(manifest.files.contains_key("workspace/basic/moon.yml"));
+        assert!(!manifest.files.contains_key("scaffoldManifest.json"));
+
+        // Unchanged sources produce the same digest
+        assert_eq!(run().digest, manifest.digest);
+
+        // Changed sources produce a different digest
+        sandbox.create_file("basic/file.ts", "export const changed = true;");
+
+        assert_ne!(run().digest, manifest.digest);
+    }
+
     #[test]
     fn can_include_more_files() {
         let (workspace_config, toolchain_config, tasks_config) = get_cases_fixture_configs();
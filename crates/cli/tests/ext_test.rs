@@ -51,6 +51,7 @@ mod ext_download {
                     PartialExtensionConfig {
                         plugin: None,
                         config: None,
+                        ..Default::default()
                     },
                 );
         });
@@ -79,6 +80,7 @@ mod ext_download {
                             path: Some(PathBuf::from("invalid.wasm")),
                         }))),
                         config: None,
+                        ..Default::default()
                     },
                 );
         });
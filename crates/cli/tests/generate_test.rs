@@ -1,7 +1,9 @@
 use moon_common::path::standardize_separators;
+use moon_config::{PartialWorkspaceConfig, PartialWorkspaceProjects};
 use moon_test_utils::{
     assert_snapshot, create_sandbox_with_config, predicates::prelude::*, Sandbox,
 };
+use starbase_utils::string_vec;
 use std::fs;
 
 fn generate_sandbox() -> Sandbox {
@@ -54,6 +56,29 @@ fn generates_files_into_default_dest() {
     assert!(sandbox.path().join("apps/foo-bar/file.txt").exists());
 }
 
+#[test]
+fn updates_files_in_default_dest() {
+    let sandbox = generate_sandbox();
+
+    sandbox
+        .run_moon(|cmd| {
+            cmd.arg("generate").arg("dest").arg("--defaults");
+        })
+        .success();
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("dest")
+            .arg("--defaults")
+            .arg("--update");
+    });
+
+    assert.success();
+
+    assert!(sandbox.path().join("apps/foo-bar/file.txt").exists());
+    assert!(!sandbox.path().join("file.txt").exists());
+}
+
 #[test]
 fn generates_files_into_workspace_relative_dest() {
     let sandbox = generate_sandbox();
@@ -446,3 +471,40 @@ mod frontmatter {
         .unwrap());
     }
 }
+
+mod hooks {
+    use super::*;
+
+    #[test]
+    fn runs_target_from_generated_project() {
+        let sandbox = create_sandbox_with_config(
+            "generator",
+            Some(PartialWorkspaceConfig {
+                projects: Some(PartialWorkspaceProjects::Globs(string_vec!["apps/*"])),
+                ..PartialWorkspaceConfig::default()
+            }),
+            None,
+            None,
+        );
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("generate")
+                .arg("project")
+                .arg("./apps/new-app")
+                .arg("--defaults");
+        });
+
+        let output = assert.output();
+
+        // The graph was loaded before the project was generated
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("apps/new-app/count.txt"))
+                .unwrap()
+                .trim(),
+            "0"
+        );
+        assert!(predicate::str::contains("hello from new-app").eval(&output));
+
+        assert.success();
+    }
+}
@@ -1,13 +1,19 @@
 use httpmock::prelude::*;
-use moon_config::PartialNotifierConfig;
+use moon_config::{PartialNotifierConfig, PartialNotifierWebhookConfig};
 use moon_test_utils::{create_sandbox_with_config, get_node_fixture_configs, Sandbox};
+use rustc_hash::FxHashMap;
 
 fn sandbox(uri: String) -> Sandbox {
+    sandbox_with_notifier(PartialNotifierConfig {
+        webhook_url: Some(format!("{uri}/webhook")),
+        ..PartialNotifierConfig::default()
+    })
+}
+
+fn sandbox_with_notifier(notifier: PartialNotifierConfig) -> Sandbox {
     let (mut workspace_config, toolchain_config, tasks_config) = get_node_fixture_configs();
 
-    workspace_config.notifier = Some(PartialNotifierConfig {
-        webhook_url: Some(format!("{uri}/webhook")),
-    });
+    workspace_config.notifier = Some(notifier);
 
     let sandbox = create_sandbox_with_config(
         "node",
@@ -103,3 +109,143 @@ async fn all_webhooks_have_same_uuid() {
 
     mock.assert_hits(22);
 }
+
+mod webhooks_setting {
+    use super::*;
+
+    #[tokio::test]
+    async fn only_sends_filtered_events() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/filtered")
+                .json_body_partial(r#"{"type":"task.ran"}"#);
+            then.status(200);
+        });
+
+        let sandbox = sandbox_with_notifier(PartialNotifierConfig {
+            webhooks: Some(vec![PartialNotifierWebhookConfig {
+                events: Some(vec!["task.ran".into()]),
+                url: Some(server.url("/filtered")),
+                ..PartialNotifierWebhookConfig::default()
+            }]),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("node:cjs");
+        });
+
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn doesnt_send_successful_events_when_failures_only() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/failures");
+            then.status(200);
+        });
+
+        let sandbox = sandbox_with_notifier(PartialNotifierConfig {
+            webhooks: Some(vec![PartialNotifierWebhookConfig {
+                failures_only: Some(true),
+                url: Some(server.url("/failures")),
+                ..PartialNotifierWebhookConfig::default()
+            }]),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("node:cjs");
+        });
+
+        mock.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn includes_custom_headers() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/headers")
+                .header("X-Custom", "value");
+            then.status(200);
+        });
+
+        let sandbox = sandbox_with_notifier(PartialNotifierConfig {
+            webhooks: Some(vec![PartialNotifierWebhookConfig {
+                events: Some(vec!["pipeline.completed".into()]),
+                headers: Some(FxHashMap::from_iter([("X-Custom".into(), "value".into())])),
+                url: Some(server.url("/headers")),
+                ..PartialNotifierWebhookConfig::default()
+            }]),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("node:cjs");
+        });
+
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn signs_requests_with_secret() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/signed")
+                .header_exists("X-Moon-Signature-256");
+            then.status(200);
+        });
+
+        let sandbox = sandbox_with_notifier(PartialNotifierConfig {
+            webhooks: Some(vec![PartialNotifierWebhookConfig {
+                events: Some(vec!["pipeline.completed".into()]),
+                secret_env: Some("WEBHOOK_SECRET".into()),
+                url: Some(server.url("/signed")),
+                ..PartialNotifierWebhookConfig::default()
+            }]),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("node:cjs")
+                .env("WEBHOOK_SECRET", "secret");
+        });
+
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn retries_failed_requests() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/retry");
+            then.status(503);
+        });
+
+        let sandbox = sandbox_with_notifier(PartialNotifierConfig {
+            webhooks: Some(vec![PartialNotifierWebhookConfig {
+                retry_count: Some(2),
+                url: Some(server.url("/retry")),
+                ..PartialNotifierWebhookConfig::default()
+            }]),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("node:cjs");
+        });
+
+        // First event + 2 retries, then disabled
+        mock.assert_hits(3);
+    }
+}
@@ -1,28 +1,57 @@
 use crate::codegen_error::CodegenError;
+use crate::funcs::register_toolchain_functions;
 use crate::template::Template;
 use miette::IntoDiagnostic;
-use moon_common::path::RelativePathBuf;
+use moon_common::path::{Component, RelativePathBuf};
 use moon_common::Id;
-use moon_config::{load_template_config_template, ConfigFinder, GeneratorConfig, TemplateLocator};
+use moon_config::{
+    load_template_config_template, ConfigFinder, GeneratorConfig, TemplateLocator, ToolchainConfig,
+};
 use moon_env::MoonEnvironment;
+use moon_oci::{
+    create_oci_digest, parse_json, ImageManifest, ImageReference, OciError, RegistryClient,
+    ANNOTATION_TITLE,
+};
 use moon_process::Command;
 use moon_time::now_millis;
+use moon_workspace_graph::WorkspaceGraph;
 use rustc_hash::FxHashMap;
+use sha2::{Digest, Sha256};
 use starbase_archive::Archiver;
 use starbase_utils::{fs, glob, net};
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::task::spawn;
 use tracing::{debug, instrument};
 
-#[derive(Debug)]
 pub struct CodeGenerator<'app> {
     pub config: &'app GeneratorConfig,
     pub templates: FxHashMap<Id, Template>,
     pub template_locations: Vec<PathBuf>,
 
-    moon_env: Arc<MoonEnvironment>,
-    workspace_root: &'app Path,
+    pub moon_env: Arc<MoonEnvironment>,
+    pub workspace_root: &'app Path,
+
+    location_locators: FxHashMap<PathBuf, TemplateLocator>,
+    toolchain_config: Option<&'app ToolchainConfig>,
+    workspace_graph: Option<Arc<WorkspaceGraph>>,
+}
+
+// The workspace graph doesn't implement `Debug`, so only note its presence
+impl fmt::Debug for CodeGenerator<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CodeGenerator")
+            .field("config", &self.config)
+            .field("templates", &self.templates)
+            .field("template_locations", &self.template_locations)
+            .field("moon_env", &self.moon_env)
+            .field("workspace_root", &self.workspace_root)
+            .field("location_locators", &self.location_locators)
+            .field("toolchain_config", &self.toolchain_config)
+            .field("has_workspace_graph", &self.workspace_graph.is_some())
+            .finish()
+    }
 }
 
 impl<'app> CodeGenerator<'app> {
@@ -38,13 +67,29 @@ impl<'app> CodeGenerator<'app> {
 
         CodeGenerator {
             config,
+            location_locators: FxHashMap::default(),
             moon_env,
             templates: FxHashMap::default(),
             template_locations: vec![],
+            toolchain_config: None,
+            workspace_graph: None,
             workspace_root,
         }
     }
 
+    /// Provide the toolchain configuration, so that templates
+    /// can render toolchain aware functions, like `toolchain_version()`.
+    pub fn set_toolchain_config(&mut self, toolchain_config: &'app ToolchainConfig) {
+        self.toolchain_config = Some(toolchain_config);
+    }
+
+    /// Provide the workspace graph for all templates, so that they can render
+    /// workspace aware functions, like `project()`. When not provided, the graph
+    /// can be set on a template with [`Template::set_workspace_graph`] instead.
+    pub fn set_workspace_graph(&mut self, workspace_graph: Arc<WorkspaceGraph>) {
+        self.workspace_graph = Some(workspace_graph);
+    }
+
     #[instrument(skip_all)]
     pub async fn load_templates(&mut self) -> miette::Result<()> {
         self.resolve_template_locations().await?;
@@ -66,8 +111,9 @@ impl<'app> CodeGenerator<'app> {
 
                 debug!(root = ?template_root, "Found a template, attempting to load");
 
-                let template =
+                let mut template =
                     Template::new(Id::clean(fs::file_name(&template_root))?, template_root)?;
+                template.locator = self.location_locators.get(location).cloned();
 
                 if let Some(existing_template) = self.templates.get(&template.id) {
                     return Err(CodegenError::DuplicateTemplate {
@@ -133,6 +179,14 @@ impl<'app> CodeGenerator<'app> {
         // Clone base template
         let mut template = template.clone();
 
+        if let Some(toolchain_config) = self.toolchain_config {
+            register_toolchain_functions(&mut template.engine, toolchain_config);
+        }
+
+        if let Some(workspace_graph) = &self.workspace_graph {
+            template.set_workspace_graph(Arc::clone(workspace_graph));
+        }
+
         // Inherit other templates
         if !template.config.extends.is_empty() {
             debug!(
@@ -177,13 +231,45 @@ impl<'app> CodeGenerator<'app> {
     #[instrument(skip_all)]
     async fn resolve_template_locations(&mut self) -> miette::Result<()> {
         let mut locations = vec![];
+        let mut locators = FxHashMap::default();
         let mut futures = vec![];
         let config_file_names = ConfigFinder::default().get_template_file_names();
 
         debug!("Resolving template locations to absolute file paths");
 
         for locator in &self.config.templates {
+            let start_index = locations.len();
+
             match locator {
+                TemplateLocator::Archive { url, checksum } => {
+                    // Ignore the query string, as signed URLs change on every request
+                    let url_path = url.split('?').next().unwrap_or(url);
+
+                    // Include the checksum so that changing it re-downloads the archive,
+                    // instead of reusing a location that was never verified against it
+                    let url_hash = hash_sha256(
+                        format!("{url_path}{}", checksum.as_deref().unwrap_or_default()).as_bytes(),
+                    );
+                    let template_location =
+                        self.moon_env.templates_dir.join("archive").join(&url_hash);
+                    let temp_file = self.moon_env.temp_dir.join(format!(
+                        "{url_hash}.{}",
+                        if url_path.ends_with(".zip") {
+                            "zip"
+                        } else {
+                            "tar.gz"
+                        }
+                    ));
+
+                    futures.push(spawn(download_and_unpack_archive(
+                        url.to_owned(),
+                        checksum.to_owned(),
+                        template_location.clone(),
+                        temp_file,
+                    )));
+
+                    locations.push(template_location);
+                }
                 TemplateLocator::File { path } => {
                     locations.push(
                         RelativePathBuf::from(path)
@@ -243,15 +329,40 @@ impl<'app> CodeGenerator<'app> {
                         temp_file,
                     )));
 
+                    locations.push(template_location);
+                }
+                TemplateLocator::Oci {
+                    registry,
+                    repository,
+                    tag,
+                } => {
+                    let template_location = self
+                        .moon_env
+                        .templates_dir
+                        .join("oci")
+                        .join(registry.replace(':', "_"))
+                        .join(repository)
+                        .join(tag.replace(':', "_"));
+
+                    futures.push(spawn(pull_oci_artifact(
+                        locator.get_oci_reference().unwrap(),
+                        template_location.clone(),
+                    )));
+
                     locations.push(template_location);
                 }
             }
+
+            for location in &locations[start_index..] {
+                locators.insert(location.clone(), locator.clone());
+            }
         }
 
         for future in futures {
             future.await.into_diagnostic()??;
         }
 
+        self.location_locators = locators;
         self.template_locations = locations;
 
         Ok(())
@@ -379,3 +490,201 @@ async fn download_and_unpack_npm_archive(
 
     Ok(())
 }
+
+fn hash_sha256(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::default();
+    hasher.update(bytes);
+
+    format!("{:x}", hasher.finalize())
+}
+
+#[instrument]
+async fn download_and_unpack_archive(
+    url: String,
+    checksum: Option<String>,
+    template_location: PathBuf,
+    temp_file: PathBuf,
+) -> miette::Result<()> {
+    debug!(url, "Resolving template location for archive");
+
+    if template_location.exists() {
+        debug!(location = ?template_location, "Template location already exists locally");
+
+        return Ok(());
+    }
+
+    // Download archive
+    debug!(url = &url, temp_file = ?temp_file, "Downloading archive");
+
+    net::download_from_url(&url, &temp_file).await?;
+
+    // Verify checksum
+    if let Some(expected) = checksum {
+        let actual = hash_sha256(&fs::read_file_bytes(&temp_file)?);
+
+        debug!(expected, actual, "Verifying archive checksum");
+
+        if actual != expected {
+            fs::remove_file(&temp_file)?;
+
+            return Err(CodegenError::ArchiveChecksumMismatch {
+                url,
+                expected,
+                actual,
+            }
+            .into());
+        }
+    }
+
+    // Unpack archive
+    debug!(
+        temp_file = ?temp_file,
+        location = ?template_location,
+        "Unpacking archive into template location",
+    );
+
+    // Unpack into a sibling directory first, and move it into place once complete,
+    // so that a failed or interrupted unpack isn't considered cached
+    let unpack_dir = template_location.with_extension("unpacking");
+
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
+
+    let result = Archiver::new(&unpack_dir, &temp_file).unpack_from_ext();
+
+    fs::remove_file(&temp_file)?;
+
+    if let Err(error) = result {
+        fs::remove_dir_all(&unpack_dir)?;
+
+        return Err(error);
+    }
+
+    fs::write_file(unpack_dir.join(".installed-at"), now_millis().to_string())?;
+    fs::rename(&unpack_dir, &template_location)?;
+
+    Ok(())
+}
+
+// Set by ORAS when a directory is pushed as a gzipped tarball
+const ORAS_UNPACK_ANNOTATION: &str = "io.deis.oras.content.unpack";
+
+#[instrument]
+async fn pull_oci_artifact(reference: String, template_location: PathBuf) -> miette::Result<()> {
+    debug!(reference, "Resolving template location for OCI artifact");
+
+    if template_location.exists() {
+        debug!(location = ?template_location, "Template location already exists locally");
+
+        return Ok(());
+    }
+
+    let client = RegistryClient::new(ImageReference::parse(&reference)?);
+    let manifest: ImageManifest = parse_json(
+        "artifact manifest",
+        &client
+            .get_manifest(client.reference.get_reference())
+            .await?,
+    )?;
+
+    debug!(
+        location = ?template_location,
+        layers = manifest.layers.len(),
+        "Pulling OCI artifact into template location",
+    );
+
+    // Pull into a sibling directory first, and move it into place once complete,
+    // so that a failed or interrupted pull isn't considered cached
+    let unpack_dir = template_location.with_extension("pulling");
+
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
+
+    let result = pull_oci_artifact_layers(&client, &reference, &manifest, &unpack_dir).await;
+
+    if let Err(error) = result {
+        fs::remove_dir_all(&unpack_dir)?;
+
+        return Err(error);
+    }
+
+    fs::write_file(unpack_dir.join(".installed-at"), now_millis().to_string())?;
+    fs::rename(&unpack_dir, &template_location)?;
+
+    Ok(())
+}
+
+async fn pull_oci_artifact_layers(
+    client: &RegistryClient,
+    reference: &str,
+    manifest: &ImageManifest,
+    unpack_dir: &Path,
+) -> miette::Result<()> {
+    fs::create_dir_all(unpack_dir)?;
+
+    for layer in &manifest.layers {
+        // Like ORAS, layers without a file name are not pulled
+        let Some(title) = layer.annotations.get(ANNOTATION_TITLE) else {
+            debug!(
+                digest = &layer.digest,
+                "Skipping artifact layer without a title"
+            );
+
+            continue;
+        };
+
+        let relative_path = RelativePathBuf::from(title);
+
+        if title.is_empty()
+            || relative_path
+                .components()
+                .any(|part| !matches!(part, Component::Normal(_)))
+        {
+            return Err(CodegenError::InvalidArtifactFile {
+                reference: reference.to_owned(),
+                file: title.to_owned(),
+            }
+            .into());
+        }
+
+        debug!(
+            digest = &layer.digest,
+            file = title,
+            "Pulling artifact layer"
+        );
+
+        let bytes = client.get_blob(&layer.digest).await?;
+        let actual = create_oci_digest(&bytes);
+
+        if actual != layer.digest {
+            return Err(OciError::DigestMismatch {
+                expected: layer.digest.clone(),
+                actual,
+            }
+            .into());
+        }
+
+        // Directories are pushed as tarballs, that contain the directory itself
+        if layer
+            .annotations
+            .get(ORAS_UNPACK_ANNOTATION)
+            .is_some_and(|value| value == "true")
+        {
+            let temp_file = unpack_dir.join(format!("{}.tar.gz", layer.get_hash()));
+
+            fs::write_file(&temp_file, bytes)?;
+
+            let result = Archiver::new(unpack_dir, &temp_file).unpack_from_ext();
+
+            fs::remove_file(&temp_file)?;
+
+            result?;
+        } else {
+            fs::write_file(relative_path.to_logical_path(unpack_dir), bytes)?;
+        }
+    }
+
+    Ok(())
+}
@@ -34,6 +34,43 @@ pub enum CodegenError {
     )]
     MissingTemplate(Id),
 
+    #[diagnostic(
+        code(codegen::template_lock::missing),
+        help = "Only destinations generated with a template lock can be updated."
+    )]
+    #[error(
+        "Unable to update {} with template {}, as no template lock exists for it at {}.",
+        .dest.style(Style::Path),
+        .id.style(Style::Id),
+        .path.style(Style::Path),
+    )]
+    MissingTemplateLock {
+        dest: PathBuf,
+        id: Id,
+        path: PathBuf,
+    },
+
+    #[diagnostic(code(codegen::template::archive_checksum_mismatch))]
+    #[error(
+        "Checksum mismatch for template archive {}.\nExpected {} but received {}.",
+        .url.style(Style::Url),
+        .expected.style(Style::Hash),
+        .actual.style(Style::Hash),
+    )]
+    ArchiveChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[diagnostic(code(codegen::template::invalid_artifact_file))]
+    #[error(
+        "Unable to pull template artifact {}, as the file {} is not a relative path within the template.",
+        .reference.style(Style::Url),
+        .file.style(Style::File),
+    )]
+    InvalidArtifactFile { reference: String, file: String },
+
     #[diagnostic(code(codegen::template::duplicate))]
     #[error(
         "Found multiple templates with the same name {}.\nOriginal template at {}.\nCurrent template at {}.",
@@ -69,6 +106,36 @@ pub enum CodegenError {
         error: Box<tera::Error>,
     },
 
+    #[diagnostic(code(codegen::args::invalid_value))]
+    #[error(
+        "Invalid value for variable {}. {}",
+        .name.style(Style::Property),
+        .error,
+    )]
+    InvalidVariableValue { name: String, error: String },
+
+    #[diagnostic(code(codegen::template::render_variable_failed))]
+    #[error(
+        "Failed to render the condition or computed value for variable {}.",
+        .name.style(Style::Property),
+    )]
+    RenderVariableFailed {
+        name: String,
+        #[source]
+        error: Box<tera::Error>,
+    },
+
+    #[diagnostic(code(codegen::template::render_hook_failed))]
+    #[error(
+        "Failed to render template hook {}.",
+        .hook.style(Style::Shell),
+    )]
+    RenderTemplateHookFailed {
+        hook: String,
+        #[source]
+        error: Box<tera::Error>,
+    },
+
     #[diagnostic(code(codegen::template_file::interpolate_path))]
     #[error(
         "Failed to interpolate variables into template file path {}.",
@@ -79,4 +146,53 @@ pub enum CodegenError {
         #[source]
         error: Box<tera::Error>,
     },
+
+    #[diagnostic(code(codegen::template_file::inject_missing_anchor))]
+    #[error(
+        "Unable to inject template file {}, as an {}, {}, or {} anchor is required.",
+        .path.style(Style::Path),
+        "after".style(Style::Property),
+        "before".style(Style::Property),
+        "marker".style(Style::Property),
+    )]
+    InjectMissingAnchor { path: PathBuf },
+
+    #[diagnostic(code(codegen::template_file::inject_missing_file))]
+    #[error(
+        "Unable to inject template file into {}, as the file does not exist.",
+        .path.style(Style::Path),
+    )]
+    InjectMissingFile { path: PathBuf },
+
+    #[diagnostic(code(codegen::template_file::inject_anchor_not_found))]
+    #[error(
+        "Unable to inject template file into {}, as the anchor {} could not be found.",
+        .path.style(Style::Path),
+        .anchor.style(Style::Symbol),
+    )]
+    InjectAnchorNotFound { anchor: String, path: PathBuf },
+
+    #[diagnostic(code(codegen::template_file::inject_invalid_anchor))]
+    #[error(
+        "Invalid inject anchor {} for template file {}.",
+        .anchor.style(Style::Symbol),
+        .path.style(Style::Path),
+    )]
+    InjectInvalidAnchor {
+        anchor: String,
+        path: PathBuf,
+        #[source]
+        error: Box<regex::Error>,
+    },
+
+    #[diagnostic(code(codegen::template_file::merge_toml_failed))]
+    #[error(
+        "Failed to merge TOML template file into {}.",
+        .path.style(Style::Path),
+    )]
+    MergeTomlFailed {
+        path: PathBuf,
+        #[source]
+        error: Box<toml_edit::TomlError>,
+    },
 }
@@ -52,6 +52,25 @@ pub fn upper_case(value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
     Ok(to_value(s.to_uppercase()).unwrap())
 }
 
+// INFLECTIONS
+
+pub fn pluralize(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    let s = try_get_value!("pluralize", "value", String, value);
+
+    let count = match args.get("count") {
+        Some(val) => try_get_value!("pluralize", "count", isize, val),
+        None => 2,
+    };
+
+    Ok(to_value(pluralizer::pluralize(&s, count, false)).unwrap())
+}
+
+pub fn singularize(value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
+    let s = try_get_value!("singularize", "value", String, value);
+
+    Ok(to_value(pluralizer::pluralize(&s, 1, false)).unwrap())
+}
+
 // PATHS
 
 pub fn path_join(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
@@ -1,9 +1,28 @@
 // HashMap is required for Tera
 #![allow(clippy::disallowed_types)]
 
-use starbase_utils::json::{JsonMap, JsonValue as Value};
+use moon_common::Id;
+use moon_config::{ToolchainConfig, UnresolvedVersionSpec};
+use moon_workspace_graph::WorkspaceGraph;
+use rustc_hash::FxHashMap;
+use starbase_utils::json::{serde_json::to_value, JsonMap, JsonValue as Value};
 use std::collections::HashMap;
-use tera::Result;
+use std::sync::Arc;
+use tera::{try_get_value, Error, Result, Tera};
+use uuid::Uuid;
+
+fn get_string_arg(
+    func_name: &str,
+    arg_name: &str,
+    args: &HashMap<String, Value>,
+) -> Result<String> {
+    match args.get(arg_name) {
+        Some(val) => Ok(try_get_value!(func_name, arg_name, String, val)),
+        None => Err(Error::msg(format!(
+            "Expected an `{arg_name}` for `{func_name}`."
+        ))),
+    }
+}
 
 pub fn variables(args: &HashMap<String, Value>) -> Result<Value> {
     let mut map = JsonMap::with_capacity(args.len());
@@ -11,3 +30,129 @@ pub fn variables(args: &HashMap<String, Value>) -> Result<Value> {
 
     Ok(Value::Object(map))
 }
+
+pub fn uuid(_: &HashMap<String, Value>) -> Result<Value> {
+    Ok(to_value(Uuid::new_v4().to_string()).unwrap())
+}
+
+// WORKSPACE
+
+pub fn project(workspace_graph: &WorkspaceGraph, args: &HashMap<String, Value>) -> Result<Value> {
+    let id = get_string_arg("project", "id", args)?;
+    let project = workspace_graph
+        .get_project(&id)
+        .map_err(|error| Error::msg(error.to_string()))?;
+
+    to_value(project.as_ref()).map_err(Error::json)
+}
+
+pub fn projects(workspace_graph: &WorkspaceGraph, args: &HashMap<String, Value>) -> Result<Value> {
+    let projects = match args.get("query") {
+        Some(val) => {
+            let query = try_get_value!("projects", "query", String, val);
+
+            moon_query::build_query(&query)
+                .and_then(|criteria| workspace_graph.query_projects(criteria))
+        }
+        None => workspace_graph.get_projects(),
+    }
+    .map_err(|error| Error::msg(error.to_string()))?;
+
+    to_value(
+        projects
+            .iter()
+            .map(|project| project.as_ref())
+            .collect::<Vec<_>>(),
+    )
+    .map_err(Error::json)
+}
+
+pub fn toolchain_version(
+    versions: &FxHashMap<Id, UnresolvedVersionSpec>,
+    args: &HashMap<String, Value>,
+) -> Result<Value> {
+    let id = get_string_arg("toolchain_version", "id", args)?;
+
+    Ok(versions
+        .get(id.as_str())
+        .map(|version| Value::String(version.to_string()))
+        .unwrap_or(Value::Null))
+}
+
+fn get_toolchain_versions(
+    toolchain_config: &ToolchainConfig,
+) -> FxHashMap<Id, UnresolvedVersionSpec> {
+    let mut versions = FxHashMap::default();
+
+    let mut inject = |id: &str, version: &Option<UnresolvedVersionSpec>| {
+        if let Some(version) = version {
+            versions.insert(Id::raw(id), version.to_owned());
+        }
+    };
+
+    if let Some(bun_config) = &toolchain_config.bun {
+        inject("bun", &bun_config.version);
+    }
+
+    if let Some(deno_config) = &toolchain_config.deno {
+        inject("deno", &deno_config.version);
+    }
+
+    if let Some(node_config) = &toolchain_config.node {
+        inject("node", &node_config.version);
+        inject("npm", &node_config.npm.version);
+
+        if let Some(pnpm_config) = &node_config.pnpm {
+            inject("pnpm", &pnpm_config.version);
+        }
+
+        if let Some(yarn_config) = &node_config.yarn {
+            inject("yarn", &yarn_config.version);
+        }
+
+        if let Some(bunpm_config) = &node_config.bun {
+            inject("bun", &bunpm_config.version);
+        }
+    }
+
+    if let Some(python_config) = &toolchain_config.python {
+        inject("python", &python_config.version);
+    }
+
+    if let Some(rust_config) = &toolchain_config.rust {
+        inject("rust", &rust_config.version);
+    }
+
+    for (id, plugin_config) in &toolchain_config.toolchains {
+        inject(id.as_str(), &plugin_config.version);
+    }
+
+    versions
+}
+
+/// Names of functions that require the workspace graph.
+pub const WORKSPACE_FUNCTIONS: [&str; 2] = ["project", "projects"];
+
+/// Register functions that require the workspace graph, which is
+/// expensive to load, so is only provided when a template requires it.
+pub fn register_workspace_functions(engine: &mut Tera, workspace_graph: Arc<WorkspaceGraph>) {
+    let graph = Arc::clone(&workspace_graph);
+
+    engine.register_function("project", move |args: &HashMap<String, Value>| {
+        project(&graph, args)
+    });
+
+    engine.register_function("projects", move |args: &HashMap<String, Value>| {
+        projects(&workspace_graph, args)
+    });
+}
+
+/// Register functions that require the toolchain configuration,
+/// which is not available when loading a template.
+pub fn register_toolchain_functions(engine: &mut Tera, toolchain_config: &ToolchainConfig) {
+    let versions = get_toolchain_versions(toolchain_config);
+
+    engine.register_function("toolchain_version", move |args: &HashMap<String, Value>| {
+        toolchain_version(&versions, args)
+    });
+}
@@ -5,14 +5,18 @@ use clap::builder::{
 };
 use clap::parser::ValueSource;
 use clap::{Arg, ArgAction, Args, Command};
-use moon_config::{TemplateVariable, TemplateVariableEnumDefault};
+use moon_config::{
+    TemplateVariable, TemplateVariableEnumDefault, TemplateVariableNumberSetting,
+    TemplateVariableStringSetting,
+};
 use moon_console::prompts::list_option::ListOption;
 use moon_console::prompts::validator::Validation;
 use moon_console::prompts::{Confirm, CustomType, MultiSelect, Select, Text};
 use moon_console::Console;
-use rustc_hash::FxHashMap;
+use regex::Regex;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::io::{stdout, IsTerminal};
-use tera::Context as TemplateContext;
+use tera::{Context as TemplateContext, Tera, Value};
 use tracing::{debug, instrument};
 
 #[derive(Args, Clone, Debug)]
@@ -41,11 +45,134 @@ pub struct GenerateArgs {
     #[arg(long, help = "Create a new template")]
     pub template: bool,
 
+    #[arg(
+        long,
+        help = "Update previously generated code by re-rendering the template and merging changes"
+    )]
+    pub update: bool,
+
     // Variable args (after --)
     #[arg(last = true, help = "Arguments to define as variable values")]
     pub vars: Vec<String>,
 }
 
+/// Validate a number against the `required`, `min`, and `max` settings.
+pub fn validate_number_variable(
+    config: &TemplateVariableNumberSetting,
+    value: isize,
+) -> Result<(), String> {
+    if config.required.is_some_and(|v| v) && value == 0 {
+        return Err("A non-zero value is required".into());
+    }
+
+    validate_number_range(config, value)
+}
+
+fn validate_number_range(
+    config: &TemplateVariableNumberSetting,
+    value: isize,
+) -> Result<(), String> {
+    if let Some(min) = config.min {
+        if value < min {
+            return Err(format!("Value must be greater than or equal to {min}"));
+        }
+    }
+
+    if let Some(max) = config.max {
+        if value > max {
+            return Err(format!("Value must be less than or equal to {max}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a string against the `required` and `pattern` settings.
+/// Empty values that are not required are not matched against the pattern.
+pub fn validate_string_variable(
+    config: &TemplateVariableStringSetting,
+    value: &str,
+) -> Result<(), String> {
+    if value.is_empty() {
+        return if config.required.is_some_and(|v| v) {
+            Err("A value is required".into())
+        } else {
+            Ok(())
+        };
+    }
+
+    validate_string_pattern(config, value)
+}
+
+fn validate_string_pattern(
+    config: &TemplateVariableStringSetting,
+    value: &str,
+) -> Result<(), String> {
+    if let Some(pattern) = &config.pattern {
+        let regex = Regex::new(pattern).map_err(|error| error.to_string())?;
+
+        if !regex.is_match(value) {
+            return Err(format!("Value must match the pattern {pattern}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a gathered variable value, regardless of where it came from (arguments,
+/// a template lock, defaults, or prompts). The `required` setting is not enforced
+/// for defaults, as they are used when prompting is skipped.
+fn validate_variable(
+    name: &str,
+    config: &TemplateVariable,
+    value: &Value,
+    is_default: bool,
+) -> miette::Result<()> {
+    let result = match config {
+        TemplateVariable::Number(cfg) => match value.as_i64() {
+            Some(number) if is_default => validate_number_range(cfg, number as isize),
+            Some(number) => validate_number_variable(cfg, number as isize),
+            None => Err(format!("Expected a number, received {value}")),
+        },
+        TemplateVariable::String(cfg) => match value.as_str() {
+            Some("") if is_default => Ok(()),
+            Some(string) if is_default => validate_string_pattern(cfg, string),
+            Some(string) => validate_string_variable(cfg, string),
+            None => Err(format!("Expected a string, received {value}")),
+        },
+        _ => Ok(()),
+    };
+
+    map_validation(name, result)
+}
+
+fn map_validation(name: &str, result: Result<(), String>) -> miette::Result<()> {
+    result.map_err(|error| {
+        CodegenError::InvalidVariableValue {
+            name: name.to_owned(),
+            error,
+        }
+        .into()
+    })
+}
+
+/// Render the `when` condition of a variable, and return true if truthy.
+fn evaluate_variable_condition(
+    engine: &mut Tera,
+    name: &str,
+    condition: &str,
+    context: &TemplateContext,
+) -> miette::Result<bool> {
+    let result = engine
+        .render_str(&format!("{{% if {condition} %}}true{{% endif %}}"), context)
+        .map_err(|error| CodegenError::RenderVariableFailed {
+            name: name.to_owned(),
+            error: Box::new(error),
+        })?;
+
+    Ok(result == "true")
+}
+
 #[instrument(skip_all)]
 pub fn parse_args_into_variables(
     args: &[String],
@@ -64,7 +191,7 @@ pub fn parse_args_into_variables(
     let mut command = Command::new(&command_name);
 
     for (name, cfg) in config {
-        if cfg.is_internal() {
+        if cfg.is_internal() || cfg.is_computed() {
             continue;
         }
 
@@ -134,7 +261,7 @@ pub fn parse_args_into_variables(
                     continue;
                 };
 
-                if cfg.is_internal() {
+                if cfg.is_internal() || cfg.is_computed() {
                     continue;
                 }
 
@@ -169,15 +296,19 @@ pub fn parse_args_into_variables(
                             vars.insert(name, value);
                         }
                     }
-                    TemplateVariable::Number(_) => {
+                    TemplateVariable::Number(inner) => {
                         if let Some(value) = matches.get_one::<isize>(arg_name) {
+                            map_validation(name, validate_number_variable(inner, *value))?;
+
                             debug!(name, value, "Setting number variable");
 
                             vars.insert(name, value);
                         }
                     }
-                    TemplateVariable::String(_) => {
+                    TemplateVariable::String(inner) => {
                         if let Some(value) = matches.get_one::<String>(arg_name) {
+                            map_validation(name, validate_string_variable(inner, value))?;
+
                             debug!(name, value, "Setting string variable");
 
                             vars.insert(name, value);
@@ -220,9 +351,30 @@ pub fn gather_variables(
     args: &GenerateArgs,
     template: &Template,
     console: &Console,
+) -> miette::Result<TemplateContext> {
+    gather_variables_with_values(args, template, console, &TemplateContext::default())
+}
+
+/// Gather variables like [`gather_variables`], but use the provided values
+/// (from a template lock) instead of prompting. Arguments take precedence.
+#[instrument(skip_all)]
+pub fn gather_variables_with_values(
+    args: &GenerateArgs,
+    template: &Template,
+    console: &Console,
+    values: &TemplateContext,
 ) -> miette::Result<TemplateContext> {
     let mut context = parse_args_into_variables(&args.vars, &template.config.variables)?;
 
+    for (name, config) in &template.config.variables {
+        // Computed variables must always be re-computed
+        if !context.contains_key(name) && !config.is_computed() {
+            if let Some(value) = values.get(name) {
+                context.insert(name, value);
+            }
+        }
+    }
+
     debug!("Gathering variable values from defaults and user prompts");
 
     let mut variables = template.config.variables.iter().collect::<Vec<_>>();
@@ -231,12 +383,45 @@ pub fn gather_variables(
     // Sort variables so prompting happens in the correct order
     variables.sort_by(|a, d| a.1.get_order().cmp(&d.1.get_order()));
 
+    let mut engine = template.engine.clone();
+    let mut computed = vec![];
+    let mut defaults = FxHashSet::default();
+    let mut disabled = FxHashSet::default();
+
     for (name, config) in variables {
-        if context.contains_key(name) {
+        if config.is_computed() {
+            computed.push((name, config));
             continue;
         }
 
-        let required = config.is_required();
+        // Only prompt when the condition is truthy, otherwise use the default
+        let enabled = match config.get_when() {
+            Some(condition) => evaluate_variable_condition(&mut engine, name, condition, &context)?,
+            None => true,
+        };
+
+        if context.contains_key(name) {
+            if enabled {
+                continue;
+            }
+
+            debug!(
+                name,
+                "Variable condition is falsy, ignoring provided value and using the default"
+            );
+
+            context.remove(name);
+        }
+
+        if !enabled {
+            disabled.insert(name);
+        }
+
+        let skip_prompts = skip_prompts || !enabled;
+
+        if skip_prompts || config.get_prompt().is_none() {
+            defaults.insert(name);
+        }
 
         match config {
             TemplateVariable::Boolean(cfg) => {
@@ -256,15 +441,16 @@ pub fn gather_variables(
                 let value = if skip_prompts || cfg.prompt.is_none() {
                     cfg.default
                 } else {
+                    let validator_cfg = cfg.clone();
+
                     console.prompt_custom(
                         CustomType::<isize>::new(cfg.prompt.as_ref().unwrap())
                             .with_default(cfg.default)
                             .with_validator(move |input: &isize| {
-                                if required && *input == 0 {
-                                    Ok(Validation::Invalid("A non-zero value is required".into()))
-                                } else {
-                                    Ok(Validation::Valid)
-                                }
+                                Ok(match validate_number_variable(&validator_cfg, *input) {
+                                    Ok(_) => Validation::Valid,
+                                    Err(error) => Validation::Invalid(error.into()),
+                                })
                             }),
                     )?
                 };
@@ -277,15 +463,16 @@ pub fn gather_variables(
                 let value = if skip_prompts || cfg.prompt.is_none() {
                     cfg.default.clone()
                 } else {
+                    let validator_cfg = cfg.clone();
+
                     console.prompt_text(
                         Text::new(cfg.prompt.as_ref().unwrap())
                             .with_default(&cfg.default)
                             .with_validator(move |input: &str| {
-                                if required && input.is_empty() {
-                                    Ok(Validation::Invalid("A value is required".into()))
-                                } else {
-                                    Ok(Validation::Valid)
-                                }
+                                Ok(match validate_string_variable(&validator_cfg, input) {
+                                    Ok(_) => Validation::Valid,
+                                    Err(error) => Validation::Invalid(error.into()),
+                                })
                             }),
                     )?
                 };
@@ -382,5 +569,82 @@ pub fn gather_variables(
         }
     }
 
+    // Computed variables are rendered last, so that they can reference all other variables
+    for (name, config) in computed {
+        let enabled = match config.get_when() {
+            Some(condition) => evaluate_variable_condition(&mut engine, name, condition, &context)?,
+            None => true,
+        };
+
+        if !enabled {
+            disabled.insert(name);
+        }
+
+        let value = if enabled {
+            engine
+                .render_str(config.get_computed().unwrap(), &context)
+                .map_err(|error| CodegenError::RenderVariableFailed {
+                    name: name.to_owned(),
+                    error: Box::new(error),
+                })?
+        } else {
+            String::new()
+        };
+
+        match config {
+            TemplateVariable::Boolean(cfg) => {
+                let value = if enabled {
+                    value.trim() == "true"
+                } else {
+                    cfg.default
+                };
+
+                debug!(name, value, "Setting computed boolean variable");
+
+                context.insert(name, &value);
+            }
+            TemplateVariable::Number(cfg) => {
+                let value = if enabled {
+                    value.trim().parse::<isize>().map_err(|error| {
+                        CodegenError::InvalidVariableValue {
+                            name: name.to_owned(),
+                            error: error.to_string(),
+                        }
+                    })?
+                } else {
+                    cfg.default
+                };
+
+                debug!(name, value, "Setting computed number variable");
+
+                context.insert(name, &value);
+            }
+            TemplateVariable::String(cfg) => {
+                let value = if enabled { value } else { cfg.default.clone() };
+
+                debug!(name, value, "Setting computed string variable");
+
+                context.insert(name, &value);
+            }
+            _ => {}
+        };
+    }
+
+    // Validate all values the same, regardless of where they came from
+    for (name, config) in &template.config.variables {
+        if disabled.contains(name) {
+            continue;
+        }
+
+        if let Some(value) = context.get(name) {
+            validate_variable(
+                name,
+                config,
+                value,
+                config.is_computed() || defaults.contains(name),
+            )?;
+        }
+    }
+
     Ok(context)
 }
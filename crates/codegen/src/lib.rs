@@ -6,7 +6,10 @@ mod funcs;
 mod generate_command;
 mod template;
 mod template_file;
+mod template_lock;
+mod template_merge;
 mod templates_command;
+mod templates_test_command;
 
 pub use asset_file::*;
 pub use codegen::*;
@@ -14,5 +17,8 @@ pub use codegen_error::*;
 pub use generate_command::*;
 pub use template::*;
 pub use template_file::*;
+pub use template_lock::*;
+pub use template_merge::*;
 pub use templates_command::*;
+pub use templates_test_command::*;
 pub use tera::Context as TemplateContext;
@@ -1,22 +1,47 @@
 use crate::asset_file::AssetFile;
 use crate::template_file::{FileState, MergeType, TemplateFile};
+use crate::template_merge::{inject_content, merge_toml};
 use crate::{filters, funcs, CodegenError};
 use miette::IntoDiagnostic;
 use moon_common::path::{to_virtual_string, RelativePathBuf};
 use moon_common::Id;
-use moon_config::{ConfigLoader, TemplateConfig};
+use moon_config::{ConfigLoader, TemplateConfig, TemplateHook, TemplateLocator};
+use moon_workspace_graph::WorkspaceGraph;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use starbase_utils::{fs, json, yaml};
 use std::collections::BTreeMap;
 use std::mem;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tera::{Context, Tera};
 use tracing::{debug, instrument};
 
+pub const TEMPLATE_SNAPSHOTS_DIR: &str = "__snapshots__";
+
 static PATH_VAR: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\[([A-Za-z0-9_]+)(?:\s*\|\s*([^\]]+))?\]").unwrap());
 
+static WORKSPACE_FUNCTION_CALL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"\b({})\s*\(",
+        funcs::WORKSPACE_FUNCTIONS.join("|")
+    ))
+    .unwrap()
+});
+
+/// Return true if the path contains `[var]` or Tera syntax that will be interpolated.
+pub fn is_interpolated_path(path: &str) -> bool {
+    PATH_VAR.is_match(path) || path.contains("{{") || path.contains("{%")
+}
+
+/// A hook to run after generating, with variables rendered.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateHookAction {
+    Command(String),
+    Target(String),
+}
+
 #[derive(Clone, Debug)]
 pub struct Template {
     pub assets: BTreeMap<RelativePathBuf, AssetFile>,
@@ -24,6 +49,7 @@ pub struct Template {
     pub engine: Tera,
     pub files: BTreeMap<RelativePathBuf, TemplateFile>,
     pub id: Id,
+    pub locator: Option<TemplateLocator>,
     pub root: PathBuf,
     pub templates: Vec<Template>, // Extending
 }
@@ -43,6 +69,9 @@ impl Template {
         engine.register_filter("upper_snake_case", filters::upper_snake_case);
         engine.register_filter("path_join", filters::path_join);
         engine.register_filter("path_relative", filters::path_relative);
+        engine.register_filter("pluralize", filters::pluralize);
+        engine.register_filter("singularize", filters::singularize);
+        engine.register_function("uuid", funcs::uuid);
         engine.register_function("variables", funcs::variables);
 
         let config = ConfigLoader::default().load_template_config(&root)?;
@@ -53,6 +82,7 @@ impl Template {
             config,
             engine,
             files: BTreeMap::new(),
+            locator: None,
             root,
             templates: vec![],
         })
@@ -70,6 +100,46 @@ impl Template {
         self.templates.push(template);
     }
 
+    /// Return true if any file in this template (or an extended template),
+    /// including the config, calls a function that requires the workspace graph.
+    pub fn uses_workspace_functions(&self) -> miette::Result<bool> {
+        let snapshots_dir = self.root.join(TEMPLATE_SNAPSHOTS_DIR);
+
+        for entry in fs::read_dir_all(&self.root)? {
+            let source_path = entry.path();
+
+            if source_path.starts_with(&snapshots_dir) {
+                continue;
+            }
+
+            let source_content = fs::read_file_bytes(&source_path)?;
+
+            if !content_inspector::inspect(&source_content).is_binary()
+                && WORKSPACE_FUNCTION_CALL.is_match(&String::from_utf8_lossy(&source_content))
+            {
+                return Ok(true);
+            }
+        }
+
+        for template in &self.templates {
+            if template.uses_workspace_functions()? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Register functions that require the workspace graph, like `project()`,
+    /// for this template and all extended templates.
+    pub fn set_workspace_graph(&mut self, workspace_graph: Arc<WorkspaceGraph>) {
+        funcs::register_workspace_functions(&mut self.engine, Arc::clone(&workspace_graph));
+
+        for template in &mut self.templates {
+            template.set_workspace_graph(Arc::clone(&workspace_graph));
+        }
+    }
+
     /// Once files have been loaded by all templates in the extends chain,
     /// we must flatten all nested files map into a single top-level map.
     #[instrument(skip_all)]
@@ -114,6 +184,8 @@ impl Template {
             "Loading template files"
         );
 
+        let snapshots_dir = self.root.join(TEMPLATE_SNAPSHOTS_DIR);
+
         for entry in fs::read_dir_all(&self.root)? {
             // This is our schema, so skip it
             if filenames
@@ -124,6 +196,11 @@ impl Template {
             }
 
             let source_path = entry.path();
+
+            // Snapshots are only used for testing the template
+            if source_path.starts_with(&snapshots_dir) {
+                continue;
+            }
             let source_content = fs::read_file_bytes(&source_path)?;
             let name =
                 self.interpolate_path(source_path.strip_prefix(&self.root).unwrap(), context)?;
@@ -216,6 +293,45 @@ impl Template {
         Ok(())
     }
 
+    /// Render variables into all `afterGenerate` hooks, starting with
+    /// hooks from extended templates.
+    pub fn get_after_generate_hooks(
+        &mut self,
+        context: &Context,
+    ) -> miette::Result<Vec<TemplateHookAction>> {
+        let mut actions = vec![];
+
+        for template in &mut self.templates {
+            actions.extend(template.get_after_generate_hooks(context)?);
+        }
+
+        for hook in self.config.hooks.after_generate.clone() {
+            let (value, is_target) = match hook {
+                TemplateHook::Command(command) => (command, false),
+                TemplateHook::Object(config) => match (config.command, config.target) {
+                    (_, Some(target)) => (target, true),
+                    (Some(command), None) => (command, false),
+                    (None, None) => continue,
+                },
+            };
+
+            let value = self.engine.render_str(&value, context).map_err(|error| {
+                CodegenError::RenderTemplateHookFailed {
+                    hook: value.clone(),
+                    error: Box::new(error),
+                }
+            })?;
+
+            actions.push(if is_target {
+                TemplateHookAction::Target(value)
+            } else {
+                TemplateHookAction::Command(value)
+            });
+        }
+
+        Ok(actions)
+    }
+
     /// Tera *does not* support iterating over the context, so we're unable
     /// to interpolate a path ourselves. Instead, let's use Tera and its
     /// template rendering to handle this.
@@ -290,6 +406,18 @@ impl Template {
                 );
 
                 match file.is_mergeable() {
+                    Some(MergeType::Inject) => {
+                        let prev = fs::read_file(&file.dest_path)?;
+
+                        if let Some(next) = inject_content(
+                            &prev,
+                            &file.content,
+                            file.get_inject_config().unwrap(),
+                            &file.dest_path,
+                        )? {
+                            fs::write_file(&file.dest_path, next)?;
+                        }
+                    }
                     Some(MergeType::Json) => {
                         let prev: json::JsonValue = json::read_file(&file.dest_path)?;
                         let next: json::JsonValue = json::parse(&file.content)?;
@@ -300,6 +428,14 @@ impl Template {
                             true,
                         )?;
                     }
+                    Some(MergeType::Toml) => {
+                        let prev = fs::read_file(&file.dest_path)?;
+
+                        fs::write_file(
+                            &file.dest_path,
+                            merge_toml(&prev, &file.content, &file.dest_path)?,
+                        )?;
+                    }
                     Some(MergeType::Yaml) => {
                         let prev: yaml::YamlValue = yaml::read_file(&file.dest_path)?;
                         let next: yaml::YamlValue = yaml::parse(&file.content)?;
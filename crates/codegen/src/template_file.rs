@@ -1,20 +1,26 @@
+use crate::template_merge::is_content_injected;
 use moon_common::path::RelativePathBuf;
-use moon_config::TemplateFrontmatterConfig;
+use moon_config::{TemplateFrontmatterConfig, TemplateFrontmatterInjectConfig};
+use starbase_utils::fs;
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MergeType {
+    Inject,
     Json,
+    Toml,
     Yaml,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum FileState {
+    Conflict,
     Create,
     Merge,
     Replace,
     Skip,
+    Update,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -54,10 +60,18 @@ impl TemplateFile {
         }
     }
 
+    pub fn get_inject_config(&self) -> Option<&TemplateFrontmatterInjectConfig> {
+        self.config.as_ref().and_then(|cfg| cfg.inject.as_ref())
+    }
+
     pub fn is_mergeable(&self) -> Option<MergeType> {
         let mut ext = self.name.as_str();
 
         if let Some(cfg) = &self.config {
+            if cfg.inject.is_some() {
+                return Some(MergeType::Inject);
+            }
+
             if let Some(to) = &cfg.to {
                 ext = to;
             }
@@ -65,6 +79,8 @@ impl TemplateFile {
 
         if ext.ends_with(".json") {
             return Some(MergeType::Json);
+        } else if ext.ends_with(".toml") {
+            return Some(MergeType::Toml);
         } else if ext.ends_with(".yaml") || ext.ends_with(".yml") {
             return Some(MergeType::Yaml);
         }
@@ -72,6 +88,19 @@ impl TemplateFile {
         None
     }
 
+    /// Return true if the content should be injected into the destination
+    /// file, and it has already been injected.
+    pub fn is_injected(&self) -> miette::Result<bool> {
+        if self.get_inject_config().is_none() || !self.dest_path.exists() {
+            return Ok(false);
+        }
+
+        Ok(is_content_injected(
+            &fs::read_file(&self.dest_path)?,
+            &self.content,
+        ))
+    }
+
     pub fn is_forced(&self) -> bool {
         self.config.as_ref().is_some_and(|cfg| cfg.force)
     }
@@ -0,0 +1,210 @@
+use crate::template::Template;
+use crate::template_file::{FileState, TemplateFile};
+use crate::template_merge::merge_three_way;
+use moon_common::path::to_virtual_string;
+use moon_common::Id;
+use moon_config::TemplateLocator;
+use serde::{Deserialize, Serialize};
+use starbase_utils::{fs, json};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tera::Context;
+use tracing::debug;
+
+// Not within a `.moon` folder, as that would be detected as a workspace root
+pub const TEMPLATE_LOCK_FILE: &str = ".moon-template-lock.json";
+
+/// Records the template, its locator, and the variables that were used
+/// when generating into a destination, along with the originally rendered
+/// content of each file that was written. The rendered content is used as the
+/// common ancestor when updating the destination with a 3-way merge.
+///
+/// Multiple templates can be generated into the same destination, so the
+/// lock file contains a lock for each template, keyed by template ID.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateLock {
+    pub template: Id,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locator: Option<TemplateLocator>,
+
+    #[serde(default)]
+    pub variables: BTreeMap<String, json::JsonValue>,
+
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl TemplateLock {
+    pub fn new(template: &Template, context: &Context, dest: &Path) -> miette::Result<Self> {
+        let mut variables = BTreeMap::new();
+        let mut files = BTreeMap::new();
+
+        for name in template.config.variables.keys() {
+            if let Some(value) = context.get(name) {
+                variables.insert(name.to_owned(), value.to_owned());
+            }
+        }
+
+        for file in template.files.values() {
+            // Injected content is not owned by the template
+            if file.is_skipped() || file.get_inject_config().is_some() {
+                continue;
+            }
+
+            if let Ok(path) = file.dest_path.strip_prefix(dest) {
+                files.insert(to_virtual_string(path)?, file.content.clone());
+            }
+        }
+
+        Ok(TemplateLock {
+            template: template.id.clone(),
+            locator: template.locator.clone(),
+            variables,
+            files,
+        })
+    }
+
+    pub fn get_path(dest: &Path) -> PathBuf {
+        dest.join(TEMPLATE_LOCK_FILE)
+    }
+
+    fn load_all(dest: &Path) -> miette::Result<BTreeMap<Id, Self>> {
+        let path = Self::get_path(dest);
+
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        debug!(path = ?path, "Loading template locks");
+
+        Ok(json::read_file(path)?)
+    }
+
+    /// Load the lock for the provided template from the destination.
+    pub fn load(dest: &Path, id: &Id) -> miette::Result<Option<Self>> {
+        Ok(Self::load_all(dest)?.remove(id))
+    }
+
+    /// Save the lock into the destination, while preserving
+    /// the locks of other templates generated into it.
+    pub fn save(&self, dest: &Path) -> miette::Result<()> {
+        let path = Self::get_path(dest);
+        let mut locks = Self::load_all(dest)?;
+
+        locks.insert(self.template.clone(), self.clone());
+
+        debug!(path = ?path, template = self.template.as_str(), "Saving template lock");
+
+        json::write_file(path, &locks, true)?;
+
+        Ok(())
+    }
+
+    /// Remove files that were not written to the destination, as they are not
+    /// owned by the template. For example, existing files that were kept or
+    /// merged into. Files owned by the previous lock remain owned, even when
+    /// skipped, so that deleted files are not re-created on the next update.
+    pub fn retain_written_files(
+        &mut self,
+        template: &Template,
+        dest: &Path,
+        prev_lock: Option<&TemplateLock>,
+    ) -> miette::Result<()> {
+        for file in template.files.values() {
+            let Ok(path) = file.dest_path.strip_prefix(dest) else {
+                continue;
+            };
+
+            let path = to_virtual_string(path)?;
+            let owned = match file.state {
+                FileState::Conflict
+                | FileState::Create
+                | FileState::Replace
+                | FileState::Update => true,
+                FileState::Merge => false,
+                FileState::Skip => prev_lock.is_some_and(|lock| lock.files.contains_key(&path)),
+            };
+
+            if !owned {
+                self.files.remove(&path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the files that were owned by this lock, but are no longer
+    /// rendered by the template in the next lock.
+    pub fn get_removed_files(&self, next_lock: &TemplateLock) -> Vec<String> {
+        self.files
+            .keys()
+            .filter(|path| !next_lock.files.contains_key(*path))
+            .cloned()
+            .collect()
+    }
+
+    /// Convert the locked variables into a context, so that they can be
+    /// used in place of prompts.
+    pub fn to_context(&self) -> Context {
+        let mut context = Context::new();
+
+        for (name, value) in &self.variables {
+            context.insert(name, value);
+        }
+
+        context
+    }
+
+    /// Return the originally rendered content of the template file.
+    pub fn get_file_content(&self, file: &TemplateFile, dest: &Path) -> Option<&String> {
+        file.dest_path
+            .strip_prefix(dest)
+            .ok()
+            .and_then(|path| to_virtual_string(path).ok())
+            .and_then(|path| self.files.get(&path))
+    }
+
+    /// Determine the state of a newly rendered template file when updating, by
+    /// 3-way merging the originally rendered content, the current content at the
+    /// destination, and the newly rendered content. Returns the number of conflicts.
+    pub fn update_file(&self, file: &mut TemplateFile, dest: &Path) -> miette::Result<usize> {
+        let base = self.get_file_content(file, dest);
+
+        if !file.dest_path.exists() {
+            // The file was removed after generating, so don't bring it back
+            file.state = if base.is_some() {
+                FileState::Skip
+            } else {
+                FileState::Create
+            };
+
+            return Ok(0);
+        }
+
+        let current = fs::read_file(&file.dest_path)?;
+        let result = merge_three_way(
+            base.map(|content| content.as_str()).unwrap_or_default(),
+            &current,
+            &file.content,
+        );
+
+        debug!(
+            file = file.name.as_str(),
+            conflicts = result.conflicts,
+            "Merged template file with destination",
+        );
+
+        file.state = if result.content == current {
+            FileState::Skip
+        } else if result.conflicts > 0 {
+            FileState::Conflict
+        } else {
+            FileState::Update
+        };
+        file.content = result.content;
+
+        Ok(result.conflicts)
+    }
+}
@@ -0,0 +1,288 @@
+use crate::CodegenError;
+use moon_config::TemplateFrontmatterInjectConfig;
+use regex::Regex;
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// Deep merge the next TOML document into the previous document, while preserving
+/// the formatting and comments of the previous document. Tables are merged recursively,
+/// arrays are appended with items that don't already exist, and all other values
+/// are replaced.
+pub fn merge_toml(prev: &str, next: &str, path: &Path) -> miette::Result<String> {
+    let parse = |content: &str| {
+        content
+            .parse::<DocumentMut>()
+            .map_err(|error| CodegenError::MergeTomlFailed {
+                path: path.to_path_buf(),
+                error: Box::new(error),
+            })
+    };
+
+    let mut prev = parse(prev)?;
+    let next = parse(next)?;
+
+    merge_toml_item(prev.as_item_mut(), next.as_item());
+
+    Ok(prev.to_string())
+}
+
+fn merge_toml_item(prev: &mut Item, next: &Item) {
+    if let (Some(prev_table), Some(next_table)) = (prev.as_table_like_mut(), next.as_table_like()) {
+        for (key, next_item) in next_table.iter() {
+            match prev_table.get_mut(key) {
+                Some(prev_item) => merge_toml_item(prev_item, next_item),
+                None => {
+                    prev_table.insert(key, next_item.clone());
+                }
+            };
+        }
+
+        return;
+    }
+
+    if let (Some(prev_array), Some(next_array)) = (prev.as_array_mut(), next.as_array()) {
+        for next_value in next_array.iter() {
+            let exists = prev_array
+                .iter()
+                .any(|prev_value| prev_value.to_string().trim() == next_value.to_string().trim());
+
+            if !exists {
+                prev_array.push(next_value.clone());
+            }
+        }
+
+        return;
+    }
+
+    if let (Some(prev_tables), Some(next_tables)) =
+        (prev.as_array_of_tables_mut(), next.as_array_of_tables())
+    {
+        for next_table in next_tables.iter() {
+            let exists = prev_tables
+                .iter()
+                .any(|prev_table| prev_table.to_string().trim() == next_table.to_string().trim());
+
+            if !exists {
+                prev_tables.push(next_table.clone());
+            }
+        }
+
+        return;
+    }
+
+    // Keep the existing whitespace and comments around the value
+    if let (Some(prev_value), Some(next_value)) = (prev.as_value_mut(), next.as_value()) {
+        let decor = prev_value.decor().clone();
+
+        *prev_value = next_value.clone();
+        *prev_value.decor_mut() = decor;
+
+        return;
+    }
+
+    *prev = next.clone();
+}
+
+/// Return true if the content has already been injected into the existing file,
+/// by matching all of its lines as a contiguous block of whole lines. Trailing
+/// whitespace is ignored, but indentation is not.
+pub fn is_content_injected(existing: &str, content: &str) -> bool {
+    let block = content
+        .trim_matches(['\r', '\n'])
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>();
+
+    if block.iter().all(|line| line.is_empty()) {
+        return true;
+    }
+
+    existing
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .windows(block.len())
+        .any(|window| window == block)
+}
+
+/// Inject the content into the existing file at the configured anchor. Returns `None`
+/// if the content has already been injected, so that injecting is idempotent.
+pub fn inject_content(
+    existing: &str,
+    content: &str,
+    config: &TemplateFrontmatterInjectConfig,
+    path: &Path,
+) -> miette::Result<Option<String>> {
+    if is_content_injected(existing, content) {
+        return Ok(None);
+    }
+
+    let create_regex = |anchor: &str| {
+        Regex::new(anchor).map_err(|error| CodegenError::InjectInvalidAnchor {
+            anchor: anchor.to_owned(),
+            path: path.to_path_buf(),
+            error: Box::new(error),
+        })
+    };
+
+    let mut lines = existing.lines().collect::<Vec<_>>();
+
+    let (anchor, index) = if let Some(marker) = &config.marker {
+        (
+            marker,
+            lines.iter().position(|line| line.contains(marker.as_str())),
+        )
+    } else if let Some(before) = &config.before {
+        let pattern = create_regex(before)?;
+
+        (before, lines.iter().position(|line| pattern.is_match(line)))
+    } else if let Some(after) = &config.after {
+        let pattern = create_regex(after)?;
+
+        (
+            after,
+            lines
+                .iter()
+                .position(|line| pattern.is_match(line))
+                .map(|index| index + 1),
+        )
+    } else {
+        return Err(CodegenError::InjectMissingAnchor {
+            path: path.to_path_buf(),
+        }
+        .into());
+    };
+
+    let Some(index) = index else {
+        return Err(CodegenError::InjectAnchorNotFound {
+            anchor: anchor.to_owned(),
+            path: path.to_path_buf(),
+        }
+        .into());
+    };
+
+    lines.splice(index..index, content.trim_end_matches(['\r', '\n']).lines());
+
+    let newline = if existing.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    let mut result = lines.join(newline);
+
+    if existing.ends_with('\n') {
+        result.push_str(newline);
+    }
+
+    Ok(Some(result))
+}
+
+/// The result of a 3-way merge.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct ThreeWayMerge {
+    /// Merged content, with conflict markers when applicable.
+    pub content: String,
+
+    /// Number of conflicting hunks.
+    pub conflicts: usize,
+}
+
+/// Map each line in the base to its matching line in the other content,
+/// if the line was unchanged.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for offset in 0..len {
+                matches[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+
+    matches
+}
+
+/// Line-based 3-way merge (diff3) of the current and next content, using the
+/// base content as the common ancestor. Hunks that were only changed on one
+/// side are applied, while hunks changed on both sides (with different results)
+/// are written with Git-style conflict markers.
+pub fn merge_three_way(base: &str, current: &str, next: &str) -> ThreeWayMerge {
+    let base_lines = base.split_inclusive('\n').collect::<Vec<_>>();
+    let current_lines = current.split_inclusive('\n').collect::<Vec<_>>();
+    let next_lines = next.split_inclusive('\n').collect::<Vec<_>>();
+
+    let current_matches = match_lines(&base_lines, &current_lines);
+    let next_matches = match_lines(&base_lines, &next_lines);
+
+    let mut result = ThreeWayMerge::default();
+    let (mut b, mut c, mut n) = (0, 0, 0);
+
+    let push_lines = |content: &mut String, lines: &[&str]| {
+        for line in lines {
+            content.push_str(line);
+        }
+    };
+
+    let push_conflict_lines = |content: &mut String, lines: &[&str]| {
+        for line in lines {
+            content.push_str(line);
+        }
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+    };
+
+    while b < base_lines.len() || c < current_lines.len() || n < next_lines.len() {
+        // All sides are in sync, so keep the line as-is
+        if b < base_lines.len() && current_matches[b] == Some(c) && next_matches[b] == Some(n) {
+            result.content.push_str(base_lines[b]);
+            b += 1;
+            c += 1;
+            n += 1;
+
+            continue;
+        }
+
+        // Otherwise find the next line where all sides are in sync again
+        let (next_b, next_c, next_n) = (b..base_lines.len())
+            .find_map(
+                |index| match (current_matches[index], next_matches[index]) {
+                    (Some(ci), Some(ni)) => Some((index, ci, ni)),
+                    _ => None,
+                },
+            )
+            .unwrap_or((base_lines.len(), current_lines.len(), next_lines.len()));
+
+        let base_hunk = &base_lines[b..next_b];
+        let current_hunk = &current_lines[c..next_c];
+        let next_hunk = &next_lines[n..next_n];
+
+        if current_hunk == next_hunk || next_hunk == base_hunk {
+            push_lines(&mut result.content, current_hunk);
+        } else if current_hunk == base_hunk {
+            push_lines(&mut result.content, next_hunk);
+        } else {
+            result.conflicts += 1;
+            result.content.push_str("<<<<<<< current\n");
+            push_conflict_lines(&mut result.content, current_hunk);
+            result.content.push_str("=======\n");
+            push_conflict_lines(&mut result.content, next_hunk);
+            result.content.push_str(">>>>>>> template\n");
+        }
+
+        b = next_b;
+        c = next_c;
+        n = next_n;
+    }
+
+    result
+}
@@ -0,0 +1,145 @@
+use crate::generate_command::{gather_variables_with_values, GenerateArgs};
+use crate::template::{Template, TEMPLATE_SNAPSHOTS_DIR};
+use crate::template_file::FileState;
+use clap::Args;
+use moon_common::path::to_virtual_string;
+use moon_config::TemplateTestConfig;
+use moon_console::Console;
+use starbase_utils::fs;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tera::Context as TemplateContext;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct TemplatesTestArgs {
+    #[arg(help = "IDs of templates to test, otherwise all templates with tests")]
+    pub ids: Vec<String>,
+
+    #[arg(long, help = "Update snapshots with the rendered output")]
+    pub update: bool,
+}
+
+/// A difference between the rendered output of a template test,
+/// and its committed snapshot.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum TemplateSnapshotChange {
+    Added(String),
+    Changed(String),
+    Missing(String),
+}
+
+fn get_relative_files(dir: &Path) -> miette::Result<BTreeMap<String, PathBuf>> {
+    let mut files = BTreeMap::new();
+
+    if dir.exists() {
+        for entry in fs::read_dir_all(dir)? {
+            let path = entry.path();
+
+            files.insert(to_virtual_string(path.strip_prefix(dir).unwrap())?, path);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Return the snapshot directory for the named test of the template.
+pub fn get_template_snapshot_dir(template: &Template, name: &str) -> PathBuf {
+    template.root.join(TEMPLATE_SNAPSHOTS_DIR).join(name)
+}
+
+/// Render the template with the variables of the test into the destination,
+/// using default values for all variables that are not defined.
+#[instrument(skip_all)]
+pub fn render_template_test(
+    template: &Template,
+    test: &TemplateTestConfig,
+    dest: &Path,
+    workspace_root: &Path,
+    console: &Console,
+) -> miette::Result<Template> {
+    let mut template = template.clone();
+
+    let mut values = TemplateContext::new();
+
+    for (name, value) in &test.variables {
+        values.insert(name, value);
+    }
+
+    let args = GenerateArgs {
+        name: template.id.to_string(),
+        dest: Some(".".into()),
+        defaults: true,
+        dry_run: false,
+        force: true,
+        template: false,
+        update: false,
+        vars: vec![],
+    };
+
+    let mut context = gather_variables_with_values(&args, &template, console, &values)?;
+    context.insert("working_dir", workspace_root);
+    context.insert("workspace_root", workspace_root);
+    context.insert("dest_dir", dest);
+    context.insert("dest_rel_dir", ".");
+
+    if dest.exists() {
+        fs::remove_dir_all(dest)?;
+    }
+
+    template.load_files(dest, &context)?;
+
+    for file in template.files.values_mut() {
+        file.state = if file.is_skipped() {
+            FileState::Skip
+        } else {
+            FileState::Create
+        };
+    }
+
+    for file in template.files.values() {
+        if file.should_write() {
+            template.write_file(file)?;
+        }
+    }
+
+    for asset in template.assets.values() {
+        template.copy_asset(asset)?;
+    }
+
+    Ok(template)
+}
+
+/// Compare the rendered output against the snapshot directory,
+/// and return a list of files that are different.
+pub fn diff_template_snapshot(
+    output_dir: &Path,
+    snapshot_dir: &Path,
+) -> miette::Result<Vec<TemplateSnapshotChange>> {
+    let output_files = get_relative_files(output_dir)?;
+    let snapshot_files = get_relative_files(snapshot_dir)?;
+    let mut changes = vec![];
+
+    for name in output_files
+        .keys()
+        .chain(snapshot_files.keys())
+        .collect::<BTreeSet<_>>()
+    {
+        match (output_files.get(name), snapshot_files.get(name)) {
+            (Some(output_file), Some(snapshot_file)) => {
+                if fs::read_file_bytes(output_file)? != fs::read_file_bytes(snapshot_file)? {
+                    changes.push(TemplateSnapshotChange::Changed(name.to_owned()));
+                }
+            }
+            (Some(_), None) => {
+                changes.push(TemplateSnapshotChange::Added(name.to_owned()));
+            }
+            (None, Some(_)) => {
+                changes.push(TemplateSnapshotChange::Missing(name.to_owned()));
+            }
+            (None, None) => {}
+        };
+    }
+
+    Ok(changes)
+}
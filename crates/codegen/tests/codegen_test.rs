@@ -4,8 +4,59 @@ use moon_config::{
     FilePath, GeneratorConfig, GlobPath, TemplateLocator, TemplateVariable, Version,
 };
 use moon_env::MoonEnvironment;
+use moon_oci::{create_oci_digest, ANNOTATION_TITLE};
+use serde_json::json;
 use starbase_sandbox::{create_empty_sandbox, create_sandbox};
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::thread;
+use tiny_http::{Response, Server};
+
+// Serve a single artifact with a file per layer from a local registry
+fn start_registry(files: Vec<(&str, &str)>) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let host = format!("127.0.0.1:{}", server.server_addr().to_ip().unwrap().port());
+    let mut blobs = BTreeMap::new();
+    let mut layers = vec![];
+
+    for (title, content) in files {
+        let digest = create_oci_digest(content.as_bytes());
+
+        layers.push(json!({
+            "mediaType": "application/vnd.oci.image.layer.v1.tar",
+            "digest": digest,
+            "size": content.len(),
+            "annotations": { ANNOTATION_TITLE: title },
+        }));
+        blobs.insert(format!("/v2/templates/blobs/{digest}"), content.to_owned());
+    }
+
+    let manifest = json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "config": {
+            "mediaType": "application/vnd.oci.empty.v1+json",
+            "digest": create_oci_digest(b"{}"),
+            "size": 2,
+        },
+        "layers": layers,
+    });
+
+    blobs.insert("/v2/templates/manifests/1.0.0".into(), manifest.to_string());
+
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let response = match blobs.get(request.url()) {
+                Some(body) => Response::from_string(body.as_str()),
+                None => Response::from_string("").with_status_code(404),
+            };
+
+            let _ = request.respond(response);
+        }
+    });
+
+    host
+}
 
 mod codegen {
     use super::*;
@@ -160,6 +211,91 @@ mod codegen {
                 .exists());
         }
 
+        #[tokio::test]
+        async fn pulls_an_oci_artifact() {
+            let sandbox = create_empty_sandbox();
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+            let host = start_registry(vec![
+                (
+                    "standard/template.yml",
+                    "title: Standard\ndescription: Test\n",
+                ),
+                ("standard/file.txt", "content"),
+            ]);
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Oci {
+                    registry: host,
+                    repository: "templates".into(),
+                    tag: "1.0.0".into(),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            let location = &codegen.template_locations[0];
+
+            assert!(location.starts_with(env.templates_dir.join("oci")));
+            assert!(location.join("standard/template.yml").exists());
+            assert_eq!(
+                std::fs::read_to_string(location.join("standard/file.txt")).unwrap(),
+                "content"
+            );
+            assert!(codegen.templates.contains_key("standard"));
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "is not a relative path within the template")]
+        async fn errors_for_oci_artifact_files_outside_the_template() {
+            let sandbox = create_empty_sandbox();
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+            let host = start_registry(vec![("../escape.txt", "content")]);
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Oci {
+                    registry: host,
+                    repository: "templates".into(),
+                    tag: "1.0.0".into(),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn downloads_an_archive() {
+            let sandbox = create_empty_sandbox();
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "https://registry.npmjs.org/@moonrepo/cli/-/cli-1.0.0.tgz".into(),
+                    checksum: None,
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+
+            assert!(codegen.template_locations[0].starts_with(env.templates_dir.join("archive")));
+            assert!(codegen.template_locations[0].join("package").exists());
+        }
+
+        #[tokio::test]
+        #[should_panic(expected = "Checksum mismatch for template archive")]
+        async fn errors_for_archive_checksum_mismatch() {
+            let sandbox = create_empty_sandbox();
+            let env = Arc::new(MoonEnvironment::new_testing(sandbox.path()));
+            let config = GeneratorConfig {
+                templates: vec![TemplateLocator::Archive {
+                    url: "https://registry.npmjs.org/@moonrepo/cli/-/cli-1.0.0.tgz".into(),
+                    checksum: Some("0".repeat(64)),
+                }],
+            };
+
+            let mut codegen = CodeGenerator::new(sandbox.path(), &config, Arc::clone(&env));
+            codegen.load_templates().await.unwrap();
+        }
+
         #[tokio::test]
         async fn walks_with_globs() {
             let sandbox = create_sandbox("include");
@@ -1,9 +1,15 @@
-use moon_codegen::parse_args_into_variables;
+use moon_codegen::{
+    gather_variables, gather_variables_with_values, parse_args_into_variables, GenerateArgs,
+    Template, TemplateContext,
+};
+use moon_common::Id;
 use moon_config::{
     TemplateVariable, TemplateVariableBoolSetting, TemplateVariableEnumSetting,
     TemplateVariableEnumValue, TemplateVariableNumberSetting, TemplateVariableStringSetting,
 };
+use moon_console::Console;
 use rustc_hash::FxHashMap;
+use starbase_sandbox::locate_fixture;
 use tera::Number;
 use tera::Value;
 
@@ -54,6 +60,28 @@ mod cli_args {
                 ..Default::default()
             }),
         );
+        vars.insert(
+            "bounded".into(),
+            TemplateVariable::Number(TemplateVariableNumberSetting {
+                min: Some(1),
+                max: Some(10),
+                ..Default::default()
+            }),
+        );
+        vars.insert(
+            "slug".into(),
+            TemplateVariable::String(TemplateVariableStringSetting {
+                pattern: Some("^[a-z-]+$".into()),
+                ..Default::default()
+            }),
+        );
+        vars.insert(
+            "computed".into(),
+            TemplateVariable::String(TemplateVariableStringSetting {
+                computed: Some("{{ slug }}".into()),
+                ..Default::default()
+            }),
+        );
         vars
     }
 
@@ -65,6 +93,12 @@ mod cli_args {
         assert!(!context.contains_key("internal"));
     }
 
+    #[test]
+    #[should_panic(expected = "unexpected argument")]
+    fn errors_if_computed_arg_passed() {
+        parse_args_into_variables(&["--computed".into(), "abc".into()], &create_vars()).unwrap();
+    }
+
     mod bool {
         use super::*;
 
@@ -147,6 +181,30 @@ mod cli_args {
         fn errors_when_no_value() {
             parse_args_into_variables(&["--number".into()], &create_vars()).unwrap();
         }
+
+        #[test]
+        fn sets_var_within_bounds() {
+            let context =
+                parse_args_into_variables(&["--bounded".into(), "5".into()], &create_vars())
+                    .unwrap();
+
+            assert_eq!(
+                context.get("bounded").unwrap(),
+                &Value::Number(Number::from(5))
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid value for variable bounded")]
+        fn errors_when_below_min() {
+            parse_args_into_variables(&["--bounded".into(), "0".into()], &create_vars()).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "Value must be less than or equal to 10")]
+        fn errors_when_above_max() {
+            parse_args_into_variables(&["--bounded".into(), "11".into()], &create_vars()).unwrap();
+        }
     }
 
     mod string {
@@ -173,6 +231,24 @@ mod cli_args {
         fn errors_when_no_value() {
             parse_args_into_variables(&["--string".into()], &create_vars()).unwrap();
         }
+
+        #[test]
+        fn sets_var_matching_pattern() {
+            let context =
+                parse_args_into_variables(&["--slug".into(), "a-slug".into()], &create_vars())
+                    .unwrap();
+
+            assert_eq!(
+                context.get("slug").unwrap(),
+                &Value::String("a-slug".into())
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Value must match the pattern ^[a-z-]+$")]
+        fn errors_when_not_matching_pattern() {
+            parse_args_into_variables(&["--slug".into(), "A_Slug".into()], &create_vars()).unwrap();
+        }
     }
 
     mod enum_single {
@@ -260,3 +336,90 @@ mod cli_args {
         }
     }
 }
+
+mod gather {
+    use super::*;
+
+    fn load_template(name: &str) -> Template {
+        Template::new(Id::raw(name), locate_fixture("variables").join(name)).unwrap()
+    }
+
+    fn create_args(vars: &[&str]) -> GenerateArgs {
+        GenerateArgs {
+            name: "test".into(),
+            dest: None,
+            defaults: true,
+            dry_run: false,
+            force: false,
+            template: false,
+            update: false,
+            vars: vars.iter().map(|var| var.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid value for variable port")]
+    fn errors_when_default_is_invalid() {
+        gather_variables(
+            &create_args(&[]),
+            &load_template("invalid"),
+            &Console::new_testing(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid value for variable port")]
+    fn errors_when_locked_value_is_invalid() {
+        let mut values = TemplateContext::new();
+        values.insert("port", &80);
+
+        gather_variables_with_values(
+            &create_args(&[]),
+            &load_template("conditional"),
+            &Console::new_testing(),
+            &values,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Value must match the pattern ^[a-z]+$")]
+    fn errors_when_locked_value_doesnt_match_pattern() {
+        let mut values = TemplateContext::new();
+        values.insert("enabled", &true);
+        values.insert("db", "Invalid");
+
+        gather_variables_with_values(
+            &create_args(&[]),
+            &load_template("conditional"),
+            &Console::new_testing(),
+            &values,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn ignores_args_when_condition_is_falsy() {
+        let context = gather_variables(
+            &create_args(&["--db", "custom"]),
+            &load_template("conditional"),
+            &Console::new_testing(),
+        )
+        .unwrap();
+
+        assert_eq!(context.get("db").unwrap(), &Value::String("app".into()));
+    }
+
+    #[test]
+    fn uses_args_when_condition_is_truthy() {
+        let context = gather_variables(
+            &create_args(&["--enabled", "--db", "custom"]),
+            &load_template("conditional"),
+            &Console::new_testing(),
+        )
+        .unwrap();
+
+        assert_eq!(context.get("db").unwrap(), &Value::String("custom".into()));
+    }
+}
@@ -1,6 +1,6 @@
 use moon_codegen::{MergeType, TemplateFile};
 use moon_common::path::RelativePathBuf;
-use moon_config::TemplateFrontmatterConfig;
+use moon_config::{TemplateFrontmatterConfig, TemplateFrontmatterInjectConfig};
 use std::path::PathBuf;
 
 mod template_file {
@@ -39,6 +39,13 @@ mod template_file {
             assert_eq!(template.is_mergeable(), Some(MergeType::Json));
         }
 
+        #[test]
+        fn is_with_toml() {
+            let template = TemplateFile::new(RelativePathBuf::from("Cargo.toml"), PathBuf::new());
+
+            assert_eq!(template.is_mergeable(), Some(MergeType::Toml));
+        }
+
         #[test]
         fn is_with_yaml() {
             let template = TemplateFile::new(RelativePathBuf::from("file.yaml"), PathBuf::new());
@@ -59,6 +66,22 @@ mod template_file {
 
             assert_eq!(template.is_mergeable(), Some(MergeType::Json));
         }
+
+        #[test]
+        fn is_with_inject_config() {
+            let mut template =
+                TemplateFile::new(RelativePathBuf::from("file.json"), PathBuf::new());
+
+            template.config = Some(TemplateFrontmatterConfig {
+                inject: Some(TemplateFrontmatterInjectConfig {
+                    marker: Some("// routes".into()),
+                    ..TemplateFrontmatterInjectConfig::default()
+                }),
+                ..TemplateFrontmatterConfig::default()
+            });
+
+            assert_eq!(template.is_mergeable(), Some(MergeType::Inject));
+        }
     }
 
     mod content {
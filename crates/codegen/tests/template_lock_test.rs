@@ -0,0 +1,239 @@
+use moon_codegen::{FileState, Template, TemplateContext, TemplateFile, TemplateLock};
+use moon_common::Id;
+use starbase_sandbox::{create_empty_sandbox, locate_fixture};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn create_lock(files: &[(&str, &str)]) -> TemplateLock {
+    TemplateLock {
+        template: Id::raw("lock"),
+        locator: None,
+        variables: BTreeMap::new(),
+        files: BTreeMap::from_iter(
+            files
+                .iter()
+                .map(|(name, content)| (name.to_string(), content.to_string())),
+        ),
+    }
+}
+
+fn create_file(content: &str, dest: &Path) -> TemplateFile {
+    let mut file = TemplateFile::new("file.txt".into(), PathBuf::from("."));
+    file.set_content(content, dest).unwrap();
+    file
+}
+
+mod template_lock {
+    use super::*;
+
+    #[test]
+    fn records_variables_and_rendered_files() {
+        let sandbox = create_empty_sandbox();
+        let mut template = Template::new(Id::raw("lock"), locate_fixture("lock")).unwrap();
+
+        let mut context = TemplateContext::new();
+        context.insert("name", "moon");
+        context.insert("working_dir", "/");
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        let lock = TemplateLock::new(&template, &context, sandbox.path()).unwrap();
+
+        assert_eq!(lock.template, Id::raw("lock"));
+        assert_eq!(
+            lock.variables,
+            BTreeMap::from_iter([("name".into(), "moon".into())])
+        );
+        assert_eq!(
+            lock.files,
+            BTreeMap::from_iter([("file.txt".into(), "name = moon\n".into())])
+        );
+    }
+
+    #[test]
+    fn saves_and_loads_from_dest() {
+        let sandbox = create_empty_sandbox();
+        let lock = create_lock(&[("file.txt", "content")]);
+
+        assert_eq!(
+            TemplateLock::load(sandbox.path(), &Id::raw("lock")).unwrap(),
+            None
+        );
+
+        lock.save(sandbox.path()).unwrap();
+
+        assert!(sandbox.path().join(".moon-template-lock.json").exists());
+        assert_eq!(
+            TemplateLock::load(sandbox.path(), &Id::raw("lock")).unwrap(),
+            Some(lock)
+        );
+    }
+
+    #[test]
+    fn saves_multiple_templates_into_same_dest() {
+        let sandbox = create_empty_sandbox();
+        let lock = create_lock(&[("file.txt", "content")]);
+        let mut other_lock = create_lock(&[("other.txt", "content")]);
+        other_lock.template = Id::raw("other");
+
+        lock.save(sandbox.path()).unwrap();
+        other_lock.save(sandbox.path()).unwrap();
+
+        assert_eq!(
+            TemplateLock::load(sandbox.path(), &Id::raw("lock")).unwrap(),
+            Some(lock)
+        );
+        assert_eq!(
+            TemplateLock::load(sandbox.path(), &Id::raw("other")).unwrap(),
+            Some(other_lock)
+        );
+        assert_eq!(
+            TemplateLock::load(sandbox.path(), &Id::raw("unknown")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn only_retains_written_files() {
+        let sandbox = create_empty_sandbox();
+        let mut template = Template::new(Id::raw("lock"), locate_fixture("lock")).unwrap();
+
+        let mut context = TemplateContext::new();
+        context.insert("name", "moon");
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        for state in [FileState::Skip, FileState::Merge] {
+            for file in template.files.values_mut() {
+                file.state = state.clone();
+            }
+
+            let mut lock = TemplateLock::new(&template, &context, sandbox.path()).unwrap();
+            lock.retain_written_files(&template, sandbox.path(), None)
+                .unwrap();
+
+            assert!(lock.files.is_empty());
+        }
+
+        for file in template.files.values_mut() {
+            file.state = FileState::Create;
+        }
+
+        let mut lock = TemplateLock::new(&template, &context, sandbox.path()).unwrap();
+        lock.retain_written_files(&template, sandbox.path(), None)
+            .unwrap();
+
+        assert_eq!(lock.files.len(), 1);
+    }
+
+    #[test]
+    fn retains_skipped_files_owned_by_previous_lock() {
+        let sandbox = create_empty_sandbox();
+        let mut template = Template::new(Id::raw("lock"), locate_fixture("lock")).unwrap();
+
+        let mut context = TemplateContext::new();
+        context.insert("name", "moon");
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        for file in template.files.values_mut() {
+            file.state = FileState::Skip;
+        }
+
+        let prev_lock = create_lock(&[("file.txt", "old")]);
+        let mut lock = TemplateLock::new(&template, &context, sandbox.path()).unwrap();
+        lock.retain_written_files(&template, sandbox.path(), Some(&prev_lock))
+            .unwrap();
+
+        assert_eq!(
+            lock.files,
+            BTreeMap::from_iter([("file.txt".into(), "name = moon\n".into())])
+        );
+    }
+
+    #[test]
+    fn returns_files_removed_from_template() {
+        let prev_lock = create_lock(&[("file.txt", "a"), ("removed.txt", "b")]);
+        let next_lock = create_lock(&[("file.txt", "a"), ("added.txt", "c")]);
+
+        assert_eq!(
+            prev_lock.get_removed_files(&next_lock),
+            vec!["removed.txt".to_owned()]
+        );
+    }
+
+    #[test]
+    fn converts_variables_to_context() {
+        let mut lock = create_lock(&[]);
+        lock.variables.insert("name".into(), "moon".into());
+
+        let context = lock.to_context();
+
+        assert_eq!(context.get("name").unwrap(), "moon");
+    }
+
+    mod update_file {
+        use super::*;
+
+        #[test]
+        fn creates_new_files() {
+            let sandbox = create_empty_sandbox();
+            let lock = create_lock(&[]);
+            let mut file = create_file("new", sandbox.path());
+
+            assert_eq!(lock.update_file(&mut file, sandbox.path()).unwrap(), 0);
+            assert_eq!(file.state, FileState::Create);
+        }
+
+        #[test]
+        fn skips_removed_files() {
+            let sandbox = create_empty_sandbox();
+            let lock = create_lock(&[("file.txt", "old")]);
+            let mut file = create_file("new", sandbox.path());
+
+            assert_eq!(lock.update_file(&mut file, sandbox.path()).unwrap(), 0);
+            assert_eq!(file.state, FileState::Skip);
+        }
+
+        #[test]
+        fn skips_unchanged_files() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("file.txt", "a\nchanged\n");
+
+            let lock = create_lock(&[("file.txt", "a\nb\n")]);
+            let mut file = create_file("a\nb\n", sandbox.path());
+
+            assert_eq!(lock.update_file(&mut file, sandbox.path()).unwrap(), 0);
+            assert_eq!(file.state, FileState::Skip);
+        }
+
+        #[test]
+        fn updates_with_template_changes() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("file.txt", "a\nchanged\nc\nd\n");
+
+            let lock = create_lock(&[("file.txt", "a\nb\nc\nd\n")]);
+            let mut file = create_file("a\nb\nc\nD\n", sandbox.path());
+
+            assert_eq!(lock.update_file(&mut file, sandbox.path()).unwrap(), 0);
+            assert_eq!(file.state, FileState::Update);
+            assert_eq!(file.content, "a\nchanged\nc\nD\n");
+        }
+
+        #[test]
+        fn reports_conflicts() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("file.txt", "a\nchanged\n");
+
+            let lock = create_lock(&[("file.txt", "a\nb\n")]);
+            let mut file = create_file("a\nB\n", sandbox.path());
+
+            assert_eq!(lock.update_file(&mut file, sandbox.path()).unwrap(), 1);
+            assert_eq!(file.state, FileState::Conflict);
+            assert_eq!(
+                file.content,
+                "a\n<<<<<<< current\nchanged\n=======\nB\n>>>>>>> template\n"
+            );
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use moon_codegen::{inject_content, is_content_injected, merge_three_way, merge_toml};
+use moon_config::TemplateFrontmatterInjectConfig;
+use std::path::PathBuf;
+
+fn path() -> PathBuf {
+    PathBuf::from("file")
+}
+
+mod merge_toml {
+    use super::*;
+
+    #[test]
+    fn merges_tables_and_preserves_formatting() {
+        let prev = r#"# Workspace
+[workspace]
+resolver = "2" # Required
+members = ["crates/a"]
+"#;
+        let next = r#"
+[workspace]
+members = ["crates/a", "crates/b"]
+
+[workspace.dependencies]
+serde = "1"
+"#;
+
+        assert_eq!(
+            merge_toml(prev, next, &path()).unwrap(),
+            r#"# Workspace
+[workspace]
+resolver = "2" # Required
+members = ["crates/a", "crates/b"]
+
+[workspace.dependencies]
+serde = "1"
+"#
+        );
+    }
+
+    #[test]
+    fn replaces_values() {
+        let prev = "[package]\nname = \"a\" # Name\nedition = \"2021\"\n";
+        let next = "[package]\nname = \"b\"\n";
+
+        assert_eq!(
+            merge_toml(prev, next, &path()).unwrap(),
+            "[package]\nname = \"b\" # Name\nedition = \"2021\"\n"
+        );
+    }
+
+    #[test]
+    fn appends_array_of_tables() {
+        let prev = "[[bin]]\nname = \"a\"\n";
+        let next = "[[bin]]\nname = \"a\"\n\n[[bin]]\nname = \"b\"\n";
+
+        assert_eq!(
+            merge_toml(prev, next, &path()).unwrap(),
+            "[[bin]]\nname = \"a\"\n\n[[bin]]\nname = \"b\"\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to merge TOML template file")]
+    fn errors_for_invalid_toml() {
+        merge_toml("[package", "", &path()).unwrap();
+    }
+}
+
+mod inject_content {
+    use super::*;
+
+    const ROUTES: &str = "import a from './a';\n\nconst routes = [\n  a,\n  // moon:routes\n];\n";
+
+    #[test]
+    fn injects_before_marker() {
+        let config = TemplateFrontmatterInjectConfig {
+            marker: Some("// moon:routes".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        assert_eq!(
+            inject_content(ROUTES, "  b,\n", &config, &path())
+                .unwrap()
+                .unwrap(),
+            "import a from './a';\n\nconst routes = [\n  a,\n  b,\n  // moon:routes\n];\n"
+        );
+    }
+
+    #[test]
+    fn injects_before_regex() {
+        let config = TemplateFrontmatterInjectConfig {
+            before: Some(r"^\s*$".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        assert_eq!(
+            inject_content(ROUTES, "import b from './b';", &config, &path())
+                .unwrap()
+                .unwrap(),
+            "import a from './a';\nimport b from './b';\n\nconst routes = [\n  a,\n  // moon:routes\n];\n"
+        );
+    }
+
+    #[test]
+    fn injects_after_regex() {
+        let config = TemplateFrontmatterInjectConfig {
+            after: Some("^import".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        assert_eq!(
+            inject_content(ROUTES, "import b from './b';", &config, &path())
+                .unwrap()
+                .unwrap(),
+            "import a from './a';\nimport b from './b';\n\nconst routes = [\n  a,\n  // moon:routes\n];\n"
+        );
+    }
+
+    #[test]
+    fn skips_when_already_injected() {
+        let config = TemplateFrontmatterInjectConfig {
+            after: Some("^import".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        assert_eq!(
+            inject_content(ROUTES, "import a from './a';\n", &config, &path()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn injects_when_only_a_substring_exists() {
+        let config = TemplateFrontmatterInjectConfig {
+            after: Some("^import".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        assert_eq!(
+            inject_content(ROUTES, "a", &config, &path())
+                .unwrap()
+                .unwrap(),
+            "import a from './a';\na\n\nconst routes = [\n  a,\n  // moon:routes\n];\n"
+        );
+    }
+
+    #[test]
+    fn matches_whole_line_blocks() {
+        assert!(is_content_injected(ROUTES, "const routes = [\n  a,\n"));
+        assert!(is_content_injected(ROUTES, "  a,   \n"));
+        assert!(is_content_injected(ROUTES, "\n"));
+        assert!(!is_content_injected(ROUTES, "a,"));
+        assert!(!is_content_injected(
+            ROUTES,
+            "const routes = [\n  // moon:routes\n"
+        ));
+    }
+
+    #[test]
+    fn appends_to_end_of_file() {
+        let config = TemplateFrontmatterInjectConfig {
+            after: Some("node_modules".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        assert_eq!(
+            inject_content("target\nnode_modules\n", ".moon/cache", &config, &path())
+                .unwrap()
+                .unwrap(),
+            "target\nnode_modules\n.moon/cache\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "as the anchor // unknown")]
+    fn errors_for_missing_anchor() {
+        let config = TemplateFrontmatterInjectConfig {
+            marker: Some("// unknown".into()),
+            ..TemplateFrontmatterInjectConfig::default()
+        };
+
+        inject_content(ROUTES, "b", &config, &path()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "as an after, before, or marker anchor")]
+    fn errors_without_anchor() {
+        inject_content(
+            ROUTES,
+            "b",
+            &TemplateFrontmatterInjectConfig::default(),
+            &path(),
+        )
+        .unwrap();
+    }
+}
+
+mod merge_three_way {
+    use super::*;
+
+    #[test]
+    fn applies_changes_from_both_sides() {
+        let result = merge_three_way("a\nb\nc\nd\n", "a\nB\nc\nd\n", "a\nb\nc\nD\n");
+
+        assert_eq!(result.content, "a\nB\nc\nD\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn keeps_identical_changes() {
+        let result = merge_three_way("a\nb\n", "a\nZ\n", "a\nZ\n");
+
+        assert_eq!(result.content, "a\nZ\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn applies_added_and_removed_lines() {
+        let result = merge_three_way("a\nb\nc\n", "a\nc\n", "a\nb\nc\nd\n");
+
+        assert_eq!(result.content, "a\nc\nd\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn marks_conflicting_changes() {
+        let result = merge_three_way("a\nb\nc\n", "a\nX\nc\n", "a\nY\nc\n");
+
+        assert_eq!(
+            result.content,
+            "a\n<<<<<<< current\nX\n=======\nY\n>>>>>>> template\nc\n"
+        );
+        assert_eq!(result.conflicts, 1);
+    }
+
+    #[test]
+    fn marks_conflicts_without_base() {
+        let result = merge_three_way("", "x", "y\n");
+
+        assert_eq!(
+            result.content,
+            "<<<<<<< current\nx\n=======\ny\n>>>>>>> template\n"
+        );
+        assert_eq!(result.conflicts, 1);
+    }
+}
@@ -1,9 +1,17 @@
-use moon_codegen::{CodeGenerator, Template, TemplateContext, TemplateFile};
+use moon_codegen::{
+    is_interpolated_path, CodeGenerator, Template, TemplateContext, TemplateFile,
+    TemplateHookAction,
+};
 use moon_common::Id;
-use moon_config::{GeneratorConfig, TemplateFrontmatterConfig};
+use moon_config::{
+    GeneratorConfig, NodeConfig, TemplateFrontmatterConfig, ToolchainConfig, UnresolvedVersionSpec,
+};
 use moon_env::MoonEnvironment;
+use moon_test_utils2::generate_workspace_graph_from_sandbox;
+use moon_workspace_graph::WorkspaceGraph;
 use starbase_sandbox::{create_sandbox, locate_fixture};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 fn create_template_file() -> TemplateFile {
     TemplateFile::new("standard".into(), PathBuf::from("."))
@@ -195,6 +203,15 @@ mod template {
     mod interpolate_path {
         use super::*;
 
+        #[test]
+        fn detects_interpolated_paths() {
+            assert!(is_interpolated_path("apps/[name]"));
+            assert!(is_interpolated_path("apps/[name | kebab_case]"));
+            assert!(is_interpolated_path("apps/{{ name }}"));
+            assert!(!is_interpolated_path("apps/name"));
+            assert!(!is_interpolated_path("."));
+        }
+
         #[test]
         fn path_segments() {
             let mut template = create_template();
@@ -425,4 +442,207 @@ mod template {
             assert_snapshot!(file.content);
         }
     }
+
+    mod hooks {
+        use super::*;
+
+        #[test]
+        fn renders_after_generate_hooks() {
+            let mut template = Template::new(Id::raw("hooks"), locate_fixture("hooks")).unwrap();
+
+            assert_eq!(
+                template
+                    .get_after_generate_hooks(&create_context())
+                    .unwrap(),
+                vec![
+                    TemplateHookAction::Command("npm install".into()),
+                    TemplateHookAction::Target("string:build".into()),
+                    TemplateHookAction::Command("echo 123".into()),
+                ]
+            );
+        }
+
+        #[test]
+        fn has_no_hooks_by_default() {
+            let mut template = create_template();
+
+            assert!(template
+                .get_after_generate_hooks(&create_context())
+                .unwrap()
+                .is_empty());
+        }
+    }
+
+    mod helpers {
+        use super::*;
+
+        #[test]
+        fn pluralizes_and_singularizes() {
+            let mut template = create_template();
+            let context = create_context();
+
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{{ 'box' | pluralize }}", &context)
+                    .unwrap(),
+                "boxes"
+            );
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{{ 'box' | pluralize(count = 1) }}", &context)
+                    .unwrap(),
+                "box"
+            );
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{{ 'people' | singularize }}", &context)
+                    .unwrap(),
+                "person"
+            );
+        }
+
+        #[test]
+        fn generates_uuids() {
+            let mut template = create_template();
+            let context = create_context();
+
+            let a = template
+                .engine
+                .render_str("{{ uuid() }}", &context)
+                .unwrap();
+            let b = template
+                .engine
+                .render_str("{{ uuid() }}", &context)
+                .unwrap();
+
+            assert_eq!(a.len(), 36);
+            assert_ne!(a, b);
+        }
+
+        #[tokio::test]
+        async fn renders_workspace_functions() {
+            let sandbox = create_sandbox("generator");
+            let config = GeneratorConfig::default();
+            let toolchain_config = ToolchainConfig {
+                node: Some(NodeConfig {
+                    version: Some(UnresolvedVersionSpec::parse("20.0.0").unwrap()),
+                    ..NodeConfig::default()
+                }),
+                ..ToolchainConfig::default()
+            };
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.set_toolchain_config(&toolchain_config);
+            codegen.set_workspace_graph(Arc::new(WorkspaceGraph::default()));
+            codegen.load_templates().await.unwrap();
+
+            let mut template = codegen.get_template("one").unwrap();
+            let context = create_context();
+
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{{ toolchain_version(id = 'node') }}", &context)
+                    .unwrap(),
+                "20.0.0"
+            );
+            assert_eq!(
+                template
+                    .engine
+                    .render_str(
+                        "{% set version = toolchain_version(id = 'deno') %}{% if version %}some{% else %}none{% endif %}",
+                        &context
+                    )
+                    .unwrap(),
+                "none"
+            );
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{{ projects(query = 'tag=app') | length }}", &context)
+                    .unwrap(),
+                "0"
+            );
+            assert!(template
+                .engine
+                .render_str("{{ project(id = 'unknown') }}", &context)
+                .is_err());
+        }
+
+        #[tokio::test]
+        async fn renders_workspace_functions_with_projects() {
+            let sandbox = create_sandbox("workspace");
+            let config = GeneratorConfig::default();
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+
+            let mut template = codegen.get_template("graph").unwrap();
+            let context = create_context();
+
+            template.set_workspace_graph(Arc::new(
+                generate_workspace_graph_from_sandbox(sandbox.path()).await,
+            ));
+
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{% set app = project(id = 'app') %}{{ app.id }}", &context)
+                    .unwrap(),
+                "app"
+            );
+            assert_eq!(
+                template
+                    .engine
+                    .render_str("{{ projects() | length }}", &context)
+                    .unwrap(),
+                "2"
+            );
+
+            template.load_files(sandbox.path(), &context).unwrap();
+
+            let file = template
+                .files
+                .values()
+                .find(|f| f.name == "projects.txt")
+                .unwrap();
+
+            assert_eq!(file.content, "lib\n");
+        }
+
+        #[tokio::test]
+        async fn detects_workspace_function_usage() {
+            let sandbox = create_sandbox("workspace");
+            let config = GeneratorConfig::default();
+
+            let mut codegen = CodeGenerator::new(
+                sandbox.path(),
+                &config,
+                MoonEnvironment::new_testing(sandbox.path()).into(),
+            );
+            codegen.load_templates().await.unwrap();
+
+            assert!(codegen
+                .get_template("graph")
+                .unwrap()
+                .uses_workspace_functions()
+                .unwrap());
+            assert!(!codegen
+                .get_template("plain")
+                .unwrap()
+                .uses_workspace_functions()
+                .unwrap());
+        }
+    }
 }
@@ -0,0 +1,114 @@
+use moon_codegen::{
+    diff_template_snapshot, get_template_snapshot_dir, render_template_test, Template,
+    TemplateSnapshotChange,
+};
+use moon_common::Id;
+use moon_console::Console;
+use starbase_sandbox::{create_empty_sandbox, locate_fixture};
+
+fn load_template() -> Template {
+    Template::new(Id::raw("tests"), locate_fixture("tests")).unwrap()
+}
+
+mod templates_test {
+    use super::*;
+
+    #[test]
+    fn renders_with_default_variables() {
+        let sandbox = create_empty_sandbox();
+        let template = load_template();
+        let test = template.config.tests.get("default").unwrap();
+
+        let template = render_template_test(
+            &template,
+            test,
+            &sandbox.path().join("out"),
+            sandbox.path(),
+            &Console::new_testing(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            template
+                .files
+                .keys()
+                .map(|k| k.as_str())
+                .collect::<Vec<_>>(),
+            vec!["file.txt"]
+        );
+        assert_eq!(
+            std::fs::read_to_string(sandbox.path().join("out/file.txt")).unwrap(),
+            "name = example\n"
+        );
+    }
+
+    #[test]
+    fn renders_with_test_variables() {
+        let sandbox = create_empty_sandbox();
+        let template = load_template();
+        let test = template.config.tests.get("custom").unwrap();
+
+        render_template_test(
+            &template,
+            test,
+            &sandbox.path().join("out"),
+            sandbox.path(),
+            &Console::new_testing(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(sandbox.path().join("out/file.txt")).unwrap(),
+            "name = custom\n"
+        );
+    }
+
+    #[test]
+    fn matches_snapshots() {
+        let sandbox = create_empty_sandbox();
+        let template = load_template();
+
+        for (name, test) in &template.config.tests {
+            let output_dir = sandbox.path().join(name);
+
+            render_template_test(
+                &template,
+                test,
+                &output_dir,
+                sandbox.path(),
+                &Console::new_testing(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                diff_template_snapshot(&output_dir, &get_template_snapshot_dir(&template, name))
+                    .unwrap(),
+                vec![]
+            );
+        }
+    }
+
+    #[test]
+    fn diffs_against_snapshot() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("output/added.txt", "added");
+        sandbox.create_file("output/changed.txt", "new");
+        sandbox.create_file("output/same.txt", "same");
+        sandbox.create_file("snapshot/changed.txt", "old");
+        sandbox.create_file("snapshot/missing.txt", "missing");
+        sandbox.create_file("snapshot/same.txt", "same");
+
+        assert_eq!(
+            diff_template_snapshot(
+                &sandbox.path().join("output"),
+                &sandbox.path().join("snapshot")
+            )
+            .unwrap(),
+            vec![
+                TemplateSnapshotChange::Added("added.txt".into()),
+                TemplateSnapshotChange::Changed("changed.txt".into()),
+                TemplateSnapshotChange::Missing("missing.txt".into()),
+            ]
+        );
+    }
+}
@@ -1,10 +1,27 @@
 use moon_config::{CodeownersConfig, OwnersConfig, OwnersPaths, VcsProvider};
 use starbase_utils::fs::{self, FsError};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tracing::{debug, trace};
 
+/// Format a path as a pattern for the `CODEOWNERS` file.
+pub fn format_pattern(path: PathBuf) -> String {
+    path.to_string_lossy()
+        // Always use forward slashes
+        .replace('\\', "/")
+        // Escape spaces
+        .replace(' ', "\\ ")
+}
+
+/// Create a pattern for a path owned by the project at the provided source.
+/// Relative paths are joined to the source, while absolute paths replace it,
+/// and are relative from the workspace root.
+pub fn create_project_pattern(source: &str, path: &str) -> String {
+    format_pattern(PathBuf::from("/").join(source).join(path))
+}
+
 pub struct CodeownersGenerator {
     pub file_path: PathBuf,
     file: File,
@@ -36,6 +53,33 @@ impl CodeownersGenerator {
         Ok(generator)
     }
 
+    pub fn add_custom_groups(
+        &mut self,
+        groups: &BTreeMap<String, Vec<String>>,
+    ) -> miette::Result<()> {
+        // Custom groups are only supported by Bitbucket
+        if groups.is_empty() || !matches!(self.provider, VcsProvider::Bitbucket) {
+            return Ok(());
+        }
+
+        trace!("Adding custom groups");
+
+        self.write("")?;
+        self.write("# (groups)")?;
+
+        for (group, members) in groups {
+            if !members.is_empty() {
+                self.write(format!(
+                    "@@@{} {}",
+                    group.trim_start_matches('@'),
+                    members.join(" ")
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn add_project_entry(
         &mut self,
         id: &str,
@@ -72,7 +116,7 @@ impl CodeownersGenerator {
             }
 
             VcsProvider::GitLab => {
-                let mut header = format!("[{id}]");
+                let mut header = format!("[{}]", config.section.as_deref().unwrap_or(id));
 
                 if config.optional {
                     header = format!("^{header}")
@@ -82,8 +126,9 @@ impl CodeownersGenerator {
                     header = format!("{header}[{}]", required_approvals);
                 }
 
-                if matches!(config.paths, OwnersPaths::List(_)) {
-                    header = format!("{header} {}", config.default_owner.as_ref().unwrap());
+                // Default owners apply to all entries in the section without owners
+                if let Some(default_owner) = &config.default_owner {
+                    header = format!("{header} {default_owner}");
                 }
 
                 self.write(header)?;
@@ -92,41 +137,54 @@ impl CodeownersGenerator {
         };
 
         // Render the owner entries
-        let root = PathBuf::from("/").join(source);
+        let random_reviewers = config
+            .random_reviewers
+            .or(root_config.random_reviewers)
+            .unwrap_or(0);
+
+        let mut entries = vec![];
 
         match &config.paths {
             OwnersPaths::List(paths) => {
                 for path in paths {
-                    if matches!(self.provider, VcsProvider::GitLab) {
-                        self.write(self.format_path(root.join(path)))?;
-                    } else {
-                        self.write(format!(
-                            "{} {}",
-                            self.format_path(root.join(path)),
-                            config.default_owner.as_ref().unwrap()
-                        ))?;
-                    }
+                    entries.push((create_project_pattern(source, path), vec![]));
                 }
             }
             OwnersPaths::Map(map) => {
                 for (path, owners) in map {
-                    if owners.is_empty() {
-                        self.write(format!(
-                            "{} {}",
-                            self.format_path(root.join(path)),
-                            config.default_owner.as_ref().unwrap()
-                        ))?;
-                    } else {
-                        self.write(format!(
-                            "{} {}",
-                            self.format_path(root.join(path)),
-                            owners.join(" ")
-                        ))?;
-                    }
+                    entries.push((create_project_pattern(source, path), owners.to_owned()));
                 }
             }
         };
 
+        for (path, mut owners) in entries {
+            if matches!(self.provider, VcsProvider::GitLab) {
+                // The section's default owner is inherited
+                if owners.is_empty() {
+                    self.write(path)?;
+                } else {
+                    self.write(format!("{} {}", path, owners.join(" ")))?;
+                }
+
+                continue;
+            }
+
+            if owners.is_empty() {
+                owners.extend(config.default_owner.clone());
+            }
+
+            if matches!(self.provider, VcsProvider::Bitbucket) && random_reviewers > 0 {
+                self.write(format!(
+                    "{} Random({}) {}",
+                    path,
+                    random_reviewers,
+                    owners.join(" ")
+                ))?;
+            } else {
+                self.write(format!("{} {}", path, owners.join(" ")))?;
+            }
+        }
+
         Ok(())
     }
 
@@ -144,7 +202,7 @@ impl CodeownersGenerator {
             if !owners.is_empty() {
                 self.write(format!(
                     "{} {}",
-                    self.format_path(PathBuf::from(path)),
+                    format_pattern(PathBuf::from(path)),
                     owners.join(" ")
                 ))?;
             }
@@ -174,14 +232,6 @@ impl CodeownersGenerator {
         Ok(true)
     }
 
-    fn format_path(&self, path: PathBuf) -> String {
-        path.to_string_lossy()
-            // Always use forward slashes
-            .replace('\\', "/")
-            // Escape spaces
-            .replace(' ', "\\ ")
-    }
-
     fn write<T: AsRef<str>>(&mut self, message: T) -> miette::Result<()> {
         writeln!(self.file, "{}", message.as_ref()).map_err(|error| FsError::Create {
             path: self.file_path.to_path_buf(),
@@ -0,0 +1,165 @@
+use crate::codeowners_generator::{create_project_pattern, format_pattern};
+use moon_config::{CodeownersConfig, OwnersConfig, OwnersPaths, VcsProvider};
+use serde::Serialize;
+use starbase_utils::glob::GlobSet;
+use std::path::PathBuf;
+use tracing::trace;
+
+/// An ownership rule, as it would be written to the `CODEOWNERS` file.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CodeownersRule {
+    /// Owners of files that match the pattern.
+    pub owners: Vec<String>,
+
+    /// Pattern relative from the workspace root, as written to the file.
+    pub pattern: String,
+
+    /// Project the rule was defined in, or `None` for global paths.
+    pub project: Option<String>,
+
+    /// Section the rule belongs to, or `None` for global paths. GitLab only,
+    /// and defaults to the project ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+}
+
+impl CodeownersRule {
+    /// Return true if the workspace relative file path matches the pattern,
+    /// using the same semantics as `.gitignore` (which code owners follow).
+    pub fn matches(&self, file: &str) -> miette::Result<bool> {
+        let pattern = self.pattern.replace("\\ ", " ");
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+
+        // Patterns with a leading or inner slash are relative from the root,
+        // otherwise they match at any depth
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+
+        if pattern.is_empty() {
+            return Ok(false);
+        }
+
+        let base = if anchored {
+            pattern.to_owned()
+        } else {
+            format!("**/{pattern}")
+        };
+
+        let mut globs = vec![format!("{base}/**")];
+
+        if !dir_only {
+            globs.push(base);
+        }
+
+        Ok(GlobSet::new(&globs)?.is_match(file))
+    }
+}
+
+/// Resolves the owners of files, using the same precedence rules as the
+/// generated `CODEOWNERS` file. Entries must be added in the same order
+/// as they are added to the [`CodeownersGenerator`](crate::CodeownersGenerator).
+pub struct CodeownersResolver {
+    provider: VcsProvider,
+    rules: Vec<CodeownersRule>,
+}
+
+impl CodeownersResolver {
+    pub fn new(provider: VcsProvider) -> Self {
+        Self {
+            provider,
+            rules: vec![],
+        }
+    }
+
+    pub fn add_project_entry(&mut self, id: &str, source: &str, config: &OwnersConfig) {
+        if config.paths.is_empty() {
+            return;
+        }
+
+        trace!(project_id = id, source, "Adding project rules");
+
+        let section = matches!(self.provider, VcsProvider::GitLab)
+            .then(|| config.section.clone().unwrap_or_else(|| id.to_owned()));
+
+        match &config.paths {
+            OwnersPaths::List(paths) => {
+                for path in paths {
+                    self.rules.push(CodeownersRule {
+                        owners: config.default_owner.clone().into_iter().collect(),
+                        pattern: create_project_pattern(source, path),
+                        project: Some(id.to_owned()),
+                        section: section.clone(),
+                    });
+                }
+            }
+            OwnersPaths::Map(map) => {
+                for (path, owners) in map {
+                    self.rules.push(CodeownersRule {
+                        owners: if owners.is_empty() {
+                            config.default_owner.clone().into_iter().collect()
+                        } else {
+                            owners.to_owned()
+                        },
+                        pattern: create_project_pattern(source, path),
+                        project: Some(id.to_owned()),
+                        section: section.clone(),
+                    });
+                }
+            }
+        };
+    }
+
+    pub fn add_workspace_entries(&mut self, config: &CodeownersConfig) {
+        trace!("Adding workspace rules");
+
+        for (path, owners) in &config.global_paths {
+            if !owners.is_empty() {
+                self.rules.push(CodeownersRule {
+                    owners: owners.to_owned(),
+                    pattern: format_pattern(PathBuf::from(path)),
+                    project: None,
+                    section: None,
+                });
+            }
+        }
+    }
+
+    /// Return all rules that apply to the workspace relative file path. The last
+    /// matching rule takes precedence, but on GitLab, each project is a section
+    /// (projects may share a section), and the last matching rule of every
+    /// section applies.
+    pub fn resolve(&self, file: &str) -> miette::Result<Vec<&CodeownersRule>> {
+        let mut matched: Vec<&CodeownersRule> = vec![];
+
+        for rule in &self.rules {
+            if !rule.matches(file)? {
+                continue;
+            }
+
+            if matches!(self.provider, VcsProvider::GitLab) {
+                matched.retain(|other| other.section != rule.section);
+                matched.push(rule);
+            } else {
+                matched = vec![rule];
+            }
+        }
+
+        Ok(matched)
+    }
+
+    /// Return a unique list of owners for the workspace relative file path.
+    pub fn resolve_owners(&self, file: &str) -> miette::Result<Vec<String>> {
+        let mut owners = vec![];
+
+        for rule in self.resolve(file)? {
+            for owner in &rule.owners {
+                if !owners.contains(owner) {
+                    owners.push(owner.to_owned());
+                }
+            }
+        }
+
+        Ok(owners)
+    }
+}
@@ -1,6 +1,7 @@
 use moon_codeowners::CodeownersGenerator;
 use moon_config::{ConfigLoader, VcsProvider};
 use starbase_sandbox::{assert_snapshot, create_empty_sandbox, locate_fixture, Sandbox};
+use std::collections::BTreeMap;
 use std::fs;
 
 fn load_generator(provider: VcsProvider) -> Sandbox {
@@ -15,11 +16,16 @@ fn load_generator(provider: VcsProvider) -> Sandbox {
     let mut generator = CodeownersGenerator::new(sandbox.path(), provider).unwrap();
     let workspace_config = config_loader.load_workspace_config(sandbox.path()).unwrap();
 
-    generator
-        .add_workspace_entries(&workspace_config.codeowners)
-        .unwrap();
+    let mut projects = vec![];
+    let mut custom_groups = BTreeMap::from_iter(workspace_config.codeowners.custom_groups.clone());
 
-    for project_fixture in ["custom-groups", "list-paths", "map-paths", "no-paths"] {
+    for project_fixture in [
+        "custom-groups",
+        "list-paths",
+        "map-paths",
+        "no-paths",
+        "provider-options",
+    ] {
         sandbox.create_file(
             format!("{}/moon.yml", project_fixture),
             fs::read_to_string(locate_fixture(project_fixture).join("moon.yml")).unwrap(),
@@ -29,6 +35,17 @@ fn load_generator(provider: VcsProvider) -> Sandbox {
             .load_project_config_from_source(sandbox.path(), project_fixture)
             .unwrap();
 
+        custom_groups.extend(project_config.owners.custom_groups.clone());
+        projects.push((project_fixture, project_config));
+    }
+
+    generator.add_custom_groups(&custom_groups).unwrap();
+
+    generator
+        .add_workspace_entries(&workspace_config.codeowners)
+        .unwrap();
+
+    for (project_fixture, project_config) in projects {
         generator
             .add_project_entry(
                 project_fixture,
@@ -0,0 +1,165 @@
+use moon_codeowners::{create_project_pattern, CodeownersResolver};
+use moon_config::{ConfigLoader, OwnersConfig, OwnersPaths, VcsProvider};
+use starbase_sandbox::{create_empty_sandbox, locate_fixture};
+use std::fs;
+
+fn load_resolver(provider: VcsProvider) -> CodeownersResolver {
+    let sandbox = create_empty_sandbox();
+    let config_loader = ConfigLoader::default();
+
+    sandbox.create_file(
+        ".moon/workspace.yml",
+        fs::read_to_string(locate_fixture("workspace").join("workspace.yml")).unwrap(),
+    );
+
+    let mut resolver = CodeownersResolver::new(provider);
+    let workspace_config = config_loader.load_workspace_config(sandbox.path()).unwrap();
+
+    resolver.add_workspace_entries(&workspace_config.codeowners);
+
+    for project_fixture in ["custom-groups", "list-paths", "map-paths", "no-paths"] {
+        sandbox.create_file(
+            format!("{}/moon.yml", project_fixture),
+            fs::read_to_string(locate_fixture(project_fixture).join("moon.yml")).unwrap(),
+        );
+
+        let project_config = config_loader
+            .load_project_config_from_source(sandbox.path(), project_fixture)
+            .unwrap();
+
+        resolver.add_project_entry(project_fixture, project_fixture, &project_config.owners);
+    }
+
+    resolver
+}
+
+#[test]
+fn falls_back_to_global_paths() {
+    let resolver = load_resolver(VcsProvider::GitHub);
+
+    assert_eq!(resolver.resolve_owners("README.md").unwrap(), ["@admins"]);
+    assert_eq!(
+        resolver.resolve_owners("config/app.yml").unwrap(),
+        ["@infra"]
+    );
+    assert_eq!(
+        resolver.resolve_owners("index.js").unwrap(),
+        ["@infra", "@frontend"]
+    );
+}
+
+#[test]
+fn last_matching_rule_wins() {
+    let resolver = load_resolver(VcsProvider::GitHub);
+
+    assert_eq!(
+        resolver.resolve_owners("map-paths/src/lib.rs").unwrap(),
+        ["@admins"]
+    );
+    assert_eq!(
+        resolver.resolve_owners("map-paths/lib.rs").unwrap(),
+        ["@rust-dev", "@api-team"]
+    );
+    assert_eq!(
+        resolver
+            .resolve_owners("map-paths/config/nested/file.json")
+            .unwrap(),
+        ["@backend"]
+    );
+    assert_eq!(
+        resolver.resolve_owners("list-paths/index.ts").unwrap(),
+        ["@frontend"]
+    );
+}
+
+#[test]
+fn handles_escaped_spaces() {
+    let resolver = load_resolver(VcsProvider::GitHub);
+
+    assert_eq!(
+        resolver
+            .resolve_owners("custom-groups/path/with space/file.txt")
+            .unwrap(),
+        ["@@@group"]
+    );
+}
+
+#[test]
+fn combines_sections_for_gitlab() {
+    let resolver = load_resolver(VcsProvider::GitLab);
+    let rules = resolver.resolve("map-paths/lib.rs").unwrap();
+
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].project, None);
+    assert_eq!(rules[1].project.as_deref(), Some("map-paths"));
+    assert_eq!(
+        resolver.resolve_owners("map-paths/lib.rs").unwrap(),
+        ["@admins", "@rust-dev", "@api-team"]
+    );
+}
+
+#[test]
+fn uses_custom_section_names_for_gitlab() {
+    let resolver = load_resolver(VcsProvider::GitLab);
+    let rules = resolver.resolve("map-paths/lib.rs").unwrap();
+
+    assert_eq!(rules[0].section, None);
+    assert_eq!(rules[1].section.as_deref(), Some("map-paths"));
+
+    let mut resolver = CodeownersResolver::new(VcsProvider::GitLab);
+
+    for (id, owner) in [("a", "@a-team"), ("b", "@b-team")] {
+        resolver.add_project_entry(
+            id,
+            "shared",
+            &OwnersConfig {
+                paths: OwnersPaths::List(vec!["src/".into()]),
+                default_owner: Some(owner.into()),
+                section: Some("Shared".into()),
+                ..OwnersConfig::default()
+            },
+        );
+    }
+
+    // Projects in the same section override each other
+    assert_eq!(
+        resolver.resolve_owners("shared/src/lib.rs").unwrap(),
+        ["@b-team"]
+    );
+}
+
+#[test]
+fn roots_absolute_paths_at_workspace() {
+    let mut resolver = CodeownersResolver::new(VcsProvider::GitHub);
+
+    resolver.add_project_entry(
+        "app",
+        "packages/app",
+        &OwnersConfig {
+            paths: OwnersPaths::List(vec!["/docs/app/".into(), "src/".into()]),
+            default_owner: Some("@app-team".into()),
+            ..OwnersConfig::default()
+        },
+    );
+
+    assert_eq!(
+        resolver.resolve("docs/app/index.md").unwrap()[0].pattern,
+        create_project_pattern("packages/app", "/docs/app/")
+    );
+    assert_eq!(
+        resolver.resolve_owners("docs/app/index.md").unwrap(),
+        ["@app-team"]
+    );
+    assert_eq!(
+        resolver
+            .resolve_owners("packages/app/docs/app/index.md")
+            .unwrap(),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        resolver
+            .resolve_owners("packages/app/src/index.ts")
+            .unwrap(),
+        ["@app-team"]
+    );
+}
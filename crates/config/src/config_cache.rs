@@ -24,6 +24,12 @@ impl ConfigCache {
             ".pkl"
         } else if url.ends_with(".yaml") || url.ends_with(".yml") {
             ".yml"
+        } else if url.ends_with(".jsonc") {
+            ".jsonc"
+        } else if url.ends_with(".json") {
+            ".json"
+        } else if url.ends_with(".toml") {
+            ".toml"
         } else {
             ""
         };
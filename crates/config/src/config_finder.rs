@@ -90,24 +90,58 @@ impl ConfigFinder {
         }
 
         label.push_str(name);
+        label.push_str(".{");
+        label.push_str(&self.get_extensions().join(","));
+        label.push('}');
+
+        label
+    }
+
+    /// Return a list of file extensions (without a leading period)
+    /// that are supported for configuration files. YAML is first
+    /// as it's the primary format.
+    pub fn get_extensions(&self) -> Vec<&'static str> {
+        let mut exts = vec!["yml", "json", "jsonc", "toml"];
 
         if self.pkl {
-            label.push_str(".{plk,yml}");
-        } else {
-            label.push_str(".yml");
+            exts.push("pkl");
         }
 
-        label
+        exts
     }
 
     pub fn get_file_names(&self, name: &str) -> Vec<String> {
-        let mut files = vec![format!("{name}.yml")];
+        self.get_extensions()
+            .into_iter()
+            .map(|ext| format!("{name}.{ext}"))
+            .collect()
+    }
 
-        if self.pkl {
-            files.push(format!("{name}.pkl"));
+    /// Return a list of glob patterns that match all supported
+    /// configuration files, relative from the `.moon` directory.
+    pub fn get_workspace_globs(&self) -> Vec<String> {
+        let mut globs = vec![];
+
+        for ext in self.get_extensions() {
+            globs.push(format!("*.{ext}"));
+            globs.push(format!("tasks/**/*.{ext}"));
         }
 
-        files
+        globs
+    }
+
+    pub fn is_config_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext == "yaml" || self.get_extensions().contains(&ext))
+    }
+
+    /// Strip a supported configuration extension from the provided
+    /// file name, returning the stem.
+    pub fn strip_extension<'name>(&self, name: &'name str) -> Option<&'name str> {
+        self.get_extensions()
+            .into_iter()
+            .find_map(|ext| name.strip_suffix(ext)?.strip_suffix('.'))
     }
 
     pub fn get_from_dir(&self, dir: PathBuf) -> miette::Result<Vec<PathBuf>> {
@@ -135,10 +169,7 @@ impl ConfigFinder {
             if file_type.is_file() {
                 // Non-config files may be located in these folders,
                 // so avoid failing when trying to parse it as a config
-                if path
-                    .extension()
-                    .is_some_and(|ext| ext == "yml" || ext == "yaml" || self.pkl && ext == "pkl")
-                {
+                if self.is_config_file(&path) {
                     files.push(path);
                 }
             } else if file_type.is_dir() {
@@ -10,7 +10,8 @@ use crate::validate::check_yml_extension;
 use crate::workspace_config::WorkspaceConfig;
 use moon_common::color;
 use moon_common::consts::CONFIG_DIRNAME;
-use schematic::{Config, ConfigLoader as Loader};
+use schematic::{Config, ConfigError, ConfigLoader as Loader, Format};
+use std::fs;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
@@ -154,11 +155,11 @@ impl ConfigLoader {
         &self,
         path: P,
     ) -> miette::Result<InheritedTasksConfig> {
-        let result = Loader::<InheritedTasksConfig>::new()
-            .file_optional(path.as_ref())?
-            .load()?;
+        let mut loader = Loader::<InheritedTasksConfig>::new();
 
-        Ok(result.config)
+        add_file_source(&mut loader, path.as_ref())?;
+
+        Ok(loader.load()?.config)
     }
 
     pub fn load_tasks_partial_config_from_path<T: AsRef<Path>, P: AsRef<Path>>(
@@ -166,10 +167,11 @@ impl ConfigLoader {
         workspace_root: T,
         path: P,
     ) -> miette::Result<PartialInheritedTasksConfig> {
-        Ok(self
-            .create_tasks_loader(workspace_root)?
-            .file_optional(check_yml_extension(path.as_ref()))?
-            .load_partial(&())?)
+        let mut loader = self.create_tasks_loader(workspace_root)?;
+
+        add_file_source(&mut loader, path.as_ref())?;
+
+        Ok(loader.load_partial(&())?)
     }
 
     pub fn load_tasks_manager<P: AsRef<Path>>(
@@ -247,20 +249,46 @@ impl ConfigLoader {
         files: Vec<PathBuf>,
     ) -> miette::Result<()> {
         for file in files {
-            if file
-                .extension()
-                .is_some_and(|ext| ext == "yml" || ext == "yaml")
-            {
-                loader.file_optional(check_yml_extension(&file))?;
-            } else {
-                loader.file_optional(file)?;
-            }
+            add_file_source(loader, &file)?;
         }
 
         Ok(())
     }
 }
 
+fn add_file_source<T: Config>(loader: &mut Loader<T>, file: &Path) -> miette::Result<()> {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("yml" | "yaml") => {
+            loader.file_optional(check_yml_extension(file))?;
+        }
+        // Schematic does not support JSONC, so strip comments and
+        // trailing commas, and load the result as JSON code
+        Some("jsonc") => {
+            if file.exists() {
+                let mut content =
+                    fs::read_to_string(file).map_err(|error| ConfigError::ReadFileFailed {
+                        path: file.to_path_buf(),
+                        error: Box::new(error),
+                    })?;
+
+                json_strip_comments::strip(&mut content).map_err(|error| {
+                    ConfigError::ReadFileFailed {
+                        path: file.to_path_buf(),
+                        error: Box::new(error),
+                    }
+                })?;
+
+                loader.code(content, Format::Json)?;
+            }
+        }
+        _ => {
+            loader.file_optional(file)?;
+        }
+    };
+
+    Ok(())
+}
+
 impl Deref for ConfigLoader {
     type Target = ConfigFinder;
 
@@ -172,9 +172,7 @@ impl InheritedTasksManager {
 
         let name = if valid_names.iter().any(|n| n == name) {
             "*"
-        } else if let Some(stripped_name) = name.strip_suffix(".yml") {
-            stripped_name
-        } else if let Some(stripped_name) = name.strip_suffix(".pkl") {
+        } else if let Some(stripped_name) = self.config_finder.strip_extension(name) {
             stripped_name
         } else {
             return;
@@ -9,11 +9,24 @@ cacheable!(
         /// A task within the current project for building the project.
         pub build_task: Option<Id>,
 
+        /// List of absolute paths within the image to mount as BuildKit
+        /// caches when installing dependencies, in addition to the
+        /// package manager caches of the project's toolchains.
+        pub cache_mounts: Vec<String>,
+
         /// The base Docker image.
         pub image: Option<String>,
 
+        /// A slim Docker image for the start stage. When defined, only the
+        /// outputs of the build task are copied into the final image.
+        pub runtime_image: Option<String>,
+
         /// A task within the current project for starting the project.
         pub start_task: Option<Id>,
+
+        /// A non-root user (or UID) to run the start stage as, when using
+        /// a runtime image. The user must exist in the runtime image.
+        pub user: Option<String>,
     }
 );
 
@@ -3,6 +3,7 @@ mod docker_config;
 mod overrides_config;
 mod owners_config;
 mod task_config;
+mod task_image_config;
 mod task_options_config;
 
 pub use dep_config::*;
@@ -10,4 +11,5 @@ pub use docker_config::*;
 pub use overrides_config::*;
 pub use owners_config::*;
 pub use task_config::*;
+pub use task_image_config::*;
 pub use task_options_config::*;
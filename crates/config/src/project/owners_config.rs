@@ -78,9 +78,38 @@ cacheable!(
         #[setting(nested, validate = validate_paths)]
         pub paths: OwnersPaths,
 
+        /// Bitbucket only. The number of reviewers to randomly assign from
+        /// the owners of each path, using the `Random()` condition.
+        pub random_reviewers: Option<u8>,
+
         /// Bitbucket and GitLab only. The number of approvals required for the
         /// request to be satisfied. For Bitbucket, utilizes the `Check()` condition.
         /// For GitLab, marks the code owners section as required.
         pub required_approvals: Option<u8>,
+
+        /// GitLab only. Name of the code owners section.
+        /// Defaults to the project ID.
+        pub section: Option<String>,
     }
 );
+
+impl OwnersConfig {
+    /// Return a unique list of all owners, including the default owner.
+    pub fn get_owners(&self) -> Vec<&str> {
+        let mut owners = vec![];
+
+        if let Some(default_owner) = &self.default_owner {
+            owners.push(default_owner.as_str());
+        }
+
+        if let OwnersPaths::Map(map) = &self.paths {
+            for list in map.values() {
+                owners.extend(list.iter().map(|owner| owner.as_str()));
+            }
+        }
+
+        owners.sort();
+        owners.dedup();
+        owners
+    }
+}
@@ -1,5 +1,7 @@
 use crate::language_platform::PlatformType;
-use crate::project::{PartialTaskOptionsConfig, TaskOptionsConfig};
+use crate::project::{
+    PartialTaskImageConfig, PartialTaskOptionsConfig, TaskImageConfig, TaskOptionsConfig,
+};
 use crate::shapes::{InputPath, OneOrMany, OutputPath};
 use moon_common::{cacheable, Id};
 use moon_target::{Target, TargetScope};
@@ -31,6 +33,26 @@ fn validate_command<C>(
     Ok(())
 }
 
+fn validate_image<C>(
+    _image: &PartialTaskImageConfig,
+    task: &PartialTaskConfig,
+    _ctx: &C,
+    _finalize: bool,
+) -> Result<(), ValidateError> {
+    let has_args = |args: &Option<PartialTaskArgs>| {
+        args.as_ref()
+            .is_some_and(|args| !matches!(args, PartialTaskArgs::None))
+    };
+
+    if has_args(&task.command) || has_args(&task.args) || task.script.is_some() {
+        return Err(ValidateError::new(
+            "an image cannot be used with a command, args, or script, as no command is ran",
+        ));
+    }
+
+    Ok(())
+}
+
 pub(crate) fn validate_deps<D, C>(
     deps: &[PartialTaskDependency],
     _task: &D,
@@ -200,6 +222,11 @@ cacheable!(
         #[setting(skip, merge = merge::append_vec)]
         pub global_inputs: Vec<InputPath>,
 
+        /// Assembles an OCI image from the outputs of the task, on top of
+        /// a base image, instead of running a command.
+        #[setting(nested, validate = validate_image)]
+        pub image: Option<TaskImageConfig>,
+
         /// Inputs and sources that will mark the task as affected when comparing
         /// against touched files. When not provided, all files within the project
         /// are considered an input. When an empty list, no files are considered.
@@ -0,0 +1,67 @@
+use crate::portable_path::ProjectFilePath;
+use moon_common::cacheable;
+use schematic::{Config, DefaultValueResult, ValidateError};
+use std::collections::BTreeMap;
+
+fn default_file<C>(_ctx: &C) -> DefaultValueResult<ProjectFilePath> {
+    Ok(Some(ProjectFilePath("image.tar".into())))
+}
+
+fn validate_base<D, C>(
+    value: &str,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> Result<(), ValidateError> {
+    if !value.contains("@sha256:") {
+        return Err(ValidateError::new(
+            "base image must be pinned by digest (name@sha256:...)",
+        ));
+    }
+
+    Ok(())
+}
+
+cacheable!(
+    /// Configures an OCI image to assemble from the outputs of a task.
+    #[derive(Clone, Config, Debug, Eq, PartialEq)]
+    pub struct TaskImageConfig {
+        /// The base image to build on top of, pinned by digest.
+        #[setting(validate = validate_base)]
+        pub base: String,
+
+        /// Default arguments for the entrypoint of the image.
+        pub cmd: Vec<String>,
+
+        /// The entrypoint of the image.
+        pub entrypoint: Vec<String>,
+
+        /// A mapping of environment variables to set in the image,
+        /// in addition to those inherited from the base image.
+        pub env: BTreeMap<String, String>,
+
+        /// File path, relative from the project root, in which to write
+        /// the OCI image layout tarball to. Is automatically added as an output.
+        #[setting(default = default_file)]
+        pub file: ProjectFilePath,
+
+        /// A mapping of labels to set in the image.
+        pub labels: BTreeMap<String, String>,
+
+        /// The platform to select when the base image supports multiple platforms.
+        #[setting(default = "linux/amd64")]
+        pub platform: String,
+
+        /// An image reference (registry/name:tag) to push the image to after
+        /// it has been built.
+        pub push: Option<String>,
+
+        /// The user (or UID) to run the image as.
+        pub user: Option<String>,
+
+        /// The directory within the image in which outputs are copied to,
+        /// and is used as the working directory.
+        #[setting(default = "/app")]
+        pub workdir: String,
+    }
+);
@@ -121,6 +121,7 @@ derive_enum!(
         #[default]
         Buffer,
         BufferOnlyFailure,
+        Group,
         Hash,
         None,
         Stream,
@@ -1,5 +1,20 @@
 use schematic::Config;
 
+/// Injects the rendered content into an existing file, at an anchor,
+/// instead of overwriting the file.
+#[derive(Clone, Config, Debug, Eq, PartialEq)]
+pub struct TemplateFrontmatterInjectConfig {
+    /// Inserts the content after the first line that matches this regex.
+    pub after: Option<String>,
+
+    /// Inserts the content before the first line that matches this regex.
+    pub before: Option<String>,
+
+    /// Inserts the content before the first line that contains this marker,
+    /// typically a comment, so that content can be injected repeatedly.
+    pub marker: Option<String>,
+}
+
 /// Docs: https://moonrepo.dev/docs/config/template#frontmatter
 #[derive(Clone, Config, Debug, Eq, PartialEq)]
 pub struct TemplateFrontmatterConfig {
@@ -12,6 +27,9 @@ pub struct TemplateFrontmatterConfig {
     pub force: bool,
     pub to: Option<String>,
     pub skip: bool,
+
+    #[setting(nested)]
+    pub inject: Option<TemplateFrontmatterInjectConfig>,
 }
 
 #[cfg(feature = "loader")]
@@ -8,6 +8,12 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
+// Query strings are allowed for signed URLs
+static ARCHIVE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^https://[^#?\s]+\.(tar\.gz|tgz|zip)(\?[^#\s]*)?$").unwrap());
+
+static CHECKSUM: Lazy<Regex> = Lazy::new(|| Regex::new("^[a-f0-9]{64}$").unwrap());
+
 static GIT: Lazy<Regex> = Lazy::new(|| {
     Regex::new("^(?<url>[a-z0-9.]+/[a-zA-Z0-9-_./]+)#(?<revision>[a-z0-9-_.@]+)$").unwrap()
 });
@@ -17,9 +23,18 @@ static NPM: Lazy<Regex> = Lazy::new(|| {
         .unwrap()
 });
 
+static OCI: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("^(?<registry>[a-z0-9.-]+(:[0-9]+)?)/(?<repository>[a-z0-9-_./]+)(:(?<tag>[a-zA-Z0-9-_.]+)|@(?<digest>sha256:[a-f0-9]{64}))$")
+        .unwrap()
+});
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged, try_from = "String", into = "String")]
 pub enum TemplateLocator {
+    Archive {
+        url: String,
+        checksum: Option<String>,
+    },
     File {
         path: FilePath,
     },
@@ -34,11 +49,37 @@ pub enum TemplateLocator {
         package: String,
         version: Version,
     },
+    Oci {
+        registry: String,
+        repository: String,
+        tag: String,
+    },
+}
+
+impl TemplateLocator {
+    pub fn get_oci_reference(&self) -> Option<String> {
+        match self {
+            TemplateLocator::Oci {
+                registry,
+                repository,
+                tag,
+            } => Some(if tag.starts_with("sha256:") {
+                format!("{registry}/{repository}@{tag}")
+            } else {
+                format!("{registry}/{repository}:{tag}")
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for TemplateLocator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            TemplateLocator::Archive { url, checksum } => match checksum {
+                Some(checksum) => write!(f, "{url}#sha256:{checksum}"),
+                None => write!(f, "{url}"),
+            },
             TemplateLocator::File { path } => write!(f, "file://{path}"),
             TemplateLocator::Glob { glob } => write!(f, "glob://{glob}"),
             TemplateLocator::Git {
@@ -46,6 +87,9 @@ impl fmt::Display for TemplateLocator {
                 revision,
             } => write!(f, "git://{remote_url}#{revision}"),
             TemplateLocator::Npm { package, version } => write!(f, "npm://{package}#{version}"),
+            TemplateLocator::Oci { .. } => {
+                write!(f, "oci://{}", self.get_oci_reference().unwrap())
+            }
         }
     }
 }
@@ -69,6 +113,41 @@ impl FromStr for TemplateLocator {
             }
 
             match protocol {
+                "http" | "https" => {
+                    let (url, checksum) = match value.split_once('#') {
+                        Some((url, checksum)) => (url, Some(checksum)),
+                        None => (value, None),
+                    };
+
+                    if !ARCHIVE.is_match(url) {
+                        return Err(ParseError::new(
+                            "Invalid archive template locator, must be a secure `https://` URL to a .tar.gz, .tgz, or .zip file",
+                        ));
+                    }
+
+                    let checksum = match checksum {
+                        Some(checksum) => {
+                            let checksum = checksum
+                                .strip_prefix("sha256:")
+                                .unwrap_or(checksum)
+                                .to_lowercase();
+
+                            if !CHECKSUM.is_match(&checksum) {
+                                return Err(ParseError::new(
+                                    "Invalid archive template checksum, must be a SHA-256 hash in the format of `url#sha256:hash`",
+                                ));
+                            }
+
+                            Some(checksum)
+                        }
+                        None => None,
+                    };
+
+                    return Ok(TemplateLocator::Archive {
+                        url: url.to_owned(),
+                        checksum,
+                    });
+                }
                 "git" | "git+http" | "git+https" => {
                     if let Some(result) = GIT.captures(inner_value) {
                         return Ok(TemplateLocator::Git {
@@ -94,6 +173,24 @@ impl FromStr for TemplateLocator {
                         "Invalid npm template locator, must be in the format of `{protocol}://package#version`"
                     )));
                 }
+                "oci" => {
+                    if let Some(result) = OCI.captures(inner_value) {
+                        return Ok(TemplateLocator::Oci {
+                            registry: result.name("registry").unwrap().as_str().to_owned(),
+                            repository: result.name("repository").unwrap().as_str().to_owned(),
+                            tag: result
+                                .name("tag")
+                                .or_else(|| result.name("digest"))
+                                .unwrap()
+                                .as_str()
+                                .to_owned(),
+                        });
+                    }
+
+                    return Err(ParseError::new(
+                        "Invalid OCI template locator, must be in the format of `oci://registry/repository:tag` or `oci://registry/repository@sha256:digest`",
+                    ));
+                }
                 "file" => {
                     return Ok(TemplateLocator::File {
                         path: FilePath::from_str(inner_value)?,
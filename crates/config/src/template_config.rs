@@ -1,13 +1,34 @@
 use crate::shapes::OneOrMany;
 use moon_common::Id;
+use moon_target::Target;
+use regex::Regex;
 use rustc_hash::FxHashMap;
 use schematic::{validate, Config, ValidateError};
 
+fn validate_pattern<D, C>(
+    value: &str,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> Result<(), ValidateError> {
+    if let Err(error) = Regex::new(value) {
+        return Err(ValidateError::new(format!(
+            "invalid regex pattern: {error}"
+        )));
+    }
+
+    Ok(())
+}
+
 macro_rules! var_setting {
-    ($name:ident, $ty:ty) => {
+    ($name:ident, $ty:ty, { $($extra:tt)* }) => {
         /// Configuration for a template variable.
         #[derive(Clone, Config, Debug, Eq, PartialEq)]
         pub struct $name {
+            /// A Tera expression that computes the value of the variable from
+            /// other variables. Computed variables are never prompted for.
+            pub computed: Option<String>,
+
             /// The default value of the variable if none was provided.
             #[setting(alias = "defaultValue")]
             pub default: $ty,
@@ -23,13 +44,31 @@ macro_rules! var_setting {
 
             /// Marks the variable as required, and will not accept an empty value.
             pub required: Option<bool>,
+
+            $($extra)*
+
+            /// A Tera expression that must be truthy for the user to be prompted.
+            /// When falsy, the default value will be used.
+            pub when: Option<String>,
         }
     };
 }
 
-var_setting!(TemplateVariableBoolSetting, bool);
-var_setting!(TemplateVariableNumberSetting, isize);
-var_setting!(TemplateVariableStringSetting, String);
+var_setting!(TemplateVariableBoolSetting, bool, {});
+
+var_setting!(TemplateVariableNumberSetting, isize, {
+    /// The maximum value allowed (inclusive).
+    pub max: Option<isize>,
+
+    /// The minimum value allowed (inclusive).
+    pub min: Option<isize>,
+});
+
+var_setting!(TemplateVariableStringSetting, String, {
+    /// A regex pattern that the value must match.
+    #[setting(validate = validate_pattern)]
+    pub pattern: Option<String>,
+});
 
 #[derive(Clone, Config, Debug, Eq, PartialEq)]
 pub struct TemplateVariableEnumValueConfig {
@@ -129,6 +168,10 @@ pub struct TemplateVariableEnumSetting {
     /// List of acceptable values for this variable.
     #[setting(nested)]
     pub values: Vec<TemplateVariableEnumValue>,
+
+    /// A Tera expression that must be truthy for the user to be prompted.
+    /// When falsy, the default value will be used.
+    pub when: Option<String>,
 }
 
 impl TemplateVariableEnumSetting {
@@ -179,6 +222,15 @@ pub enum TemplateVariable {
 }
 
 impl TemplateVariable {
+    pub fn get_computed(&self) -> Option<&String> {
+        match self {
+            Self::Boolean(cfg) => cfg.computed.as_ref(),
+            Self::Number(cfg) => cfg.computed.as_ref(),
+            Self::String(cfg) => cfg.computed.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn get_order(&self) -> usize {
         let order = match self {
             Self::Boolean(cfg) => cfg.order.as_ref(),
@@ -190,6 +242,28 @@ impl TemplateVariable {
         order.copied().unwrap_or(100)
     }
 
+    pub fn get_prompt(&self) -> Option<&String> {
+        match self {
+            Self::Boolean(cfg) => cfg.prompt.as_ref(),
+            Self::Enum(cfg) => cfg.prompt.as_ref(),
+            Self::Number(cfg) => cfg.prompt.as_ref(),
+            Self::String(cfg) => cfg.prompt.as_ref(),
+        }
+    }
+
+    pub fn get_when(&self) -> Option<&String> {
+        match self {
+            Self::Boolean(cfg) => cfg.when.as_ref(),
+            Self::Enum(cfg) => cfg.when.as_ref(),
+            Self::Number(cfg) => cfg.when.as_ref(),
+            Self::String(cfg) => cfg.when.as_ref(),
+        }
+    }
+
+    pub fn is_computed(&self) -> bool {
+        self.get_computed().is_some()
+    }
+
     pub fn is_internal(&self) -> bool {
         match self {
             Self::Boolean(cfg) => cfg.internal,
@@ -217,6 +291,54 @@ impl TemplateVariable {
     }
 }
 
+/// Configures a command or target to run as a hook.
+#[derive(Clone, Config, Debug, Eq, PartialEq)]
+pub struct TemplateHookConfig {
+    /// A command to run within the destination directory.
+    pub command: Option<String>,
+
+    /// A moon target to run.
+    pub target: Option<String>,
+}
+
+/// A command, or a command or target object, to run as a hook.
+#[derive(Clone, Config, Debug, Eq, PartialEq)]
+#[config(serde(
+    untagged,
+    expecting = "expected a command string, or an object with a command or target"
+))]
+pub enum TemplateHook {
+    Command(String),
+    #[setting(nested)]
+    Object(TemplateHookConfig),
+}
+
+/// Configures hooks to run during the generation process.
+#[derive(Clone, Config, Debug, Eq, PartialEq)]
+pub struct TemplateHooksConfig {
+    /// A list of commands and targets to run, in order, after files
+    /// have been generated. Variables are rendered into each hook.
+    #[setting(nested)]
+    pub after_generate: Vec<TemplateHook>,
+
+    /// Sync all projects after files have been generated, and before
+    /// running hooks, so that new projects are immediately runnable.
+    pub sync_projects: bool,
+}
+
+/// Configures a test case for a template, which renders the template
+/// and compares the output against a snapshot.
+#[derive(Clone, Config, Debug, PartialEq)]
+pub struct TemplateTestConfig {
+    /// A task target to run after the rendered output has been compared
+    /// against the snapshot. The output directory is passed as an argument.
+    pub task: Option<Target>,
+
+    /// A mapping of variable values to render with. Variables that
+    /// are not defined will use their default value.
+    pub variables: FxHashMap<String, serde_json::Value>,
+}
+
 /// Configures a template and its files to be scaffolded.
 /// Docs: https://moonrepo.dev/docs/config/template
 #[derive(Clone, Config, Debug, PartialEq)]
@@ -238,9 +360,18 @@ pub struct TemplateConfig {
     /// Extends one or many other templates.
     pub extends: OneOrMany<Id>,
 
+    /// Hooks to run during the generation process.
+    #[setting(nested)]
+    pub hooks: TemplateHooksConfig,
+
     /// Overrides the ID of the template, instead of using the folder name.
     pub id: Option<Id>,
 
+    /// A mapping of test cases, keyed by name, to render and compare
+    /// against snapshots with `moon templates test`.
+    #[setting(nested)]
+    pub tests: FxHashMap<String, TemplateTestConfig>,
+
     /// A human-readable title for the template.
     #[setting(validate = validate::not_empty)]
     pub title: String,
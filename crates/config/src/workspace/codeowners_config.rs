@@ -1,5 +1,6 @@
 use indexmap::IndexMap;
 use moon_common::cacheable;
+use rustc_hash::FxHashMap;
 use schematic::{derive_enum, Config, ConfigEnum};
 
 derive_enum!(
@@ -18,6 +19,10 @@ cacheable!(
     /// Configures code ownership rules for generating a `CODEOWNERS` file.
     #[derive(Clone, Config, Debug, PartialEq)]
     pub struct CodeownersConfig {
+        /// Bitbucket only. A mapping of custom groups (prefixed with `@@@`),
+        /// to a list of user and normal groups. Available to all projects.
+        pub custom_groups: FxHashMap<String, Vec<String>>,
+
         /// Paths that are applied globally to all projects. Can be relative
         /// from the workspace root, or a wildcard match for any depth.
         pub global_paths: IndexMap<String, Vec<String>>,
@@ -25,6 +30,10 @@ cacheable!(
         /// How to order ownership rules within the generated file.
         pub order_by: CodeownersOrderBy,
 
+        /// Bitbucket only. The number of reviewers to randomly assign from
+        /// the owners of each path. This will be applied to all paths.
+        pub random_reviewers: Option<u8>,
+
         /// Bitbucket and GitLab only. The number of approvals required for the
         /// request to be satisfied. This will be applied to all paths.
         pub required_approvals: Option<u8>,
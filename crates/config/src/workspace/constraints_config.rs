@@ -1,16 +1,77 @@
+use crate::project_config::StackType;
 use moon_common::Id;
 use rustc_hash::FxHashMap;
 use schematic::Config;
 
+/// Denies dependencies between projects. Projects are matched by ID
+/// or source path glob, or by tag when prefixed with `#`.
+#[derive(Clone, Config, Debug, PartialEq)]
+pub struct ConstraintsDenyRule {
+    /// Projects that must not depend on the `to` projects.
+    pub from: String,
+
+    /// Projects that must not be depended on, either directly
+    /// or transitively, by the `from` projects.
+    pub to: String,
+}
+
+/// Enforces a single version policy for external dependencies,
+/// as declared in each project's manifest (`package.json`,
+/// `Cargo.toml`, `requirements.txt`).
+#[derive(Clone, Config, Debug, PartialEq)]
+pub struct ConstraintsDependencyVersionsConfig {
+    /// Enforces that each external dependency is declared
+    /// with the same version across all projects.
+    pub enabled: bool,
+
+    /// A mapping of dependency names, to additional versions that are
+    /// allowed to differ. When the list is empty, all versions are allowed.
+    pub exceptions: FxHashMap<String, Vec<String>>,
+}
+
 /// Configures boundaries and constraints between projects.
 #[derive(Clone, Config, Debug, PartialEq)]
 pub struct ConstraintsConfig {
+    /// Enforces a single version policy for external dependencies.
+    #[setting(nested)]
+    pub dependency_versions: ConstraintsDependencyVersionsConfig,
+
+    /// A list of rules that deny dependencies between projects.
+    #[setting(nested)]
+    pub deny: Vec<ConstraintsDenyRule>,
+
     /// Enforces relationships between projects based on each project's
     /// `type` setting.
     #[setting(default = true)]
     pub enforce_project_type_relationships: bool,
 
+    /// An ordered list of tags that represent architectural layers, from
+    /// the highest to the lowest. A project may only depend on projects
+    /// within the same or a lower layer.
+    pub layers: Vec<Id>,
+
+    /// The maximum number of projects that a project can depend on.
+    pub max_dependencies: Option<usize>,
+
+    /// The maximum number of projects that can depend on a project.
+    pub max_dependents: Option<usize>,
+
+    /// Denies dependencies on projects of specific stacks, based on each
+    /// project's `tags` setting. Requires a mapping of tags, to denied stacks.
+    pub tag_denied_stacks: FxHashMap<Id, Vec<StackType>>,
+
     /// Enforces relationships between projects based on each project's
     /// `tags` setting. Requires a mapping of tags, to acceptable tags.
     pub tag_relationships: FxHashMap<Id, Vec<Id>>,
 }
+
+impl ConstraintsConfig {
+    /// Return true if any dependency graph wide constraints have been configured.
+    pub fn has_graph_constraints(&self) -> bool {
+        !self.deny.is_empty()
+            || !self.layers.is_empty()
+            || self.max_dependencies.is_some()
+            || self.max_dependents.is_some()
+            || !self.tag_denied_stacks.is_empty()
+    }
+}
@@ -1,9 +1,61 @@
+use rustc_hash::FxHashMap;
 use schematic::{validate, Config};
 
+/// Configures an individual webhook endpoint.
+#[derive(Clone, Config, Debug, PartialEq)]
+pub struct NotifierWebhookConfig {
+    /// List of event types (e.g. `task.ran`) to send to the endpoint.
+    /// If empty, all events will be sent.
+    pub events: Vec<String>,
+
+    /// Only send events that contain an error, like a failed task.
+    pub failures_only: bool,
+
+    /// Custom headers to include in every request.
+    pub headers: FxHashMap<String, String>,
+
+    /// Maximum number of times to retry a failed request,
+    /// using an exponential backoff between attempts. Cannot exceed 10.
+    #[setting(default = 3, validate = validate::in_range(0, 10))]
+    pub retry_count: u8,
+
+    /// Name of an environment variable that contains a secret, which will
+    /// be used to sign the request body with HMAC-SHA256.
+    pub secret_env: Option<String>,
+
+    /// A secure URL in which to send webhooks to.
+    #[setting(required, validate = validate::url_secure)]
+    pub url: Option<String>,
+}
+
 /// Configures how and where notifications are sent.
 #[derive(Clone, Config, Debug, PartialEq)]
 pub struct NotifierConfig {
     /// A secure URL in which to send webhooks to.
     #[setting(validate = validate::url_secure)]
     pub webhook_url: Option<String>,
+
+    /// A list of webhook endpoints, each with their own
+    /// filtering, signing, and retry settings.
+    #[setting(nested)]
+    pub webhooks: Vec<NotifierWebhookConfig>,
+}
+
+impl NotifierConfig {
+    /// Return all configured webhook endpoints, including the
+    /// legacy `webhookUrl` setting (which receives all events).
+    pub fn get_webhooks(&self) -> Vec<NotifierWebhookConfig> {
+        let mut webhooks = vec![];
+
+        if let Some(url) = &self.webhook_url {
+            webhooks.push(NotifierWebhookConfig {
+                url: Some(url.to_owned()),
+                retry_count: 0,
+                ..Default::default()
+            });
+        }
+
+        webhooks.extend(self.webhooks.iter().cloned());
+        webhooks
+    }
 }
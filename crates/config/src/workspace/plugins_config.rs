@@ -11,6 +11,10 @@ pub struct ExtensionConfig {
     #[setting(required)]
     pub plugin: Option<PluginLocator>,
 
+    /// Subscribe the extension to action pipeline events, which will
+    /// be passed to the plugin's `on_pipeline_event` function.
+    pub pipeline_events: bool,
+
     /// Arbitrary configuration that'll be passed to the WASM plugin.
     #[setting(flatten)]
     pub config: FxHashMap<String, serde_json::Value>,
@@ -31,6 +35,7 @@ pub(crate) fn default_extensions() -> FxHashMap<Id, ExtensionConfig> {
                     url: "https://github.com/moonrepo/moon-extensions/releases/download/moon_download_extension-v0.0.7/moon_download_extension.wasm".into()
                 }))),
                 config: FxHashMap::default(),
+                pipeline_events: false,
             },
         ),
          (
@@ -40,6 +45,7 @@ pub(crate) fn default_extensions() -> FxHashMap<Id, ExtensionConfig> {
                     url: "https://github.com/moonrepo/moon-extensions/releases/download/moon_migrate_nx_extension-v0.0.7/moon_migrate_nx_extension.wasm".into()
                 }))),
                 config: FxHashMap::default(),
+                pipeline_events: false,
             },
         ),
         (
@@ -49,6 +55,7 @@ pub(crate) fn default_extensions() -> FxHashMap<Id, ExtensionConfig> {
                     url: "https://github.com/moonrepo/moon-extensions/releases/download/moon_migrate_turborepo_extension-v0.1.4/moon_migrate_turborepo_extension.wasm".into()
                 }))),
                 config: FxHashMap::default(),
+                pipeline_events: false,
             },
         ),
     ])
@@ -1,5 +1,5 @@
 use rustc_hash::FxHashMap;
-use schematic::{derive_enum, Config, ConfigEnum};
+use schematic::{derive_enum, validate, Config, ConfigEnum};
 
 derive_enum!(
     /// The VCS being utilized by the repository.
@@ -7,6 +7,9 @@ derive_enum!(
     pub enum VcsManager {
         #[default]
         Git,
+
+        Jujutsu,
+        Sapling,
     }
 );
 
@@ -41,6 +44,15 @@ derive_enum!(
 /// Configures the version control system (VCS).
 #[derive(Clone, Config, Debug, PartialEq)]
 pub struct VcsConfig {
+    /// Automatically fetch more history in a shallow checkout when the
+    /// merge base of the compared revisions cannot be found.
+    pub auto_deepen: bool,
+
+    /// The maximum number of times to deepen a shallow checkout,
+    /// doubling the fetched depth on each attempt. Must be between 1 and 20.
+    #[setting(default = 5, validate = validate::in_range(1, 20))]
+    pub auto_deepen_limit: u8,
+
     /// The default branch / base.
     #[setting(default = "master")]
     pub default_branch: String,
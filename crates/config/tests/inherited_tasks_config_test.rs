@@ -533,6 +533,30 @@ mod task_manager {
         );
     }
 
+    #[test]
+    fn loads_task_configs_of_different_formats() {
+        let sandbox = create_empty_sandbox();
+
+        sandbox.create_file(
+            "tasks.json",
+            r#"{ "tasks": { "lint": { "command": "lint" } } }"#,
+        );
+        sandbox.create_file("tasks/node.yml", "tasks:\n  build:\n    command: 'build'");
+        sandbox.create_file(
+            "tasks/rust.toml",
+            "[tasks.check]\ncommand = \"cargo check\"",
+        );
+        sandbox.create_file("tasks/deno.jsonc", "{\n  // Comment\n  \"tasks\": {}\n}");
+        sandbox.create_file("tasks/other.txt", "");
+
+        let manager = load_manager_from_root(sandbox.path(), sandbox.path()).unwrap();
+
+        let mut keys = manager.configs.keys().collect::<Vec<_>>();
+        keys.sort();
+
+        assert_eq!(keys, vec!["*", "deno", "node", "rust"]);
+    }
+
     #[test]
     fn can_nest_configs_in_folders() {
         let sandbox = create_sandbox("inheritance/nested");
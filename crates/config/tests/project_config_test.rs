@@ -202,6 +202,41 @@ dependsOn:
         }
     }
 
+    mod docker {
+        use super::*;
+        use moon_config::ProjectDockerFileConfig;
+
+        #[test]
+        fn can_set_file_settings() {
+            let config = test_load_config(
+                "moon.yml",
+                r"
+docker:
+  file:
+    buildTask: build
+    cacheMounts: ['/root/.cache/custom']
+    image: 'node:latest'
+    runtimeImage: 'node:slim'
+    startTask: start
+    user: node
+",
+                |path| load_config_from_root(path, "."),
+            );
+
+            assert_eq!(
+                config.docker.file,
+                ProjectDockerFileConfig {
+                    build_task: Some(Id::raw("build")),
+                    cache_mounts: vec!["/root/.cache/custom".into()],
+                    image: Some("node:latest".into()),
+                    runtime_image: Some("node:slim".into()),
+                    start_task: Some(Id::raw("start")),
+                    user: Some("node".into()),
+                }
+            );
+        }
+    }
+
     mod file_groups {
         use super::*;
 
@@ -282,7 +317,9 @@ fileGroups:
             assert_eq!(config.owners.default_owner, None);
             assert!(!config.owners.optional);
             assert_eq!(config.owners.paths, OwnersPaths::List(vec![]));
+            assert_eq!(config.owners.random_reviewers, None);
             assert_eq!(config.owners.required_approvals, None);
+            assert_eq!(config.owners.section, None);
         }
 
         #[test]
@@ -296,7 +333,9 @@ owners:
     bar: [x, y, z]
   defaultOwner: x
   optional: true
+  randomReviewers: 1
   requiredApprovals: 2
+  section: Frontend
 ",
                 |path| load_config_from_root(path, "."),
             );
@@ -310,7 +349,9 @@ owners:
             );
             assert_eq!(config.owners.default_owner, Some("x".to_string()));
             assert!(config.owners.optional);
+            assert_eq!(config.owners.random_reviewers, Some(1));
             assert_eq!(config.owners.required_approvals, Some(2));
+            assert_eq!(config.owners.section, Some("Frontend".to_string()));
         }
 
         #[test]
@@ -636,6 +677,66 @@ workspace:
         }
     }
 
+    mod formats {
+        use super::*;
+
+        #[test]
+        fn loads_json() {
+            let config = test_load_config(
+                "moon.json",
+                r#"{ "language": "rust", "tags": ["a", "b"] }"#,
+                |path| load_config_from_root(path, "."),
+            );
+
+            assert_eq!(config.language, LanguageType::Rust);
+            assert_eq!(config.tags, vec![Id::raw("a"), Id::raw("b")]);
+        }
+
+        #[test]
+        fn loads_jsonc() {
+            let config = test_load_config(
+                "moon.jsonc",
+                r#"{
+    // Comments are supported
+    "language": "rust",
+}"#,
+                |path| load_config_from_root(path, "."),
+            );
+
+            assert_eq!(config.language, LanguageType::Rust);
+        }
+
+        #[test]
+        fn loads_toml() {
+            let config = test_load_config(
+                "moon.toml",
+                r#"
+language = "rust"
+tags = ["a", "b"]
+
+[tasks.build]
+command = "cargo build"
+"#,
+                |path| load_config_from_root(path, "."),
+            );
+
+            assert_eq!(config.language, LanguageType::Rust);
+            assert_eq!(config.tags, vec![Id::raw("a"), Id::raw("b")]);
+            assert_eq!(
+                config.tasks.get("build").unwrap().command,
+                TaskArgs::String("cargo build".to_owned())
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "unknown field `unknown`")]
+        fn errors_for_unknown_field_in_toml() {
+            test_load_config("moon.toml", "unknown = 123", |path| {
+                load_config_from_root(path, ".")
+            });
+        }
+    }
+
     mod pkl {
         use super::*;
         use moon_common::Id;
@@ -667,6 +768,7 @@ workspace:
                             build_task: Some(Id::raw("build")),
                             image: Some("node:latest".into()),
                             start_task: Some(Id::raw("start")),
+                            ..Default::default()
                         },
                         scaffold: ProjectDockerScaffoldConfig {
                             include: vec![GlobPath("*.js".into())]
@@ -690,7 +792,9 @@ workspace:
                         default_owner: Some("owner".into()),
                         optional: true,
                         paths: OwnersPaths::List(vec!["dir/".into(), "file.txt".into()]),
-                        required_approvals: Some(5)
+                        random_reviewers: None,
+                        required_approvals: Some(5),
+                        section: None,
                     },
                     platform: Some(PlatformType::Node),
                     project: Some(ProjectMetadataConfig {
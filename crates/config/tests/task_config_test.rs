@@ -5,7 +5,7 @@ mod utils;
 use moon_common::Id;
 use moon_config::{
     FilePath, InputPath, OneOrMany, OutputPath, PlatformType, TaskArgs, TaskConfig, TaskDependency,
-    TaskDependencyConfig, TaskMergeStrategy, TaskOutputStyle, TaskType,
+    TaskDependencyConfig, TaskImageConfig, TaskMergeStrategy, TaskOutputStyle, TaskType,
 };
 use moon_target::Target;
 use rustc_hash::FxHashMap;
@@ -29,7 +29,7 @@ mod task_config {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `unknown`, expected one of `extends`, `description`, `command`, `args`, `deps`, `env`, `inputs`, `local`, `outputs`, `options`, `platform`, `preset`, `script`, `toolchain`, `type`"
+        expected = "unknown field `unknown`, expected one of `extends`, `description`, `command`, `args`, `deps`, `env`, `image`, `inputs`, `local`, `outputs`, `options`, `platform`, `preset`, `script`, `toolchain`, `type`"
     )]
     fn error_unknown_field() {
         test_parse_config("unknown: 123", load_config_from_code);
@@ -338,6 +338,105 @@ outputs:
         }
     }
 
+    mod image {
+        use super::*;
+
+        const BASE: &str =
+            "node@sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+        #[test]
+        fn loads_defaults() {
+            let config =
+                test_parse_config(&format!("image:\n  base: {BASE}"), load_config_from_code);
+
+            assert_eq!(
+                config.image,
+                Some(TaskImageConfig {
+                    base: BASE.into(),
+                    file: "image.tar".try_into().unwrap(),
+                    platform: "linux/amd64".into(),
+                    workdir: "/app".into(),
+                    ..Default::default()
+                })
+            );
+        }
+
+        #[test]
+        fn can_set_fields() {
+            let config = test_parse_config(
+                &format!(
+                    r"
+image:
+  base: {BASE}
+  cmd: [dist/index.js]
+  entrypoint: [node]
+  env:
+    NODE_ENV: production
+  file: dist/app.tar
+  platform: linux/arm64
+  push: ghcr.io/org/app:1.0
+  user: node
+  workdir: /srv
+"
+                ),
+                load_config_from_code,
+            );
+
+            let image = config.image.unwrap();
+
+            assert_eq!(image.cmd, ["dist/index.js"]);
+            assert_eq!(image.entrypoint, ["node"]);
+            assert_eq!(image.env.get("NODE_ENV").unwrap(), "production");
+            assert_eq!(image.file, "dist/app.tar");
+            assert_eq!(image.platform, "linux/arm64");
+            assert_eq!(image.push.as_deref(), Some("ghcr.io/org/app:1.0"));
+            assert_eq!(image.user.as_deref(), Some("node"));
+            assert_eq!(image.workdir, "/srv");
+        }
+
+        #[test]
+        #[should_panic(expected = "base image must be pinned by digest")]
+        fn errors_without_digest() {
+            test_parse_config("image:\n  base: node:20", load_config_from_code);
+        }
+
+        #[test]
+        #[should_panic(expected = "globs are not supported")]
+        fn errors_for_glob_file() {
+            test_parse_config(
+                &format!("image:\n  base: {BASE}\n  file: '*.tar'"),
+                load_config_from_code,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "an image cannot be used with a command, args, or script")]
+        fn errors_with_command() {
+            test_parse_config(
+                &format!("command: node\nimage:\n  base: {BASE}"),
+                load_config_from_code,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "an image cannot be used with a command, args, or script")]
+        fn errors_with_args() {
+            test_parse_config(
+                &format!("args: [--release]\nimage:\n  base: {BASE}"),
+                load_config_from_code,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "an image cannot be used with a command, args, or script")]
+        fn errors_with_script() {
+            test_parse_config(
+                &format!("script: 'node index.js'\nimage:\n  base: {BASE}"),
+                load_config_from_code,
+            );
+        }
+    }
+
     mod platform {
         use super::*;
 
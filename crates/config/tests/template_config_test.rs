@@ -1,6 +1,6 @@
 mod utils;
 
-use moon_config::{ConfigLoader, TemplateConfig, TemplateVariableEnumDefault};
+use moon_config::{ConfigLoader, TemplateConfig, TemplateHooksConfig, TemplateVariableEnumDefault};
 use rustc_hash::FxHashMap;
 use std::path::Path;
 use utils::*;
@@ -14,7 +14,7 @@ mod template_config {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `unknown`, expected one of `$schema`, `description`, `destination`, `extends`, `id`, `title`, `variables`"
+        expected = "unknown field `unknown`, expected one of `$schema`, `description`, `destination`, `extends`, `hooks`, `id`, `tests`, `title`, `variables`"
     )]
     fn error_unknown_field() {
         test_load_config("template.yml", "unknown: 123", |path| {
@@ -33,6 +33,7 @@ mod template_config {
         assert_eq!(config.title, "title");
         assert_eq!(config.description, "description");
         assert_eq!(config.variables, FxHashMap::default());
+        assert_eq!(config.hooks, TemplateHooksConfig::default());
     }
 
     mod title {
@@ -75,6 +76,113 @@ mod template_config {
         }
     }
 
+    mod hooks {
+        use super::*;
+        use moon_config::{TemplateHook, TemplateHookConfig};
+
+        #[test]
+        fn loads_after_generate() {
+            let config = test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+hooks:
+  afterGenerate:
+    - npm install
+    - target: '{{ name }}:build'
+    - command: prettier --write .
+  syncProjects: true
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.hooks.after_generate,
+                vec![
+                    TemplateHook::Command("npm install".into()),
+                    TemplateHook::Object(TemplateHookConfig {
+                        command: None,
+                        target: Some("{{ name }}:build".into()),
+                    }),
+                    TemplateHook::Object(TemplateHookConfig {
+                        command: Some("prettier --write .".into()),
+                        target: None,
+                    }),
+                ]
+            );
+            assert!(config.hooks.sync_projects);
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "expected a command string, or an object with a command or target"
+        )]
+        fn invalid_hook() {
+            test_load_config(
+                "template.yml",
+                "title: title\ndescription: description\nhooks:\n  afterGenerate: [123]",
+                load_config_from_root,
+            );
+        }
+    }
+
+    mod tests {
+        use super::*;
+        use moon_config::TemplateTestConfig;
+        use moon_target::Target;
+
+        #[test]
+        fn loads_tests() {
+            let config = test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+tests:
+  default: {}
+  custom:
+    variables:
+      name: example
+      enabled: true
+    task: app:test
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.tests.get("default").unwrap(),
+                &TemplateTestConfig::default()
+            );
+            assert_eq!(
+                config.tests.get("custom").unwrap(),
+                &TemplateTestConfig {
+                    task: Some(Target::parse("app:test").unwrap()),
+                    variables: FxHashMap::from_iter([
+                        ("name".into(), serde_json::Value::String("example".into())),
+                        ("enabled".into(), serde_json::Value::Bool(true)),
+                    ]),
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "tests.custom.task")]
+        fn errors_for_invalid_task_target() {
+            test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+tests:
+  custom:
+    task: 'a b c'
+",
+                load_config_from_root,
+            );
+        }
+    }
+
     mod variables {
         use super::*;
         use moon_config::{
@@ -121,11 +229,13 @@ variables:
             assert_eq!(
                 *config.variables.get("bool").unwrap(),
                 TemplateVariable::Boolean(TemplateVariableBoolSetting {
+                    computed: None,
                     default: false,
                     internal: false,
                     order: None,
                     prompt: Some("prompt".into()),
-                    required: Some(true)
+                    required: Some(true),
+                    when: None,
                 })
             );
         }
@@ -167,11 +277,15 @@ variables:
             assert_eq!(
                 *config.variables.get("num").unwrap(),
                 TemplateVariable::Number(TemplateVariableNumberSetting {
+                    computed: None,
                     default: 123,
                     internal: false,
                     order: None,
                     prompt: Some("prompt".into()),
-                    required: Some(false)
+                    required: Some(false),
+                    max: None,
+                    min: None,
+                    when: None,
                 })
             );
         }
@@ -211,11 +325,14 @@ variables:
             assert_eq!(
                 *config.variables.get("str").unwrap(),
                 TemplateVariable::String(TemplateVariableStringSetting {
+                    computed: None,
                     default: "abc".into(),
                     internal: false,
                     order: None,
                     prompt: None,
-                    required: None
+                    required: None,
+                    pattern: None,
+                    when: None,
                 })
             );
         }
@@ -237,6 +354,85 @@ variables:
             );
         }
 
+        #[test]
+        fn loads_validation_and_conditions() {
+            let config = test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+variables:
+  useDatabase:
+    type: boolean
+    default: false
+  dbName:
+    type: string
+    default: db
+    pattern: '^[a-z]+$'
+    when: useDatabase
+  dbPort:
+    type: number
+    default: 5432
+    min: 1
+    max: 65535
+  dbSlug:
+    type: string
+    default: ''
+    computed: '{{ dbName | kebab_case }}'
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                *config.variables.get("dbName").unwrap(),
+                TemplateVariable::String(TemplateVariableStringSetting {
+                    computed: None,
+                    default: "db".into(),
+                    internal: false,
+                    order: None,
+                    prompt: None,
+                    required: None,
+                    pattern: Some("^[a-z]+$".into()),
+                    when: Some("useDatabase".into()),
+                })
+            );
+
+            assert_eq!(
+                *config.variables.get("dbPort").unwrap(),
+                TemplateVariable::Number(TemplateVariableNumberSetting {
+                    computed: None,
+                    default: 5432,
+                    internal: false,
+                    order: None,
+                    prompt: None,
+                    required: None,
+                    max: Some(65535),
+                    min: Some(1),
+                    when: None,
+                })
+            );
+
+            assert!(config.variables.get("dbSlug").unwrap().is_computed());
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid regex pattern")]
+        fn invalid_string_pattern() {
+            test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+variables:
+  str:
+    type: string
+    default: abc
+    pattern: '[a-z'
+",
+                load_config_from_root,
+            );
+        }
+
         #[test]
         fn loads_string_enum() {
             let config = test_load_config(
@@ -274,6 +470,7 @@ variables:
                             value: "c".into()
                         })
                     ],
+                    when: None,
                 })
             );
         }
@@ -318,6 +515,7 @@ variables:
                             value: "c".into()
                         })
                     ],
+                    when: None,
                 })
             );
         }
@@ -405,11 +603,13 @@ variables:
                         (
                             "boolean".into(),
                             TemplateVariable::Boolean(TemplateVariableBoolSetting {
+                                computed: None,
                                 default: false,
                                 internal: false,
                                 order: None,
                                 prompt: Some("Why?".into()),
-                                required: Some(true)
+                                required: Some(true),
+                                when: None,
                             })
                         ),
                         (
@@ -429,27 +629,35 @@ variables:
                                         }
                                     ),
                                     TemplateVariableEnumValue::String("c".into())
-                                ]
+                                ],
+                                when: None,
                             })
                         ),
                         (
                             "number".into(),
                             TemplateVariable::Number(TemplateVariableNumberSetting {
+                                computed: None,
                                 default: 123,
                                 internal: false,
                                 order: Some(1),
                                 prompt: Some("Why?".into()),
-                                required: None
+                                required: None,
+                                max: None,
+                                min: None,
+                                when: None,
                             })
                         ),
                         (
                             "string".into(),
                             TemplateVariable::String(TemplateVariableStringSetting {
+                                computed: None,
                                 default: "abc".into(),
                                 internal: true,
                                 order: None,
                                 prompt: Some("Why?".into()),
-                                required: None
+                                required: None,
+                                pattern: None,
+                                when: None,
                             })
                         ),
                     ]),
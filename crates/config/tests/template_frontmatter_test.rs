@@ -1,6 +1,6 @@
 mod utils;
 
-use moon_config::TemplateFrontmatterConfig;
+use moon_config::{TemplateFrontmatterConfig, TemplateFrontmatterInjectConfig};
 use utils::*;
 
 mod template_frontmatter {
@@ -8,7 +8,7 @@ mod template_frontmatter {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `title`, expected one of `$schema`, `force`, `to`, `skip`"
+        expected = "unknown field `title`, expected one of `$schema`, `force`, `to`, `skip`, `inject`"
     )]
     fn error_unknown_field() {
         test_parse_config("title: test", |code| TemplateFrontmatterConfig::parse(code));
@@ -21,6 +21,7 @@ mod template_frontmatter {
         assert!(!config.force);
         assert!(!config.skip);
         assert_eq!(config.to, None);
+        assert_eq!(config.inject, None);
     }
 
     #[test]
@@ -59,4 +60,29 @@ mod template_frontmatter {
     fn invalid_to() {
         test_parse_config("to: true", |code| TemplateFrontmatterConfig::parse(code));
     }
+
+    #[test]
+    fn can_set_inject() {
+        let config = test_parse_config("inject:\n  after: '^\\[workspace\\]'", |code| {
+            TemplateFrontmatterConfig::parse(code)
+        });
+
+        assert_eq!(
+            config.inject,
+            Some(TemplateFrontmatterInjectConfig {
+                after: Some("^\\[workspace\\]".into()),
+                before: None,
+                marker: None,
+            })
+        );
+    }
+
+    #[test]
+    fn can_set_inject_marker() {
+        let config = test_parse_config("inject:\n  marker: '# moon:routes'", |code| {
+            TemplateFrontmatterConfig::parse(code)
+        });
+
+        assert_eq!(config.inject.unwrap().marker, Some("# moon:routes".into()));
+    }
 }
@@ -3,8 +3,8 @@ mod utils;
 use httpmock::prelude::*;
 use moon_common::Id;
 use moon_config::{
-    ConfigLoader, ExtensionConfig, FilePath, GlobPath, TemplateLocator, VcsProvider,
-    WorkspaceConfig, WorkspaceProjects,
+    ConfigLoader, ConstraintsDenyRule, ExtensionConfig, FilePath, GlobPath, NotifierWebhookConfig,
+    StackType, TemplateLocator, VcsProvider, WorkspaceConfig, WorkspaceProjects,
 };
 use proto_core::warpgate::UrlLocator;
 use rustc_hash::FxHashMap;
@@ -328,6 +328,43 @@ constraints:
             );
         }
 
+        #[test]
+        fn can_set_graph_constraints() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+constraints:
+  deny:
+    - from: '#public'
+      to: 'internal-*'
+  layers: ['app', 'ui']
+  maxDependencies: 5
+  tagDeniedStacks:
+    browser: ['backend']
+",
+                load_config_from_root,
+            );
+
+            assert!(config.constraints.has_graph_constraints());
+            assert_eq!(
+                config.constraints.deny,
+                vec![ConstraintsDenyRule {
+                    from: "#public".into(),
+                    to: "internal-*".into(),
+                }]
+            );
+            assert_eq!(
+                config.constraints.layers,
+                vec![Id::raw("app"), Id::raw("ui")]
+            );
+            assert_eq!(config.constraints.max_dependencies, Some(5));
+            assert_eq!(config.constraints.max_dependents, None);
+            assert_eq!(
+                config.constraints.tag_denied_stacks,
+                FxHashMap::from_iter([(Id::raw("browser"), vec![StackType::Backend])])
+            );
+        }
+
         #[test]
         #[should_panic(
             expected = "invalid type: integer `123`, expected struct PartialConstraintsConfig"
@@ -529,6 +566,133 @@ generator:
                 r"
 generator:
   templates: ['npm://@scope/package']
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        fn can_set_archive_locations() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates:
+    - https://artifacts.company.com/templates/1.2.3.tar.gz
+    - https://artifacts.company.com/templates/1.2.3.zip#sha256:E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.generator.templates,
+                vec![
+                    TemplateLocator::Archive {
+                        url: "https://artifacts.company.com/templates/1.2.3.tar.gz".into(),
+                        checksum: None,
+                    },
+                    TemplateLocator::Archive {
+                        url: "https://artifacts.company.com/templates/1.2.3.zip".into(),
+                        checksum: Some(
+                            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                                .into()
+                        ),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn can_set_archive_locations_with_query_strings() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates:
+    - https://bucket.s3.amazonaws.com/templates.tar.gz?X-Amz-Expires=3600&X-Amz-Signature=abc123
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.generator.templates,
+                vec![TemplateLocator::Archive {
+                    url: "https://bucket.s3.amazonaws.com/templates.tar.gz?X-Amz-Expires=3600&X-Amz-Signature=abc123".into(),
+                    checksum: None,
+                }]
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid archive template locator")]
+        fn errors_for_insecure_archive_url() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['http://artifacts.company.com/templates.tar.gz']
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid archive template checksum")]
+        fn errors_for_invalid_archive_checksum() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['https://artifacts.company.com/templates.tar.gz#sha256:abc']
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        fn can_set_oci_locations() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates:
+    - oci://ghcr.io/org/templates:1.2.3
+    - oci://localhost:5000/some/org/templates@sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.generator.templates,
+                vec![
+                    TemplateLocator::Oci {
+                        registry: "ghcr.io".into(),
+                        repository: "org/templates".into(),
+                        tag: "1.2.3".into(),
+                    },
+                    TemplateLocator::Oci {
+                        registry: "localhost:5000".into(),
+                        repository: "some/org/templates".into(),
+                        tag: "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                            .into(),
+                    },
+                ]
+            );
+
+            assert_eq!(
+                config.generator.templates[1].to_string(),
+                "oci://localhost:5000/some/org/templates@sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid OCI template locator")]
+        fn errors_for_no_oci_tag() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['oci://ghcr.io/org/templates']
 ",
                 load_config_from_root,
             );
@@ -616,6 +780,95 @@ notifier:
             );
         }
 
+        #[test]
+        fn can_set_webhooks() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+notifier:
+  webhooks:
+    - url: 'https://domain.com/slack'
+      events: ['pipeline.completed', 'task.ran']
+      failuresOnly: true
+      secretEnv: 'SLACK_SECRET'
+      headers:
+        X-Custom: 'value'
+    - url: 'https://domain.com/other'
+      retryCount: 0
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.notifier.webhooks,
+                vec![
+                    NotifierWebhookConfig {
+                        events: vec!["pipeline.completed".into(), "task.ran".into()],
+                        failures_only: true,
+                        headers: FxHashMap::from_iter([("X-Custom".into(), "value".into())]),
+                        retry_count: 3,
+                        secret_env: Some("SLACK_SECRET".into()),
+                        url: Some("https://domain.com/slack".into()),
+                    },
+                    NotifierWebhookConfig {
+                        retry_count: 0,
+                        url: Some("https://domain.com/other".into()),
+                        ..Default::default()
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn includes_legacy_url_in_webhooks() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+notifier:
+  webhookUrl: 'https://domain.com/legacy'
+  webhooks:
+    - url: 'https://domain.com/new'
+",
+                load_config_from_root,
+            );
+
+            let webhooks = config.notifier.get_webhooks();
+
+            assert_eq!(webhooks.len(), 2);
+            assert_eq!(webhooks[0].url, Some("https://domain.com/legacy".into()));
+            assert_eq!(webhooks[0].retry_count, 0);
+            assert_eq!(webhooks[1].url, Some("https://domain.com/new".into()));
+        }
+
+        #[test]
+        #[should_panic(expected = "url: this setting is required")]
+        fn errors_on_missing_webhook_url() {
+            test_load_config(
+                FILENAME,
+                r"
+notifier:
+  webhooks:
+    - events: ['task.ran']
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "retry_count: greater than 10")]
+        fn errors_on_too_many_retries() {
+            test_load_config(
+                FILENAME,
+                r"
+notifier:
+  webhooks:
+    - url: 'https://domain.com/slack'
+      retryCount: 100
+",
+                load_config_from_root,
+            );
+        }
+
         #[test]
         #[should_panic(expected = "only secure URLs are allowed")]
         fn errors_on_non_https_url() {
@@ -701,6 +954,8 @@ runner:
                 config.vcs.remote_candidates,
                 vec!["origin".to_string(), "upstream".to_string()]
             );
+            assert!(!config.vcs.auto_deepen);
+            assert_eq!(config.vcs.auto_deepen_limit, 5);
         }
 
         #[test]
@@ -720,7 +975,39 @@ vcs:
         }
 
         #[test]
-        #[should_panic(expected = "unknown variant `mercurial`, expected `git`")]
+        fn can_enable_auto_deepen() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+vcs:
+  autoDeepen: true
+  autoDeepenLimit: 3
+",
+                load_config_from_root,
+            );
+
+            assert!(config.vcs.auto_deepen);
+            assert_eq!(config.vcs.auto_deepen_limit, 3);
+        }
+
+        #[test]
+        #[should_panic(expected = "auto_deepen_limit: greater than 20")]
+        fn errors_on_out_of_range_deepen_limit() {
+            test_load_config(
+                FILENAME,
+                r"
+vcs:
+  autoDeepen: true
+  autoDeepenLimit: 50
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "unknown variant `mercurial`, expected one of `git`, `jujutsu`, `sapling`"
+        )]
         fn errors_on_invalid_manager() {
             test_load_config(
                 FILENAME,
@@ -745,6 +1032,71 @@ vcs:
         }
     }
 
+    mod formats {
+        use super::*;
+
+        #[test]
+        fn loads_toml() {
+            let config = test_load_config(
+                ".moon/workspace.toml",
+                r#"
+projects = ["apps/*"]
+
+[runner]
+cacheLifetime = "1 day"
+"#,
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.projects,
+                WorkspaceProjects::Globs(vec!["apps/*".into()])
+            );
+            assert_eq!(config.runner.cache_lifetime, "1 day");
+        }
+
+        #[test]
+        fn loads_json() {
+            let config = test_load_config(
+                ".moon/workspace.json",
+                r#"{ "projects": ["apps/*"], "runner": { "cacheLifetime": "1 day" } }"#,
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.projects,
+                WorkspaceProjects::Globs(vec!["apps/*".into()])
+            );
+            assert_eq!(config.runner.cache_lifetime, "1 day");
+        }
+
+        #[test]
+        fn can_extend_across_formats() {
+            let sandbox = create_empty_sandbox();
+
+            sandbox.create_file(
+                "shared/base.toml",
+                r#"
+[runner]
+cacheLifetime = "1 day"
+"#,
+            );
+
+            sandbox.create_file(
+                ".moon/workspace.json",
+                r#"{ "extends": "../shared/base.toml", "projects": ["apps/*"] }"#,
+            );
+
+            let config = test_config(sandbox.path(), load_config_from_root);
+
+            assert_eq!(
+                config.projects,
+                WorkspaceProjects::Globs(vec!["apps/*".into()])
+            );
+            assert_eq!(config.runner.cache_lifetime, "1 day");
+        }
+    }
+
     mod extensions {
         use super::*;
         use proto_core::PluginLocator;
@@ -808,6 +1160,7 @@ extensions:
                 config.extensions.get("test-id").unwrap(),
                 &ExtensionConfig {
                     config: FxHashMap::default(),
+                    pipeline_events: false,
                     plugin: Some(PluginLocator::Url(Box::new(UrlLocator {
                         url: "https://domain.com".into()
                     }))),
@@ -815,6 +1168,25 @@ extensions:
             );
         }
 
+        #[test]
+        fn can_subscribe_to_pipeline_events() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+extensions:
+    test-id:
+        plugin: 'https://domain.com'
+        pipelineEvents: true
+",
+                load_config_from_root,
+            );
+
+            let ext = config.extensions.get("test-id").unwrap();
+
+            assert!(ext.pipeline_events);
+            assert!(ext.config.is_empty());
+        }
+
         #[test]
         fn can_set_additional_object_config() {
             let config = test_load_config(
@@ -836,6 +1208,7 @@ extensions:
                         ("fooBar".into(), serde_json::Value::String("abc".into())),
                         ("bar-baz".into(), serde_json::Value::Bool(true)),
                     ]),
+                    pipeline_events: false,
                     plugin: Some(PluginLocator::Url(Box::new(UrlLocator {
                         url: "https://domain.com".into()
                     }))),
@@ -861,11 +1234,13 @@ extensions:
             assert_eq!(
                 config.codeowners,
                 CodeownersConfig {
+                    custom_groups: FxHashMap::default(),
                     global_paths: IndexMap::from_iter([(
                         "*".to_owned(),
                         vec!["@admins".to_owned()]
                     )]),
                     order_by: CodeownersOrderBy::ProjectName,
+                    random_reviewers: None,
                     required_approvals: Some(1),
                     sync_on_run: true,
                 }
@@ -878,6 +1253,7 @@ extensions:
                         Id::raw("a"),
                         vec![Id::raw("b"), Id::raw("c")]
                     )]),
+                    ..ConstraintsConfig::default()
                 }
             );
             assert_eq!(
@@ -916,7 +1292,8 @@ extensions:
             assert_eq!(
                 config.notifier,
                 NotifierConfig {
-                    webhook_url: Some("http://localhost".into())
+                    webhook_url: Some("http://localhost".into()),
+                    webhooks: vec![],
                 }
             );
             assert_eq!(
@@ -943,6 +1320,8 @@ extensions:
             assert_eq!(
                 config.vcs,
                 VcsConfig {
+                    auto_deepen: true,
+                    auto_deepen_limit: 10,
                     default_branch: "main".into(),
                     hooks: FxHashMap::from_iter([(
                         "pre-commit".into(),
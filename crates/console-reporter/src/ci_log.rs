@@ -0,0 +1,269 @@
+use ci_env::{CiOutput, CiProvider};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Formats log groups (collapsible sections) and annotations for the
+/// current CI provider, using the provider's native syntax.
+pub struct CiLog {
+    provider: CiProvider,
+    output: Option<CiOutput>,
+}
+
+impl Default for CiLog {
+    fn default() -> Self {
+        Self::detect()
+    }
+}
+
+impl CiLog {
+    pub fn new(provider: CiProvider, output: Option<CiOutput>) -> Self {
+        Self { provider, output }
+    }
+
+    pub fn detect() -> Self {
+        Self::new(ci_env::detect_provider(), ci_env::get_output())
+    }
+
+    /// Return the line that opens a collapsible group with the provided name.
+    /// The ID must be unique for the group, and is only used by some providers.
+    pub fn open_group(&self, id: &str, name: &str) -> Option<String> {
+        match self.provider {
+            CiProvider::Azure => Some(format!("##[group]{name}")),
+            CiProvider::Buildkite => Some(format!("--- {name}")),
+            CiProvider::GithubActions => Some(format!("::group::{name}")),
+            CiProvider::Gitlab => Some(format!(
+                "\x1b[0Ksection_start:{}:{}[collapsed=true]\r\x1b[0K{name}",
+                now_secs(),
+                create_section_id(id),
+            )),
+            _ => self
+                .output
+                .as_ref()
+                .filter(|output| !output.open_log_group.is_empty())
+                .map(|output| output.open_log_group.replace("{name}", name)),
+        }
+    }
+
+    /// Return the line that closes the group previously opened with the ID.
+    pub fn close_group(&self, id: &str) -> Option<String> {
+        match self.provider {
+            CiProvider::Azure => Some("##[endgroup]".into()),
+            // Groups are closed when the next one is opened
+            CiProvider::Buildkite => None,
+            CiProvider::GithubActions => Some("::endgroup::".into()),
+            CiProvider::Gitlab => Some(format!(
+                "\x1b[0Ksection_end:{}:{}\r\x1b[0K",
+                now_secs(),
+                create_section_id(id),
+            )),
+            _ => self
+                .output
+                .as_ref()
+                .filter(|output| !output.close_log_group.is_empty())
+                .map(|output| output.close_log_group.to_owned()),
+        }
+    }
+
+    /// Return the line that marks a previously opened group as failed,
+    /// so that it's expanded by default, if supported by the provider.
+    pub fn expand_group(&self) -> Option<String> {
+        match self.provider {
+            CiProvider::Buildkite => Some("^^^ +++".into()),
+            _ => None,
+        }
+    }
+
+    /// Return the line that creates an error annotation for the current
+    /// workflow run, if supported by the provider. When a workspace relative
+    /// file is provided, the annotation will be attached to it.
+    pub fn annotate_error(&self, title: &str, message: &str, file: Option<&str>) -> Option<String> {
+        match self.provider {
+            CiProvider::Azure => Some(format!(
+                "##vso[task.logissue type=error{}]{title}: {}",
+                file.map(|file| format!(";sourcepath={}", escape_azure_property(file)))
+                    .unwrap_or_default(),
+                escape_azure_data(message)
+            )),
+            CiProvider::GithubActions => Some(format!(
+                "::error {}title={}::{}",
+                file.map(|file| format!("file={},", escape_github_property(file)))
+                    .unwrap_or_default(),
+                escape_github_property(title),
+                escape_github_data(message)
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+// GitLab section names only support `[a-zA-Z0-9_.-]`
+fn create_section_id(id: &str) -> String {
+    id.chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '.' || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn escape_azure_data(value: &str) -> String {
+    value
+        .replace('%', "%AZP25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_azure_property(value: &str) -> String {
+    escape_azure_data(value)
+        .replace(';', "%3B")
+        .replace(']', "%5D")
+}
+
+fn escape_github_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_log(provider: CiProvider) -> CiLog {
+        CiLog::new(provider, None)
+    }
+
+    mod groups {
+        use super::*;
+
+        #[test]
+        fn azure() {
+            let log = create_log(CiProvider::Azure);
+
+            assert_eq!(
+                log.open_group("app:build", "app:build").unwrap(),
+                "##[group]app:build"
+            );
+            assert_eq!(log.close_group("app:build").unwrap(), "##[endgroup]");
+            assert_eq!(log.expand_group(), None);
+        }
+
+        #[test]
+        fn buildkite() {
+            let log = create_log(CiProvider::Buildkite);
+
+            assert_eq!(
+                log.open_group("app:build", "app:build").unwrap(),
+                "--- app:build"
+            );
+            assert_eq!(log.close_group("app:build"), None);
+            assert_eq!(log.expand_group().unwrap(), "^^^ +++");
+        }
+
+        #[test]
+        fn github() {
+            let log = create_log(CiProvider::GithubActions);
+
+            assert_eq!(
+                log.open_group("app:build", "app:build (failed)").unwrap(),
+                "::group::app:build (failed)"
+            );
+            assert_eq!(log.close_group("app:build").unwrap(), "::endgroup::");
+        }
+
+        #[test]
+        fn gitlab() {
+            let log = create_log(CiProvider::Gitlab);
+            let open = log.open_group("app:build-2", "app:build").unwrap();
+            let close = log.close_group("app:build-2").unwrap();
+
+            assert!(open.starts_with("\x1b[0Ksection_start:"));
+            assert!(open.ends_with(":app_build-2[collapsed=true]\r\x1b[0Kapp:build"));
+            assert!(close.starts_with("\x1b[0Ksection_end:"));
+            assert!(close.ends_with(":app_build-2\r\x1b[0K"));
+        }
+
+        #[test]
+        fn fallback_output() {
+            let log = CiLog::new(
+                CiProvider::Jenkins,
+                Some(CiOutput {
+                    open_log_group: "[group] {name}",
+                    close_log_group: "[endgroup]",
+                }),
+            );
+
+            assert_eq!(
+                log.open_group("app:build", "app:build").unwrap(),
+                "[group] app:build"
+            );
+            assert_eq!(log.close_group("app:build").unwrap(), "[endgroup]");
+        }
+
+        #[test]
+        fn unsupported() {
+            let log = create_log(CiProvider::Jenkins);
+
+            assert_eq!(log.open_group("app:build", "app:build"), None);
+            assert_eq!(log.close_group("app:build"), None);
+        }
+    }
+
+    mod annotations {
+        use super::*;
+
+        #[test]
+        fn azure() {
+            assert_eq!(
+                create_log(CiProvider::Azure)
+                    .annotate_error("Task failed", "100%\ndone", Some("app/moon.yml"))
+                    .unwrap(),
+                "##vso[task.logissue type=error;sourcepath=app/moon.yml]Task failed: 100%AZP25%0Adone"
+            );
+        }
+
+        #[test]
+        fn github() {
+            assert_eq!(
+                create_log(CiProvider::GithubActions)
+                    .annotate_error("Task app:build failed", "100%\ndone", Some("app/moon.yml"))
+                    .unwrap(),
+                "::error file=app/moon.yml,title=Task app%3Abuild failed::100%25%0Adone"
+            );
+        }
+
+        #[test]
+        fn github_without_file() {
+            assert_eq!(
+                create_log(CiProvider::GithubActions)
+                    .annotate_error("Task failed", "error", None)
+                    .unwrap(),
+                "::error title=Task failed::error"
+            );
+        }
+
+        #[test]
+        fn unsupported() {
+            assert_eq!(
+                create_log(CiProvider::Gitlab).annotate_error("Task failed", "error", None),
+                None
+            );
+        }
+    }
+}
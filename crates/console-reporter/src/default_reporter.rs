@@ -1,3 +1,4 @@
+use crate::ci_log::CiLog;
 use moon_action::{Action, ActionNode, ActionStatus, Operation, OperationList};
 use moon_common::color::paint;
 use moon_common::{color, is_test_env};
@@ -8,6 +9,7 @@ use moon_time as time;
 use std::sync::Arc;
 
 pub struct DefaultReporter {
+    ci_log: CiLog,
     err: Arc<ConsoleBuffer>,
     out: Arc<ConsoleBuffer>,
 }
@@ -15,6 +17,7 @@ pub struct DefaultReporter {
 impl Default for DefaultReporter {
     fn default() -> Self {
         Self {
+            ci_log: CiLog::detect(),
             err: Arc::new(ConsoleBuffer::empty(ConsoleStream::Stderr)),
             out: Arc::new(ConsoleBuffer::empty(ConsoleStream::Stdout)),
         }
@@ -153,7 +156,127 @@ impl DefaultReporter {
         Ok(())
     }
 
-    fn print_pipeline_failures(&self, actions: &[Action]) -> miette::Result<()> {
+    // Wrap the output in a collapsible group for the current CI provider,
+    // so that the output of each task is contained and easier to navigate.
+    // Both streams are written to stdout in a single write, so that stderr
+    // does not escape the group, and concurrent tasks do not interleave.
+    pub fn format_grouped_operation_output(
+        &self,
+        target: &Target,
+        operation: &Operation,
+        item: &TaskReportItem,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+        let id = if item.attempt_current > 1 {
+            format!("{target}-{}", item.attempt_current)
+        } else {
+            target.to_string()
+        };
+
+        let mut name = target.to_string();
+
+        if let Some(comment) = self.get_status_meta_comment(operation.status, || {
+            if operation.has_failed() {
+                Some("failed".into())
+            } else if item.attempt_current > 1 {
+                Some(format!(
+                    "attempt {}/{}",
+                    item.attempt_current, item.attempt_total
+                ))
+            } else {
+                None
+            }
+        }) {
+            name.push_str(&format!(" ({comment})"));
+        }
+
+        if let Some(line) = self.ci_log.open_group(&id, &name) {
+            lines.push(line);
+        }
+
+        // The group provides context, so a prefix is redundant
+        if let Some(output) = operation.get_output() {
+            for log in [&output.stdout, &output.stderr].into_iter().flatten() {
+                let log = log.trim();
+
+                if !log.is_empty() {
+                    lines.push(log.to_owned());
+                }
+            }
+        }
+
+        if let Some(line) = self.ci_log.close_group(&id) {
+            lines.push(line);
+        }
+
+        if operation.has_failed() {
+            if let Some(line) = self.ci_log.expand_group() {
+                lines.push(line);
+            }
+        }
+
+        lines
+    }
+
+    pub fn print_grouped_operation_output(
+        &self,
+        target: &Target,
+        operation: &Operation,
+        item: &TaskReportItem,
+    ) -> miette::Result<()> {
+        let lines = self.format_grouped_operation_output(target, operation, item);
+
+        if !lines.is_empty() {
+            self.out.write_line(lines.join("\n"))?;
+        }
+
+        Ok(())
+    }
+
+    // Annotate the workflow run with the failure, which includes the exit
+    // code and the tail of the output, and attach it to the task's config file
+    pub fn format_failure_annotation(
+        &self,
+        target: &Target,
+        operation: &Operation,
+        item: &TaskReportItem,
+    ) -> Option<String> {
+        let output = operation.get_output()?;
+        let mut message = format!("Task exited with code {}", output.get_exit_code());
+
+        if let Some(log) = output
+            .stderr
+            .as_ref()
+            .filter(|log| !log.trim().is_empty())
+            .or(output.stdout.as_ref())
+        {
+            let lines = log.trim().lines().collect::<Vec<_>>();
+
+            message.push_str("\n\n");
+            message.push_str(&lines[lines.len().saturating_sub(20)..].join("\n"));
+        }
+
+        self.ci_log.annotate_error(
+            &format!("Task {target} failed"),
+            &message,
+            item.config_file.as_deref(),
+        )
+    }
+
+    pub fn print_failure_annotation(
+        &self,
+        target: &Target,
+        operation: &Operation,
+        item: &TaskReportItem,
+    ) -> miette::Result<()> {
+        if let Some(line) = self.format_failure_annotation(target, operation, item) {
+            self.out.write_line(line)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn print_pipeline_failures(&self, actions: &[Action]) -> miette::Result<()> {
         for action in actions {
             if !action.has_failed() {
                 continue;
@@ -373,7 +496,7 @@ impl Reporter for DefaultReporter {
     // When an attempt has finished, print the output if captured
     fn on_task_finished(
         &self,
-        _target: &Target,
+        target: &Target,
         attempt: &Operation,
         item: &TaskReportItem,
         _error: Option<&miette::Report>,
@@ -381,7 +504,11 @@ impl Reporter for DefaultReporter {
         // Task output was captured, so there was no output
         // sent to the console, so manually print the logs we have!
         if !item.output_streamed && attempt.has_output() {
-            self.print_operation_output(attempt, item)?;
+            if item.output_style == Some(TaskOutputStyle::Group) {
+                self.print_grouped_operation_output(target, attempt, item)?;
+            } else {
+                self.print_operation_output(attempt, item)?;
+            }
         }
 
         Ok(())
@@ -399,7 +526,15 @@ impl Reporter for DefaultReporter {
             // If cached, the finished event above is not fired,
             // so handle printing the captured logs here!
             if operation.is_cached() && operation.has_output() {
-                self.print_operation_output(operation, item)?;
+                if item.output_style == Some(TaskOutputStyle::Group) {
+                    self.print_grouped_operation_output(target, operation, item)?;
+                } else {
+                    self.print_operation_output(operation, item)?;
+                }
+            }
+
+            if operation.has_failed() && item.output_style == Some(TaskOutputStyle::Group) {
+                self.print_failure_annotation(target, operation, item)?;
             }
 
             // Then print the success checkpoint. The success
@@ -428,3 +563,105 @@ fn label_to_the_moon() -> String {
     .collect::<Vec<_>>()
     .join("")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ci_env::CiProvider;
+
+    fn create_reporter(provider: CiProvider) -> DefaultReporter {
+        DefaultReporter {
+            ci_log: CiLog::new(provider, None),
+            ..DefaultReporter::default()
+        }
+    }
+
+    fn create_operation(status: ActionStatus) -> Operation {
+        let mut operation = Operation::task_execution("test");
+        operation.status = status;
+
+        let output = operation.get_output_mut().unwrap();
+        output.exit_code = Some(if status == ActionStatus::Failed { 1 } else { 0 });
+        output.set_stdout("out\n".into());
+        output.set_stderr("err\n".into());
+
+        operation
+    }
+
+    mod grouping {
+        use super::*;
+
+        #[test]
+        fn wraps_both_streams_in_group() {
+            let reporter = create_reporter(CiProvider::GithubActions);
+            let target = Target::parse("app:build").unwrap();
+
+            assert_eq!(
+                reporter.format_grouped_operation_output(
+                    &target,
+                    &create_operation(ActionStatus::Passed),
+                    &TaskReportItem::default()
+                ),
+                ["::group::app:build", "out", "err", "::endgroup::"]
+            );
+        }
+
+        #[test]
+        fn includes_status_and_attempt() {
+            let reporter = create_reporter(CiProvider::Buildkite);
+            let target = Target::parse("app:build").unwrap();
+
+            assert_eq!(
+                reporter.format_grouped_operation_output(
+                    &target,
+                    &create_operation(ActionStatus::Failed),
+                    &TaskReportItem {
+                        attempt_current: 2,
+                        attempt_total: 2,
+                        ..TaskReportItem::default()
+                    }
+                ),
+                ["--- app:build (failed)", "out", "err", "^^^ +++"]
+            );
+        }
+
+        #[test]
+        fn doesnt_group_when_unsupported() {
+            let reporter = create_reporter(CiProvider::Jenkins);
+            let target = Target::parse("app:build").unwrap();
+
+            assert_eq!(
+                reporter.format_grouped_operation_output(
+                    &target,
+                    &create_operation(ActionStatus::Passed),
+                    &TaskReportItem::default()
+                ),
+                ["out", "err"]
+            );
+        }
+    }
+
+    mod annotations {
+        use super::*;
+
+        #[test]
+        fn attaches_to_config_file() {
+            let reporter = create_reporter(CiProvider::GithubActions);
+            let target = Target::parse("app:build").unwrap();
+
+            assert_eq!(
+                reporter
+                    .format_failure_annotation(
+                        &target,
+                        &create_operation(ActionStatus::Failed),
+                        &TaskReportItem {
+                            config_file: Some("app/moon.yml".into()),
+                            ..TaskReportItem::default()
+                        }
+                    )
+                    .unwrap(),
+                "::error file=app/moon.yml,title=Task app%3Abuild failed::Task exited with code 1%0A%0Aerr"
+            );
+        }
+    }
+}
@@ -1,3 +1,7 @@
+mod ci_log;
 mod default_reporter;
+mod tui_reporter;
 
+pub use ci_log::*;
 pub use default_reporter::*;
+pub use tui_reporter::*;
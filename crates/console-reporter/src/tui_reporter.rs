@@ -0,0 +1,809 @@
+use crate::default_reporter::DefaultReporter;
+use moon_action::{Action, ActionNode, ActionStatus, Operation, OperationList};
+use moon_console::*;
+use moon_target::Target;
+use moon_time as time;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use rustc_hash::FxHashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const MAX_OUTPUT_LINES: usize = 5000;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TuiActionStatus {
+    Queued,
+    Running,
+    Completed(ActionStatus),
+}
+
+struct TuiAction {
+    label: String,
+    output: Vec<String>,
+    persistent: bool,
+    started_at: Option<Instant>,
+    duration: Option<Duration>,
+    status: TuiActionStatus,
+    target: Option<Target>,
+}
+
+impl TuiAction {
+    fn push_output(&mut self, line: &str) {
+        self.output.push(strip_ansi_codes(line));
+
+        if self.output.len() > MAX_OUTPUT_LINES {
+            self.output.drain(0..(self.output.len() - MAX_OUTPUT_LINES));
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum TuiPane {
+    #[default]
+    Actions,
+    Persistent,
+}
+
+#[derive(Default)]
+struct TuiState {
+    actions: Vec<TuiAction>,
+    indexes: FxHashMap<String, usize>,
+    targets: FxHashMap<Target, usize>,
+    signals: FxHashMap<Target, TaskSignal>,
+    pane: TuiPane,
+    selected_action: usize,
+    selected_persistent: usize,
+    scroll: u16,
+    follow: bool,
+    quitting: bool,
+    running: bool,
+}
+
+impl TuiState {
+    fn get_action_mut(&mut self, label: &str) -> Option<&mut TuiAction> {
+        self.indexes
+            .get(label)
+            .copied()
+            .and_then(|index| self.actions.get_mut(index))
+    }
+
+    fn get_target_mut(&mut self, target: &Target) -> Option<&mut TuiAction> {
+        self.targets
+            .get(target)
+            .copied()
+            .and_then(|index| self.actions.get_mut(index))
+    }
+
+    fn get_pane_indexes(&self, pane: TuiPane) -> Vec<usize> {
+        self.actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| action.persistent == (pane == TuiPane::Persistent))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn get_selected(&self) -> Option<&TuiAction> {
+        let indexes = self.get_pane_indexes(self.pane);
+        let selected = match self.pane {
+            TuiPane::Actions => self.selected_action,
+            TuiPane::Persistent => self.selected_persistent,
+        };
+
+        indexes
+            .get(selected)
+            .and_then(|index| self.actions.get(*index))
+    }
+
+    fn select(&mut self, offset: isize) {
+        let count = self.get_pane_indexes(self.pane).len();
+
+        if count == 0 {
+            return;
+        }
+
+        let selected = match self.pane {
+            TuiPane::Actions => &mut self.selected_action,
+            TuiPane::Persistent => &mut self.selected_persistent,
+        };
+
+        *selected = (*selected as isize + offset).clamp(0, count as isize - 1) as usize;
+
+        self.scroll = 0;
+        self.follow = true;
+    }
+
+    fn send_signal(&mut self, signal: TaskSignal) {
+        let Some(action) = self.get_selected() else {
+            return;
+        };
+
+        if action.status != TuiActionStatus::Running {
+            return;
+        }
+
+        if let Some(target) = action.target.clone() {
+            self.signals.insert(target, signal);
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.quitting = true;
+            }
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.quitting = true;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select(-1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select(1);
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                if self.pane == TuiPane::Actions
+                    && !self.get_pane_indexes(TuiPane::Persistent).is_empty()
+                {
+                    self.pane = TuiPane::Persistent;
+                } else {
+                    self.pane = TuiPane::Actions;
+                }
+
+                self.scroll = 0;
+                self.follow = true;
+            }
+            KeyCode::PageUp => {
+                self.follow = false;
+                self.scroll = self.scroll.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.scroll = self.scroll.saturating_add(10);
+            }
+            KeyCode::End => {
+                self.follow = true;
+            }
+            KeyCode::Char('r') => {
+                self.send_signal(TaskSignal::Restart);
+            }
+            KeyCode::Char('x') => {
+                self.send_signal(TaskSignal::Kill);
+            }
+            _ => {}
+        };
+    }
+}
+
+/// A full-screen terminal UI that renders the state of all actions in the
+/// pipeline, and the buffered output of each task. When the pipeline has
+/// completed, the terminal is restored and a summary is printed.
+pub struct TuiReporter {
+    handle: Mutex<Option<JoinHandle<()>>>,
+    inner: DefaultReporter,
+    state: Arc<Mutex<TuiState>>,
+}
+
+impl Default for TuiReporter {
+    fn default() -> Self {
+        Self {
+            handle: Mutex::new(None),
+            inner: DefaultReporter::default(),
+            state: Arc::new(Mutex::new(TuiState {
+                follow: true,
+                ..TuiState::default()
+            })),
+        }
+    }
+}
+
+impl TuiReporter {
+    fn state(&self) -> MutexGuard<'_, TuiState> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn stop(&self) {
+        self.state().running = false;
+
+        let handle = self
+            .handle
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .take();
+
+        if let Some(handle) = handle {
+            let _ = handle.join();
+        }
+    }
+
+    fn update_action(&self, action: &Action, status: TuiActionStatus) {
+        let mut state = self.state();
+
+        if let Some(item) = state.get_action_mut(&action.label) {
+            if status == TuiActionStatus::Running {
+                item.started_at = Some(Instant::now());
+            }
+
+            item.duration = action.duration;
+            item.status = status;
+        }
+    }
+
+    fn push_operation_output(&self, target: &Target, operation: &Operation) {
+        let mut state = self.state();
+
+        if let (Some(item), Some(output)) = (state.get_target_mut(target), operation.get_output()) {
+            for log in [&output.stdout, &output.stderr].into_iter().flatten() {
+                for line in log.trim().lines() {
+                    item.push_output(line);
+                }
+            }
+        }
+    }
+}
+
+impl Reporter for TuiReporter {
+    fn inherit_streams(&mut self, err: Arc<ConsoleBuffer>, out: Arc<ConsoleBuffer>) {
+        self.inner.inherit_streams(err, out);
+    }
+
+    fn on_pipeline_started(&self, nodes: &[&ActionNode]) -> miette::Result<()> {
+        {
+            let mut state = self.state();
+
+            for node in nodes {
+                let label = node.label();
+                let target = match node {
+                    ActionNode::RunTask(inner) => Some(inner.target.clone()),
+                    _ => None,
+                };
+
+                if state.indexes.contains_key(&label) {
+                    continue;
+                }
+
+                let index = state.actions.len();
+
+                if let Some(target) = &target {
+                    state.targets.insert(target.clone(), index);
+                }
+
+                state.indexes.insert(label.clone(), index);
+                state.actions.push(TuiAction {
+                    label,
+                    output: vec![],
+                    persistent: node.is_persistent(),
+                    started_at: None,
+                    duration: None,
+                    status: TuiActionStatus::Queued,
+                    target,
+                });
+            }
+
+            state.running = true;
+        }
+
+        let state = Arc::clone(&self.state);
+
+        *self
+            .handle
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) =
+            Some(thread::spawn(move || render_loop(state)));
+
+        Ok(())
+    }
+
+    fn on_pipeline_completed(
+        &self,
+        actions: &[Action],
+        item: &PipelineReportItem,
+        error: Option<&miette::Report>,
+    ) -> miette::Result<()> {
+        self.stop();
+
+        // The output was only rendered in the TUI, so print failures to the
+        // console once it has been restored (the summary already does this)
+        if !item.summarize && actions.iter().any(|action| action.has_failed()) {
+            self.inner.print_pipeline_failures(actions)?;
+        }
+
+        self.inner.on_pipeline_completed(actions, item, error)
+    }
+
+    fn on_pipeline_aborted(
+        &self,
+        actions: &[Action],
+        item: &PipelineReportItem,
+        error: Option<&miette::Report>,
+    ) -> miette::Result<()> {
+        self.stop();
+
+        self.inner.on_pipeline_aborted(actions, item, error)
+    }
+
+    fn on_action_started(&self, action: &Action) -> miette::Result<()> {
+        self.update_action(action, TuiActionStatus::Running);
+
+        Ok(())
+    }
+
+    fn on_action_completed(
+        &self,
+        action: &Action,
+        _error: Option<&miette::Report>,
+    ) -> miette::Result<()> {
+        self.update_action(action, TuiActionStatus::Completed(action.status));
+
+        Ok(())
+    }
+
+    fn on_task_completed(
+        &self,
+        target: &Target,
+        operations: &OperationList,
+        _item: &TaskReportItem,
+        _error: Option<&miette::Report>,
+    ) -> miette::Result<()> {
+        // Cached tasks do not execute, so render the hydrated output
+        if let Some(operation) = operations.get_last_process() {
+            if operation.is_cached() && operation.has_output() {
+                self.push_operation_output(target, operation);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_task_output(&self, target: &Target, line: &str, _stderr: bool) -> miette::Result<()> {
+        if let Some(item) = self.state().get_target_mut(target) {
+            item.push_output(line);
+        }
+
+        Ok(())
+    }
+
+    fn poll_task_signal(&self, target: &Target) -> Option<TaskSignal> {
+        let mut state = self.state();
+
+        if state.quitting {
+            return Some(TaskSignal::Kill);
+        }
+
+        state.signals.remove(target)
+    }
+
+    fn should_cancel(&self) -> bool {
+        self.state().quitting
+    }
+
+    fn renders_task_output(&self) -> bool {
+        true
+    }
+}
+
+fn render_loop(state: Arc<Mutex<TuiState>>) {
+    let mut terminal = ratatui::init();
+    let lock = || state.lock().unwrap_or_else(|error| error.into_inner());
+
+    while lock().running {
+        let _ = terminal.draw(|frame| render(frame, &mut lock()));
+
+        if event::poll(Duration::from_millis(100)).unwrap_or(false) {
+            if let Ok(Event::Key(key)) = event::read() {
+                if key.kind == KeyEventKind::Press {
+                    lock().handle_key(key.code, key.modifiers);
+                }
+            }
+        }
+    }
+
+    ratatui::restore();
+}
+
+fn render(frame: &mut Frame, state: &mut TuiState) {
+    let has_persistent = state.actions.iter().any(|action| action.persistent);
+
+    let [header_area, body_area, footer_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let [list_area, output_area] =
+        Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .areas(body_area);
+
+    render_header(frame, state, header_area);
+
+    if has_persistent {
+        let [actions_area, persistent_area] =
+            Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(list_area);
+
+        render_list(frame, state, TuiPane::Actions, actions_area);
+        render_list(frame, state, TuiPane::Persistent, persistent_area);
+    } else {
+        render_list(frame, state, TuiPane::Actions, list_area);
+    }
+
+    render_output(frame, state, output_area);
+
+    frame.render_widget(
+        Paragraph::new(if has_persistent {
+            " ↑/↓ select • tab switch pane • pgup/pgdn scroll • r restart • x kill • q quit"
+        } else {
+            " ↑/↓ select • pgup/pgdn scroll • r restart • x kill • q quit"
+        })
+        .style(Style::new().fg(Color::DarkGray)),
+        footer_area,
+    );
+}
+
+fn render_header(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let mut queued = 0;
+    let mut running = 0;
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for action in &state.actions {
+        match action.status {
+            TuiActionStatus::Queued => queued += 1,
+            TuiActionStatus::Running => running += 1,
+            TuiActionStatus::Completed(
+                ActionStatus::Aborted | ActionStatus::Failed | ActionStatus::TimedOut,
+            ) => failed += 1,
+            TuiActionStatus::Completed(_) => passed += 1,
+        };
+    }
+
+    let mut spans = vec![
+        Span::styled(" moon ", Style::new().bold().fg(Color::Magenta)),
+        Span::raw(format!(
+            "{running} running, {queued} queued, {passed} completed"
+        )),
+    ];
+
+    if failed > 0 {
+        spans.push(Span::styled(
+            format!(", {failed} failed"),
+            Style::new().fg(Color::Red),
+        ));
+    }
+
+    if state.quitting {
+        spans.push(Span::styled(
+            " (stopping...)",
+            Style::new().fg(Color::Yellow),
+        ));
+    }
+
+    frame.render_widget(Line::from(spans), area);
+}
+
+fn render_list(frame: &mut Frame, state: &TuiState, pane: TuiPane, area: Rect) {
+    let items = state
+        .get_pane_indexes(pane)
+        .into_iter()
+        .map(|index| {
+            let action = &state.actions[index];
+            let (symbol, color) = match action.status {
+                TuiActionStatus::Queued => ("·", Color::DarkGray),
+                TuiActionStatus::Running => ("▶", Color::Cyan),
+                TuiActionStatus::Completed(status) => match status {
+                    ActionStatus::Passed => ("✔", Color::Green),
+                    ActionStatus::Cached | ActionStatus::CachedFromRemote => ("✔", Color::Blue),
+                    ActionStatus::Skipped => ("-", Color::DarkGray),
+                    ActionStatus::Invalid => ("!", Color::Yellow),
+                    _ => ("✘", Color::Red),
+                },
+            };
+
+            let duration = match action.status {
+                TuiActionStatus::Running => action.started_at.map(|start| start.elapsed()),
+                TuiActionStatus::Completed(_) => action.duration,
+                TuiActionStatus::Queued => None,
+            };
+
+            let mut spans = vec![
+                Span::styled(format!("{symbol} "), Style::new().fg(color)),
+                Span::raw(
+                    action
+                        .target
+                        .as_ref()
+                        .map(|target| target.as_str().to_owned())
+                        .unwrap_or_else(|| action.label.clone()),
+                ),
+            ];
+
+            if let Some(elapsed) = duration.and_then(time::elapsed_opt) {
+                spans.push(Span::styled(
+                    format!(" {elapsed}"),
+                    Style::new().fg(Color::DarkGray),
+                ));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect::<Vec<_>>();
+
+    let focused = state.pane == pane;
+    let mut list_state = ListState::default().with_selected(if focused {
+        Some(match pane {
+            TuiPane::Actions => state.selected_action,
+            TuiPane::Persistent => state.selected_persistent,
+        })
+    } else {
+        None
+    });
+
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(match pane {
+                    TuiPane::Actions => " Actions ",
+                    TuiPane::Persistent => " Persistent ",
+                })
+                .border_style(if focused {
+                    Style::new().fg(Color::Magenta)
+                } else {
+                    Style::new().fg(Color::DarkGray)
+                }),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn render_output(frame: &mut Frame, state: &mut TuiState, area: Rect) {
+    let line_count = state
+        .get_selected()
+        .map(|action| action.output.len())
+        .unwrap_or_default();
+
+    // Follow the end of the output, unless scrolled. This must happen
+    // before the lines are built, as they borrow from the state
+    let height = area.height.saturating_sub(2);
+    let max_scroll = u16::try_from(line_count)
+        .unwrap_or(u16::MAX)
+        .saturating_sub(height);
+
+    if state.follow || state.scroll > max_scroll {
+        state.scroll = max_scroll;
+    }
+
+    let (title, lines) = match state.get_selected() {
+        Some(action) => (
+            format!(" {} ", action.label),
+            action
+                .output
+                .iter()
+                .map(|line| Line::raw(line.as_str()))
+                .collect::<Vec<_>>(),
+        ),
+        None => (" Output ".into(), vec![]),
+    };
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false })
+            .scroll((state.scroll, 0)),
+        area,
+    );
+}
+
+/// Remove ANSI escape sequences (colors, cursor movement)
+/// from a line, as they can't be rendered within a widget.
+fn strip_ansi_codes(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\u{1b}' {
+            result.push(ch);
+            continue;
+        }
+
+        if chars.peek() == Some(&'[') {
+            chars.next();
+
+            // Consume until the final byte of the sequence
+            for next in chars.by_ref() {
+                if ('@'..='~').contains(&next) {
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_action(label: &str, status: TuiActionStatus, persistent: bool) -> TuiAction {
+        TuiAction {
+            label: label.into(),
+            output: vec![],
+            persistent,
+            started_at: None,
+            duration: None,
+            status,
+            target: Target::parse(label).ok(),
+        }
+    }
+
+    fn create_state() -> TuiState {
+        TuiState {
+            actions: vec![
+                create_action("app:build", TuiActionStatus::Running, false),
+                create_action("app:test", TuiActionStatus::Queued, false),
+                create_action("app:dev", TuiActionStatus::Running, true),
+            ],
+            follow: true,
+            ..TuiState::default()
+        }
+    }
+
+    mod handle_key {
+        use super::*;
+
+        #[test]
+        fn quits_on_q_esc_and_ctrl_c() {
+            for (code, modifiers) in [
+                (KeyCode::Char('q'), KeyModifiers::NONE),
+                (KeyCode::Esc, KeyModifiers::NONE),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ] {
+                let mut state = create_state();
+                state.handle_key(code, modifiers);
+
+                assert!(state.quitting);
+            }
+        }
+
+        #[test]
+        fn doesnt_quit_on_c_without_ctrl() {
+            let mut state = create_state();
+            state.handle_key(KeyCode::Char('c'), KeyModifiers::NONE);
+
+            assert!(!state.quitting);
+        }
+
+        #[test]
+        fn switches_panes() {
+            let mut state = create_state();
+
+            state.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+
+            assert!(state.pane == TuiPane::Persistent);
+            assert_eq!(state.get_selected().unwrap().label, "app:dev");
+
+            state.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+
+            assert!(state.pane == TuiPane::Actions);
+        }
+
+        #[test]
+        fn doesnt_switch_panes_without_persistent() {
+            let mut state = create_state();
+            state.actions.pop();
+
+            state.handle_key(KeyCode::Tab, KeyModifiers::NONE);
+
+            assert!(state.pane == TuiPane::Actions);
+        }
+
+        #[test]
+        fn scrolls_output() {
+            let mut state = create_state();
+
+            state.handle_key(KeyCode::PageDown, KeyModifiers::NONE);
+
+            assert_eq!(state.scroll, 10);
+
+            state.handle_key(KeyCode::PageUp, KeyModifiers::NONE);
+
+            assert_eq!(state.scroll, 0);
+            assert!(!state.follow);
+
+            state.handle_key(KeyCode::End, KeyModifiers::NONE);
+
+            assert!(state.follow);
+        }
+    }
+
+    mod select {
+        use super::*;
+
+        #[test]
+        fn clamps_to_pane_bounds() {
+            let mut state = create_state();
+
+            state.select(-1);
+
+            assert_eq!(state.selected_action, 0);
+
+            state.select(5);
+
+            assert_eq!(state.selected_action, 1);
+            assert_eq!(state.get_selected().unwrap().label, "app:test");
+        }
+
+        #[test]
+        fn resets_scroll() {
+            let mut state = create_state();
+            state.scroll = 20;
+            state.follow = false;
+
+            state.handle_key(KeyCode::Down, KeyModifiers::NONE);
+
+            assert_eq!(state.scroll, 0);
+            assert!(state.follow);
+        }
+    }
+
+    mod send_signal {
+        use super::*;
+
+        #[test]
+        fn sends_to_running_task() {
+            let mut state = create_state();
+
+            state.handle_key(KeyCode::Char('r'), KeyModifiers::NONE);
+
+            assert_eq!(
+                state.signals.get(&Target::parse("app:build").unwrap()),
+                Some(&TaskSignal::Restart)
+            );
+
+            state.handle_key(KeyCode::Char('x'), KeyModifiers::NONE);
+
+            assert_eq!(
+                state.signals.get(&Target::parse("app:build").unwrap()),
+                Some(&TaskSignal::Kill)
+            );
+        }
+
+        #[test]
+        fn ignores_non_running_task() {
+            let mut state = create_state();
+            state.select(1);
+
+            state.send_signal(TaskSignal::Kill);
+
+            assert!(state.signals.is_empty());
+        }
+    }
+
+    mod strip_ansi_codes {
+        use super::*;
+
+        #[test]
+        fn removes_colors() {
+            assert_eq!(
+                strip_ansi_codes("\u{1b}[32mpassed\u{1b}[0m in 1s"),
+                "passed in 1s"
+            );
+        }
+
+        #[test]
+        fn removes_cursor_movement() {
+            assert_eq!(strip_ansi_codes("\u{1b}[2K\u{1b}[1Gline"), "line");
+        }
+
+        #[test]
+        fn keeps_plain_text() {
+            assert_eq!(strip_ansi_codes("plain [text]"), "plain [text]");
+        }
+    }
+}
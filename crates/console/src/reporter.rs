@@ -17,12 +17,20 @@ pub struct PipelineReportItem {
 pub struct TaskReportItem {
     pub attempt_current: u8,
     pub attempt_total: u8,
+    pub config_file: Option<String>,
     pub hash: Option<String>,
     pub output_prefix: Option<String>,
     pub output_streamed: bool,
     pub output_style: Option<TaskOutputStyle>,
 }
 
+/// Signals that can be sent to a running task by a reporter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TaskSignal {
+    Kill,
+    Restart,
+}
+
 pub trait Reporter: Send + Sync {
     fn inherit_streams(&mut self, _err: Arc<ConsoleBuffer>, _out: Arc<ConsoleBuffer>) {}
 
@@ -90,6 +98,31 @@ pub trait Reporter: Send + Sync {
     ) -> miette::Result<()> {
         Ok(())
     }
+
+    /// Receive a line of output from a running task. This is only called
+    /// when [`Reporter::renders_task_output`] returns true.
+    fn on_task_output(&self, _target: &Target, _line: &str, _stderr: bool) -> miette::Result<()> {
+        Ok(())
+    }
+
+    /// Poll for a signal that should be applied to a running task,
+    /// for example, a restart or kill requested by the user.
+    fn poll_task_signal(&self, _target: &Target) -> Option<TaskSignal> {
+        None
+    }
+
+    /// Return true if the reporter has requested the pipeline to be cancelled,
+    /// for example, the user quit while tasks are still queued.
+    fn should_cancel(&self) -> bool {
+        false
+    }
+
+    /// Return true if the reporter renders task output itself. When enabled, output
+    /// is always captured and passed to [`Reporter::on_task_output`], instead of
+    /// being written to the console.
+    fn renders_task_output(&self) -> bool {
+        false
+    }
 }
 
 pub type BoxedReporter = Box<dyn Reporter>;
@@ -5,12 +5,18 @@ use tera::{Context, Tera};
 
 #[derive(Debug, Default)]
 pub struct GenerateDockerfileOptions {
+    pub build_outputs: Vec<String>,
     pub build_task: Option<Target>,
+    pub cache_mounts: Vec<String>,
     pub disable_toolchain: bool,
     pub image: String,
     pub project: Id,
     pub prune: bool,
+    pub runtime_image: Option<String>,
+    pub start_command: Vec<String>,
+    pub start_dir: String,
     pub start_task: Option<Target>,
+    pub user: Option<String>,
 }
 
 pub fn generate_dockerfile(mut options: GenerateDockerfileOptions) -> miette::Result<String> {
@@ -22,7 +28,11 @@ pub fn generate_dockerfile(mut options: GenerateDockerfileOptions) -> miette::Re
         options.disable_toolchain = true;
     }
 
+    options.cache_mounts.sort();
+    options.cache_mounts.dedup();
+
     let mut context = Context::new();
+    context.insert("cache_mounts", &options.cache_mounts);
     context.insert("disable_toolchain", &options.disable_toolchain);
     context.insert("image", &options.image);
     context.insert("project", &options.project);
@@ -36,6 +46,27 @@ pub fn generate_dockerfile(mut options: GenerateDockerfileOptions) -> miette::Re
         context.insert("start_task", task);
     }
 
+    // A slim runtime stage requires outputs to copy
+    if let Some(image) = &options.runtime_image {
+        if !options.build_outputs.is_empty() {
+            context.insert("build_outputs", &options.build_outputs);
+            context.insert("runtime_image", image);
+            context.insert("start_command", &options.start_command);
+            context.insert(
+                "start_dir",
+                &if options.start_dir.is_empty() || options.start_dir == "." {
+                    "/app".to_owned()
+                } else {
+                    format!("/app/{}", options.start_dir)
+                },
+            );
+
+            if let Some(user) = &options.user {
+                context.insert("user", user);
+            }
+        }
+    }
+
     let result = Tera::one_off(
         include_str!("../templates/Dockerfile.tera"),
         &context,
@@ -44,4 +44,43 @@ mod dockerfile {
 
         assert_snapshot!(generate_dockerfile(options).unwrap());
     }
+
+    #[test]
+    fn with_cache_mounts() {
+        let mut options = create_options();
+        options.prune = true;
+        options.cache_mounts = vec!["/root/.npm".into(), "/root/.cache/custom".into()];
+
+        assert_snapshot!(generate_dockerfile(options).unwrap());
+    }
+
+    #[test]
+    fn with_runtime_image() {
+        let mut options = create_options();
+        options.prune = true;
+        options.build_task = Some(Target::parse("app:compile").unwrap());
+        options.build_outputs = vec!["apps/app/dist".into(), "node_modules".into()];
+        options.start_task = Some(Target::parse("app:serve").unwrap());
+        options.start_command = vec!["node".into(), "dist/index.js".into()];
+        options.start_dir = "apps/app".into();
+        options.runtime_image = Some("node:slim".into());
+        options.user = Some("node".into());
+
+        assert_snapshot!(generate_dockerfile(options).unwrap());
+    }
+
+    #[test]
+    fn ignores_runtime_image_without_outputs() {
+        let mut options = create_options();
+        options.build_task = Some(Target::parse("app:compile").unwrap());
+        options.start_task = Some(Target::parse("app:serve").unwrap());
+        options.runtime_image = Some("node:slim".into());
+        options.user = Some("node".into());
+
+        let content = generate_dockerfile(options).unwrap();
+
+        assert!(!content.contains("node:slim"));
+        assert!(!content.contains("USER"));
+        assert!(content.contains("CMD moon run app:serve"));
+    }
 }
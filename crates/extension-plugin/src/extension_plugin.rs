@@ -3,7 +3,7 @@ use moon_pdk_api::*;
 use moon_plugin::{Plugin, PluginContainer, PluginId, PluginRegistration, PluginType};
 use std::fmt;
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{instrument, trace};
 
 pub struct ExtensionPlugin {
     pub id: PluginId,
@@ -21,6 +21,42 @@ impl ExtensionPlugin {
 
         Ok(())
     }
+
+    #[instrument(skip(self, payload, context))]
+    pub async fn on_pipeline_event(
+        &self,
+        event: &str,
+        payload: &serde_json::Value,
+        context: MoonContext,
+    ) -> miette::Result<()> {
+        if !self.plugin.has_func("on_pipeline_event").await || !self.subscribes_to_event(event) {
+            return Ok(());
+        }
+
+        trace!(
+            extension_id = self.id.as_str(),
+            event,
+            "Sending pipeline event to extension"
+        );
+
+        self.plugin
+            .call_func_without_output(
+                "on_pipeline_event",
+                PipelineEventInput {
+                    context,
+                    event: event.to_owned(),
+                    payload: payload.to_owned(),
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub fn subscribes_to_event(&self, event: &str) -> bool {
+        self.metadata.pipeline_events.is_empty()
+            || self.metadata.pipeline_events.iter().any(|ev| ev == event)
+    }
 }
 
 #[async_trait]
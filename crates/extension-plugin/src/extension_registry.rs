@@ -56,6 +56,23 @@ impl ExtensionRegistry {
 
         self.get_instance(id).await
     }
+
+    pub fn get_pipeline_subscriber_ids(&self) -> Vec<PluginId> {
+        let mut ids = self
+            .configs
+            .iter()
+            .filter_map(|(id, config)| {
+                if config.pipeline_events {
+                    Some(id.to_owned())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        ids
+    }
 }
 
 impl Deref for ExtensionRegistry {
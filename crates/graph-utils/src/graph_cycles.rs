@@ -0,0 +1,75 @@
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::Direction;
+use rustc_hash::FxHashSet;
+
+/// Find all elementary cycles (no repeated nodes) in the graph, up to the
+/// provided limit. Strongly connected components are first located with
+/// Tarjan's algorithm, and then each cycle path is reconstructed from the
+/// smallest node in the cycle, so that every cycle is only returned once.
+pub fn find_elementary_cycles<N, E>(graph: &DiGraph<N, E>, limit: usize) -> Vec<Vec<NodeIndex>> {
+    let mut cycles = vec![];
+
+    for mut component in tarjan_scc(graph) {
+        if component.len() == 1 {
+            let node = component[0];
+
+            if graph.contains_edge(node, node) {
+                cycles.push(vec![node]);
+            }
+
+            continue;
+        }
+
+        component.sort();
+
+        for (i, start) in component.iter().enumerate() {
+            let allowed = FxHashSet::from_iter(component[i..].iter().copied());
+            let mut path = vec![*start];
+
+            walk_cycles(graph, &allowed, &mut path, &mut cycles, limit);
+
+            if cycles.len() >= limit {
+                return cycles;
+            }
+        }
+    }
+
+    cycles
+}
+
+fn walk_cycles<N, E>(
+    graph: &DiGraph<N, E>,
+    allowed: &FxHashSet<NodeIndex>,
+    path: &mut Vec<NodeIndex>,
+    cycles: &mut Vec<Vec<NodeIndex>>,
+    limit: usize,
+) {
+    let start = path[0];
+    let current = path[path.len() - 1];
+
+    let mut neighbors = graph
+        .neighbors_directed(current, Direction::Outgoing)
+        .collect::<Vec<_>>();
+    neighbors.sort();
+    neighbors.dedup();
+
+    for next in neighbors {
+        if cycles.len() >= limit {
+            return;
+        }
+
+        if next == start {
+            cycles.push(path.clone());
+            continue;
+        }
+
+        if !allowed.contains(&next) || path.contains(&next) {
+            continue;
+        }
+
+        path.push(next);
+        walk_cycles(graph, allowed, path, cycles, limit);
+        path.pop();
+    }
+}
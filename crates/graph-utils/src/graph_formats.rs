@@ -54,3 +54,107 @@ pub trait GraphToJson<N: Serialize, E: Serialize, K>: GraphData<N, E, K> {
         )?)
     }
 }
+
+/// Format a graph as a Mermaid flowchart. Nodes are labeled with their display
+/// value, and edges are only labeled when their display value is not empty.
+pub fn format_graph_as_mermaid<N: Display, E: Display>(graph: &DiGraph<N, E>) -> String {
+    let escape = |value: String| value.replace('"', "#quot;");
+    let mut lines = vec!["flowchart TD".to_owned()];
+
+    for index in graph.node_indices() {
+        lines.push(format!(
+            "    node{}[\"{}\"]",
+            index.index(),
+            escape(graph[index].to_string())
+        ));
+    }
+
+    for edge in graph.edge_references() {
+        let label = edge.weight().to_string();
+
+        lines.push(if label.is_empty() {
+            format!(
+                "    node{} --> node{}",
+                edge.source().index(),
+                edge.target().index()
+            )
+        } else {
+            format!(
+                "    node{} -->|\"{}\"| node{}",
+                edge.source().index(),
+                escape(label),
+                edge.target().index()
+            )
+        });
+    }
+
+    lines.join("\n")
+}
+
+/// Format a graph as a GraphML document. Node and edge labels
+/// are stored as a `label` data attribute.
+pub fn format_graph_as_graphml<N: Display, E: Display>(graph: &DiGraph<N, E>) -> String {
+    let escape = |value: String| {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    };
+
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_owned(),
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">"#.to_owned(),
+        r#"  <key id="node_label" for="node" attr.name="label" attr.type="string"/>"#.to_owned(),
+        r#"  <key id="edge_label" for="edge" attr.name="label" attr.type="string"/>"#.to_owned(),
+        r#"  <graph id="G" edgedefault="directed">"#.to_owned(),
+    ];
+
+    for index in graph.node_indices() {
+        lines.push(format!(
+            r#"    <node id="n{}"><data key="node_label">{}</data></node>"#,
+            index.index(),
+            escape(graph[index].to_string())
+        ));
+    }
+
+    for edge in graph.edge_references() {
+        let label = edge.weight().to_string();
+
+        lines.push(if label.is_empty() {
+            format!(
+                r#"    <edge id="e{}" source="n{}" target="n{}"/>"#,
+                edge.id().index(),
+                edge.source().index(),
+                edge.target().index()
+            )
+        } else {
+            format!(
+                r#"    <edge id="e{}" source="n{}" target="n{}"><data key="edge_label">{}</data></edge>"#,
+                edge.id().index(),
+                edge.source().index(),
+                edge.target().index(),
+                escape(label)
+            )
+        });
+    }
+
+    lines.push("  </graph>".into());
+    lines.push("</graphml>".into());
+    lines.join("\n")
+}
+
+pub trait GraphToMermaid<N: Display, E: Display, K>: GraphData<N, E, K> {
+    /// Format graph as a Mermaid flowchart string.
+    fn to_mermaid(&self) -> String {
+        format_graph_as_mermaid(self.get_graph())
+    }
+}
+
+pub trait GraphToGraphML<N: Display, E: Display, K>: GraphData<N, E, K> {
+    /// Format graph as a GraphML (XML) string.
+    fn to_graphml(&self) -> String {
+        format_graph_as_graphml(self.get_graph())
+    }
+}
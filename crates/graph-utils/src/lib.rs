@@ -1,7 +1,9 @@
 mod graph_context;
+mod graph_cycles;
 mod graph_formats;
 mod graph_traits;
 
 pub use graph_context::*;
+pub use graph_cycles::*;
 pub use graph_formats::*;
 pub use graph_traits::*;
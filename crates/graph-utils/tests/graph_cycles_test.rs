@@ -0,0 +1,90 @@
+use moon_graph_utils::find_elementary_cycles;
+use petgraph::graph::{DiGraph, NodeIndex};
+
+fn labels(graph: &DiGraph<&str, ()>, cycles: Vec<Vec<NodeIndex>>) -> Vec<Vec<String>> {
+    cycles
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|i| graph[i].to_owned()).collect())
+        .collect()
+}
+
+#[test]
+fn returns_nothing_for_acyclic_graph() {
+    let mut graph = DiGraph::<&str, ()>::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let c = graph.add_node("c");
+
+    graph.add_edge(a, b, ());
+    graph.add_edge(b, c, ());
+    graph.add_edge(a, c, ());
+
+    assert!(find_elementary_cycles(&graph, 100).is_empty());
+}
+
+#[test]
+fn finds_self_loops() {
+    let mut graph = DiGraph::<&str, ()>::new();
+    let a = graph.add_node("a");
+
+    graph.add_edge(a, a, ());
+
+    let cycles = find_elementary_cycles(&graph, 100);
+
+    assert_eq!(labels(&graph, cycles), vec![vec!["a"]]);
+}
+
+#[test]
+fn finds_all_cycles_in_a_component() {
+    let mut graph = DiGraph::<&str, ()>::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let c = graph.add_node("c");
+    let d = graph.add_node("d");
+
+    graph.add_edge(a, b, ());
+    graph.add_edge(b, c, ());
+    graph.add_edge(c, a, ());
+    graph.add_edge(b, a, ());
+    graph.add_edge(c, d, ());
+
+    let mut cycles = labels(&graph, find_elementary_cycles(&graph, 100));
+    cycles.sort();
+
+    assert_eq!(cycles, vec![vec!["a", "b"], vec!["a", "b", "c"]]);
+}
+
+#[test]
+fn finds_cycles_across_components() {
+    let mut graph = DiGraph::<&str, ()>::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let c = graph.add_node("c");
+    let d = graph.add_node("d");
+
+    graph.add_edge(a, b, ());
+    graph.add_edge(b, a, ());
+    graph.add_edge(c, d, ());
+    graph.add_edge(d, c, ());
+    graph.add_edge(b, c, ());
+
+    let mut cycles = labels(&graph, find_elementary_cycles(&graph, 100));
+    cycles.sort();
+
+    assert_eq!(cycles, vec![vec!["a", "b"], vec!["c", "d"]]);
+}
+
+#[test]
+fn stops_at_limit() {
+    let mut graph = DiGraph::<&str, ()>::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let c = graph.add_node("c");
+
+    graph.add_edge(a, b, ());
+    graph.add_edge(b, a, ());
+    graph.add_edge(b, c, ());
+    graph.add_edge(c, a, ());
+
+    assert_eq!(find_elementary_cycles(&graph, 1).len(), 1);
+}
@@ -0,0 +1,62 @@
+use moon_graph_utils::{format_graph_as_graphml, format_graph_as_mermaid};
+use petgraph::graph::DiGraph;
+
+fn create_graph() -> DiGraph<String, String> {
+    let mut graph = DiGraph::new();
+    let app = graph.add_node("app".to_owned());
+    let lib = graph.add_node("lib \"core\"".to_owned());
+    let utils = graph.add_node("<utils>".to_owned());
+
+    graph.add_edge(app, lib, "production".to_owned());
+    graph.add_edge(lib, utils, String::new());
+    graph
+}
+
+mod mermaid {
+    use super::*;
+
+    #[test]
+    fn renders_nodes_and_edges() {
+        assert_eq!(
+            format_graph_as_mermaid(&create_graph()),
+            r#"flowchart TD
+    node0["app"]
+    node1["lib #quot;core#quot;"]
+    node2["<utils>"]
+    node0 -->|"production"| node1
+    node1 --> node2"#
+        );
+    }
+
+    #[test]
+    fn renders_empty_graph() {
+        assert_eq!(
+            format_graph_as_mermaid(&DiGraph::<String, String>::new()),
+            "flowchart TD"
+        );
+    }
+}
+
+mod graphml {
+    use super::*;
+
+    #[test]
+    fn renders_nodes_and_edges() {
+        let output = format_graph_as_graphml(&create_graph());
+
+        assert!(output.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(output.contains(r#"<graph id="G" edgedefault="directed">"#));
+        assert!(output.contains(r#"<node id="n0"><data key="node_label">app</data></node>"#));
+        assert!(output.contains(
+            r#"<node id="n1"><data key="node_label">lib &quot;core&quot;</data></node>"#
+        ));
+        assert!(
+            output.contains(r#"<node id="n2"><data key="node_label">&lt;utils&gt;</data></node>"#)
+        );
+        assert!(output.contains(
+            r#"<edge id="e0" source="n0" target="n1"><data key="edge_label">production</data></edge>"#
+        ));
+        assert!(output.contains(r#"<edge id="e1" source="n1" target="n2"/>"#));
+        assert!(output.ends_with("</graphml>"));
+    }
+}
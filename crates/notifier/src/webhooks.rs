@@ -1,13 +1,25 @@
 use ci_env::{get_environment, CiEnvironment};
+use hmac::{Hmac, Mac};
 use moon_common::color;
+use moon_config::NotifierWebhookConfig;
 use moon_time::chrono::NaiveDateTime;
 use moon_time::now_timestamp;
+use reqwest::StatusCode;
 use serde::Serialize;
+use sha2::Sha256;
 use starbase_utils::json;
+use std::env;
+use std::time::Duration;
 use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout_at, Instant};
 use tracing::{debug, trace, warn};
 use uuid::Uuid;
 
+pub const SIGNATURE_HEADER: &str = "X-Moon-Signature-256";
+
+const RETRY_BASE_DELAY: u64 = 250;
+const RETRY_MAX_DELAY: u64 = 30_000;
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebhookPayload<'data, T: Serialize> {
@@ -23,38 +35,141 @@ pub struct WebhookPayload<'data, T: Serialize> {
     pub uuid: &'data str,
 }
 
-pub async fn notify_webhook(
-    url: String,
-    body: String,
-) -> Result<reqwest::Response, reqwest::Error> {
-    reqwest::Client::new()
-        .post(url)
-        .body(body)
-        .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .header("Connection", "keep-alive")
-        .header("Keep-Alive", "timeout=30, max=120")
-        .send()
-        .await
+#[derive(Clone, Debug, Default)]
+pub struct WebhookRequest {
+    pub body: String,
+    pub deadline: Option<Instant>,
+    pub headers: Vec<(String, String)>,
+    pub retry_count: u8,
+    pub url: String,
+}
+
+pub async fn notify_webhook(request: WebhookRequest) -> Result<reqwest::Response, reqwest::Error> {
+    let client = reqwest::Client::new();
+    let mut attempt: u8 = 0;
+
+    loop {
+        let mut builder = client
+            .post(&request.url)
+            .body(request.body.clone())
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .header("Connection", "keep-alive")
+            .header("Keep-Alive", "timeout=30, max=120");
+
+        for (key, value) in &request.headers {
+            builder = builder.header(key, value);
+        }
+
+        let result = builder.send().await;
+
+        // Only retry on network failures, server errors, or rate limits,
+        // as client errors will continue to fail no matter what
+        let retryable = match &result {
+            Ok(response) => {
+                let status = response.status();
+
+                status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+            }
+            // Builder errors (invalid headers, etc) will never succeed
+            Err(error) => !error.is_builder(),
+        };
+
+        if !retryable || attempt >= request.retry_count {
+            return result;
+        }
+
+        attempt += 1;
+
+        let delay = Duration::from_millis(get_retry_delay(attempt));
+
+        // Don't start a retry that would be abandoned before it completes
+        if request
+            .deadline
+            .is_some_and(|deadline| Instant::now() + delay >= deadline)
+        {
+            trace!(
+                url = &request.url,
+                attempt,
+                "Webhook request failed, but the deadline has been reached, not retrying"
+            );
+
+            return result;
+        }
+
+        trace!(
+            url = &request.url,
+            attempt,
+            "Webhook request failed, retrying in {}ms",
+            delay.as_millis()
+        );
+
+        sleep(delay).await;
+    }
+}
+
+/// Return the exponential backoff delay (in milliseconds) for the
+/// provided retry attempt, capped to a maximum delay.
+pub fn get_retry_delay(attempt: u8) -> u64 {
+    2_u64
+        .checked_pow(u32::from(attempt.saturating_sub(1)))
+        .map(|factor| RETRY_BASE_DELAY.saturating_mul(factor))
+        .unwrap_or(RETRY_MAX_DELAY)
+        .min(RETRY_MAX_DELAY)
+}
+
+pub fn sign_webhook_body(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+
+    mac.update(body.as_bytes());
+
+    format!("sha256={:x}", mac.finalize().into_bytes())
 }
 
 pub struct WebhooksNotifier {
+    config: NotifierWebhookConfig,
+    deadline: Option<Instant>,
     enabled: bool,
     environment: Option<CiEnvironment>,
     requests: Vec<JoinHandle<()>>,
+    secret: Option<String>,
     url: String,
     uuid: String,
     verified: bool,
 }
 
 impl WebhooksNotifier {
-    pub fn new(url: String) -> Self {
+    pub fn new(config: NotifierWebhookConfig) -> Self {
+        let url = config.url.clone().unwrap_or_default();
+        let mut enabled = true;
+        let mut secret = None;
+
         debug!("Creating webhooks notifier for {}", color::url(&url));
 
+        if let Some(secret_env) = &config.secret_env {
+            match env::var(secret_env) {
+                Ok(value) if !value.is_empty() => {
+                    secret = Some(value);
+                }
+                _ => {
+                    enabled = false;
+
+                    warn!(
+                        "Webhook for {} requires a signing secret, but the environment variable {} is not set, webhook requests will be disabled",
+                        color::url(&url),
+                        color::symbol(secret_env),
+                    );
+                }
+            };
+        }
+
         WebhooksNotifier {
-            enabled: true,
+            deadline: None,
+            enabled,
             environment: get_environment(),
             requests: vec![],
+            secret,
             uuid: if url.contains("127.0.0.1") {
                 "XXXX-XXXX-XXXX-XXXX".into()
             } else {
@@ -62,7 +177,18 @@ impl WebhooksNotifier {
             },
             url,
             verified: false,
+            config,
+        }
+    }
+
+    /// Return true if the event should be sent to the endpoint,
+    /// based on the configured event types and failure filter.
+    pub fn should_notify(&self, name: &str, failed: bool) -> bool {
+        if !self.enabled || self.config.failures_only && !failed {
+            return false;
         }
+
+        self.config.events.is_empty() || self.config.events.iter().any(|event| event == name)
     }
 
     pub async fn notify<T: Serialize>(&mut self, name: &str, event: T) -> miette::Result<()> {
@@ -80,13 +206,13 @@ impl WebhooksNotifier {
             uuid: &self.uuid,
         };
         let body = json::format(&payload, false)?;
-        let url = self.url.to_owned();
+        let request = self.create_request(body);
 
         // For the first event, we want to ensure that the webhook URL is valid
         // by sending the request and checking for a failure. If failed,
         // we will disable subsequent requests from being called.
         if !self.verified {
-            let response = notify_webhook(url, body).await;
+            let response = notify_webhook(request).await;
 
             if response.is_err() || !response.unwrap().status().is_success() {
                 self.enabled = false;
@@ -100,16 +226,94 @@ impl WebhooksNotifier {
         // We will also avoid awaiting the request to not slow down the overall runner.
         else {
             self.requests.push(tokio::spawn(async {
-                let _ = notify_webhook(url, body).await;
+                let _ = notify_webhook(request).await;
             }));
         }
 
         Ok(())
     }
 
+    /// Set a deadline for all current and subsequent requests. Retries
+    /// will not be attempted past this point, and any requests still in
+    /// flight when waiting will be aborted.
+    pub fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
+
     pub async fn wait_for_requests(&mut self) {
-        for future in self.requests.drain(0..) {
-            let _ = future.await;
+        let mut aborted = 0;
+
+        for mut future in self.requests.drain(0..) {
+            match self.deadline {
+                Some(deadline) => {
+                    if timeout_at(deadline, &mut future).await.is_err() {
+                        future.abort();
+                        aborted += 1;
+                    }
+                }
+                None => {
+                    let _ = future.await;
+                }
+            };
+        }
+
+        if aborted > 0 {
+            warn!(
+                "Aborted {} webhook request(s) to {} that did not finish in time",
+                aborted,
+                color::url(&self.url),
+            );
+        }
+    }
+
+    fn create_request(&self, body: String) -> WebhookRequest {
+        let mut headers = self
+            .config
+            .headers
+            .iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect::<Vec<_>>();
+
+        if let Some(secret) = &self.secret {
+            headers.push((SIGNATURE_HEADER.into(), sign_webhook_body(secret, &body)));
         }
+
+        WebhookRequest {
+            body,
+            deadline: self.deadline,
+            headers,
+            retry_count: self.config.retry_count,
+            url: self.url.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_retry_delay() {
+        assert_eq!(get_retry_delay(1), 250);
+        assert_eq!(get_retry_delay(2), 500);
+        assert_eq!(get_retry_delay(3), 1000);
+        assert_eq!(get_retry_delay(10), RETRY_MAX_DELAY);
+        assert_eq!(get_retry_delay(u8::MAX), RETRY_MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn doesnt_retry_past_deadline() {
+        let request = WebhookRequest {
+            body: "{}".into(),
+            deadline: Some(Instant::now()),
+            retry_count: 10,
+            // Nothing listens on this port, so the request will fail
+            url: "http://127.0.0.1:1".into(),
+            ..WebhookRequest::default()
+        };
+
+        let result = tokio::time::timeout(Duration::from_secs(5), notify_webhook(request)).await;
+
+        assert!(result.expect("should not retry").is_err());
     }
 }
@@ -0,0 +1,567 @@
+use crate::image_layer::{create_image_layer, ImageLayer};
+use crate::image_reference::ImageReference;
+use crate::oci_error::OciError;
+use crate::oci_spec::*;
+use crate::registry_client::RegistryClient;
+use moon_common::color;
+use moon_config::TaskImageConfig;
+use moon_remote::{Blob, Digest, RemoteService};
+use serde_json::{json, Map, Value};
+use starbase_utils::fs;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tar::{Archive, Builder, EntryType, Header};
+use tracing::{debug, instrument, warn};
+
+// Images must be reproducible, so timestamps are always the epoch
+const CREATED_AT: &str = "1970-01-01T00:00:00Z";
+
+/// The result of building an image.
+pub struct OciImage {
+    pub config: Descriptor,
+    pub layers: Vec<Descriptor>,
+    pub manifest: Descriptor,
+    pub manifest_bytes: Vec<u8>,
+}
+
+/// Assembles an OCI image from a base image and a list of files, without
+/// a container runtime. Blobs are cached locally, and in the remote service
+/// when enabled, so that base images are only pulled from the registry once.
+pub struct OciImageBuilder<'cfg> {
+    config: &'cfg TaskImageConfig,
+    files: BTreeMap<String, PathBuf>,
+
+    /// Directory in which blobs are cached, by their sha256 hash.
+    pub blobs_dir: PathBuf,
+}
+
+impl<'cfg> OciImageBuilder<'cfg> {
+    pub fn new(config: &'cfg TaskImageConfig, cache_dir: &Path) -> Self {
+        Self {
+            config,
+            files: BTreeMap::new(),
+            blobs_dir: cache_dir.join("blobs").join("sha256"),
+        }
+    }
+
+    /// Add a file to the image, at a path relative to the configured `workdir`.
+    pub fn add_file(&mut self, rel_path: impl AsRef<str>, source: PathBuf) {
+        let workdir = self.config.workdir.trim_matches('/');
+        let rel_path = rel_path.as_ref().trim_start_matches('/');
+
+        self.files.insert(
+            if workdir.is_empty() {
+                rel_path.to_owned()
+            } else {
+                format!("{workdir}/{rel_path}")
+            },
+            source,
+        );
+    }
+
+    pub fn get_blob_path(&self, descriptor: &Descriptor) -> PathBuf {
+        self.blobs_dir.join(descriptor.get_hash())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn build(&self, dest: &Path) -> miette::Result<OciImage> {
+        let base = ImageReference::parse(&self.config.base)?;
+        let platform = Platform::parse(&self.config.platform);
+
+        let Some(base_digest) = base.digest.clone() else {
+            return Err(OciError::MissingBaseDigest {
+                reference: self.config.base.clone(),
+            }
+            .into());
+        };
+
+        debug!(
+            base = base.to_string(),
+            platform = &self.config.platform,
+            files = self.files.len(),
+            "Building OCI image"
+        );
+
+        let client = RegistryClient::new(base.clone());
+
+        // Resolve the base manifest, selecting a platform from an index
+        let mut base_manifest_desc = Descriptor {
+            digest: base_digest,
+            ..Default::default()
+        };
+        let mut base_manifest_bytes = self.load_blob(&client, &base_manifest_desc, true).await?;
+        let root: Value = parse_json("base image manifest", &base_manifest_bytes)?;
+
+        if root.get("manifests").is_some() {
+            let index: ImageIndex = parse_json("base image index", &base_manifest_bytes)?;
+
+            let Some(desc) = index.manifests.into_iter().find(|desc| {
+                desc.platform
+                    .as_ref()
+                    .is_some_and(|item| platform.matches(item))
+            }) else {
+                return Err(OciError::MissingPlatform {
+                    reference: base.to_string(),
+                    platform: self.config.platform.clone(),
+                }
+                .into());
+            };
+
+            base_manifest_bytes = self.load_blob(&client, &desc, true).await?;
+            base_manifest_desc = desc;
+        }
+
+        let base_manifest: ImageManifest = parse_json("base image manifest", &base_manifest_bytes)?;
+        let base_config = self
+            .load_blob(&client, &base_manifest.config, false)
+            .await?;
+
+        let mut layers = vec![];
+
+        for layer in &base_manifest.layers {
+            self.load_blob(&client, layer, false).await?;
+
+            layers.push(Descriptor {
+                media_type: convert_media_type(&layer.media_type),
+                ..layer.clone()
+            });
+        }
+
+        // Create a new layer from the files, and an updated config
+        let layer = create_image_layer(&self.files, &self.blobs_dir)?;
+
+        // The layer is streamed to disk, so only read it when it must be uploaded
+        if RemoteService::session().is_some() {
+            self.save_remote_blob(&layer.descriptor, fs::read_file_bytes(&layer.path)?)
+                .await;
+        }
+
+        layers.push(layer.descriptor.clone());
+
+        let config_bytes = self.create_config(&base_config, &layer)?;
+        let config = Descriptor::new(MEDIA_TYPE_IMAGE_CONFIG, &config_bytes);
+
+        self.save_blob(&config, config_bytes).await?;
+
+        // And finally the manifest that ties it all together
+        let manifest_bytes = to_json(
+            "image manifest",
+            &ImageManifest {
+                schema_version: 2,
+                media_type: Some(MEDIA_TYPE_IMAGE_MANIFEST.into()),
+                config: config.clone(),
+                layers: layers.clone(),
+                annotations: BTreeMap::from_iter([
+                    (
+                        ANNOTATION_BASE_DIGEST.to_owned(),
+                        base_manifest_desc.digest.clone(),
+                    ),
+                    (ANNOTATION_BASE_NAME.to_owned(), base.get_name()),
+                ]),
+            },
+        )?;
+        let manifest = Descriptor {
+            platform: Some(platform),
+            ..Descriptor::new(MEDIA_TYPE_IMAGE_MANIFEST, &manifest_bytes)
+        };
+
+        self.save_blob(&manifest, manifest_bytes.clone()).await?;
+
+        let image = OciImage {
+            config,
+            layers,
+            manifest,
+            manifest_bytes,
+        };
+
+        self.write_archive(&image, dest)?;
+
+        debug!(
+            digest = &image.manifest.digest,
+            dest = ?dest,
+            "Built OCI image"
+        );
+
+        Ok(image)
+    }
+
+    /// Push the image to a registry. The tag of the target reference is used
+    /// for the manifest, otherwise the image is pushed by digest.
+    #[instrument(skip(self, image))]
+    pub async fn push(&self, image: &OciImage, target: &str) -> miette::Result<()> {
+        let reference = ImageReference::parse(target)?;
+        let client = RegistryClient::new(reference.clone());
+
+        debug!(
+            digest = &image.manifest.digest,
+            target = reference.to_string(),
+            "Pushing OCI image to registry"
+        );
+
+        for blob in image.layers.iter().chain([&image.config]) {
+            client
+                .push_blob(&blob.digest, &self.get_blob_path(blob))
+                .await?;
+        }
+
+        client
+            .push_manifest(
+                reference
+                    .tag
+                    .as_deref()
+                    .unwrap_or(image.manifest.digest.as_str()),
+                MEDIA_TYPE_IMAGE_MANIFEST,
+                &image.manifest_bytes,
+            )
+            .await
+    }
+
+    /// Load an image from an image layout tarball that was previously built,
+    /// for example, when hydrated from the cache. Blobs are extracted into
+    /// the local cache, so that the image can be pushed.
+    #[instrument(skip(self))]
+    pub fn load(&self, archive: &Path) -> miette::Result<OciImage> {
+        let map_error = |error: std::io::Error| OciError::ArchiveReadFailed {
+            path: archive.to_path_buf(),
+            error: Box::new(error),
+        };
+
+        let file = std::fs::File::open(archive).map_err(map_error)?;
+        let mut tarball = Archive::new(file);
+        let mut index_bytes = vec![];
+
+        fs::create_dir_all(&self.blobs_dir)?;
+
+        for entry in tarball.entries().map_err(map_error)? {
+            let mut entry = entry.map_err(map_error)?;
+            let entry_path = entry
+                .path()
+                .map_err(map_error)?
+                .to_string_lossy()
+                .to_string();
+
+            if entry_path == "index.json" {
+                entry.read_to_end(&mut index_bytes).map_err(map_error)?;
+            } else if let Some(hash) = entry_path.strip_prefix("blobs/sha256/") {
+                let blob_path = self.blobs_dir.join(hash);
+
+                if !blob_path.exists() {
+                    let mut blob = std::fs::File::create(&blob_path).map_err(map_error)?;
+
+                    std::io::copy(&mut entry, &mut blob).map_err(map_error)?;
+                }
+            }
+        }
+
+        let index: ImageIndex = parse_json("image index", &index_bytes)?;
+
+        let Some(manifest) = index.manifests.into_iter().next() else {
+            return Err(OciError::ArchiveMissingManifest {
+                path: archive.to_path_buf(),
+            }
+            .into());
+        };
+
+        let manifest_bytes = fs::read_file_bytes(self.get_blob_path(&manifest))?;
+        let image_manifest: ImageManifest = parse_json("image manifest", &manifest_bytes)?;
+
+        Ok(OciImage {
+            config: image_manifest.config,
+            layers: image_manifest.layers,
+            manifest: Descriptor {
+                annotations: BTreeMap::new(),
+                ..manifest
+            },
+            manifest_bytes,
+        })
+    }
+
+    fn create_config(&self, base_config: &[u8], layer: &ImageLayer) -> miette::Result<Vec<u8>> {
+        let mut image: Map<String, Value> = parse_json("base image config", base_config)?;
+
+        let mut container = match image.remove("config") {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+
+        // Preserve the order of inherited variables, as it may be significant
+        let mut env: Vec<(String, String)> = container
+            .get("Env")
+            .and_then(|value| value.as_array())
+            .map(|list| {
+                list.iter()
+                    .filter_map(|item| item.as_str())
+                    .filter_map(|item| item.split_once('='))
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        for (key, value) in &self.config.env {
+            match env.iter_mut().find(|(existing, _)| existing == key) {
+                Some(entry) => entry.1 = value.to_owned(),
+                None => env.push((key.to_owned(), value.to_owned())),
+            };
+        }
+
+        container.insert(
+            "Env".into(),
+            json!(env
+                .into_iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()),
+        );
+
+        // Like Docker, a new entrypoint resets the inherited command
+        if !self.config.entrypoint.is_empty() {
+            container.insert("Entrypoint".into(), json!(self.config.entrypoint));
+            container.insert(
+                "Cmd".into(),
+                if self.config.cmd.is_empty() {
+                    Value::Null
+                } else {
+                    json!(self.config.cmd)
+                },
+            );
+        } else if !self.config.cmd.is_empty() {
+            container.insert("Cmd".into(), json!(self.config.cmd));
+        }
+
+        container.insert("WorkingDir".into(), json!(self.config.workdir));
+
+        if let Some(user) = &self.config.user {
+            container.insert("User".into(), json!(user));
+        }
+
+        if !self.config.labels.is_empty() {
+            let mut labels = match container.remove("Labels") {
+                Some(Value::Object(map)) => map,
+                _ => Map::new(),
+            };
+
+            for (key, value) in &self.config.labels {
+                labels.insert(key.to_owned(), json!(value));
+            }
+
+            container.insert("Labels".into(), Value::Object(labels));
+        }
+
+        image.insert("config".into(), Value::Object(container));
+        image.insert("created".into(), json!(CREATED_AT));
+
+        // Append the new layer to the filesystem and history
+        let mut diff_ids = image
+            .get("rootfs")
+            .and_then(|rootfs| rootfs.get("diff_ids"))
+            .and_then(|value| value.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        diff_ids.push(json!(layer.diff_id));
+
+        image.insert(
+            "rootfs".into(),
+            json!({ "type": "layers", "diff_ids": diff_ids }),
+        );
+
+        let mut history = image
+            .get("history")
+            .and_then(|value| value.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        history.push(json!({
+            "created": CREATED_AT,
+            "created_by": "moon",
+            "comment": "task outputs",
+        }));
+
+        image.insert("history".into(), Value::Array(history));
+
+        to_json("image config", &image)
+    }
+
+    // Load a blob from the local cache, then the remote service,
+    // and lastly the registry, caching the result along the way
+    async fn load_blob(
+        &self,
+        client: &RegistryClient,
+        descriptor: &Descriptor,
+        is_manifest: bool,
+    ) -> miette::Result<Vec<u8>> {
+        let blob_path = self.get_blob_path(descriptor);
+
+        if blob_path.exists() {
+            let bytes = fs::read_file_bytes(&blob_path)?;
+
+            if create_oci_digest(&bytes) == descriptor.digest {
+                return Ok(bytes);
+            }
+        }
+
+        // The size of the root manifest is unknown until it's been fetched
+        if descriptor.size > 0 {
+            if let Some(remote) = RemoteService::session() {
+                let digest = create_remote_digest(descriptor);
+
+                if let Some(blob) = remote
+                    .load_blobs(&digest, vec![digest.clone()])
+                    .await?
+                    .into_iter()
+                    .next()
+                {
+                    debug!(
+                        digest = &descriptor.digest,
+                        "Loaded blob from the remote service"
+                    );
+
+                    fs::write_file(&blob_path, &blob.bytes)?;
+
+                    return Ok(blob.bytes);
+                }
+            }
+        }
+
+        debug!(
+            digest = &descriptor.digest,
+            registry = &client.reference.registry,
+            "Pulling blob from registry"
+        );
+
+        let bytes = if is_manifest {
+            client.get_manifest(&descriptor.digest).await?
+        } else {
+            client.get_blob(&descriptor.digest).await?
+        };
+
+        let actual = create_oci_digest(&bytes);
+
+        if actual != descriptor.digest {
+            return Err(OciError::DigestMismatch {
+                expected: descriptor.digest.clone(),
+                actual,
+            }
+            .into());
+        }
+
+        let descriptor = Descriptor {
+            size: bytes.len() as u64,
+            ..descriptor.clone()
+        };
+
+        self.save_blob(&descriptor, bytes.clone()).await?;
+
+        Ok(bytes)
+    }
+
+    async fn save_blob(&self, descriptor: &Descriptor, bytes: Vec<u8>) -> miette::Result<()> {
+        fs::write_file(self.get_blob_path(descriptor), &bytes)?;
+
+        self.save_remote_blob(descriptor, bytes).await;
+
+        Ok(())
+    }
+
+    async fn save_remote_blob(&self, descriptor: &Descriptor, bytes: Vec<u8>) {
+        let Some(remote) = RemoteService::session() else {
+            return;
+        };
+
+        let digest = create_remote_digest(descriptor);
+
+        if let Err(error) = remote
+            .save_blobs(
+                &digest,
+                vec![Blob {
+                    bytes,
+                    digest: digest.clone(),
+                }],
+            )
+            .await
+        {
+            warn!(
+                digest = &descriptor.digest,
+                "Failed to save blob to the remote service: {}",
+                color::muted_light(error.to_string()),
+            );
+        }
+    }
+
+    // Write an OCI image layout as a tarball, which can be loaded
+    // with `docker load`, `podman load`, or `skopeo copy oci-archive:`
+    fn write_archive(&self, image: &OciImage, dest: &Path) -> miette::Result<()> {
+        let map_error = |error: std::io::Error| OciError::ArchiveFailed {
+            path: dest.to_path_buf(),
+            error: Box::new(error),
+        };
+
+        let mut index_manifest = image.manifest.clone();
+
+        if let Some(push) = &self.config.push {
+            if let Some(tag) = ImageReference::parse(push)?.tag {
+                index_manifest
+                    .annotations
+                    .insert(ANNOTATION_REF_NAME.to_owned(), tag);
+            }
+        }
+
+        let index = to_json(
+            "image index",
+            &ImageIndex {
+                schema_version: 2,
+                media_type: Some(MEDIA_TYPE_IMAGE_INDEX.into()),
+                manifests: vec![index_manifest],
+                ..Default::default()
+            },
+        )?;
+        let layout = to_json("image layout", &json!({ "imageLayoutVersion": "1.0.0" }))?;
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = std::fs::File::create(dest).map_err(map_error)?;
+        let mut builder = Builder::new(file);
+
+        let mut append = |path: &str, size: u64, data: &mut dyn std::io::Read| {
+            let mut header = Header::new_gnu();
+            header.set_entry_type(EntryType::Regular);
+            header.set_mode(0o644);
+            header.set_size(size);
+            header.set_mtime(0);
+
+            builder
+                .append_data(&mut header, path, data)
+                .map_err(map_error)
+        };
+
+        append("oci-layout", layout.len() as u64, &mut layout.as_slice())?;
+        append("index.json", index.len() as u64, &mut index.as_slice())?;
+
+        let blobs = image
+            .layers
+            .iter()
+            .chain([&image.config, &image.manifest])
+            .map(|desc| (desc.get_hash().to_owned(), self.get_blob_path(desc)))
+            .collect::<BTreeSet<_>>();
+
+        for (hash, blob_path) in blobs {
+            let mut blob = std::fs::File::open(&blob_path).map_err(map_error)?;
+            let size = blob.metadata().map_err(map_error)?.len();
+
+            append(&format!("blobs/sha256/{hash}"), size, &mut blob)?;
+        }
+
+        builder.finish().map_err(map_error)?;
+
+        Ok(())
+    }
+}
+
+fn create_remote_digest(descriptor: &Descriptor) -> Digest {
+    Digest {
+        hash: descriptor.get_hash().to_owned(),
+        size_bytes: descriptor.size as i64,
+    }
+}
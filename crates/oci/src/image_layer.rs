@@ -0,0 +1,182 @@
+use crate::oci_error::OciError;
+use crate::oci_spec::*;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use starbase_utils::fs;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tar::{Builder, EntryType, Header};
+use tracing::{debug, instrument};
+
+/// A gzipped tarball layer written to the blobs directory, with its
+/// descriptor, and the digest of the uncompressed tarball (the diff ID).
+pub struct ImageLayer {
+    pub descriptor: Descriptor,
+    pub diff_id: String,
+    pub path: PathBuf,
+}
+
+// Hashes and counts bytes as they're written through to the inner writer,
+// so that layers can be streamed to disk instead of buffered in memory
+struct HashWriter<W: Write> {
+    hasher: Sha256,
+    inner: W,
+    size: u64,
+}
+
+impl<W: Write> HashWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            hasher: Sha256::default(),
+            inner,
+            size: 0,
+        }
+    }
+
+    fn finish(self) -> (W, String, u64) {
+        (
+            self.inner,
+            format!("sha256:{:x}", self.hasher.finalize()),
+            self.size,
+        )
+    }
+}
+
+impl<W: Write> Write for HashWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+
+        self.hasher.update(&buf[0..written]);
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn map_error(error: std::io::Error) -> OciError {
+    OciError::LayerFailed {
+        error: Box::new(error),
+    }
+}
+
+#[cfg(unix)]
+fn get_file_mode(path: &Path) -> miette::Result<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = fs::metadata(path)?;
+
+    Ok(if metadata.permissions().mode() & 0o111 != 0 {
+        0o755
+    } else {
+        0o644
+    })
+}
+
+#[cfg(windows)]
+fn get_file_mode(path: &Path) -> miette::Result<u32> {
+    Ok(if path.extension().is_some_and(|ext| ext == "exe") {
+        0o755
+    } else {
+        0o644
+    })
+}
+
+fn create_header(entry_type: EntryType, mode: u32, size: u64) -> Header {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_mode(mode);
+    header.set_size(size);
+    // Timestamps and ownership are zeroed so that layers are deterministic
+    header.set_mtime(0);
+    header.set_uid(0);
+    header.set_gid(0);
+    header
+}
+
+/// Create a layer from a mapping of paths within the image, to source files
+/// on the host, and write it to the blobs directory. Entries are sorted and
+/// normalized, so the same files will always produce the same layer digest.
+#[instrument(skip(files))]
+pub fn create_image_layer(
+    files: &BTreeMap<String, PathBuf>,
+    blobs_dir: &Path,
+) -> miette::Result<ImageLayer> {
+    let mut dirs = BTreeSet::new();
+
+    for image_path in files.keys() {
+        let mut current = Path::new(image_path);
+
+        while let Some(parent) = current.parent() {
+            if parent.as_os_str().is_empty() {
+                break;
+            }
+
+            dirs.insert(format!("{}/", parent.to_string_lossy()));
+            current = parent;
+        }
+    }
+
+    debug!(
+        files = files.len(),
+        dirs = dirs.len(),
+        "Creating image layer"
+    );
+
+    // The digest is unknown until the layer has been written,
+    // so write to a temporary file and rename it afterwards
+    fs::create_dir_all(blobs_dir)?;
+
+    let temp_path = blobs_dir.join(format!("{}.tmp", uuid::Uuid::new_v4()));
+    let temp_file = std::fs::File::create(&temp_path).map_err(map_error)?;
+
+    let encoder = GzEncoder::new(HashWriter::new(temp_file), Compression::default());
+    let mut builder = Builder::new(HashWriter::new(encoder));
+
+    for dir in dirs {
+        let mut header = create_header(EntryType::Directory, 0o755, 0);
+
+        builder
+            .append_data(&mut header, dir, std::io::empty())
+            .map_err(map_error)?;
+    }
+
+    for (image_path, source) in files {
+        let file = std::fs::File::open(source).map_err(map_error)?;
+        let mut header = create_header(
+            EntryType::Regular,
+            get_file_mode(source)?,
+            file.metadata().map_err(map_error)?.len(),
+        );
+
+        builder
+            .append_data(&mut header, image_path, file)
+            .map_err(map_error)?;
+    }
+
+    let (encoder, diff_id, _) = builder.into_inner().map_err(map_error)?.finish();
+    let (mut temp_file, digest, size) = encoder.finish().map_err(map_error)?.finish();
+
+    temp_file.flush().map_err(map_error)?;
+
+    let descriptor = Descriptor {
+        media_type: MEDIA_TYPE_IMAGE_LAYER.into(),
+        digest,
+        size,
+        ..Default::default()
+    };
+    let path = blobs_dir.join(descriptor.get_hash());
+
+    fs::rename(&temp_path, &path)?;
+
+    Ok(ImageLayer {
+        descriptor,
+        diff_id,
+        path,
+    })
+}
@@ -0,0 +1,111 @@
+use crate::oci_error::OciError;
+use std::fmt;
+
+pub const DEFAULT_REGISTRY: &str = "docker.io";
+
+/// A parsed image reference, in the format of `registry/repository:tag@digest`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageReference {
+    pub registry: String,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+impl ImageReference {
+    pub fn parse(value: &str) -> miette::Result<Self> {
+        let invalid = |error: &str| OciError::InvalidReference {
+            reference: value.to_owned(),
+            error: error.to_owned(),
+        };
+
+        let (name, digest) = match value.split_once('@') {
+            Some((name, digest)) => {
+                if !digest.starts_with("sha256:") || digest.len() != 71 {
+                    return Err(invalid("only sha256 digests are supported").into());
+                }
+
+                (name, Some(digest.to_owned()))
+            }
+            None => (value, None),
+        };
+
+        // A tag can only exist after the last path separator,
+        // otherwise the colon belongs to the registry port
+        let (name, tag) = match name.rsplit_once(':') {
+            Some((left, right)) if !right.contains('/') => (left, Some(right.to_owned())),
+            _ => (name, None),
+        };
+
+        if name.is_empty() {
+            return Err(invalid("missing image name").into());
+        }
+
+        let (registry, repository) = match name.split_once('/') {
+            Some((host, path))
+                if host.contains('.') || host.contains(':') || host == "localhost" =>
+            {
+                (host.to_owned(), path.to_owned())
+            }
+            _ => (DEFAULT_REGISTRY.to_owned(), name.to_owned()),
+        };
+
+        // Official images on Docker Hub live in the library namespace
+        let repository = if registry == DEFAULT_REGISTRY && !repository.contains('/') {
+            format!("library/{repository}")
+        } else {
+            repository
+        };
+
+        Ok(Self {
+            registry,
+            repository,
+            tag,
+            digest,
+        })
+    }
+
+    /// Return the base URL of the registry API. Local registries
+    /// are assumed to not use TLS.
+    pub fn get_api_url(&self) -> String {
+        let host = if self.registry == DEFAULT_REGISTRY {
+            "registry-1.docker.io"
+        } else {
+            &self.registry
+        };
+
+        if host.starts_with("localhost") || host.starts_with("127.0.0.1") {
+            format!("http://{host}/v2/{}", self.repository)
+        } else {
+            format!("https://{host}/v2/{}", self.repository)
+        }
+    }
+
+    /// Return the digest, or the tag, or `latest`, for use in manifest requests.
+    pub fn get_reference(&self) -> &str {
+        self.digest
+            .as_deref()
+            .or(self.tag.as_deref())
+            .unwrap_or("latest")
+    }
+
+    /// Return the reference without a digest, for use in annotations.
+    pub fn get_name(&self) -> String {
+        match &self.tag {
+            Some(tag) => format!("{}/{}:{tag}", self.registry, self.repository),
+            None => format!("{}/{}", self.registry, self.repository),
+        }
+    }
+}
+
+impl fmt::Display for ImageReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_name())?;
+
+        if let Some(digest) = &self.digest {
+            write!(f, "@{digest}")?;
+        }
+
+        Ok(())
+    }
+}
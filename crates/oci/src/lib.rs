@@ -0,0 +1,13 @@
+mod image_builder;
+mod image_layer;
+mod image_reference;
+mod oci_error;
+mod oci_spec;
+mod registry_client;
+
+pub use image_builder::*;
+pub use image_layer::*;
+pub use image_reference::*;
+pub use oci_error::*;
+pub use oci_spec::*;
+pub use registry_client::*;
@@ -0,0 +1,101 @@
+use miette::Diagnostic;
+use moon_common::{Style, Stylize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum OciError {
+    #[diagnostic(code(oci::invalid_reference))]
+    #[error("Invalid image reference {}: {error}", .reference.style(Style::Label))]
+    InvalidReference { reference: String, error: String },
+
+    #[diagnostic(
+        code(oci::missing_base_digest),
+        help = "Pin the base image with a digest, for example: node@sha256:..."
+    )]
+    #[error(
+        "Base image {} must be pinned by digest, so that images are deterministic.",
+        .reference.style(Style::Label)
+    )]
+    MissingBaseDigest { reference: String },
+
+    #[diagnostic(code(oci::missing_platform))]
+    #[error(
+        "Base image {} does not support the {} platform.",
+        .reference.style(Style::Label),
+        .platform.style(Style::Symbol)
+    )]
+    MissingPlatform { reference: String, platform: String },
+
+    #[diagnostic(code(oci::digest_mismatch))]
+    #[error(
+        "Digest mismatch for blob {}, received {} instead.",
+        .expected.style(Style::Hash),
+        .actual.style(Style::Hash)
+    )]
+    DigestMismatch { expected: String, actual: String },
+
+    #[diagnostic(code(oci::invalid_json))]
+    #[error("Failed to parse {}.", .what.style(Style::Label))]
+    InvalidJson {
+        what: String,
+        #[source]
+        error: Box<serde_json::Error>,
+    },
+
+    #[diagnostic(code(oci::layer_failed))]
+    #[error("Failed to create image layer.")]
+    LayerFailed {
+        #[source]
+        error: Box<std::io::Error>,
+    },
+
+    #[diagnostic(code(oci::archive_failed))]
+    #[error("Failed to write image archive {}.", .path.style(Style::Path))]
+    ArchiveFailed {
+        path: std::path::PathBuf,
+        #[source]
+        error: Box<std::io::Error>,
+    },
+
+    #[diagnostic(code(oci::archive_read_failed))]
+    #[error("Failed to read image archive {}.", .path.style(Style::Path))]
+    ArchiveReadFailed {
+        path: std::path::PathBuf,
+        #[source]
+        error: Box<std::io::Error>,
+    },
+
+    #[diagnostic(code(oci::archive_missing_manifest))]
+    #[error(
+        "Image archive {} does not contain an image manifest.",
+        .path.style(Style::Path)
+    )]
+    ArchiveMissingManifest { path: std::path::PathBuf },
+
+    #[diagnostic(code(oci::blob_read_failed))]
+    #[error("Failed to read image blob {}.", .path.style(Style::Path))]
+    BlobReadFailed {
+        path: std::path::PathBuf,
+        #[source]
+        error: Box<std::io::Error>,
+    },
+
+    #[diagnostic(code(oci::registry::request_failed))]
+    #[error("Failed to make registry request to {}.", .url.style(Style::Url))]
+    RequestFailed {
+        url: String,
+        #[source]
+        error: Box<reqwest::Error>,
+    },
+
+    #[diagnostic(code(oci::registry::unexpected_status))]
+    #[error(
+        "Registry request to {} failed with status {status}.",
+        .url.style(Style::Url)
+    )]
+    UnexpectedStatus { url: String, status: u16 },
+
+    #[diagnostic(code(oci::registry::auth_failed))]
+    #[error("Failed to authenticate with registry {}.", .registry.style(Style::Url))]
+    AuthFailed { registry: String },
+}
@@ -0,0 +1,150 @@
+use crate::oci_error::OciError;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub const MEDIA_TYPE_IMAGE_CONFIG: &str = "application/vnd.oci.image.config.v1+json";
+pub const MEDIA_TYPE_IMAGE_INDEX: &str = "application/vnd.oci.image.index.v1+json";
+pub const MEDIA_TYPE_IMAGE_LAYER: &str = "application/vnd.oci.image.layer.v1.tar+gzip";
+pub const MEDIA_TYPE_IMAGE_MANIFEST: &str = "application/vnd.oci.image.manifest.v1+json";
+
+pub const MEDIA_TYPE_DOCKER_CONFIG: &str = "application/vnd.docker.container.image.v1+json";
+pub const MEDIA_TYPE_DOCKER_LAYER: &str = "application/vnd.docker.image.rootfs.diff.tar.gzip";
+pub const MEDIA_TYPE_DOCKER_MANIFEST: &str = "application/vnd.docker.distribution.manifest.v2+json";
+pub const MEDIA_TYPE_DOCKER_MANIFEST_LIST: &str =
+    "application/vnd.docker.distribution.manifest.list.v2+json";
+
+pub const ANNOTATION_BASE_DIGEST: &str = "org.opencontainers.image.base.digest";
+pub const ANNOTATION_BASE_NAME: &str = "org.opencontainers.image.base.name";
+pub const ANNOTATION_REF_NAME: &str = "org.opencontainers.image.ref.name";
+pub const ANNOTATION_TITLE: &str = "org.opencontainers.image.title";
+
+/// Return the `sha256:` prefixed digest of the bytes.
+pub fn create_oci_digest(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::default();
+    hasher.update(bytes);
+
+    format!("sha256:{:x}", hasher.finalize())
+}
+
+/// Convert Docker media types to their OCI equivalent,
+/// as the underlying content is the same.
+pub fn convert_media_type(media_type: &str) -> String {
+    match media_type {
+        MEDIA_TYPE_DOCKER_CONFIG => MEDIA_TYPE_IMAGE_CONFIG,
+        MEDIA_TYPE_DOCKER_LAYER => MEDIA_TYPE_IMAGE_LAYER,
+        MEDIA_TYPE_DOCKER_MANIFEST => MEDIA_TYPE_IMAGE_MANIFEST,
+        MEDIA_TYPE_DOCKER_MANIFEST_LIST => MEDIA_TYPE_IMAGE_INDEX,
+        other => other,
+    }
+    .to_owned()
+}
+
+pub fn parse_json<T: DeserializeOwned>(what: &str, bytes: &[u8]) -> miette::Result<T> {
+    serde_json::from_slice(bytes).map_err(|error| {
+        OciError::InvalidJson {
+            what: what.to_owned(),
+            error: Box::new(error),
+        }
+        .into()
+    })
+}
+
+pub fn to_json<T: Serialize>(what: &str, value: &T) -> miette::Result<Vec<u8>> {
+    serde_json::to_vec(value).map_err(|error| {
+        OciError::InvalidJson {
+            what: what.to_owned(),
+            error: Box::new(error),
+        }
+        .into()
+    })
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Platform {
+    pub architecture: String,
+    pub os: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+}
+
+impl Platform {
+    /// Parse a platform in the format of `os/arch/variant`.
+    pub fn parse(value: &str) -> Self {
+        let mut parts = value.split('/');
+
+        Self {
+            os: parts.next().unwrap_or("linux").to_owned(),
+            architecture: parts.next().unwrap_or("amd64").to_owned(),
+            variant: parts.next().map(|variant| variant.to_owned()),
+        }
+    }
+
+    pub fn matches(&self, other: &Platform) -> bool {
+        self.os == other.os
+            && self.architecture == other.architecture
+            && (self.variant.is_none() || self.variant == other.variant)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Descriptor {
+    pub media_type: String,
+    pub digest: String,
+    pub size: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<Platform>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+impl Descriptor {
+    pub fn new(media_type: &str, bytes: &[u8]) -> Self {
+        Self {
+            media_type: media_type.to_owned(),
+            digest: create_oci_digest(bytes),
+            size: bytes.len() as u64,
+            ..Default::default()
+        }
+    }
+
+    /// Return the hex portion of the digest, without the algorithm.
+    pub fn get_hash(&self) -> &str {
+        self.digest.trim_start_matches("sha256:")
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageManifest {
+    pub schema_version: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    pub config: Descriptor,
+
+    pub layers: Vec<Descriptor>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageIndex {
+    pub schema_version: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    pub manifests: Vec<Descriptor>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
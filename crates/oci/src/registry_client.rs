@@ -0,0 +1,519 @@
+use crate::image_reference::{ImageReference, DEFAULT_REGISTRY};
+use crate::oci_error::OciError;
+use crate::oci_spec::*;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use reqwest::header::{
+    HeaderMap, ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, LOCATION, WWW_AUTHENTICATE,
+};
+use reqwest::{Body, Client, RequestBuilder, Response, StatusCode};
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use starbase_utils::{fs, json};
+use std::env;
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+use tokio_util::codec::{BytesCodec, FramedRead};
+use tracing::{debug, instrument, trace};
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DockerConfig {
+    #[serde(default)]
+    auths: FxHashMap<String, DockerConfigAuth>,
+}
+
+#[derive(Deserialize)]
+struct DockerConfigAuth {
+    auth: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Clone)]
+enum RegistryAuth {
+    Basic,
+    Bearer(String),
+}
+
+/// A client for the OCI distribution API, scoped to a single repository.
+/// Credentials are only loaded for the registry of the repository, from the
+/// `MOON_OCI_<HOST>_USERNAME` and `MOON_OCI_<HOST>_PASSWORD` environment variables,
+/// or the Docker `config.json`, otherwise requests are anonymous.
+pub struct RegistryClient {
+    auth: RwLock<Option<RegistryAuth>>,
+    client: Client,
+    credentials: Option<(String, String)>,
+    pub reference: ImageReference,
+}
+
+impl RegistryClient {
+    pub fn new(reference: ImageReference) -> Self {
+        Self {
+            auth: RwLock::new(None),
+            client: Client::new(),
+            credentials: load_credentials(&reference.registry),
+            reference,
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_manifest(&self, reference: &str) -> miette::Result<Vec<u8>> {
+        let url = format!("{}/manifests/{reference}", self.reference.get_api_url());
+        let accept = [
+            MEDIA_TYPE_IMAGE_INDEX,
+            MEDIA_TYPE_IMAGE_MANIFEST,
+            MEDIA_TYPE_DOCKER_MANIFEST_LIST,
+            MEDIA_TYPE_DOCKER_MANIFEST,
+        ]
+        .join(", ");
+
+        let response = self
+            .send(&url, |client| {
+                client.get(&url).header(ACCEPT, accept.as_str())
+            })
+            .await?;
+
+        self.read_bytes(&url, response, StatusCode::OK).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_blob(&self, digest: &str) -> miette::Result<Vec<u8>> {
+        let url = format!("{}/blobs/{digest}", self.reference.get_api_url());
+        let response = self.send(&url, |client| client.get(&url)).await?;
+
+        self.read_bytes(&url, response, StatusCode::OK).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn has_blob(&self, digest: &str) -> miette::Result<bool> {
+        let url = format!("{}/blobs/{digest}", self.reference.get_api_url());
+        let response = self.send(&url, |client| client.head(&url)).await?;
+
+        Ok(response.status() == StatusCode::OK)
+    }
+
+    /// Push the blob at the provided path to the registry. The file is
+    /// streamed in the request body, instead of being read into memory.
+    #[instrument(skip(self))]
+    pub async fn push_blob(&self, digest: &str, path: &Path) -> miette::Result<()> {
+        if self.has_blob(digest).await? {
+            trace!(digest, "Blob already exists in registry, skipping upload");
+
+            return Ok(());
+        }
+
+        let map_read_error = |error: std::io::Error| OciError::BlobReadFailed {
+            path: path.to_path_buf(),
+            error: Box::new(error),
+        };
+        let file = tokio::fs::File::open(path).await.map_err(map_read_error)?;
+        let size = file.metadata().await.map_err(map_read_error)?.len();
+
+        debug!(digest, size, "Uploading blob to registry");
+
+        // Start an upload session, and then complete it in a single request
+        let url = format!("{}/blobs/uploads/", self.reference.get_api_url());
+        let response = self.send(&url, |client| client.post(&url)).await?;
+
+        if response.status() != StatusCode::ACCEPTED {
+            return Err(OciError::UnexpectedStatus {
+                url,
+                status: response.status().as_u16(),
+            }
+            .into());
+        }
+
+        let location = get_location(&url, response.headers())?;
+        let url = format!(
+            "{location}{}digest={digest}",
+            if location.contains('?') { "&" } else { "?" }
+        );
+
+        // A streamed body can't be sent again, but the upload session
+        // request above has already authenticated with the registry
+        let response = self
+            .authorize(
+                self.client
+                    .put(&url)
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .header(CONTENT_LENGTH, size)
+                    .body(Body::wrap_stream(FramedRead::new(file, BytesCodec::new()))),
+            )
+            .await
+            .send()
+            .await
+            .map_err(|error| map_request_error(&url, error))?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(self.create_auth_error());
+        }
+
+        self.read_bytes(&url, response, StatusCode::CREATED)
+            .await
+            .map(|_| ())
+    }
+
+    #[instrument(skip(self, bytes))]
+    pub async fn push_manifest(
+        &self,
+        reference: &str,
+        media_type: &str,
+        bytes: &[u8],
+    ) -> miette::Result<()> {
+        let url = format!("{}/manifests/{reference}", self.reference.get_api_url());
+
+        debug!(reference, "Uploading manifest to registry");
+
+        let response = self
+            .send(&url, |client| {
+                client
+                    .put(&url)
+                    .header(CONTENT_TYPE, media_type)
+                    .body(bytes.to_vec())
+            })
+            .await?;
+
+        self.read_bytes(&url, response, StatusCode::CREATED)
+            .await
+            .map(|_| ())
+    }
+
+    async fn read_bytes(
+        &self,
+        url: &str,
+        response: Response,
+        expected: StatusCode,
+    ) -> miette::Result<Vec<u8>> {
+        if response.status() != expected {
+            return Err(OciError::UnexpectedStatus {
+                url: url.to_owned(),
+                status: response.status().as_u16(),
+            }
+            .into());
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|error| map_request_error(url, error))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    // Send the request, and if the registry requires authentication,
+    // authenticate based on the challenge and send the request again
+    async fn send(
+        &self,
+        url: &str,
+        build: impl Fn(&Client) -> RequestBuilder,
+    ) -> miette::Result<Response> {
+        let response = self.authorize(build(&self.client)).await.send().await;
+        let response = response.map_err(|error| map_request_error(url, error))?;
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let Some(challenge) = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return Err(self.create_auth_error());
+        };
+
+        self.authenticate(challenge).await?;
+
+        let response = self.authorize(build(&self.client)).await.send().await;
+        let response = response.map_err(|error| map_request_error(url, error))?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(self.create_auth_error());
+        }
+
+        Ok(response)
+    }
+
+    async fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match (&*self.auth.read().await, &self.credentials) {
+            (Some(RegistryAuth::Bearer(token)), _) => request.bearer_auth(token),
+            (Some(RegistryAuth::Basic), Some((user, pass))) => request.basic_auth(user, Some(pass)),
+            _ => request,
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn authenticate(&self, challenge: &str) -> miette::Result<()> {
+        let Some(params) = challenge.strip_prefix("Bearer ") else {
+            if challenge.starts_with("Basic") && self.credentials.is_some() {
+                *self.auth.write().await = Some(RegistryAuth::Basic);
+
+                return Ok(());
+            }
+
+            return Err(self.create_auth_error());
+        };
+
+        let params = parse_challenge_params(params);
+        let Some(realm) = params.iter().find(|(key, _)| key == "realm") else {
+            return Err(self.create_auth_error());
+        };
+
+        let mut query = vec![];
+
+        for (key, value) in &params {
+            if key == "service" || key == "scope" {
+                query.push((key.as_str(), value.as_str()));
+            }
+        }
+
+        // Pushing requires write access, which is not always in the challenge scope
+        let scope = format!("repository:{}:pull,push", self.reference.repository);
+
+        if self.credentials.is_some() && !query.iter().any(|(key, _)| *key == "scope") {
+            query.push(("scope", scope.as_str()));
+        }
+
+        debug!(
+            registry = &self.reference.registry,
+            realm = &realm.1,
+            "Requesting registry token"
+        );
+
+        let mut request = self.client.get(&realm.1).query(&query);
+
+        if let Some((user, pass)) = &self.credentials {
+            request = request.basic_auth(user, Some(pass));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|error| map_request_error(&realm.1, error))?;
+
+        if response.status() != StatusCode::OK {
+            return Err(self.create_auth_error());
+        }
+
+        let data: TokenResponse = response
+            .json()
+            .await
+            .map_err(|error| map_request_error(&realm.1, error))?;
+
+        match data.token.or(data.access_token) {
+            Some(token) => {
+                *self.auth.write().await = Some(RegistryAuth::Bearer(token));
+
+                Ok(())
+            }
+            None => Err(self.create_auth_error()),
+        }
+    }
+
+    fn create_auth_error(&self) -> miette::Report {
+        OciError::AuthFailed {
+            registry: self.reference.registry.clone(),
+        }
+        .into()
+    }
+}
+
+/// Return the environment variable prefix for a registry host, for example,
+/// `ghcr.io` becomes `MOON_OCI_GHCR_IO`.
+pub fn get_credentials_env_prefix(registry: &str) -> String {
+    let host = registry
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    format!("MOON_OCI_{host}")
+}
+
+fn load_credentials(registry: &str) -> Option<(String, String)> {
+    let prefix = get_credentials_env_prefix(registry);
+
+    if let (Ok(user), Ok(pass)) = (
+        env::var(format!("{prefix}_USERNAME")),
+        env::var(format!("{prefix}_PASSWORD")),
+    ) {
+        if !user.is_empty() {
+            return Some((user, pass));
+        }
+    }
+
+    let config_path = match env::var_os("DOCKER_CONFIG") {
+        Some(dir) => PathBuf::from(dir).join("config.json"),
+        None => dirs::home_dir()?.join(".docker").join("config.json"),
+    };
+
+    if !config_path.exists() {
+        return None;
+    }
+
+    let content = fs::read_file(&config_path).ok()?;
+
+    find_docker_config_credentials(&content, registry)
+}
+
+// Keys in the `auths` map may include a scheme and path, and Docker Hub
+// is keyed by its legacy index URL
+fn normalize_registry_host(value: &str) -> &str {
+    let host = value
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = host.split('/').next().unwrap_or(host);
+
+    match host {
+        "index.docker.io" | "registry-1.docker.io" => DEFAULT_REGISTRY,
+        _ => host,
+    }
+}
+
+fn find_docker_config_credentials(content: &str, registry: &str) -> Option<(String, String)> {
+    let config: DockerConfig = json::parse(content).ok()?;
+    let registry = normalize_registry_host(registry);
+
+    let auth = config
+        .auths
+        .into_iter()
+        .find(|(key, _)| normalize_registry_host(key) == registry)
+        .map(|(_, auth)| auth)?;
+
+    if let (Some(user), Some(pass)) = (auth.username, auth.password) {
+        return Some((user, pass));
+    }
+
+    let decoded = BASE64_STANDARD.decode(auth.auth?).ok()?;
+
+    String::from_utf8(decoded)
+        .ok()?
+        .split_once(':')
+        .map(|(user, pass)| (user.to_owned(), pass.to_owned()))
+}
+
+fn map_request_error(url: &str, error: reqwest::Error) -> miette::Report {
+    OciError::RequestFailed {
+        url: url.to_owned(),
+        error: Box::new(error),
+    }
+    .into()
+}
+
+// The location of an upload may be relative to the registry host
+fn get_location(url: &str, headers: &HeaderMap) -> miette::Result<String> {
+    let Some(location) = headers.get(LOCATION).and_then(|value| value.to_str().ok()) else {
+        return Err(OciError::UnexpectedStatus {
+            url: url.to_owned(),
+            status: StatusCode::ACCEPTED.as_u16(),
+        }
+        .into());
+    };
+
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return Ok(location.to_owned());
+    }
+
+    let origin_end = url
+        .find("://")
+        .and_then(|index| url[index + 3..].find('/').map(|end| index + 3 + end))
+        .unwrap_or(url.len());
+
+    Ok(format!("{}{location}", &url[0..origin_end]))
+}
+
+// Parse `key="value",key="value"` pairs from an authenticate challenge
+fn parse_challenge_params(value: &str) -> Vec<(String, String)> {
+    let mut params = vec![];
+    let mut rest = value.trim();
+
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_owned();
+
+        let (value, remaining) = if let Some(quoted) = after.strip_prefix('"') {
+            match quoted.split_once('"') {
+                Some((value, remaining)) => (value.to_owned(), remaining),
+                None => (quoted.to_owned(), ""),
+            }
+        } else {
+            match after.split_once(',') {
+                Some((value, remaining)) => (value.to_owned(), remaining),
+                None => (after.to_owned(), ""),
+            }
+        };
+
+        params.push((key, value));
+        rest = remaining.trim_start_matches(',').trim();
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_challenge() {
+        assert_eq!(
+            parse_challenge_params(
+                r#"realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:library/node:pull""#
+            ),
+            vec![
+                ("realm".into(), "https://auth.docker.io/token".into()),
+                ("service".into(), "registry.docker.io".into()),
+                ("scope".into(), "repository:library/node:pull".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn creates_env_prefix_per_host() {
+        assert_eq!(get_credentials_env_prefix("ghcr.io"), "MOON_OCI_GHCR_IO");
+        assert_eq!(
+            get_credentials_env_prefix("localhost:5000"),
+            "MOON_OCI_LOCALHOST_5000"
+        );
+    }
+
+    #[test]
+    fn finds_docker_config_credentials_for_registry() {
+        let content = r#"{
+            "auths": {
+                "https://index.docker.io/v1/": { "auth": "aHViOnNlY3JldA==" },
+                "ghcr.io": { "username": "gh", "password": "token" }
+            }
+        }"#;
+
+        assert_eq!(
+            find_docker_config_credentials(content, "docker.io"),
+            Some(("hub".into(), "secret".into()))
+        );
+        assert_eq!(
+            find_docker_config_credentials(content, "ghcr.io"),
+            Some(("gh".into(), "token".into()))
+        );
+        assert_eq!(find_docker_config_credentials(content, "quay.io"), None);
+    }
+
+    #[test]
+    fn resolves_relative_locations() {
+        let mut headers = HeaderMap::new();
+        headers.insert(LOCATION, "/v2/app/blobs/uploads/123".parse().unwrap());
+
+        assert_eq!(
+            get_location("http://localhost:5000/v2/app/blobs/uploads/", &headers).unwrap(),
+            "http://localhost:5000/v2/app/blobs/uploads/123"
+        );
+    }
+}
@@ -0,0 +1,333 @@
+mod utils;
+
+use moon_config::TaskImageConfig;
+use moon_oci::*;
+use serde_json::Value;
+use starbase_sandbox::create_empty_sandbox;
+use std::collections::BTreeMap;
+use std::path::Path;
+use utils::LocalRegistry;
+
+fn create_config(base: String) -> TaskImageConfig {
+    TaskImageConfig {
+        base,
+        file: "image.tar".try_into().unwrap(),
+        platform: "linux/amd64".into(),
+        workdir: "/app".into(),
+        ..Default::default()
+    }
+}
+
+fn read_archive(path: &Path) -> BTreeMap<String, Vec<u8>> {
+    let mut archive = tar::Archive::new(std::fs::File::open(path).unwrap());
+    let mut entries = BTreeMap::new();
+
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let mut bytes = vec![];
+
+        std::io::Read::read_to_end(&mut entry, &mut bytes).unwrap();
+
+        entries.insert(entry.path().unwrap().to_string_lossy().to_string(), bytes);
+    }
+
+    entries
+}
+
+fn read_json(path: &Path) -> Value {
+    serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+}
+
+mod oci_image_builder {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_image_layout_archive() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "console.log('app');");
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let config = create_config(base);
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        let image = builder
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+        let entries = read_archive(&sandbox.path().join("image.tar"));
+
+        assert!(entries.contains_key("oci-layout"));
+        assert!(entries.contains_key(&format!("blobs/sha256/{}", image.manifest.get_hash())));
+        assert!(entries.contains_key(&format!("blobs/sha256/{}", image.config.get_hash())));
+
+        for layer in &image.layers {
+            assert!(entries.contains_key(&format!("blobs/sha256/{}", layer.get_hash())));
+        }
+
+        let index: ImageIndex = serde_json::from_slice(&entries["index.json"]).unwrap();
+
+        assert_eq!(index.manifests[0].digest, image.manifest.digest);
+    }
+
+    #[tokio::test]
+    async fn converts_base_media_types() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "");
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let config = create_config(base);
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        let image = builder
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+
+        assert_eq!(image.layers.len(), 2);
+        assert!(image
+            .layers
+            .iter()
+            .all(|layer| layer.media_type == MEDIA_TYPE_IMAGE_LAYER));
+        assert_eq!(image.config.media_type, MEDIA_TYPE_IMAGE_CONFIG);
+    }
+
+    #[tokio::test]
+    async fn is_deterministic() {
+        let registry = LocalRegistry::start();
+        let mut digests = vec![];
+
+        for _ in 0..2 {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("dist/index.js", "console.log('app');");
+
+            let (base, _) = registry.add_base_index("node", sandbox.path());
+            let config = create_config(base);
+
+            let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+            builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+            digests.push(
+                builder
+                    .build(&sandbox.path().join("image.tar"))
+                    .await
+                    .unwrap()
+                    .manifest
+                    .digest,
+            );
+        }
+
+        assert_eq!(digests[0], digests[1]);
+    }
+
+    #[tokio::test]
+    async fn merges_base_config() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "");
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let mut config = create_config(base);
+        config.entrypoint = vec!["node".into()];
+        config.cmd = vec!["dist/index.js".into()];
+        config.env = BTreeMap::from_iter([
+            ("LANG".into(), "en_US.UTF-8".into()),
+            ("NODE_ENV".into(), "production".into()),
+        ]);
+        config.labels = BTreeMap::from_iter([("team".into(), "web".into())]);
+        config.user = Some("1000".into());
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        let image = builder
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+        let data = read_json(&builder.get_blob_path(&image.config));
+
+        assert_eq!(
+            data["config"]["Env"],
+            serde_json::json!([
+                "PATH=/usr/local/bin:/usr/bin",
+                "LANG=en_US.UTF-8",
+                "NODE_ENV=production"
+            ])
+        );
+        assert_eq!(data["config"]["Entrypoint"], serde_json::json!(["node"]));
+        assert_eq!(data["config"]["Cmd"], serde_json::json!(["dist/index.js"]));
+        assert_eq!(data["config"]["WorkingDir"], "/app");
+        assert_eq!(data["config"]["User"], "1000");
+        assert_eq!(data["config"]["Labels"]["team"], "web");
+        assert_eq!(data["created"], "1970-01-01T00:00:00Z");
+        assert_eq!(data["rootfs"]["diff_ids"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn selects_platform_from_index() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "");
+
+        let registry = LocalRegistry::start();
+        let (base, manifests) = registry.add_base_index("node", sandbox.path());
+        let mut config = create_config(base);
+        config.platform = "linux/arm64".into();
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        let image = builder
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+        let manifest: ImageManifest = serde_json::from_slice(&image.manifest_bytes).unwrap();
+
+        assert_eq!(
+            manifest.annotations[ANNOTATION_BASE_DIGEST],
+            manifests[1].digest
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "does not support the")]
+    async fn errors_for_unsupported_platform() {
+        let sandbox = create_empty_sandbox();
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let mut config = create_config(base);
+        config.platform = "linux/s390x".into();
+
+        OciImageBuilder::new(&config, &sandbox.path().join(".cache"))
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "must be pinned by digest")]
+    async fn errors_without_base_digest() {
+        let sandbox = create_empty_sandbox();
+        let config = create_config("node:20".into());
+
+        OciImageBuilder::new(&config, &sandbox.path().join(".cache"))
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn caches_base_blobs() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "");
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let config = create_config(base);
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        builder.build(&sandbox.path().join("a.tar")).await.unwrap();
+
+        let pulls = registry.get_pull_count();
+
+        // index, manifest, config, layer
+        assert_eq!(pulls, 4);
+
+        builder.build(&sandbox.path().join("b.tar")).await.unwrap();
+
+        assert_eq!(registry.get_pull_count(), pulls);
+        assert_eq!(
+            std::fs::read(sandbox.path().join("a.tar")).unwrap(),
+            std::fs::read(sandbox.path().join("b.tar")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn pushes_to_registry() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "");
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let mut config = create_config(base);
+        config.push = Some(format!("{}/app:1.0.0", registry.host));
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        let image = builder
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+
+        builder
+            .push(&image, config.push.as_ref().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            registry.get_manifest("app", "1.0.0").unwrap(),
+            image.manifest_bytes
+        );
+        assert!(registry.has_blob(&image.config.digest));
+
+        for layer in &image.layers {
+            assert!(registry.has_blob(&layer.digest));
+        }
+
+        // Tagged in the layout for loading
+        let entries = read_archive(&sandbox.path().join("image.tar"));
+        let index: ImageIndex = serde_json::from_slice(&entries["index.json"]).unwrap();
+
+        assert_eq!(index.manifests[0].annotations[ANNOTATION_REF_NAME], "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn pushes_loaded_archive_to_registry() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "");
+
+        let registry = LocalRegistry::start();
+        let (base, _) = registry.add_base_index("node", sandbox.path());
+        let mut config = create_config(base);
+        config.push = Some(format!("{}/app:1.0.0", registry.host));
+
+        let mut builder = OciImageBuilder::new(&config, &sandbox.path().join(".cache"));
+        builder.add_file("dist/index.js", sandbox.path().join("dist/index.js"));
+
+        let image = builder
+            .build(&sandbox.path().join("image.tar"))
+            .await
+            .unwrap();
+
+        // Load with an empty cache, like an archive hydrated from the remote cache
+        let builder = OciImageBuilder::new(&config, &sandbox.path().join(".other-cache"));
+        let loaded = builder.load(&sandbox.path().join("image.tar")).unwrap();
+
+        assert_eq!(loaded.manifest.digest, image.manifest.digest);
+        assert_eq!(loaded.manifest_bytes, image.manifest_bytes);
+        assert_eq!(loaded.config, image.config);
+        assert_eq!(loaded.layers, image.layers);
+
+        builder
+            .push(&loaded, config.push.as_ref().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            registry.get_manifest("app", "1.0.0").unwrap(),
+            image.manifest_bytes
+        );
+
+        for layer in &image.layers {
+            assert!(registry.has_blob(&layer.digest));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use moon_oci::*;
+use starbase_sandbox::create_empty_sandbox;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+fn create_files(root: &Path) -> BTreeMap<String, std::path::PathBuf> {
+    BTreeMap::from_iter([
+        ("app/dist/index.js".to_owned(), root.join("dist/index.js")),
+        ("app/package.json".to_owned(), root.join("package.json")),
+    ])
+}
+
+mod image_layer {
+    use super::*;
+
+    #[test]
+    fn includes_parent_dirs_and_files() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "console.log('app');");
+        sandbox.create_file("package.json", "{}");
+
+        let layer =
+            create_image_layer(&create_files(sandbox.path()), &sandbox.path().join("blobs"))
+                .unwrap();
+        let bytes = std::fs::read(&layer.path).unwrap();
+
+        let mut tarball = vec![];
+        flate2::read::GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut tarball)
+            .unwrap();
+
+        let mut archive = tar::Archive::new(tarball.as_slice());
+        let paths = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+
+                assert_eq!(entry.header().mtime().unwrap(), 0);
+                assert_eq!(entry.header().uid().unwrap(), 0);
+
+                entry.path().unwrap().to_string_lossy().to_string()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            ["app/", "app/dist/", "app/dist/index.js", "app/package.json"]
+        );
+        assert_eq!(layer.diff_id, create_oci_digest(&tarball));
+        assert_eq!(layer.descriptor.media_type, MEDIA_TYPE_IMAGE_LAYER);
+        assert_eq!(layer.descriptor.digest, create_oci_digest(&bytes));
+        assert_eq!(layer.descriptor.size, bytes.len() as u64);
+        assert_eq!(
+            layer.path,
+            sandbox
+                .path()
+                .join("blobs")
+                .join(layer.descriptor.get_hash())
+        );
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let a = create_empty_sandbox();
+        a.create_file("dist/index.js", "console.log('app');");
+        a.create_file("package.json", "{}");
+
+        let b = create_empty_sandbox();
+        b.create_file("package.json", "{}");
+        b.create_file("dist/index.js", "console.log('app');");
+
+        assert_eq!(
+            create_image_layer(&create_files(a.path()), &a.path().join("blobs"))
+                .unwrap()
+                .descriptor,
+            create_image_layer(&create_files(b.path()), &b.path().join("blobs"))
+                .unwrap()
+                .descriptor
+        );
+    }
+
+    #[test]
+    fn changes_when_content_changes() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("dist/index.js", "console.log('app');");
+        sandbox.create_file("package.json", "{}");
+
+        let blobs_dir = sandbox.path().join("blobs");
+        let before = create_image_layer(&create_files(sandbox.path()), &blobs_dir).unwrap();
+
+        sandbox.create_file("dist/index.js", "console.log('changed');");
+
+        let after = create_image_layer(&create_files(sandbox.path()), &blobs_dir).unwrap();
+
+        assert_ne!(before.descriptor.digest, after.descriptor.digest);
+        assert_ne!(before.diff_id, after.diff_id);
+    }
+}
@@ -0,0 +1,68 @@
+use moon_oci::ImageReference;
+
+const DIGEST: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+mod image_reference {
+    use super::*;
+
+    #[test]
+    fn parses_official_image() {
+        let reference = ImageReference::parse("node").unwrap();
+
+        assert_eq!(reference.registry, "docker.io");
+        assert_eq!(reference.repository, "library/node");
+        assert_eq!(reference.tag, None);
+        assert_eq!(reference.digest, None);
+        assert_eq!(reference.get_reference(), "latest");
+        assert_eq!(
+            reference.get_api_url(),
+            "https://registry-1.docker.io/v2/library/node"
+        );
+    }
+
+    #[test]
+    fn parses_namespaced_image() {
+        let reference = ImageReference::parse("moonrepo/moon:1.0").unwrap();
+
+        assert_eq!(reference.registry, "docker.io");
+        assert_eq!(reference.repository, "moonrepo/moon");
+        assert_eq!(reference.tag.as_deref(), Some("1.0"));
+        assert_eq!(reference.get_reference(), "1.0");
+    }
+
+    #[test]
+    fn parses_custom_registry() {
+        let reference = ImageReference::parse(&format!("ghcr.io/org/app:2@{DIGEST}")).unwrap();
+
+        assert_eq!(reference.registry, "ghcr.io");
+        assert_eq!(reference.repository, "org/app");
+        assert_eq!(reference.tag.as_deref(), Some("2"));
+        assert_eq!(reference.digest.as_deref(), Some(DIGEST));
+        assert_eq!(reference.get_reference(), DIGEST);
+        assert_eq!(reference.get_api_url(), "https://ghcr.io/v2/org/app");
+        assert_eq!(reference.get_name(), "ghcr.io/org/app:2");
+        assert_eq!(reference.to_string(), format!("ghcr.io/org/app:2@{DIGEST}"));
+    }
+
+    #[test]
+    fn parses_registry_with_port() {
+        let reference = ImageReference::parse("localhost:5000/app").unwrap();
+
+        assert_eq!(reference.registry, "localhost:5000");
+        assert_eq!(reference.repository, "app");
+        assert_eq!(reference.tag, None);
+        assert_eq!(reference.get_api_url(), "http://localhost:5000/v2/app");
+    }
+
+    #[test]
+    #[should_panic(expected = "only sha256 digests are supported")]
+    fn errors_for_unsupported_digest() {
+        ImageReference::parse("node@md5:abc").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "missing image name")]
+    fn errors_for_missing_name() {
+        ImageReference::parse(&format!("@{DIGEST}")).unwrap();
+    }
+}
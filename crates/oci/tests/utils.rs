@@ -0,0 +1,242 @@
+#![allow(dead_code)]
+
+use moon_oci::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// An in-memory stand-in for an OCI distribution registry.
+#[derive(Clone, Default)]
+pub struct RegistryState {
+    pub blobs: Arc<Mutex<BTreeMap<String, Vec<u8>>>>,
+    pub manifests: Arc<Mutex<BTreeMap<String, Vec<u8>>>>,
+    pub pulls: Arc<AtomicUsize>,
+    uploads: Arc<AtomicUsize>,
+}
+
+pub struct LocalRegistry {
+    pub host: String,
+    pub state: RegistryState,
+}
+
+impl LocalRegistry {
+    pub fn start() -> Self {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let host = format!("127.0.0.1:{}", server.server_addr().to_ip().unwrap().port());
+        let state = RegistryState::default();
+        let thread_state = state.clone();
+
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                handle_request(&thread_state, request);
+            }
+        });
+
+        Self { host, state }
+    }
+
+    pub fn get_pull_count(&self) -> usize {
+        self.state.pulls.load(Ordering::SeqCst)
+    }
+
+    pub fn get_manifest(&self, repo: &str, reference: &str) -> Option<Vec<u8>> {
+        self.state
+            .manifests
+            .lock()
+            .unwrap()
+            .get(&format!("{repo}:{reference}"))
+            .cloned()
+    }
+
+    pub fn has_blob(&self, digest: &str) -> bool {
+        self.state.blobs.lock().unwrap().contains_key(digest)
+    }
+
+    pub fn add_blob(&self, bytes: Vec<u8>) -> String {
+        let digest = create_oci_digest(&bytes);
+
+        self.state
+            .blobs
+            .lock()
+            .unwrap()
+            .insert(digest.clone(), bytes);
+
+        digest
+    }
+
+    pub fn add_manifest(&self, repo: &str, bytes: Vec<u8>) -> String {
+        let digest = create_oci_digest(&bytes);
+
+        self.state
+            .manifests
+            .lock()
+            .unwrap()
+            .insert(format!("{repo}:{digest}"), bytes);
+
+        digest
+    }
+
+    /// Add a single platform image using Docker media types, and return
+    /// the descriptor of its manifest.
+    pub fn add_base_image(&self, repo: &str, root: &Path, arch: &str) -> Descriptor {
+        let layer_file = root.join(format!("base-{arch}.txt"));
+        std::fs::write(&layer_file, arch).unwrap();
+
+        let layer = create_image_layer(
+            &BTreeMap::from_iter([("etc/base.txt".to_owned(), layer_file)]),
+            &root.join(".layers"),
+        )
+        .unwrap();
+
+        self.add_blob(std::fs::read(&layer.path).unwrap());
+
+        let config = serde_json::to_vec(&json!({
+            "architecture": arch,
+            "os": "linux",
+            "config": {
+                "Env": ["PATH=/usr/local/bin:/usr/bin", "LANG=C"],
+                "Cmd": ["sh"],
+            },
+            "rootfs": {
+                "type": "layers",
+                "diff_ids": [layer.diff_id],
+            },
+        }))
+        .unwrap();
+
+        let config_digest = self.add_blob(config.clone());
+
+        let manifest = serde_json::to_vec(&json!({
+            "schemaVersion": 2,
+            "mediaType": MEDIA_TYPE_DOCKER_MANIFEST,
+            "config": {
+                "mediaType": MEDIA_TYPE_DOCKER_CONFIG,
+                "digest": config_digest,
+                "size": config.len(),
+            },
+            "layers": [{
+                "mediaType": MEDIA_TYPE_DOCKER_LAYER,
+                "digest": layer.descriptor.digest,
+                "size": layer.descriptor.size,
+            }],
+        }))
+        .unwrap();
+
+        let descriptor = Descriptor {
+            platform: Some(Platform::parse(&format!("linux/{arch}"))),
+            ..Descriptor::new(MEDIA_TYPE_DOCKER_MANIFEST, &manifest)
+        };
+
+        self.add_manifest(repo, manifest);
+
+        descriptor
+    }
+
+    /// Add a multi-platform image index, and return its reference.
+    pub fn add_base_index(&self, repo: &str, root: &Path) -> (String, Vec<Descriptor>) {
+        let manifests = vec![
+            self.add_base_image(repo, root, "amd64"),
+            self.add_base_image(repo, root, "arm64"),
+        ];
+
+        let index = serde_json::to_vec(&ImageIndex {
+            schema_version: 2,
+            media_type: Some(MEDIA_TYPE_IMAGE_INDEX.into()),
+            manifests: manifests.clone(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let digest = self.add_manifest(repo, index);
+
+        (format!("{}/{repo}@{digest}", self.host), manifests)
+    }
+}
+
+fn respond(request: Request, status: u16, body: Vec<u8>, headers: Vec<(&str, String)>) {
+    let mut response = Response::from_data(body).with_status_code(status);
+
+    for (key, value) in headers {
+        response.add_header(Header::from_bytes(key.as_bytes(), value.as_bytes()).unwrap());
+    }
+
+    let _ = request.respond(response);
+}
+
+fn handle_request(state: &RegistryState, mut request: Request) {
+    let method = request.method().clone();
+    let url = request.url().to_owned();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let Some(path) = path.strip_prefix("/v2/") else {
+        return respond(request, 404, vec![], vec![]);
+    };
+
+    let mut body = vec![];
+    request.as_reader().read_to_end(&mut body).unwrap();
+
+    if let Some((repo, reference)) = path.split_once("/manifests/") {
+        let key = format!("{repo}:{reference}");
+
+        match method {
+            Method::Get => match state.manifests.lock().unwrap().get(&key).cloned() {
+                Some(bytes) => {
+                    state.pulls.fetch_add(1, Ordering::SeqCst);
+                    respond(request, 200, bytes, vec![]);
+                }
+                None => respond(request, 404, vec![], vec![]),
+            },
+            Method::Put => {
+                let digest = create_oci_digest(&body);
+                let mut manifests = state.manifests.lock().unwrap();
+                manifests.insert(format!("{repo}:{digest}"), body.clone());
+                manifests.insert(key, body);
+                drop(manifests);
+
+                respond(request, 201, vec![], vec![]);
+            }
+            _ => respond(request, 405, vec![], vec![]),
+        }
+    } else if let Some((repo, upload)) = path.split_once("/blobs/uploads/") {
+        match method {
+            Method::Post => {
+                let id = state.uploads.fetch_add(1, Ordering::SeqCst);
+
+                respond(
+                    request,
+                    202,
+                    vec![],
+                    vec![("Location", format!("/v2/{repo}/blobs/uploads/{id}"))],
+                );
+            }
+            Method::Put if !upload.is_empty() => {
+                let digest = query.trim_start_matches("digest=").replace("%3A", ":");
+
+                if create_oci_digest(&body) != digest {
+                    return respond(request, 400, vec![], vec![]);
+                }
+
+                state.blobs.lock().unwrap().insert(digest, body);
+
+                respond(request, 201, vec![], vec![]);
+            }
+            _ => respond(request, 405, vec![], vec![]),
+        }
+    } else if let Some((_, digest)) = path.split_once("/blobs/") {
+        let blob = state.blobs.lock().unwrap().get(digest).cloned();
+
+        match (method, blob) {
+            (Method::Head, Some(_)) => respond(request, 200, vec![], vec![]),
+            (Method::Get, Some(bytes)) => {
+                state.pulls.fetch_add(1, Ordering::SeqCst);
+                respond(request, 200, bytes, vec![]);
+            }
+            _ => respond(request, 404, vec![], vec![]),
+        }
+    } else {
+        respond(request, 404, vec![], vec![]);
+    }
+}
@@ -27,6 +27,11 @@ api_struct!(
 
         /// Version of the plugin.
         pub plugin_version: String,
+
+        /// List of pipeline event types (e.g. `task.ran`) that the extension
+        /// wants to receive. If empty, all events will be received.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub pipeline_events: Vec<String>,
     }
 );
 
@@ -42,3 +47,19 @@ api_struct!(
         pub context: MoonContext,
     }
 );
+
+// PIPELINE
+
+api_struct!(
+    /// Input passed to the `on_pipeline_event` function.
+    pub struct PipelineEventInput {
+        /// Current moon context.
+        pub context: MoonContext,
+
+        /// Type of event that was emitted, e.g. `task.ran`.
+        pub event: String,
+
+        /// Data associated with the event, serialized to JSON.
+        pub payload: serde_json::Value,
+    }
+);
@@ -1,3 +1,4 @@
+use crate::command::OutputLineHandler;
 use crate::command_inspector::CommandInspector;
 use crate::output_to_error;
 use crate::process_error::ProcessError;
@@ -12,6 +13,7 @@ pub struct AsyncCommand<'cmd> {
     pub console: Option<Arc<Console>>,
     pub inner: Command,
     pub inspector: CommandInspector<'cmd>,
+    pub output_handler: Option<Arc<OutputLineHandler>>,
 
     pub current_id: Option<u32>,
 }
@@ -146,6 +148,9 @@ impl AsyncCommand<'_> {
         let stderr_prefix = Arc::clone(&prefix);
         let stdout_prefix = Arc::clone(&prefix);
 
+        let stderr_handler = self.output_handler.clone();
+        let stdout_handler = self.output_handler.clone();
+
         let console = self
             .console
             .as_ref()
@@ -158,7 +163,10 @@ impl AsyncCommand<'_> {
             let mut captured_lines = vec![];
 
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = if let Some(prefix) = &*stderr_prefix {
+                let _ = if let Some(handler) = &stderr_handler {
+                    handler(&line, true);
+                    Ok(())
+                } else if let Some(prefix) = &*stderr_prefix {
                     stderr_stream.write_line_with_prefix(&line, prefix)
                 } else {
                     stderr_stream.write_line(&line)
@@ -178,7 +186,10 @@ impl AsyncCommand<'_> {
             let mut captured_lines = vec![];
 
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = if let Some(prefix) = &*stdout_prefix {
+                let _ = if let Some(handler) = &stdout_handler {
+                    handler(&line, false);
+                    Ok(())
+                } else if let Some(prefix) = &*stdout_prefix {
                     stdout_stream.write_line_with_prefix(&line, prefix)
                 } else {
                     stdout_stream.write_line(&line)
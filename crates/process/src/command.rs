@@ -9,6 +9,8 @@ use std::{
 };
 use tokio::process::Command as TokioCommand;
 
+pub type OutputLineHandler = dyn Fn(&str, bool) + Send + Sync;
+
 pub struct Command {
     pub args: Vec<OsString>,
 
@@ -27,6 +29,9 @@ pub struct Command {
     /// Values to pass to stdin
     pub input: Vec<OsString>,
 
+    /// Handler to pass streamed output lines to, instead of the console
+    pub output_handler: Option<Arc<OutputLineHandler>>,
+
     /// Prefix to prepend to all log lines
     pub prefix: Option<String>,
 
@@ -50,6 +55,7 @@ impl Command {
             error_on_nonzero: true,
             escape_args: true,
             input: vec![],
+            output_handler: None,
             prefix: None,
             print_command: false,
             shell: Some(Shell::default()),
@@ -107,6 +113,7 @@ impl Command {
             console: self.console.clone(),
             inner: command,
             inspector,
+            output_handler: self.output_handler.clone(),
             current_id: None,
         }
     }
@@ -187,6 +194,14 @@ impl Command {
         self
     }
 
+    pub fn set_output_handler(
+        &mut self,
+        handler: impl Fn(&str, bool) + Send + Sync + 'static,
+    ) -> &mut Command {
+        self.output_handler = Some(Arc::new(handler));
+        self
+    }
+
     pub fn set_prefix(&mut self, prefix: &str) -> &mut Command {
         self.prefix = Some(prefix.to_owned());
         self
@@ -1,4 +1,4 @@
-use moon_common::path::WorkspaceRelativePath;
+use moon_common::path::{WorkspaceRelativePath, WorkspaceRelativePathBuf};
 use moon_common::{color, Id};
 use moon_config::{
     ConfigLoader, DependencyConfig, DependencyScope, DependencySource, InheritedTasksManager,
@@ -12,8 +12,8 @@ use moon_task_builder::{TasksBuilder, TasksBuilderContext};
 use moon_toolchain::detect::{
     detect_project_language, detect_project_toolchains, get_project_toolchains,
 };
-use rustc_hash::FxHashMap;
-use std::collections::BTreeMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::{btree_map::Entry, BTreeMap};
 use std::path::{Path, PathBuf};
 use tracing::{debug, instrument, trace};
 
@@ -32,6 +32,10 @@ pub struct ProjectBuilder<'app> {
     // Configs to derive information from
     global_config: Option<InheritedTasksResult>,
     local_config: Option<ProjectConfig>,
+    local_config_file: Option<WorkspaceRelativePathBuf>,
+
+    // Tasks implicitly derived from the project graph
+    implicit_task_ids: FxHashSet<Id>,
 
     // Values to be continually built
     id: &'app Id,
@@ -71,6 +75,8 @@ impl<'app> ProjectBuilder<'app> {
             alias: None,
             global_config: None,
             local_config: None,
+            local_config_file: None,
+            implicit_task_ids: FxHashSet::default(),
             language: LanguageType::Unknown,
             toolchains_tasks: vec![],
             toolchains_config: vec![],
@@ -176,6 +182,14 @@ impl<'app> ProjectBuilder<'app> {
     pub async fn load_local_config(&mut self) -> miette::Result<()> {
         let config = self.context.config_loader.load_project_config(&self.root)?;
 
+        self.local_config_file = self
+            .context
+            .config_loader
+            .get_project_file_names()
+            .into_iter()
+            .find(|name| self.root.join(name).exists())
+            .map(|name| self.source.join(name));
+
         self.inherit_local_config(&config).await?;
 
         Ok(())
@@ -206,7 +220,10 @@ impl<'app> ProjectBuilder<'app> {
             .as_mut()
             .expect("Local config must be loaded before extending tasks!");
 
-        local_config.tasks.entry(id).or_insert(config);
+        if let Entry::Vacant(entry) = local_config.tasks.entry(id) {
+            self.implicit_task_ids.insert(entry.key().to_owned());
+            entry.insert(config);
+        }
 
         self
     }
@@ -392,7 +409,7 @@ impl<'app> ProjectBuilder<'app> {
 
         if let Some(global_config) = &self.global_config {
             tasks_builder.inherit_global_tasks(
-                &global_config.config,
+                global_config,
                 self.local_config
                     .as_ref()
                     .map(|cfg| &cfg.workspace.inherited_tasks),
@@ -400,9 +417,25 @@ impl<'app> ProjectBuilder<'app> {
         }
 
         if let Some(local_config) = &self.local_config {
-            tasks_builder.load_local_tasks(local_config);
+            tasks_builder.load_local_tasks(local_config, self.local_config_file.as_deref());
+        }
+
+        let mut tasks = tasks_builder.build().await?;
+
+        // Implicit tasks were not defined in the project config,
+        // so fallback to the inherited config they may extend
+        for id in &self.implicit_task_ids {
+            if let Some(task) = tasks.get_mut(id) {
+                task.state.config_file = self.global_config.as_ref().and_then(|global_config| {
+                    global_config
+                        .task_layers
+                        .get(id.as_str())
+                        .and_then(|layers| layers.last())
+                        .map(WorkspaceRelativePathBuf::from)
+                });
+            }
         }
 
-        tasks_builder.build().await
+        Ok(tasks)
     }
 }
@@ -0,0 +1,222 @@
+use crate::ProjectConstraintsError;
+use moon_common::Id;
+use moon_config::ConstraintsDependencyVersionsConfig;
+use moon_node_lang::PackageJsonCache;
+use moon_project::Project;
+use moon_python_lang::load_lockfile_dependencies;
+use moon_rust_lang::cargo_toml::{CargoTomlCache, Dependency, DepsSet};
+use rustc_hash::FxHashSet;
+use std::collections::BTreeMap;
+use tracing::{debug, instrument};
+
+const NPM_PROTOCOLS: [&str; 10] = [
+    "catalog:",
+    "file:",
+    "git+",
+    "git:",
+    "http:",
+    "https:",
+    "link:",
+    "npm:",
+    "portal:",
+    "workspace:",
+];
+
+/// An external dependency that has been declared with multiple versions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DependencyVersionViolation {
+    /// Manifest file name the dependency was declared in.
+    pub manifest: &'static str,
+
+    /// Name of the external dependency.
+    pub name: String,
+
+    /// A mapping of declared versions, to the projects that declared them.
+    pub versions: BTreeMap<String, Vec<Id>>,
+}
+
+type DeclaredVersions = BTreeMap<(&'static str, String), BTreeMap<String, Vec<Id>>>;
+
+/// Check that each external dependency is declared with the same version across
+/// all projects, by loading each project's `package.json`, `Cargo.toml`, and
+/// `requirements.txt`. Returns a violation for each inconsistent dependency.
+#[instrument(skip_all)]
+pub fn check_dependency_versions<'app>(
+    config: &ConstraintsDependencyVersionsConfig,
+    projects: impl IntoIterator<Item = &'app Project>,
+) -> miette::Result<Vec<DependencyVersionViolation>> {
+    let projects = projects.into_iter().collect::<Vec<_>>();
+    let mut declared = DeclaredVersions::default();
+
+    // Dependencies on other projects within the workspace are not external
+    let internal = projects
+        .iter()
+        .flat_map(|project| [Some(project.id.as_str()), project.alias.as_deref()])
+        .flatten()
+        .collect::<FxHashSet<_>>();
+
+    for project in &projects {
+        let mut declare = |manifest: &'static str, name: &str, version: &str| {
+            if internal.contains(name) {
+                return;
+            }
+
+            let projects = declared
+                .entry((manifest, name.to_owned()))
+                .or_default()
+                .entry(version.trim().to_owned())
+                .or_default();
+
+            if !projects.contains(&project.id) {
+                projects.push(project.id.clone());
+            }
+        };
+
+        if let Some(package_json) = PackageJsonCache::read(&project.root)? {
+            for deps in [
+                &package_json.data.dependencies,
+                &package_json.data.dev_dependencies,
+                &package_json.data.optional_dependencies,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                for (name, version) in deps {
+                    if !NPM_PROTOCOLS
+                        .iter()
+                        .any(|protocol| version.starts_with(protocol))
+                    {
+                        declare("package.json", name, version);
+                    }
+                }
+            }
+        }
+
+        if let Some(cargo_toml) = CargoTomlCache::read(&project.root)? {
+            for deps in [
+                &cargo_toml.dependencies,
+                &cargo_toml.dev_dependencies,
+                &cargo_toml.build_dependencies,
+            ] {
+                for (name, version) in extract_cargo_versions(deps) {
+                    declare("Cargo.toml", name, version);
+                }
+            }
+        }
+
+        let requirements_path = project.root.join("requirements.txt");
+
+        if requirements_path.exists() {
+            for (name, lines) in load_lockfile_dependencies(requirements_path)? {
+                for line in lines {
+                    if let Some(version) = extract_pip_version(&line, &name) {
+                        declare("requirements.txt", &normalize_pip_name(&name), version);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut violations = vec![];
+
+    for ((manifest, name), mut versions) in declared {
+        let exceptions = if manifest == "requirements.txt" {
+            config
+                .exceptions
+                .iter()
+                .find(|(key, _)| normalize_pip_name(key) == name)
+                .map(|(_, exceptions)| exceptions)
+        } else {
+            config.exceptions.get(&name)
+        };
+
+        if let Some(exceptions) = exceptions {
+            if exceptions.is_empty() {
+                continue;
+            }
+
+            versions.retain(|version, _| !exceptions.contains(version));
+        }
+
+        if versions.len() > 1 {
+            violations.push(DependencyVersionViolation {
+                manifest,
+                name,
+                versions,
+            });
+        }
+    }
+
+    debug!(
+        "Found {} external dependencies with inconsistent versions",
+        violations.len()
+    );
+
+    Ok(violations)
+}
+
+/// Check that each external dependency is declared with the same version
+/// across all projects, and error if any are inconsistent.
+pub fn enforce_dependency_versions<'app>(
+    config: &ConstraintsDependencyVersionsConfig,
+    projects: impl IntoIterator<Item = &'app Project>,
+) -> miette::Result<()> {
+    let violations = check_dependency_versions(config, projects)?;
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    Err(ProjectConstraintsError::InconsistentDependencyVersions { violations }.into())
+}
+
+// Path, Git, and workspace inherited dependencies are not versioned
+fn extract_cargo_versions(deps: &DepsSet) -> Vec<(&str, &str)> {
+    deps.iter()
+        .filter_map(|(name, dep)| match dep {
+            Dependency::Simple(version) => Some((name.as_str(), version.as_str())),
+            Dependency::Detailed(detail) if detail.path.is_none() && detail.git.is_none() => detail
+                .version
+                .as_deref()
+                .map(|version| (detail.package.as_deref().unwrap_or(name.as_str()), version)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Normalize a package name as per PEP 503, as pip names are case-insensitive,
+// and runs of `-`, `_`, and `.` are equivalent
+fn normalize_pip_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+
+    for ch in name.chars() {
+        if matches!(ch, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(ch.to_ascii_lowercase());
+        }
+    }
+
+    normalized
+}
+
+// Strip the name and extras from a requirement, for example
+// `requests[socks]>=2.31` becomes `>=2.31`. Returns `None` if the
+// line does not start with the name.
+fn extract_pip_version<'line>(line: &'line str, name: &str) -> Option<&'line str> {
+    let line = line.trim();
+    let prefix = line.get(0..name.len())?;
+
+    if !prefix.eq_ignore_ascii_case(name) {
+        return None;
+    }
+
+    let version = line[name.len()..].trim_start();
+
+    Some(match version.strip_prefix('[') {
+        Some(rest) => rest.split_once(']').map(|(_, rest)| rest).unwrap_or(rest),
+        None => version,
+    })
+}
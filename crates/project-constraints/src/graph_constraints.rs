@@ -0,0 +1,296 @@
+use crate::ProjectConstraintsError;
+use moon_common::Id;
+use moon_config::{ConstraintsConfig, DependencyScope};
+use moon_project::Project;
+use rustc_hash::{FxHashMap, FxHashSet};
+use starbase_utils::glob::GlobSet;
+use std::collections::{BTreeMap, VecDeque};
+
+/// A constraint that was violated, and the dependency path that violated it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectConstraintViolation {
+    pub message: String,
+    pub path: Vec<Id>,
+}
+
+impl ProjectConstraintViolation {
+    pub fn get_path(&self) -> String {
+        self.path
+            .iter()
+            .map(|id| id.as_str())
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+}
+
+enum ProjectMatcher<'glob> {
+    Glob(GlobSet<'glob>),
+    Tag(Id),
+}
+
+impl<'glob> ProjectMatcher<'glob> {
+    fn new(pattern: &'glob str) -> miette::Result<Self> {
+        Ok(match pattern.strip_prefix('#') {
+            Some(tag) => Self::Tag(Id::new(tag)?),
+            None => Self::Glob(GlobSet::new([pattern])?),
+        })
+    }
+
+    fn matches(&self, project: &Project) -> bool {
+        match self {
+            Self::Glob(glob) => {
+                glob.is_match(project.id.as_str()) || glob.is_match(project.source.as_str())
+            }
+            Self::Tag(tag) => project.config.tags.contains(tag),
+        }
+    }
+}
+
+/// Enforces constraints that span the entire dependency graph: layers,
+/// deny rules, dependency limits, and denied stacks. Unlike the other
+/// constraints, all violations are collected and reported together.
+pub struct ProjectGraphConstraints<'app> {
+    config: &'app ConstraintsConfig,
+    dependencies: FxHashMap<&'app Id, Vec<&'app Id>>,
+    projects: BTreeMap<&'app Id, &'app Project>,
+}
+
+impl<'app> ProjectGraphConstraints<'app> {
+    pub fn new(
+        config: &'app ConstraintsConfig,
+        projects: impl IntoIterator<Item = &'app Project>,
+    ) -> Self {
+        let projects = projects
+            .into_iter()
+            .map(|project| (&project.id, project))
+            .collect::<BTreeMap<_, _>>();
+
+        // Build and root dependencies are implicitly applied by moon,
+        // so don't take them into account when enforcing constraints.
+        let dependencies = projects
+            .values()
+            .map(|project| {
+                let mut deps = project
+                    .dependencies
+                    .iter()
+                    .filter(|dep_config| {
+                        !matches!(
+                            dep_config.scope,
+                            DependencyScope::Build | DependencyScope::Root
+                        ) && projects.contains_key(&dep_config.id)
+                    })
+                    .map(|dep_config| &dep_config.id)
+                    .collect::<Vec<_>>();
+
+                deps.sort();
+                deps.dedup();
+
+                (&project.id, deps)
+            })
+            .collect();
+
+        Self {
+            config,
+            dependencies,
+            projects,
+        }
+    }
+
+    /// Check all constraints and return every violation.
+    pub fn check(&self) -> miette::Result<Vec<ProjectConstraintViolation>> {
+        let mut violations = vec![];
+
+        self.check_layers(&mut violations);
+        self.check_deny_rules(&mut violations)?;
+        self.check_dependency_limits(&mut violations);
+        self.check_denied_stacks(&mut violations);
+
+        Ok(violations)
+    }
+
+    /// Check all constraints and error if any were violated.
+    pub fn enforce(&self) -> miette::Result<()> {
+        let violations = self.check()?;
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        Err(ProjectConstraintsError::GraphConstraintsViolated { violations }.into())
+    }
+
+    fn get_dependencies(&self, id: &Id) -> &[&'app Id] {
+        self.dependencies
+            .get(id)
+            .map(|deps| deps.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn get_layer(&self, project: &Project) -> Option<usize> {
+        self.config
+            .layers
+            .iter()
+            .position(|tag| project.config.tags.contains(tag))
+    }
+
+    fn check_layers(&self, violations: &mut Vec<ProjectConstraintViolation>) {
+        if self.config.layers.is_empty() {
+            return;
+        }
+
+        for (id, project) in &self.projects {
+            let Some(layer) = self.get_layer(project) else {
+                continue;
+            };
+
+            for dep_id in self.get_dependencies(id) {
+                let Some(dep_layer) = self.get_layer(self.projects[dep_id]) else {
+                    continue;
+                };
+
+                if dep_layer < layer {
+                    violations.push(ProjectConstraintViolation {
+                        message: format!(
+                            "Project {id} in layer #{} cannot depend on project {dep_id} in the higher layer #{}.",
+                            self.config.layers[layer], self.config.layers[dep_layer]
+                        ),
+                        path: vec![(*id).to_owned(), (*dep_id).to_owned()],
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_deny_rules(
+        &self,
+        violations: &mut Vec<ProjectConstraintViolation>,
+    ) -> miette::Result<()> {
+        for rule in &self.config.deny {
+            let from = ProjectMatcher::new(&rule.from)?;
+            let to = ProjectMatcher::new(&rule.to)?;
+
+            for (id, project) in &self.projects {
+                if !from.matches(project) {
+                    continue;
+                }
+
+                for path in self.find_paths(id, |dep| to.matches(dep)) {
+                    violations.push(ProjectConstraintViolation {
+                        message: format!(
+                            "Project {id} cannot depend on project {}, as dependencies from {} to {} are denied.",
+                            path[path.len() - 1],
+                            rule.from,
+                            rule.to,
+                        ),
+                        path,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_dependency_limits(&self, violations: &mut Vec<ProjectConstraintViolation>) {
+        if let Some(max) = self.config.max_dependencies {
+            for id in self.projects.keys() {
+                let deps = self.get_dependencies(id);
+
+                if deps.len() > max {
+                    violations.push(ProjectConstraintViolation {
+                        message: format!(
+                            "Project {id} has {} dependencies, exceeding the maximum of {max}.",
+                            deps.len()
+                        ),
+                        path: vec![(*id).to_owned()],
+                    });
+                }
+            }
+        }
+
+        if let Some(max) = self.config.max_dependents {
+            let mut dependents = BTreeMap::<&Id, usize>::default();
+
+            for deps in self.dependencies.values() {
+                for dep_id in deps {
+                    *dependents.entry(*dep_id).or_default() += 1;
+                }
+            }
+
+            for (id, count) in dependents {
+                if count > max {
+                    violations.push(ProjectConstraintViolation {
+                        message: format!(
+                            "Project {id} has {count} dependents, exceeding the maximum of {max}."
+                        ),
+                        path: vec![id.to_owned()],
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_denied_stacks(&self, violations: &mut Vec<ProjectConstraintViolation>) {
+        let mut tags = self.config.tag_denied_stacks.iter().collect::<Vec<_>>();
+        tags.sort_by_key(|(tag, _)| *tag);
+
+        for (tag, stacks) in tags {
+            if stacks.is_empty() {
+                continue;
+            }
+
+            for (id, project) in &self.projects {
+                if !project.config.tags.contains(tag) {
+                    continue;
+                }
+
+                for path in self.find_paths(id, |dep| stacks.contains(&dep.stack)) {
+                    let dep = self.projects[&path[path.len() - 1]];
+
+                    violations.push(ProjectConstraintViolation {
+                        message: format!(
+                            "Project {id} with tag #{tag} cannot depend on project {} of stack {}.",
+                            dep.id, dep.stack,
+                        ),
+                        path,
+                    });
+                }
+            }
+        }
+    }
+
+    // Breadth-first traversal of all transitive dependencies, which
+    // returns the shortest path to each dependency that matches
+    fn find_paths(&self, source: &Id, predicate: impl Fn(&Project) -> bool) -> Vec<Vec<Id>> {
+        let mut parents = FxHashMap::<&Id, &Id>::default();
+        let mut visited = FxHashSet::from_iter([source]);
+        let mut queue = VecDeque::from([source]);
+        let mut paths = vec![];
+
+        while let Some(id) = queue.pop_front() {
+            for dep_id in self.get_dependencies(id).iter().copied() {
+                if !visited.insert(dep_id) {
+                    continue;
+                }
+
+                parents.insert(dep_id, id);
+                queue.push_back(dep_id);
+
+                if predicate(self.projects[dep_id]) {
+                    let mut path = vec![(*dep_id).to_owned()];
+                    let mut current = dep_id;
+
+                    while let Some(parent) = parents.get(current).copied() {
+                        path.push(parent.to_owned());
+                        current = parent;
+                    }
+
+                    path.reverse();
+                    paths.push(path);
+                }
+            }
+        }
+
+        paths
+    }
+}
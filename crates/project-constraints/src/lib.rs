@@ -1,3 +1,9 @@
+mod dependency_versions;
+mod graph_constraints;
+
+pub use dependency_versions::*;
+pub use graph_constraints::*;
+
 use miette::Diagnostic;
 use moon_common::{Id, Style, Stylize};
 use moon_config::{DependencyScope, StackType};
@@ -34,6 +40,68 @@ pub enum ProjectConstraintsError {
         dep_id: Id,
         allowed: String,
     },
+
+    #[diagnostic(code(project_constraints::graph_constraints_violated))]
+    #[error(
+        "Project constraints have been violated.\n\n{}\n\nThis can be customized with the {} setting.",
+        format_violations(.violations),
+        "constraints".style(Style::Property),
+    )]
+    GraphConstraintsViolated {
+        violations: Vec<ProjectConstraintViolation>,
+    },
+
+    #[diagnostic(code(project_constraints::inconsistent_dependency_versions))]
+    #[error(
+        "Inconsistent external dependency versions. Each dependency must be declared with the same version across all projects.\n\n{}\n\nExceptions can be configured with the {} setting.",
+        format_version_violations(.violations),
+        "constraints.dependencyVersions.exceptions".style(Style::Property),
+    )]
+    InconsistentDependencyVersions {
+        violations: Vec<DependencyVersionViolation>,
+    },
+}
+
+fn format_version_violations(violations: &[DependencyVersionViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| {
+            let mut lines = vec![format!(
+                "- {} ({})",
+                violation.name.style(Style::Label),
+                violation.manifest.style(Style::File)
+            )];
+
+            for (version, project_ids) in &violation.versions {
+                lines.push(format!(
+                    "  {}: {}",
+                    version.style(Style::Symbol),
+                    project_ids
+                        .iter()
+                        .map(|id| id.style(Style::Id).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+
+            lines.join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_violations(violations: &[ProjectConstraintViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| {
+            format!(
+                "- {}\n  {}",
+                violation.message,
+                violation.get_path().style(Style::Id)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 pub fn enforce_project_type_relationships(
@@ -1,7 +1,12 @@
 use moon_common::Id;
-use moon_config::{DependencyScope, StackType};
+use moon_config::{
+    ConstraintsConfig, ConstraintsDenyRule, DependencyConfig, DependencyScope, StackType,
+};
 use moon_project::{Project, ProjectConfig, ProjectType};
-use moon_project_constraints::{enforce_project_type_relationships, enforce_tag_relationships};
+use moon_project_constraints::{
+    enforce_project_type_relationships, enforce_tag_relationships, ProjectGraphConstraints,
+};
+use rustc_hash::FxHashMap;
 
 fn create_project(id: &str, type_of: ProjectType) -> Project {
     Project {
@@ -654,3 +659,181 @@ mod by_tag {
         .unwrap();
     }
 }
+
+mod graph {
+    use super::*;
+
+    fn create_graph_project(id: &str, tags: &[&str], deps: &[&str]) -> Project {
+        let mut project = create_project_with_tags(id, tags.iter().map(Id::raw).collect());
+
+        project.dependencies = deps
+            .iter()
+            .map(|dep| DependencyConfig {
+                id: Id::raw(dep),
+                ..DependencyConfig::default()
+            })
+            .collect();
+
+        project
+    }
+
+    fn get_paths(config: &ConstraintsConfig, projects: &[Project]) -> Vec<String> {
+        ProjectGraphConstraints::new(config, projects)
+            .check()
+            .unwrap()
+            .into_iter()
+            .map(|violation| violation.get_path())
+            .collect()
+    }
+
+    mod layers {
+        use super::*;
+
+        fn create_config() -> ConstraintsConfig {
+            ConstraintsConfig {
+                layers: vec![Id::raw("app"), Id::raw("ui"), Id::raw("util")],
+                ..ConstraintsConfig::default()
+            }
+        }
+
+        #[test]
+        fn allows_same_and_lower_layers() {
+            let projects = [
+                create_graph_project("a", &["app"], &["b", "c"]),
+                create_graph_project("b", &["ui"], &["c", "d"]),
+                create_graph_project("c", &["util"], &[]),
+                create_graph_project("d", &["ui"], &[]),
+            ];
+
+            assert!(get_paths(&create_config(), &projects).is_empty());
+        }
+
+        #[test]
+        fn reports_upward_deps() {
+            let projects = [
+                create_graph_project("a", &["app"], &[]),
+                create_graph_project("b", &["ui"], &["a"]),
+                create_graph_project("c", &["util"], &["a", "b"]),
+            ];
+
+            assert_eq!(
+                get_paths(&create_config(), &projects),
+                ["b → a", "c → a", "c → b"]
+            );
+        }
+
+        #[test]
+        fn ignores_projects_without_layer() {
+            let projects = [
+                create_graph_project("a", &["app"], &[]),
+                create_graph_project("b", &[], &["a"]),
+            ];
+
+            assert!(get_paths(&create_config(), &projects).is_empty());
+        }
+    }
+
+    mod deny {
+        use super::*;
+
+        #[test]
+        fn reports_transitive_deps_with_path() {
+            let config = ConstraintsConfig {
+                deny: vec![ConstraintsDenyRule {
+                    from: "#public".into(),
+                    to: "internal-*".into(),
+                }],
+                ..ConstraintsConfig::default()
+            };
+            let projects = [
+                create_graph_project("a", &["public"], &["b"]),
+                create_graph_project("b", &[], &["internal-c"]),
+                create_graph_project("internal-c", &[], &[]),
+            ];
+
+            assert_eq!(get_paths(&config, &projects), ["a → b → internal-c"]);
+        }
+
+        #[test]
+        fn ignores_build_and_root_scopes() {
+            let config = ConstraintsConfig {
+                deny: vec![ConstraintsDenyRule {
+                    from: "a".into(),
+                    to: "b".into(),
+                }],
+                ..ConstraintsConfig::default()
+            };
+            let mut a = create_graph_project("a", &[], &["b"]);
+            a.dependencies[0].scope = DependencyScope::Build;
+
+            let projects = [a, create_graph_project("b", &[], &[])];
+
+            assert!(get_paths(&config, &projects).is_empty());
+        }
+
+        #[test]
+        #[should_panic(expected = "Project constraints have been violated")]
+        fn errors_when_enforced() {
+            let config = ConstraintsConfig {
+                deny: vec![ConstraintsDenyRule {
+                    from: "a".into(),
+                    to: "b".into(),
+                }],
+                ..ConstraintsConfig::default()
+            };
+            let projects = [
+                create_graph_project("a", &[], &["b"]),
+                create_graph_project("b", &[], &[]),
+            ];
+
+            ProjectGraphConstraints::new(&config, &projects)
+                .enforce()
+                .unwrap();
+        }
+    }
+
+    mod limits {
+        use super::*;
+
+        #[test]
+        fn reports_fan_out_and_fan_in() {
+            let config = ConstraintsConfig {
+                max_dependencies: Some(1),
+                max_dependents: Some(1),
+                ..ConstraintsConfig::default()
+            };
+            let projects = [
+                create_graph_project("a", &[], &["b", "c"]),
+                create_graph_project("b", &[], &["c"]),
+                create_graph_project("c", &[], &[]),
+            ];
+
+            assert_eq!(get_paths(&config, &projects), ["a", "c"]);
+        }
+    }
+
+    mod denied_stacks {
+        use super::*;
+
+        #[test]
+        fn reports_deps_of_denied_stack() {
+            let config = ConstraintsConfig {
+                tag_denied_stacks: FxHashMap::from_iter([(
+                    Id::raw("browser"),
+                    vec![StackType::Backend],
+                )]),
+                ..ConstraintsConfig::default()
+            };
+            let mut c = create_graph_project("c", &[], &[]);
+            c.stack = StackType::Backend;
+
+            let projects = [
+                create_graph_project("a", &["browser"], &["b"]),
+                create_graph_project("b", &[], &["c"]),
+                c,
+            ];
+
+            assert_eq!(get_paths(&config, &projects), ["a → b → c"]);
+        }
+    }
+}
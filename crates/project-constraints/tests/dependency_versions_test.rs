@@ -0,0 +1,213 @@
+use moon_common::Id;
+use moon_config::ConstraintsDependencyVersionsConfig;
+use moon_project::Project;
+use moon_project_constraints::{check_dependency_versions, enforce_dependency_versions};
+use rustc_hash::FxHashMap;
+use starbase_sandbox::{create_empty_sandbox, Sandbox};
+use std::collections::BTreeMap;
+
+fn create_project(sandbox: &Sandbox, id: &str) -> Project {
+    Project {
+        id: Id::raw(id),
+        root: sandbox.path().join(id),
+        ..Project::default()
+    }
+}
+
+fn create_config() -> ConstraintsDependencyVersionsConfig {
+    ConstraintsDependencyVersionsConfig {
+        enabled: true,
+        ..ConstraintsDependencyVersionsConfig::default()
+    }
+}
+
+#[test]
+fn passes_when_versions_match() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/package.json",
+        r#"{ "dependencies": { "react": "18.3.0" } }"#,
+    );
+    sandbox.create_file(
+        "b/package.json",
+        r#"{ "devDependencies": { "react": "18.3.0" } }"#,
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+
+    enforce_dependency_versions(&create_config(), &projects).unwrap();
+}
+
+#[test]
+fn reports_mismatched_npm_versions() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/package.json",
+        r#"{ "dependencies": { "react": "18.2.0" } }"#,
+    );
+    sandbox.create_file(
+        "b/package.json",
+        r#"{ "dependencies": { "react": "18.3.0" } }"#,
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+    let violations = check_dependency_versions(&create_config(), &projects).unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].manifest, "package.json");
+    assert_eq!(violations[0].name, "react");
+    assert_eq!(
+        violations[0].versions,
+        BTreeMap::from_iter([
+            ("18.2.0".to_owned(), vec![Id::raw("a")]),
+            ("18.3.0".to_owned(), vec![Id::raw("b")]),
+        ])
+    );
+}
+
+#[test]
+fn ignores_internal_and_protocol_npm_deps() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/package.json",
+        r#"{ "dependencies": { "b": "1.0.0", "c": "workspace:*" } }"#,
+    );
+    sandbox.create_file(
+        "b/package.json",
+        r#"{ "dependencies": { "c": "file:../c" } }"#,
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+
+    assert!(check_dependency_versions(&create_config(), &projects)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn ignores_alias_and_catalog_npm_deps() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/package.json",
+        r#"{ "dependencies": { "react": "catalog:", "lodash": "npm:lodash-es@4.17.21" } }"#,
+    );
+    sandbox.create_file(
+        "b/package.json",
+        r#"{ "dependencies": { "react": "catalog:react18", "lodash": "4.17.20" } }"#,
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+
+    assert!(check_dependency_versions(&create_config(), &projects)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn reports_mismatched_cargo_versions() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/Cargo.toml",
+        "[package]\nname = \"a\"\n\n[dependencies]\nserde = \"1.0.200\"\nlocal = { path = \"../local\" }\n",
+    );
+    sandbox.create_file(
+        "b/Cargo.toml",
+        "[package]\nname = \"b\"\n\n[dependencies]\nserde = { version = \"1.0.210\" }\nlocal = { path = \"../other\" }\n",
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+    let violations = check_dependency_versions(&create_config(), &projects).unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].manifest, "Cargo.toml");
+    assert_eq!(violations[0].name, "serde");
+}
+
+#[test]
+fn reports_mismatched_pip_versions() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("a/requirements.txt", "requests[socks]==2.31.0\n");
+    sandbox.create_file("b/requirements.txt", "requests==2.32.0\n");
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+    let violations = check_dependency_versions(&create_config(), &projects).unwrap();
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].versions.keys().collect::<Vec<_>>(),
+        ["==2.31.0", "==2.32.0"]
+    );
+}
+
+#[test]
+fn compares_pip_names_case_insensitively() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("a/requirements.txt", "Django==5.0\n");
+    sandbox.create_file("b/requirements.txt", "django==5.1\n");
+    sandbox.create_file("c/requirements.txt", "typing_extensions==4.11\n");
+    sandbox.create_file("d/requirements.txt", "Typing.Extensions==4.12\n");
+
+    let projects = [
+        create_project(&sandbox, "a"),
+        create_project(&sandbox, "b"),
+        create_project(&sandbox, "c"),
+        create_project(&sandbox, "d"),
+    ];
+    let violations = check_dependency_versions(&create_config(), &projects).unwrap();
+
+    assert_eq!(
+        violations
+            .iter()
+            .map(|violation| violation.name.as_str())
+            .collect::<Vec<_>>(),
+        ["django", "typing-extensions"]
+    );
+    assert_eq!(
+        violations[0].versions.keys().collect::<Vec<_>>(),
+        ["==5.0", "==5.1"]
+    );
+}
+
+#[test]
+fn supports_exceptions() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/package.json",
+        r#"{ "dependencies": { "react": "17.0.2", "typescript": "5.6.0" } }"#,
+    );
+    sandbox.create_file(
+        "b/package.json",
+        r#"{ "dependencies": { "react": "18.3.0", "typescript": "5.7.0" } }"#,
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+    let config = ConstraintsDependencyVersionsConfig {
+        enabled: true,
+        exceptions: FxHashMap::from_iter([
+            ("react".to_owned(), vec!["17.0.2".to_owned()]),
+            ("typescript".to_owned(), vec![]),
+        ]),
+    };
+
+    assert!(check_dependency_versions(&config, &projects)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Inconsistent external dependency versions")]
+fn errors_when_enforced() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "a/package.json",
+        r#"{ "dependencies": { "react": "18.2.0" } }"#,
+    );
+    sandbox.create_file(
+        "b/package.json",
+        r#"{ "dependencies": { "react": "18.3.0" } }"#,
+    );
+
+    let projects = [create_project(&sandbox, "a"), create_project(&sandbox, "b")];
+
+    enforce_dependency_versions(&create_config(), &projects).unwrap();
+}
@@ -287,4 +287,8 @@ impl GraphConversions<Project, DependencyScope, Id> for ProjectGraph {}
 
 impl GraphToDot<Project, DependencyScope, Id> for ProjectGraph {}
 
+impl GraphToGraphML<Project, DependencyScope, Id> for ProjectGraph {}
+
 impl GraphToJson<Project, DependencyScope, Id> for ProjectGraph {}
+
+impl GraphToMermaid<Project, DependencyScope, Id> for ProjectGraph {}
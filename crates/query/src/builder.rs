@@ -14,6 +14,7 @@ pub type FieldValues<'l> = Vec<FieldValue<'l>>;
 #[derive(Debug, PartialEq)]
 pub enum Field<'l> {
     Language(Vec<LanguageType>),
+    Owner(FieldValues<'l>),
     Project(FieldValues<'l>),
     ProjectAlias(FieldValues<'l>),
     ProjectName(FieldValues<'l>),
@@ -120,6 +121,7 @@ fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
                     "language" => {
                         Field::Language(build_criteria_enum::<LanguageType>(&field, &op, value)?)
                     }
+                    "owner" => Field::Owner(value),
                     "project" => Field::Project(value),
                     "projectAlias" => Field::ProjectAlias(value),
                     "projectName" => Field::ProjectName(value),
@@ -533,6 +533,40 @@ mod mql_build {
         }
     }
 
+    mod owner {
+        use super::*;
+
+        #[test]
+        fn owner_eq() {
+            assert_eq!(
+                build_query("owner=@team-payments").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Owner(value_list(["@team-payments"])),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("owner=@team-payments".into())
+                }
+            );
+        }
+
+        #[test]
+        fn owner_like() {
+            assert_eq!(
+                build_query("owner~@org/*").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Owner(value_list(["@org/*"])),
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("owner~@org/*".into())
+                }
+            );
+        }
+    }
+
     mod tag {
         use super::*;
 
@@ -10,6 +10,9 @@ use bazel_remote_apis::build::bazel::remote::execution::v2::{
     ActionResult, BatchReadBlobsRequest, BatchUpdateBlobsRequest, Digest, GetActionResultRequest,
     GetCapabilitiesRequest, ServerCapabilities, UpdateActionResultRequest,
 };
+use bazel_remote_apis::google::bytestream::{
+    byte_stream_client::ByteStreamClient, ReadRequest, WriteRequest,
+};
 use moon_common::color;
 use moon_config::{RemoteCompression, RemoteConfig};
 use std::{error::Error, path::Path};
@@ -36,6 +39,9 @@ fn map_status_error(error: tonic::Status) -> RemoteError {
     }
 }
 
+// Keep each streamed chunk well below the default gRPC message limit of 4mb
+const STREAM_CHUNK_SIZE: usize = 1024 * 1024;
+
 #[derive(Default)]
 pub struct GrpcRemoteClient {
     channel: Option<Channel>,
@@ -43,6 +49,29 @@ pub struct GrpcRemoteClient {
     instance_name: String,
 }
 
+impl GrpcRemoteClient {
+    // https://github.com/bazelbuild/remote-apis/blob/main/build/bazel/remote/execution/v2/remote_execution.proto#L256
+    fn get_blob_path(&self, digest: &Digest) -> String {
+        match self.compression {
+            RemoteCompression::None => format!("blobs/{}/{}", digest.hash, digest.size_bytes),
+            RemoteCompression::Zstd => {
+                format!(
+                    "compressed-blobs/zstd/{}/{}",
+                    digest.hash, digest.size_bytes
+                )
+            }
+        }
+    }
+
+    fn get_resource_name(&self, path: String) -> String {
+        if self.instance_name.is_empty() {
+            path
+        } else {
+            format!("{}/{path}", self.instance_name)
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl RemoteClient for GrpcRemoteClient {
     async fn connect_to_host(
@@ -377,4 +406,130 @@ impl RemoteClient for GrpcRemoteClient {
 
         Ok(digests)
     }
+    // https://github.com/googleapis/googleapis/blob/master/google/bytestream/bytestream.proto#L49
+    async fn stream_read_blob(
+        &self,
+        digest: &Digest,
+        blob_digest: Digest,
+    ) -> miette::Result<Option<Blob>> {
+        let mut client = ByteStreamClient::new(self.channel.clone().unwrap());
+
+        trace!(
+            hash = &digest.hash,
+            blob_hash = &blob_digest.hash,
+            size = blob_digest.size_bytes,
+            compression = self.compression.to_string(),
+            "Streaming output blob download"
+        );
+
+        let mut stream = match client
+            .read(ReadRequest {
+                resource_name: self.get_resource_name(self.get_blob_path(&blob_digest)),
+                read_offset: 0,
+                read_limit: 0,
+            })
+            .await
+        {
+            Ok(res) => res.into_inner(),
+            Err(status) => {
+                return if matches!(status.code(), Code::NotFound) {
+                    trace!(blob_hash = &blob_digest.hash, "Blob does not exist");
+
+                    Ok(None)
+                } else {
+                    Err(map_status_error(status).into())
+                };
+            }
+        };
+
+        let mut data = vec![];
+
+        while let Some(chunk) = stream.message().await.map_err(map_status_error)? {
+            data.extend(chunk.data);
+        }
+
+        Ok(Some(Blob {
+            digest: blob_digest,
+            bytes: decompress_blob(self.compression, data)?,
+        }))
+    }
+
+    // https://github.com/googleapis/googleapis/blob/master/google/bytestream/bytestream.proto#L69
+    async fn stream_update_blob(
+        &self,
+        digest: &Digest,
+        blob: Blob,
+    ) -> miette::Result<Option<Digest>> {
+        let mut client = ByteStreamClient::new(self.channel.clone().unwrap());
+
+        trace!(
+            hash = &digest.hash,
+            blob_hash = &blob.digest.hash,
+            size = blob.digest.size_bytes,
+            compression = self.compression.to_string(),
+            "Streaming output blob upload"
+        );
+
+        let resource_name = self.get_resource_name(format!(
+            "uploads/{}/{}",
+            uuid::Uuid::new_v4(),
+            self.get_blob_path(&blob.digest)
+        ));
+        let data = compress_blob(self.compression, blob.bytes)?;
+        let total_size = data.len();
+        let mut requests = vec![];
+        let mut offset = 0;
+
+        for chunk in data.chunks(STREAM_CHUNK_SIZE) {
+            requests.push(WriteRequest {
+                // Only the first request requires the resource name
+                resource_name: if offset == 0 {
+                    resource_name.clone()
+                } else {
+                    String::new()
+                },
+                write_offset: offset as i64,
+                finish_write: offset + chunk.len() == total_size,
+                data: chunk.to_vec(),
+            });
+
+            offset += chunk.len();
+        }
+
+        match client.write(futures::stream::iter(requests)).await {
+            Ok(response) => {
+                let committed_size = response.into_inner().committed_size;
+
+                // The server may short-circuit when the blob already exists,
+                // and respond with the full size (or -1 when compressed)
+                if committed_size == -1 || committed_size as usize >= total_size {
+                    trace!(blob_hash = &blob.digest.hash, "Uploaded output blob");
+
+                    Ok(Some(blob.digest))
+                } else {
+                    warn!(
+                        blob_hash = &blob.digest.hash,
+                        committed_size, total_size, "Failed to upload entire blob"
+                    );
+
+                    Ok(None)
+                }
+            }
+            Err(status) => {
+                let code = status.code();
+
+                if matches!(code, Code::ResourceExhausted) {
+                    warn!(
+                        code = ?code,
+                        "Remote service exhausted resource: {}",
+                        status.message()
+                    );
+
+                    Ok(None)
+                } else {
+                    Err(map_status_error(status).into())
+                }
+            }
+        }
+    }
 }
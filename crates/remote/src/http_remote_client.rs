@@ -200,4 +200,20 @@ impl RemoteClient for HttpRemoteClient {
     ) -> miette::Result<Vec<Option<Digest>>> {
         Ok(vec![])
     }
+
+    async fn stream_read_blob(
+        &self,
+        digest: &Digest,
+        blob_digest: Digest,
+    ) -> miette::Result<Option<Blob>> {
+        Err(RemoteError::NoHttpStreamSupport.into())
+    }
+
+    async fn stream_update_blob(
+        &self,
+        digest: &Digest,
+        blob: Blob,
+    ) -> miette::Result<Option<Digest>> {
+        Err(RemoteError::NoHttpStreamSupport.into())
+    }
 }
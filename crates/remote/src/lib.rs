@@ -17,5 +17,4 @@ pub use remote_service::*;
 // - Other digest functions besides sha256
 // - Proper error handling
 // - Directory blob types
-// - Write/read bytestream for large blobs
 // - TLS/mTLS issues
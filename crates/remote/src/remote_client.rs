@@ -34,4 +34,16 @@ pub trait RemoteClient: Send + Sync {
         digest: &Digest,
         blobs: Vec<Blob>,
     ) -> miette::Result<Vec<Option<Digest>>>;
+
+    async fn stream_read_blob(
+        &self,
+        digest: &Digest,
+        blob_digest: Digest,
+    ) -> miette::Result<Option<Blob>>;
+
+    async fn stream_update_blob(
+        &self,
+        digest: &Digest,
+        blob: Blob,
+    ) -> miette::Result<Option<Digest>>;
 }
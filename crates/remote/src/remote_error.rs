@@ -46,6 +46,12 @@ pub enum RemoteError {
     #[error("The HTTP based remote service is currently not supported, use gRPC instead.")]
     NoHttpClient,
 
+    #[diagnostic(code(remote::http::no_stream_support))]
+    #[error(
+        "Streaming blobs is not supported by the HTTP based remote service, use gRPC instead."
+    )]
+    NoHttpStreamSupport,
+
     #[diagnostic(code(remote::unsupported_protocol))]
     #[error("Unknown remote host protocol, only gRPC is supported.")]
     UnknownHostProtocol,
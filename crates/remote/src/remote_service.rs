@@ -358,6 +358,62 @@ impl RemoteService {
         Ok(())
     }
 
+    #[instrument(skip(self, blob_digests))]
+    pub async fn load_blobs(
+        &self,
+        digest: &Digest,
+        blob_digests: Vec<Digest>,
+    ) -> miette::Result<Vec<Blob>> {
+        if !self.cache_enabled {
+            return Ok(vec![]);
+        }
+
+        let blobs = download_blobs(
+            Arc::clone(&self.client),
+            digest,
+            blob_digests,
+            self.get_max_batch_size() as usize,
+        )
+        .await?
+        .into_iter()
+        // Blobs that failed to download are returned without contents
+        .filter(|blob| create_digest(&blob.bytes) == blob.digest)
+        .collect::<Vec<_>>();
+
+        debug!(
+            hash = &digest.hash,
+            "Loaded {} blobs from the remote service",
+            blobs.len()
+        );
+
+        Ok(blobs)
+    }
+
+    #[instrument(skip(self, blobs))]
+    pub async fn save_blobs(&self, digest: &Digest, blobs: Vec<Blob>) -> miette::Result<bool> {
+        if !self.cache_enabled {
+            return Ok(false);
+        }
+
+        if blobs.is_empty() {
+            return Ok(false);
+        }
+
+        debug!(
+            hash = &digest.hash,
+            "Saving {} blobs to the remote service",
+            blobs.len()
+        );
+
+        batch_upload_blobs(
+            Arc::clone(&self.client),
+            digest.to_owned(),
+            blobs,
+            self.get_max_batch_size() as usize,
+        )
+        .await
+    }
+
     #[instrument(skip(self))]
     pub async fn wait_for_requests(&self) {
         let mut requests = self.upload_requests.write().await;
@@ -416,9 +472,10 @@ async fn batch_upload_blobs(
     blobs: Vec<Blob>,
     max_size: usize,
 ) -> miette::Result<bool> {
+    let (blobs, large_blobs) = split_by_max_size(blobs, max_size, |blob| blob.bytes.len());
     let blob_groups = partition_into_groups(blobs, max_size, |blob| blob.bytes.len());
 
-    if blob_groups.is_empty() {
+    if blob_groups.is_empty() && large_blobs.is_empty() {
         return Ok(false);
     }
 
@@ -457,6 +514,30 @@ async fn batch_upload_blobs(
         });
     }
 
+    // Blobs larger than the max size can't be batched, so stream them individually
+    for blob in large_blobs {
+        let client = Arc::clone(&client);
+        let digest = digest.to_owned();
+
+        set.spawn(async move {
+            let blob_hash = blob.digest.hash.clone();
+
+            match client.stream_update_blob(&digest, blob).await {
+                Ok(uploaded) => uploaded.is_some(),
+                Err(error) => {
+                    warn!(
+                        hash = &digest.hash,
+                        blob_hash,
+                        "Failed to upload blob: {}",
+                        color::muted_light(error.to_string()),
+                    );
+
+                    false
+                }
+            }
+        });
+    }
+
     let results = set.join_all().await;
 
     Ok(results.into_iter().all(|passed| passed))
@@ -480,10 +561,38 @@ async fn batch_download_blobs(
         }
     }
 
+    for blob in download_blobs(client, digest, digests, max_size).await? {
+        if let Some(file) = file_map.get(&blob.digest.hash) {
+            write_output_file(workspace_root.join(&file.path), blob.bytes, file)?;
+        }
+    }
+
+    // Create symlinks after blob files have been written,
+    // as the link target may reference one of these outputs
+    for link in &result.output_symlinks {
+        link_output_file(
+            workspace_root.join(&link.target),
+            workspace_root.join(&link.path),
+            link,
+        )?;
+    }
+
+    Ok(())
+}
+
+async fn download_blobs(
+    client: Arc<Box<dyn RemoteClient>>,
+    digest: &Digest,
+    digests: Vec<Digest>,
+    max_size: usize,
+) -> miette::Result<Vec<Blob>> {
+    let (digests, large_digests) =
+        split_by_max_size(digests, max_size, |dig| dig.size_bytes as usize);
     let digest_groups = partition_into_groups(digests, max_size, |dig| dig.size_bytes as usize);
+    let mut blobs = vec![];
 
-    if digest_groups.is_empty() {
-        return Ok(());
+    if digest_groups.is_empty() && large_digests.is_empty() {
+        return Ok(blobs);
     }
 
     let group_total = digest_groups.len();
@@ -508,25 +617,37 @@ async fn batch_download_blobs(
         set.spawn(async move { client.batch_read_blobs(&digest, group.items).await });
     }
 
-    while let Some(res) = set.join_next().await {
-        for blob in res.into_diagnostic()?? {
-            if let Some(file) = file_map.get(&blob.digest.hash) {
-                write_output_file(workspace_root.join(&file.path), blob.bytes, file)?;
-            }
-        }
+    // Blobs larger than the max size can't be batched, so stream them individually
+    for blob_digest in large_digests {
+        let client = Arc::clone(&client);
+        let digest = digest.to_owned();
+
+        set.spawn(async move {
+            Ok(client
+                .stream_read_blob(&digest, blob_digest)
+                .await?
+                .into_iter()
+                .collect())
+        });
     }
 
-    // Create symlinks after blob files have been written,
-    // as the link target may reference one of these outputs
-    for link in &result.output_symlinks {
-        link_output_file(
-            workspace_root.join(&link.target),
-            workspace_root.join(&link.path),
-            link,
-        )?;
+    while let Some(res) = set.join_next().await {
+        blobs.extend(res.into_diagnostic()??);
     }
 
-    Ok(())
+    Ok(blobs)
+}
+
+// Blobs larger than the max size can't be sent in a batch request,
+// and must be sent with the ByteStream API instead
+fn split_by_max_size<T>(
+    items: Vec<T>,
+    max_size: usize,
+    get_size: impl Fn(&T) -> usize,
+) -> (Vec<T>, Vec<T>) {
+    items
+        .into_iter()
+        .partition(|item| get_size(item) < max_size)
 }
 
 struct Partition<T> {
@@ -545,16 +666,6 @@ fn partition_into_groups<T>(
         let item_size = get_size(&item);
         let mut index_to_use = -1;
 
-        if item_size >= max_size {
-            warn!(
-                size = item_size,
-                max_size,
-                "Encountered a blob larger than the max size; this is currently not supported until we support the ByteStream API; aborting"
-            );
-
-            return BTreeMap::default();
-        }
-
         // Try and find a partition that this item can go into
         for (index, group) in &groups {
             if group.size + item_size < max_size {
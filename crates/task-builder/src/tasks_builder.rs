@@ -1,10 +1,10 @@
 #![allow(dead_code)]
 
 use crate::tasks_builder_error::TasksBuilderError;
-use moon_common::path::{is_root_level_source, WorkspaceRelativePath};
+use moon_common::path::{is_root_level_source, WorkspaceRelativePath, WorkspaceRelativePathBuf};
 use moon_common::{color, supports_pkl_configs, Id};
 use moon_config::{
-    is_glob_like, InheritedTasksConfig, InputPath, ProjectConfig,
+    is_glob_like, InheritedTasksResult, InputPath, OutputPath, ProjectConfig,
     ProjectWorkspaceInheritedTasksConfig, TaskArgs, TaskConfig, TaskDependency,
     TaskDependencyConfig, TaskMergeStrategy, TaskOptionRunInCI, TaskOptionsConfig, TaskOutputStyle,
     TaskPreset, TaskType, ToolchainConfig,
@@ -85,6 +85,7 @@ pub struct TasksBuilder<'proj> {
 
     // Tasks to merge and build
     task_ids: FxHashSet<&'proj Id>,
+    task_config_files: FxHashMap<&'proj Id, WorkspaceRelativePathBuf>,
     global_tasks: FxHashMap<&'proj Id, &'proj TaskConfig>,
     global_task_options: Option<&'proj TaskOptionsConfig>,
     local_tasks: FxHashMap<&'proj Id, &'proj TaskConfig>,
@@ -107,6 +108,7 @@ impl<'proj> TasksBuilder<'proj> {
             implicit_deps: vec![],
             implicit_inputs: vec![],
             task_ids: FxHashSet::default(),
+            task_config_files: FxHashMap::default(),
             global_tasks: FxHashMap::default(),
             global_task_options: None,
             local_tasks: FxHashMap::default(),
@@ -117,9 +119,10 @@ impl<'proj> TasksBuilder<'proj> {
     #[instrument(skip_all)]
     pub fn inherit_global_tasks(
         &mut self,
-        global_config: &'proj InheritedTasksConfig,
+        global_result: &'proj InheritedTasksResult,
         global_filters: Option<&'proj ProjectWorkspaceInheritedTasksConfig>,
     ) -> &mut Self {
+        let global_config = &global_result.config;
         let mut include_all = true;
         let mut include_set = FxHashSet::default();
         let mut exclude = vec![];
@@ -197,6 +200,18 @@ impl<'proj> TasksBuilder<'proj> {
                 task_id
             };
 
+            // The last layer has the highest precedence, but local
+            // configs take precedence over all layers
+            if let Some(layer) = global_result
+                .task_layers
+                .get(task_id.as_str())
+                .and_then(|layers| layers.last())
+            {
+                self.task_config_files
+                    .entry(task_key)
+                    .or_insert_with(|| WorkspaceRelativePathBuf::from(layer));
+            }
+
             self.global_tasks.insert(task_key, task_config);
             self.task_ids.insert(task_key);
         }
@@ -209,7 +224,11 @@ impl<'proj> TasksBuilder<'proj> {
     }
 
     #[instrument(skip_all)]
-    pub fn load_local_tasks(&mut self, local_config: &'proj ProjectConfig) -> &mut Self {
+    pub fn load_local_tasks(
+        &mut self,
+        local_config: &'proj ProjectConfig,
+        local_config_file: Option<&WorkspaceRelativePath>,
+    ) -> &mut Self {
         for (key, value) in &local_config.env {
             self.project_env.insert(key, value);
         }
@@ -224,6 +243,10 @@ impl<'proj> TasksBuilder<'proj> {
 
         for id in local_config.tasks.keys() {
             self.task_ids.insert(id);
+
+            if let Some(file) = local_config_file {
+                self.task_config_files.insert(id, file.to_owned());
+            }
         }
 
         self
@@ -303,6 +326,7 @@ impl<'proj> TasksBuilder<'proj> {
 
         task.preset = preset;
         task.options = self.build_task_options(id, preset)?;
+        task.state.config_file = self.task_config_files.get(id).cloned();
         task.state.local_only = is_local;
         task.state.root_level = is_root_level_source(self.project_source);
 
@@ -408,6 +432,10 @@ impl<'proj> TasksBuilder<'proj> {
             if config.description.is_some() {
                 task.description = config.description.clone();
             }
+
+            if config.image.is_some() {
+                task.image = config.image.clone();
+            }
         }
 
         // Inputs are tricky, as they come from many sources. We need to ensure that user configured
@@ -457,6 +485,15 @@ impl<'proj> TasksBuilder<'proj> {
             task.command = "noop".into();
         }
 
+        // The image tarball is an output, so that it's cached and hydrated
+        if let Some(image) = &task.image {
+            let image_output = OutputPath::ProjectFile(image.file.as_str().to_owned());
+
+            if !task.outputs.contains(&image_output) {
+                task.outputs.push(image_output);
+            }
+        }
+
         if !global_deps.is_empty() {
             task.deps = self.merge_vec(
                 task.deps,
@@ -722,7 +759,7 @@ impl<'proj> TasksBuilder<'proj> {
             .map(|dep| (*dep).to_owned())
             .collect::<Vec<_>>();
 
-        global_inputs.push(InputPath::WorkspaceGlob(".moon/*.yml".into()));
+        global_inputs.push(InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()));
 
         if supports_pkl_configs() {
             global_inputs.push(InputPath::WorkspaceGlob(".moon/*.pkl".into()));
@@ -36,7 +36,16 @@ async fn build_tasks_with_config(
         },
     );
 
-    builder.load_local_tasks(&local_config);
+    let local_config_file = source.join("moon.yml");
+
+    builder.load_local_tasks(
+        &local_config,
+        if local_config_file.to_logical_path(root).exists() {
+            Some(&local_config_file)
+        } else {
+            None
+        },
+    );
 
     let global_manager = ConfigLoader::default()
         .load_tasks_manager_from(root, root.join(global_name.unwrap_or("global")))
@@ -52,7 +61,7 @@ async fn build_tasks_with_config(
         .unwrap();
 
     builder.inherit_global_tasks(
-        &global_config.config,
+        &global_config,
         Some(&local_config.workspace.inherited_tasks),
     );
 
@@ -128,7 +137,7 @@ mod tasks_builder {
             build.inputs,
             vec![
                 InputPath::ProjectFile("abc".into()),
-                InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
             ]
         );
         assert_eq!(build.outputs, vec![OutputPath::ProjectFile("out".into())]);
@@ -141,7 +150,7 @@ mod tasks_builder {
             run.inputs,
             vec![
                 InputPath::ProjectFile("xyz".into()),
-                InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
             ]
         );
         assert_eq!(run.outputs, vec![]);
@@ -154,7 +163,7 @@ mod tasks_builder {
             test.inputs,
             vec![
                 InputPath::ProjectGlob("**/*".into()),
-                InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
             ]
         );
         assert!(!test.state.local_only);
@@ -175,7 +184,7 @@ mod tasks_builder {
                 build.inputs,
                 vec![
                     InputPath::ProjectFile("abc".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert_eq!(build.outputs, vec![OutputPath::ProjectFile("out".into())]);
@@ -188,13 +197,28 @@ mod tasks_builder {
                 run.inputs,
                 vec![
                     InputPath::ProjectFile("xyz".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert_eq!(run.outputs, vec![]);
             assert!(run.state.local_only);
         }
 
+        #[tokio::test]
+        async fn records_the_config_file_of_each_task() {
+            let sandbox = create_sandbox("builder");
+            let tasks = build_tasks(sandbox.path(), "local/moon.yml").await;
+
+            assert_eq!(
+                tasks.get("local-build").unwrap().state.config_file,
+                Some(WorkspaceRelativePathBuf::from("local/moon.yml"))
+            );
+            assert_eq!(
+                tasks.get("global-build").unwrap().state.config_file,
+                Some(WorkspaceRelativePathBuf::from("global/tasks.yml"))
+            );
+        }
+
         #[tokio::test]
         async fn inherits_global_tasks_from_all_scopes() {
             let sandbox = create_sandbox("builder");
@@ -771,6 +795,51 @@ mod tasks_builder {
         }
     }
 
+    mod images {
+        use super::*;
+
+        #[tokio::test]
+        async fn sets_image_and_noop_command() {
+            let sandbox = create_sandbox("builder");
+            let tasks = build_tasks(sandbox.path(), "images/moon.yml").await;
+            let task = tasks.get("image").unwrap();
+
+            assert!(task.is_image());
+            assert_eq!(task.command, "noop");
+            assert_eq!(task.image.as_ref().unwrap().file, "image.tar");
+        }
+
+        #[tokio::test]
+        async fn adds_image_file_as_an_output() {
+            let sandbox = create_sandbox("builder");
+            let tasks = build_tasks(sandbox.path(), "images/moon.yml").await;
+            let task = tasks.get("image").unwrap();
+
+            assert_eq!(
+                task.outputs,
+                vec![
+                    OutputPath::ProjectGlob("dist/**/*".into()),
+                    OutputPath::ProjectFile("image.tar".into()),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn doesnt_duplicate_image_output() {
+            let sandbox = create_sandbox("builder");
+            let tasks = build_tasks(sandbox.path(), "images/moon.yml").await;
+            let task = tasks.get("image-custom-file").unwrap();
+
+            assert_eq!(
+                task.outputs,
+                vec![
+                    OutputPath::ProjectGlob("dist/**/*".into()),
+                    OutputPath::ProjectFile("out/app.tar".into()),
+                ]
+            );
+        }
+    }
+
     mod default_options {
         use super::*;
 
@@ -927,7 +996,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectGlob("**/*".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -936,7 +1005,7 @@ mod tasks_builder {
 
             assert_eq!(
                 task.inputs,
-                vec![InputPath::WorkspaceGlob(".moon/*.yml".into())]
+                vec![InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())]
             );
             assert!(task.state.empty_inputs);
 
@@ -946,7 +1015,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectGlob("local/*".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -961,7 +1030,7 @@ mod tasks_builder {
 
             assert_eq!(
                 task.inputs,
-                vec![InputPath::WorkspaceGlob(".moon/*.yml".into())]
+                vec![InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())]
             );
             assert!(task.state.empty_inputs);
             assert!(task.state.root_level);
@@ -970,7 +1039,7 @@ mod tasks_builder {
 
             assert_eq!(
                 task.inputs,
-                vec![InputPath::WorkspaceGlob(".moon/*.yml".into())]
+                vec![InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())]
             );
             assert!(task.state.empty_inputs);
             assert!(task.state.root_level);
@@ -981,7 +1050,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectGlob("local/*".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into())
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -999,7 +1068,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectGlob("**/*".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into())
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -1009,7 +1078,7 @@ mod tasks_builder {
 
             assert_eq!(
                 task.inputs,
-                vec![InputPath::WorkspaceGlob(".moon/*.yml".into())]
+                vec![InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())]
             );
             assert!(task.state.empty_inputs);
             assert!(task.state.root_level);
@@ -1020,7 +1089,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectGlob("local/*".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into())
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -1039,7 +1108,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectGlob("src/**/*".into()),
                     InputPath::WorkspaceFile("workspace-local".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -1050,7 +1119,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("local.json".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -1059,7 +1128,7 @@ mod tasks_builder {
 
             assert_eq!(
                 task.inputs,
-                vec![InputPath::WorkspaceGlob(".moon/*.yml".into())]
+                vec![InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into())]
             );
             assert!(task.state.empty_inputs);
         }
@@ -1105,7 +1174,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectFile("global".into()),
                     InputPath::ProjectFile("local".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1158,7 +1227,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectFile("global".into()),
                     InputPath::ProjectFile("local".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1211,7 +1280,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectFile("local".into()),
                     InputPath::ProjectFile("global".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1264,7 +1333,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectFile("local".into()),
                     InputPath::ProjectFile("global".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1314,7 +1383,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("local".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1358,7 +1427,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("local".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1459,7 +1528,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("global".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1505,7 +1574,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("global".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1550,7 +1619,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("global".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1594,7 +1663,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectFile("global".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-merge.yml".into()),
                 ]
             );
@@ -1858,7 +1927,7 @@ mod tasks_builder {
                     InputPath::ProjectGlob("**/*".into()),
                     InputPath::ProjectGlob("project/**/*".into()),
                     InputPath::WorkspaceFile("workspace.json".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -1875,7 +1944,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectGlob("project/**/*".into()),
                     InputPath::WorkspaceFile("workspace.json".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(task.state.empty_inputs);
@@ -1893,7 +1962,7 @@ mod tasks_builder {
                     InputPath::ProjectGlob("local/*".into()),
                     InputPath::ProjectGlob("project/**/*".into()),
                     InputPath::WorkspaceFile("workspace.json".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
             assert!(!task.state.empty_inputs);
@@ -2012,7 +2081,7 @@ mod tasks_builder {
                 task.inputs,
                 vec![
                     InputPath::ProjectGlob("src/**/*".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                 ]
             );
         }
@@ -2055,7 +2124,7 @@ mod tasks_builder {
                     InputPath::ProjectFile("global-extender".into()),
                     InputPath::ProjectFile("local-base".into()),
                     InputPath::ProjectFile("local-extender".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-extends.yml".into()),
                 ]
             );
@@ -2078,7 +2147,7 @@ mod tasks_builder {
                 vec![
                     InputPath::ProjectFile("global-base".into()),
                     InputPath::ProjectFile("local-extender".into()),
-                    InputPath::WorkspaceGlob(".moon/*.yml".into()),
+                    InputPath::WorkspaceGlob(".moon/*.{yml,json,toml}".into()),
                     InputPath::WorkspaceFile("global/tasks/tag-extends.yml".into()),
                 ]
             );
@@ -200,4 +200,8 @@ impl GraphConversions<Task, DependencyType, Target> for TaskGraph {}
 
 impl GraphToDot<Task, DependencyType, Target> for TaskGraph {}
 
+impl GraphToGraphML<Task, DependencyType, Target> for TaskGraph {}
+
 impl GraphToJson<Task, DependencyType, Target> for TaskGraph {}
+
+impl GraphToMermaid<Task, DependencyType, Target> for TaskGraph {}
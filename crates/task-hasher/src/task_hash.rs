@@ -1,6 +1,6 @@
 use moon_common::path::WorkspaceRelativePathBuf;
 use moon_common::Id;
-use moon_config::OutputPath;
+use moon_config::{OutputPath, TaskImageConfig};
 use moon_hash::hash_content;
 use moon_project::Project;
 use moon_task::{Target, Task};
@@ -20,6 +20,10 @@ hash_content!(
         // Environment variables
         pub env: BTreeMap<&'task str, &'task str>,
 
+        // Task `image`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub image: Option<&'task TaskImageConfig>,
+
         // Input files and globs mapped to a unique hash
         pub inputs: BTreeMap<WorkspaceRelativePathBuf, String>,
 
@@ -57,6 +61,7 @@ impl<'task> TaskHash<'task> {
                 .iter()
                 .map(|(k, v)| (k.as_str(), v.as_str()))
                 .collect(),
+            image: task.image.as_ref(),
             inputs: BTreeMap::new(),
             input_env: BTreeMap::new(),
             outputs: task.outputs.iter().collect(),
@@ -2,7 +2,7 @@ use crate::task_hash::TaskHash;
 use miette::IntoDiagnostic;
 use moon_common::path::{PathExt, WorkspaceRelativePath, WorkspaceRelativePathBuf};
 use moon_common::{color, is_ci};
-use moon_config::{HasherConfig, HasherWalkStrategy};
+use moon_config::{ConfigFinder, HasherConfig, HasherWalkStrategy};
 use moon_project::Project;
 use moon_task::{Target, Task};
 use moon_vcs::BoxedVcs;
@@ -166,9 +166,13 @@ impl<'task> TaskHasher<'task> {
         workspace_relative_path: &WorkspaceRelativePath,
     ) -> bool {
         // Don't invalidate existing hashes when moon.* changes
-        // as we already hash the contents of each task!
-        if workspace_relative_path.ends_with("moon.yml")
-            || workspace_relative_path.ends_with("moon.pkl")
+        // as we already hash the contents of each task! Pkl is always
+        // included, regardless of whether it's enabled, so that hashes
+        // are stable across environments
+        if ConfigFinder::with_pkl()
+            .get_project_file_names()
+            .iter()
+            .any(|name| workspace_relative_path.ends_with(name))
         {
             return false;
         }
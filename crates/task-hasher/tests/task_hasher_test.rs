@@ -81,6 +81,30 @@ mod task_hasher {
         );
     }
 
+    #[tokio::test]
+    async fn filters_out_project_config_files() {
+        let sandbox = create_sandbox("ignore-patterns");
+        sandbox.create_file("moon.pkl", "");
+        sandbox.create_file("moon.toml", "");
+        sandbox.enable_git();
+
+        let (wg, vcs) = generate_graph(sandbox.path()).await;
+        let project = wg.get_project("root").unwrap();
+        let task = wg.get_task_from_project("root", "testPatterns").unwrap();
+
+        let hasher_config = HasherConfig {
+            ignore_patterns: vec![GlobPath::from_str("**/out/**").unwrap()],
+            ..HasherConfig::default()
+        };
+
+        let result = generate_hash(&project, &task, &vcs, sandbox.path(), &hasher_config).await;
+
+        assert_eq!(
+            result.inputs.keys().collect::<Vec<_>>(),
+            [".gitignore", "package.json"]
+        );
+    }
+
     mod input_aggregation {
         use super::*;
 
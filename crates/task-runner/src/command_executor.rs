@@ -3,11 +3,12 @@ use moon_action_context::{ActionContext, TargetState};
 use moon_app_context::AppContext;
 use moon_common::{color, is_ci, is_test_env};
 use moon_config::TaskOutputStyle;
-use moon_console::TaskReportItem;
+use moon_console::{TaskReportItem, TaskSignal};
 use moon_process::{args::join_args, AsyncCommand, Command};
 use moon_project::Project;
 use moon_task::Task;
 use std::process::Output;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::{self, JoinHandle};
 use tokio::time::{sleep, timeout};
@@ -119,9 +120,19 @@ impl<'task> CommandExecutor<'task> {
                 }
             }
 
+            // Wait for a signal from the reporter, or forever if not supported
+            async fn wait_for_signal(handle: Option<&mut JoinHandle<TaskSignal>>) -> TaskSignal {
+                match handle {
+                    Some(handle) => handle.await.unwrap_or(TaskSignal::Kill),
+                    None => std::future::pending().await,
+                }
+            }
+
             let timeout_token = CancellationToken::new();
             let timeout_handle =
                 self.monitor_timeout(self.task.options.timeout, timeout_token.clone());
+            let mut signal_handle = self.monitor_signals();
+            let mut received_signal = None;
 
             let attempt_result = tokio::select! {
                 // Run conditions in order!
@@ -132,6 +143,12 @@ impl<'task> CommandExecutor<'task> {
                     Ok(None)
                 }
 
+                // Cancel if the reporter sent a signal
+                signal = wait_for_signal(signal_handle.as_mut()) => {
+                    received_signal = Some(signal);
+                    Ok(None)
+                }
+
                 // Or run the job to completion
                 result = execute_command(
                     self.command.create_async(),
@@ -145,6 +162,10 @@ impl<'task> CommandExecutor<'task> {
                 handle.abort();
             }
 
+            if let Some(handle) = signal_handle {
+                handle.abort();
+            }
+
             // Handle the execution result
             match attempt_result {
                 // Zero and non-zero exit codes
@@ -162,6 +183,15 @@ impl<'task> CommandExecutor<'task> {
                         );
 
                         attempt.finish_from_output(output);
+                    } else if let Some(signal) = received_signal {
+                        debug!(
+                            task_target = self.task.target.as_str(),
+                            command = self.command.bin.to_str(),
+                            signal = ?signal,
+                            "Task was signaled to stop",
+                        );
+
+                        attempt.finish(ActionStatus::Aborted);
                     } else {
                         debug!(
                             task_target = self.task.target.as_str(),
@@ -181,6 +211,17 @@ impl<'task> CommandExecutor<'task> {
 
                     self.attempts.push(attempt);
 
+                    // Restart was requested, so run again without
+                    // counting it as a new attempt
+                    if received_signal == Some(TaskSignal::Restart) {
+                        debug!(
+                            task_target = self.task.target.as_str(),
+                            "Task was signaled to restart, running again",
+                        );
+
+                        continue;
+                    }
+
                     // Successful execution, so break the loop
                     if is_success {
                         debug!(
@@ -192,7 +233,7 @@ impl<'task> CommandExecutor<'task> {
                         break None;
                     }
                     // Unsuccessful execution (maybe flaky), attempt again
-                    else if self.attempt_index < self.attempt_total {
+                    else if received_signal.is_none() && self.attempt_index < self.attempt_total {
                         debug!(
                             task_target = self.task.target.as_str(),
                             "Task was unsuccessful, attempting again",
@@ -285,6 +326,25 @@ impl<'task> CommandExecutor<'task> {
         })
     }
 
+    fn monitor_signals(&self) -> Option<JoinHandle<TaskSignal>> {
+        if !self.app.console.reporter.renders_task_output() {
+            return None;
+        }
+
+        let console = self.app.console.clone();
+        let target = self.task.target.clone();
+
+        Some(task::spawn(async move {
+            loop {
+                sleep(Duration::from_millis(250)).await;
+
+                if let Some(signal) = console.reporter.poll_task_signal(&target) {
+                    return signal;
+                }
+            }
+        }))
+    }
+
     fn stop_monitoring(&mut self) {
         if let Some(handle) = self.handle.take() {
             handle.abort();
@@ -320,6 +380,28 @@ impl<'task> CommandExecutor<'task> {
             self.command.set_prefix(prefix);
         }
 
+        // When the reporter renders output itself (like the TUI), it owns the terminal,
+        // so output must always be captured and streamed to the reporter
+        if self.app.console.reporter.renders_task_output() {
+            let reporter = Arc::clone(&self.app.console.reporter);
+            let target = self.task.target.clone();
+
+            // The reporter owns stdin, so interactive tasks can't receive input
+            if self.interactive {
+                let _ = reporter.on_task_output(
+                    &target,
+                    "Interactive tasks are not supported by this reporter, running without stdin",
+                    true,
+                );
+            }
+
+            self.interactive = false;
+            self.stream = true;
+            self.command.set_output_handler(move |line, stderr| {
+                let _ = reporter.on_task_output(&target, line, stderr);
+            });
+        }
+
         report_item.attempt_current = self.attempt_index;
         report_item.attempt_total = self.attempt_total;
         report_item.output_streamed = self.stream;
@@ -355,7 +437,14 @@ impl<'task> CommandExecutor<'task> {
             }),
         ));
 
-        self.app.console.out.write_line(message)?;
+        if self.app.console.reporter.renders_task_output() {
+            self.app
+                .console
+                .reporter
+                .on_task_output(&self.task.target, &message, false)?;
+        } else {
+            self.app.console.out.write_line(message)?;
+        }
 
         Ok(())
     }
@@ -3,6 +3,7 @@ pub mod command_builder;
 pub mod command_executor;
 pub mod output_archiver;
 pub mod output_hydrater;
+pub mod output_image_builder;
 mod run_state;
 mod task_runner;
 mod task_runner_error;
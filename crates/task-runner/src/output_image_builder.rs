@@ -0,0 +1,99 @@
+use moon_app_context::AppContext;
+use moon_common::path;
+use moon_config::TaskImageConfig;
+use moon_oci::{OciImage, OciImageBuilder};
+use moon_project::Project;
+use moon_task::Task;
+use starbase_utils::fs;
+use std::path::PathBuf;
+use tracing::{debug, instrument};
+
+/// Assemble the outputs of a task into an OCI image, on top of a base image,
+/// and write it to the project as an image layout tarball. Blobs are cached
+/// in `.moon/cache/oci`, and in the remote cache when enabled.
+pub struct OutputImageBuilder<'task> {
+    pub app: &'task AppContext,
+    pub project: &'task Project,
+    pub task: &'task Task,
+}
+
+impl OutputImageBuilder<'_> {
+    #[instrument(skip(self, config))]
+    pub async fn build(&self, config: &TaskImageConfig) -> miette::Result<(OciImage, PathBuf)> {
+        let image_file = self.project.root.join(config.file.as_str());
+
+        let mut builder = self.create_builder(config);
+
+        for file in self.task.get_output_files(&self.app.workspace_root, true)? {
+            let abs_file = file.to_logical_path(&self.app.workspace_root);
+
+            // Outputs may be directories, so add all files within them
+            let abs_files = if abs_file.is_dir() {
+                fs::read_dir_all(&abs_file)?
+                    .into_iter()
+                    .map(|entry| entry.path())
+                    .collect()
+            } else if abs_file.is_file() {
+                vec![abs_file]
+            } else {
+                vec![]
+            };
+
+            for abs_file in abs_files {
+                // The image itself is an output, but should not be within itself!
+                if abs_file == image_file {
+                    continue;
+                }
+
+                // Files outside of the project maintain their workspace structure
+                let Ok(rel_file) = abs_file
+                    .strip_prefix(&self.project.root)
+                    .or_else(|_| abs_file.strip_prefix(&self.app.workspace_root))
+                else {
+                    continue;
+                };
+
+                builder.add_file(path::to_virtual_string(rel_file)?, abs_file.clone());
+            }
+        }
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            image_file = ?image_file,
+            "Building OCI image from task outputs"
+        );
+
+        let image = builder.build(&image_file).await?;
+
+        if let Some(target) = &config.push {
+            builder.push(&image, target).await?;
+        }
+
+        Ok((image, image_file))
+    }
+
+    /// Push a previously built image to the configured registry, for example,
+    /// when the image was hydrated from the cache instead of being built.
+    #[instrument(skip(self, config))]
+    pub async fn push(&self, config: &TaskImageConfig) -> miette::Result<()> {
+        let Some(target) = &config.push else {
+            return Ok(());
+        };
+
+        let image_file = self.project.root.join(config.file.as_str());
+        let builder = self.create_builder(config);
+        let image = builder.load(&image_file)?;
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            image_file = ?image_file,
+            "Pushing cached OCI image"
+        );
+
+        builder.push(&image, target).await
+    }
+
+    fn create_builder<'cfg>(&self, config: &'cfg TaskImageConfig) -> OciImageBuilder<'cfg> {
+        OciImageBuilder::new(config, &self.app.cache_engine.cache_dir.join("oci"))
+    }
+}
@@ -7,6 +7,7 @@ cache_item!(
     pub struct TaskRunCacheState {
         pub exit_code: i32,
         pub hash: String,
+        pub last_run_duration: u128,
         pub last_run_time: u128,
         pub target: String,
     }
@@ -2,6 +2,7 @@ use crate::command_builder::CommandBuilder;
 use crate::command_executor::CommandExecutor;
 use crate::output_archiver::OutputArchiver;
 use crate::output_hydrater::{HydrateFrom, OutputHydrater};
+use crate::output_image_builder::OutputImageBuilder;
 use crate::run_state::*;
 use crate::task_runner_error::TaskRunnerError;
 use moon_action::{ActionNode, ActionStatus, Operation, OperationList, OperationMeta};
@@ -9,6 +10,7 @@ use moon_action_context::{ActionContext, TargetState};
 use moon_api::Moonbase;
 use moon_app_context::AppContext;
 use moon_cache::CacheItem;
+use moon_config::TaskImageConfig;
 use moon_console::TaskReportItem;
 use moon_platform::PlatformManager;
 use moon_process::ProcessError;
@@ -76,6 +78,7 @@ impl<'task> TaskRunner<'task> {
             platform_manager: PlatformManager::read(),
             project,
             report_item: TaskReportItem {
+                config_file: task.state.config_file.as_ref().map(|file| file.to_string()),
                 output_style: task.options.output_style,
                 ..Default::default()
             },
@@ -114,6 +117,18 @@ impl<'task> TaskRunner<'task> {
 
             // Exit early if this build has already been cached/hashed
             if self.hydrate(context, &hash).await? {
+                // Cached images must still be pushed, as the registry
+                // or tag may not have received them yet
+                if let Some(image) = &self.task.image {
+                    OutputImageBuilder {
+                        app: self.app,
+                        project: self.project,
+                        task: self.task,
+                    }
+                    .push(image)
+                    .await?;
+                }
+
                 return Ok(Some(hash));
             }
 
@@ -480,6 +495,13 @@ impl<'task> TaskRunner<'task> {
         context: &ActionContext,
         node: &ActionNode,
     ) -> miette::Result<()> {
+        let task = self.task;
+
+        // If the task assembles an image, there's no command to run
+        if let Some(image) = &task.image {
+            return self.build_image(context, image).await;
+        }
+
         // If the task is a no-operation, we should exit early
         if self.task.is_no_op() {
             self.skip_no_op(context)?;
@@ -564,6 +586,80 @@ impl<'task> TaskRunner<'task> {
         Ok(())
     }
 
+    #[instrument(skip(self, context, image))]
+    pub async fn build_image(
+        &mut self,
+        context: &ActionContext,
+        image: &TaskImageConfig,
+    ) -> miette::Result<()> {
+        debug!(
+            task_target = self.task.target.as_str(),
+            base = &image.base,
+            "Building an OCI image from the task outputs"
+        );
+
+        let mut operation = Operation::task_execution(format!("oci-image {}", image.base));
+
+        self.app.console.reporter.on_task_started(
+            &self.task.target,
+            &operation,
+            &self.report_item,
+        )?;
+
+        let result = OutputImageBuilder {
+            app: self.app,
+            project: self.project,
+            task: self.task,
+        }
+        .build(image)
+        .await;
+
+        if let Some(output) = operation.get_output_mut() {
+            match &result {
+                Ok((image, image_file)) => {
+                    output.exit_code = Some(0);
+                    output.set_stdout(format!(
+                        "Built image {} to {}",
+                        image.manifest.digest,
+                        image_file.display()
+                    ));
+                }
+                Err(error) => {
+                    output.exit_code = Some(1);
+                    output.set_stderr(error.to_string());
+                }
+            };
+        }
+
+        operation.finish(if result.is_ok() {
+            ActionStatus::Passed
+        } else {
+            ActionStatus::Failed
+        });
+
+        self.app.console.reporter.on_task_finished(
+            &self.task.target,
+            &operation,
+            &self.report_item,
+            result.as_ref().err(),
+        )?;
+
+        self.persist_state(&operation)?;
+
+        context.set_target_state(
+            &self.task.target,
+            if result.is_ok() {
+                TargetState::from_hash(self.report_item.hash.as_deref())
+            } else {
+                TargetState::Failed
+            },
+        );
+
+        self.operations.push(operation);
+
+        result.map(|_| ())
+    }
+
     #[instrument(skip_all)]
     pub fn skip(&mut self, context: &ActionContext) -> miette::Result<()> {
         debug!(task_target = self.task.target.as_str(), "Skipping task");
@@ -760,6 +856,14 @@ impl<'task> TaskRunner<'task> {
             self.cache.data.exit_code = output.get_exit_code();
         }
 
+        // Only track the duration of actual executions, and not hydrations,
+        // so that it can be used for distributing tasks across CI jobs
+        if operation.meta.is_task_execution() {
+            if let Some(duration) = operation.duration {
+                self.cache.data.last_run_duration = duration.as_millis();
+            }
+        }
+
         Ok(())
     }
 }
@@ -5,7 +5,8 @@ use moon_common::{
     Id,
 };
 use moon_config::{
-    InputPath, OutputPath, PlatformType, TaskDependencyConfig, TaskPreset, TaskType,
+    InputPath, OutputPath, PlatformType, TaskDependencyConfig, TaskImageConfig, TaskPreset,
+    TaskType,
 };
 use moon_target::Target;
 use once_cell::sync::OnceCell;
@@ -17,6 +18,11 @@ use std::path::{Path, PathBuf};
 cacheable!(
     #[derive(Clone, Debug, Default, Eq, PartialEq)]
     pub struct TaskState {
+        // Config file the task was defined in, with the project
+        // config taking precedence over inherited configs
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub config_file: Option<WorkspaceRelativePathBuf>,
+
         // Inputs were configured explicitly as `[]`
         pub empty_inputs: bool,
 
@@ -48,6 +54,9 @@ cacheable!(
 
         pub id: Id,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub image: Option<TaskImageConfig>,
+
         pub inputs: Vec<InputPath>,
 
         #[serde(skip_serializing_if = "FxHashSet::is_empty")]
@@ -196,6 +205,11 @@ impl Task {
         matches!(self.type_of, TaskType::Build) || !self.outputs.is_empty()
     }
 
+    /// Return true if the task assembles an OCI image instead of running a command.
+    pub fn is_image(&self) -> bool {
+        self.image.is_some()
+    }
+
     /// Return true if the task has been expanded.
     pub fn is_expanded(&self) -> bool {
         self.state.expanded
@@ -262,6 +276,7 @@ impl Default for Task {
             description: None,
             env: FxHashMap::default(),
             id: Id::default(),
+            image: None,
             inputs: vec![],
             input_env: FxHashSet::default(),
             input_files: FxHashSet::default(),
@@ -3,6 +3,7 @@ use moon_config::{VcsConfig, VcsHookFormat};
 use moon_vcs::BoxedVcs;
 use rustc_hash::FxHashMap;
 use starbase_utils::fs;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, instrument, warn};
 
@@ -61,6 +62,8 @@ impl<'app> HooksGenerator<'app> {
             }
         }
 
+        self.vcs.register_hooks(&BTreeMap::new()).await?;
+
         debug!(dir = ?self.output_dir, "Removing local hooks");
 
         fs::remove_dir_all(&self.output_dir)?;
@@ -123,6 +126,7 @@ impl<'app> HooksGenerator<'app> {
     async fn sync_to_vcs(&self, hooks: FxHashMap<&'app String, PathBuf>) -> miette::Result<()> {
         let hooks_dir = self.vcs.get_hooks_dir().await?;
         let repo_root = self.vcs.get_repository_root().await?;
+        let mut registered_hooks = BTreeMap::default();
 
         for (hook_name, internal_path) in hooks {
             let external_path = hooks_dir.join(hook_name);
@@ -172,8 +176,12 @@ impl<'app> HooksGenerator<'app> {
                     ),
                 )?;
             }
+
+            registered_hooks.insert(hook_name.to_owned(), external_path);
         }
 
+        self.vcs.register_hooks(&registered_hooks).await?;
+
         Ok(())
     }
 
@@ -1,5 +1,5 @@
 use moon_config::VcsConfig;
-use moon_vcs::{BoxedVcs, Git};
+use moon_vcs::{BoxedVcs, Git, Sapling};
 use moon_vcs_hooks::HooksGenerator;
 use rustc_hash::FxHashMap;
 use starbase_sandbox::{assert_snapshot, create_empty_sandbox};
@@ -99,6 +99,38 @@ async fn cleans_up_hooks() {
     assert!(!local_hooks.exists());
 }
 
+#[tokio::test]
+async fn registers_hooks_in_sapling_config() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(".sl/requires", "");
+
+    let sl: BoxedVcs = Box::new(Sapling::load(sandbox.path(), "main", &[]).unwrap());
+
+    HooksGenerator::new(&sl, &create_config(), sandbox.path())
+        .generate()
+        .await
+        .unwrap();
+
+    let pre_commit = sandbox.path().join(".sl/hooks/pre-commit");
+    let hooks_config = sandbox.path().join(".sl/moon-hooks.rc");
+
+    assert!(pre_commit.exists());
+    assert!(fs::read_to_string(&hooks_config)
+        .unwrap()
+        .contains(&format!("pre-commit.moon = {}", pre_commit.display())));
+    assert!(fs::read_to_string(sandbox.path().join(".sl/config"))
+        .unwrap()
+        .contains("%include moon-hooks.rc"));
+
+    HooksGenerator::new(&sl, &create_config(), sandbox.path())
+        .cleanup()
+        .await
+        .unwrap();
+
+    assert!(!pre_commit.exists());
+    assert!(!hooks_config.exists());
+}
+
 #[cfg(unix)]
 mod unix {
     use super::*;
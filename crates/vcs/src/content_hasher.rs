@@ -0,0 +1,34 @@
+use moon_common::path::WorkspaceRelativePathBuf;
+use sha2::{Digest, Sha256};
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Hash the contents of the provided files (relative from the workspace root)
+/// using SHA-256. This is used by VCS's that don't provide a mechanism for
+/// hashing arbitrary files, like `git hash-object`.
+pub(crate) fn hash_file_contents(
+    workspace_root: &Path,
+    files: &[String],
+    include: impl Fn(&Path) -> bool,
+) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+    let mut map = BTreeMap::new();
+
+    for file in files {
+        let abs_file = workspace_root.join(file);
+
+        if !abs_file.is_file() || !include(&abs_file) {
+            continue;
+        }
+
+        let mut hasher = Sha256::default();
+        hasher.update(fs::read_file_bytes(&abs_file)?);
+
+        map.insert(
+            WorkspaceRelativePathBuf::from(file),
+            format!("{:x}", hasher.finalize()),
+        );
+    }
+
+    Ok(map)
+}
@@ -17,7 +17,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::{cmp, env};
 use thiserror::Error;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 pub static STATUS_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^(M|T|A|D|R|C|U|\?|!| )(M|T|A|D|R|C|U|\?|!| ) ").unwrap());
@@ -26,6 +26,11 @@ pub static DIFF_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(A|D|M|T|U|X)$
 
 pub static DIFF_SCORE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(C|M|R)(\d{3})$").unwrap());
 
+const DEEPEN_INITIAL_DEPTH: u32 = 50;
+const DEEPEN_MAX_DEPTH: u32 = 10_000;
+
+pub static COMMIT_HASH: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[0-9a-f]{7,64}$").unwrap());
+
 pub static VERSION_CLEAN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\.(windows|win|msysgit|msys|vfs)(\.\d+){1,2}").unwrap());
 
@@ -93,11 +98,78 @@ pub enum GitError {
     },
 }
 
+/// Load ignore rules from a `.gitignore` file in the provided repository root.
+/// This is shared by other VCS's that also respect `.gitignore`.
+pub(crate) fn load_gitignore(repository_root: &Path) -> miette::Result<Option<Gitignore>> {
+    let ignore_path = repository_root.join(".gitignore");
+
+    if !ignore_path.exists() {
+        return Ok(None);
+    }
+
+    debug!(
+        ignore_file = ?ignore_path,
+        "Loading ignore rules from .gitignore",
+    );
+
+    let mut builder = GitignoreBuilder::new(repository_root);
+
+    if let Some(error) = builder.add(ignore_path) {
+        return Err(GitError::GitignoreLoadFailed {
+            error: Box::new(error),
+        }
+        .into());
+    }
+
+    Ok(Some(builder.build().map_err(|error| {
+        GitError::GitignoreLoadFailed {
+            error: Box::new(error),
+        }
+    })?))
+}
+
+/// Map a base revision to a refspec that can be fetched from the provided remote.
+/// The base may be a branch, a branch on a remote, a full ref, or a commit hash.
+/// Returns `None` if the base belongs to another remote.
+pub fn get_fetch_refspec(base: &str, remote: &str, remote_candidates: &[String]) -> Option<String> {
+    if COMMIT_HASH.is_match(base) {
+        return Some(base.to_owned());
+    }
+
+    let branch = if let Some(branch) = base.strip_prefix("refs/heads/") {
+        branch
+    } else if let Some(remote_ref) = base.strip_prefix("refs/remotes/") {
+        remote_ref.strip_prefix(remote)?.strip_prefix('/')?
+    } else if let Some(full_ref) = base.strip_prefix("refs/") {
+        return Some(format!("+refs/{full_ref}:refs/{full_ref}"));
+    } else if let Some(branch) = base
+        .strip_prefix(remote)
+        .and_then(|branch| branch.strip_prefix('/'))
+    {
+        branch
+    } else if remote_candidates
+        .iter()
+        .any(|other| base.starts_with(&format!("{other}/")))
+    {
+        return None;
+    } else {
+        base
+    };
+
+    Some(format!(
+        "+refs/heads/{branch}:refs/remotes/{remote}/{branch}"
+    ))
+}
+
 #[derive(Debug)]
 pub struct Git {
     /// Ignore rules derived from a root `.gitignore` file.
     ignore: Option<Gitignore>,
 
+    /// Maximum number of times to deepen a shallow checkout, when a
+    /// merge base cannot be found. A value of 0 disables deepening.
+    pub deepen_limit: u8,
+
     /// Default git branch name.
     pub default_branch: Arc<String>,
 
@@ -186,32 +258,7 @@ impl Git {
         }
 
         // Load .gitignore
-        let ignore_path = repository_root.join(".gitignore");
-        let mut ignore: Option<Gitignore> = None;
-
-        if ignore_path.exists() {
-            debug!(
-                ignore_file = ?ignore_path,
-                "Loading ignore rules from .gitignore",
-            );
-
-            let mut builder = GitignoreBuilder::new(&repository_root);
-
-            if let Some(error) = builder.add(ignore_path) {
-                return Err(GitError::GitignoreLoadFailed {
-                    error: Box::new(error),
-                }
-                .into());
-            }
-
-            ignore = Some(
-                builder
-                    .build()
-                    .map_err(|error| GitError::GitignoreLoadFailed {
-                        error: Box::new(error),
-                    })?,
-            );
-        }
+        let ignore = load_gitignore(&repository_root)?;
 
         // Load .gitmodules
         let modules_path = repository_root.join(".gitmodules");
@@ -234,6 +281,7 @@ impl Git {
         }
 
         let git = Git {
+            deepen_limit: 0,
             default_branch: Arc::new(default_branch.as_ref().to_owned()),
             ignore,
             remote_candidates: remote_candidates.to_owned(),
@@ -262,7 +310,7 @@ impl Git {
         Ok(git)
     }
 
-    async fn get_merge_base(&self, base: &str, head: &str) -> miette::Result<Option<Arc<String>>> {
+    async fn find_merge_base(&self, base: &str, head: &str) -> miette::Result<Option<Arc<String>>> {
         let mut args = vec!["merge-base", head];
         let mut candidates = vec![base.to_owned()];
 
@@ -284,13 +332,114 @@ impl Git {
 
         // Then we need to run it again and extract the base hash.
         // This is necessary to support comparisons between forks!
-        if let Ok(hash) = self.process.run(args, true).await {
+        if args.len() > 2 {
+            if let Ok(hash) = self.process.run(args, true).await {
+                return Ok(Some(hash));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn get_merge_base(&self, base: &str, head: &str) -> miette::Result<Option<Arc<String>>> {
+        if let Some(hash) = self.find_merge_base(base, head).await? {
             return Ok(Some(hash));
         }
 
+        if self.deepen_limit == 0 || !self.is_shallow_checkout().await? {
+            return Ok(None);
+        }
+
+        // In a shallow checkout, the merge base is most likely not in the
+        // fetched history, so incrementally fetch more until it's found
+        let mut depth = DEEPEN_INITIAL_DEPTH;
+        let mut attempts = 0;
+
+        for attempt in 1..=self.deepen_limit {
+            debug!(
+                base,
+                head,
+                depth,
+                attempt,
+                limit = self.deepen_limit,
+                "Unable to find a merge base in shallow checkout, deepening history",
+            );
+
+            attempts = attempt;
+
+            self.deepen_history(base, head, depth).await?;
+
+            if let Some(hash) = self.find_merge_base(base, head).await? {
+                debug!(base, head, hash = hash.as_str(), "Found a merge base");
+
+                return Ok(Some(hash));
+            }
+
+            // Fetched the entire history and still nothing
+            if !self.git_root.join("shallow").exists() {
+                break;
+            }
+
+            depth = depth.saturating_mul(2).min(DEEPEN_MAX_DEPTH);
+        }
+
+        warn!(
+            base,
+            head,
+            "Unable to find a merge base after deepening the shallow checkout {} time(s)",
+            attempts,
+        );
+
         Ok(None)
     }
 
+    async fn deepen_history(&self, base: &str, head: &str, depth: u32) -> miette::Result<()> {
+        let head_hash = self.process.run(["rev-parse", head], true).await?;
+
+        for remote in &self.remote_candidates {
+            let Some(refspec) = get_fetch_refspec(base, remote, &self.remote_candidates) else {
+                continue;
+            };
+
+            // Fetch the base, as it may not exist in single-branch clones
+            let fetched_base = self
+                .process
+                .run_command_without_cache(
+                    self.process.create_command([
+                        "fetch",
+                        "--no-tags",
+                        &format!("--depth={depth}"),
+                        remote,
+                        &refspec,
+                    ]),
+                    true,
+                )
+                .await;
+
+            if fetched_base.is_err() {
+                continue;
+            }
+
+            // Then deepen the head from the current shallow boundary
+            self.process
+                .run_command_without_cache(
+                    self.process.create_command([
+                        "fetch",
+                        "--no-tags",
+                        &format!("--deepen={depth}"),
+                        remote,
+                        head_hash.as_str(),
+                    ]),
+                    true,
+                )
+                .await?;
+
+            break;
+        }
+
+        Ok(())
+    }
+
     pub async fn get_remote_default_branch(&self) -> miette::Result<Arc<String>> {
         let extract_branch = |result: Arc<String>| -> Option<Arc<String>> {
             if let Some(branch) = result.strip_prefix("origin/") {
@@ -857,6 +1006,10 @@ impl Vcs for Git {
         false
     }
 
+    fn can_deepen_shallow_checkout(&self) -> bool {
+        self.deepen_limit > 0
+    }
+
     fn is_enabled(&self) -> bool {
         self.git_root.exists()
     }
@@ -0,0 +1,491 @@
+use crate::content_hasher::hash_file_contents;
+use crate::git::load_gitignore;
+use crate::process_cache::ProcessCache;
+use crate::touched_files::TouchedFiles;
+use crate::vcs::Vcs;
+use async_trait::async_trait;
+use ignore::gitignore::Gitignore;
+use miette::Diagnostic;
+use moon_common::path::WorkspaceRelativePathBuf;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rustc_hash::FxHashSet;
+use semver::Version;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+pub static JJ_VERSION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d+)\.(\d+)\.(\d+)").unwrap());
+
+pub static JJ_RENAME_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(.*)\{(.*) => (.*)\}(.*)$").unwrap());
+
+pub fn clean_jj_version(version: String) -> String {
+    // jj 0.24.0-3d7a0a3f9e8d
+    JJ_VERSION_PATTERN
+        .captures(&version)
+        .map(|caps| format!("{}.{}.{}", &caps[1], &caps[2], &caps[3]))
+        .unwrap_or_else(|| "0.0.0".into())
+}
+
+/// Expand a renamed or copied path from a `jj diff --summary` line,
+/// in the format of `prefix/{old => new}/suffix`, into the old and new paths.
+pub fn expand_jj_renamed_path(value: &str) -> (String, String) {
+    match JJ_RENAME_PATTERN.captures(value) {
+        Some(caps) => {
+            let join = |middle: &str| {
+                format!("{}{middle}{}", &caps[1], &caps[4])
+                    .replace("//", "/")
+                    .trim_start_matches('/')
+                    .to_owned()
+            };
+
+            (join(&caps[2]), join(&caps[3]))
+        }
+        None => (value.to_owned(), value.to_owned()),
+    }
+}
+
+/// Parse the output of `jj diff --summary` into touched files. Since jj has no
+/// concept of a staging area, changes will be marked as unstaged when comparing
+/// the working copy, otherwise as staged.
+pub fn parse_jj_diff_summary(output: &str, working_copy: bool) -> TouchedFiles {
+    let mut added = FxHashSet::default();
+    let mut deleted = FxHashSet::default();
+    let mut modified = FxHashSet::default();
+    let mut changed = FxHashSet::default();
+
+    for line in output.lines() {
+        let Some((status, path)) = line.split_once(' ') else {
+            continue;
+        };
+
+        // Paths are relative from the current directory (workspace root),
+        // so ignore any files that are outside of the workspace
+        let to_path = |path: &str| {
+            if path.starts_with("../") {
+                None
+            } else {
+                Some(WorkspaceRelativePathBuf::from(path))
+            }
+        };
+
+        match status {
+            "A" => {
+                if let Some(file) = to_path(path) {
+                    added.insert(file.clone());
+                    changed.insert(file);
+                }
+            }
+            "D" => {
+                if let Some(file) = to_path(path) {
+                    deleted.insert(file.clone());
+                    changed.insert(file);
+                }
+            }
+            "M" => {
+                if let Some(file) = to_path(path) {
+                    modified.insert(file.clone());
+                    changed.insert(file);
+                }
+            }
+            "R" | "C" => {
+                let (old_path, new_path) = expand_jj_renamed_path(path);
+
+                if status == "R" {
+                    if let Some(file) = to_path(&old_path) {
+                        deleted.insert(file.clone());
+                        changed.insert(file);
+                    }
+                }
+
+                if let Some(file) = to_path(&new_path) {
+                    added.insert(file.clone());
+                    changed.insert(file);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (staged, unstaged) = if working_copy {
+        (FxHashSet::default(), changed)
+    } else {
+        (changed, FxHashSet::default())
+    };
+
+    TouchedFiles {
+        added,
+        deleted,
+        modified,
+        staged,
+        unstaged,
+        untracked: FxHashSet::default(),
+    }
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum JujutsuError {
+    #[diagnostic(code(jj::invalid_version))]
+    #[error("Invalid or unsupported jj version.")]
+    InvalidVersion {
+        #[source]
+        error: Box<semver::Error>,
+    },
+
+    #[diagnostic(code(jj::repository::extract_slug))]
+    #[error("Failed to extract a repository slug from jj git remote candidates.")]
+    ExtractRepoSlugFailed,
+
+    #[diagnostic(
+        code(jj::hooks::unsupported),
+        help = "Colocate the repository with git to use hooks."
+    )]
+    #[error("jj does not support hooks, unless the repository is colocated with git.")]
+    HooksNotSupported,
+}
+
+#[derive(Debug)]
+pub struct Jujutsu {
+    /// Ignore rules derived from a root `.gitignore` file.
+    ignore: Option<Gitignore>,
+
+    /// Default bookmark (branch) name.
+    pub default_branch: Arc<String>,
+
+    /// Root of the `.git` directory, when colocated with git.
+    pub git_root: Option<PathBuf>,
+
+    /// Root of the `.jj` directory.
+    pub jj_root: PathBuf,
+
+    /// Run and cache `jj` commands.
+    pub process: ProcessCache,
+
+    /// List of git remotes to use as merge candidates.
+    pub remote_candidates: Vec<String>,
+
+    /// Root of the repository that contains `.jj`.
+    pub repository_root: PathBuf,
+}
+
+impl Jujutsu {
+    pub fn load<R: AsRef<Path>, B: AsRef<str>>(
+        workspace_root: R,
+        default_branch: B,
+        remote_candidates: &[String],
+    ) -> miette::Result<Jujutsu> {
+        debug!("Using jj (Jujutsu) as a version control system");
+
+        let workspace_root = workspace_root.as_ref();
+
+        debug!(
+            starting_dir = ?workspace_root,
+            "Attempting to find a .jj directory"
+        );
+
+        let mut current_dir = workspace_root;
+        let repository_root;
+
+        loop {
+            if current_dir.join(".jj").is_dir() {
+                repository_root = current_dir.to_path_buf();
+                break;
+            }
+
+            match current_dir.parent() {
+                Some(parent) => current_dir = parent,
+                None => {
+                    debug!("Unable to find .jj, falling back to workspace root");
+
+                    repository_root = workspace_root.to_path_buf();
+                    break;
+                }
+            };
+        }
+
+        let git_root = repository_root.join(".git");
+
+        Ok(Jujutsu {
+            default_branch: Arc::new(default_branch.as_ref().to_owned()),
+            git_root: if git_root.is_dir() {
+                debug!(git = ?git_root, "Found a colocated .git directory");
+
+                Some(git_root)
+            } else {
+                None
+            },
+            ignore: load_gitignore(&repository_root)?,
+            jj_root: repository_root.join(".jj"),
+            process: ProcessCache::new("jj", workspace_root),
+            remote_candidates: remote_candidates.to_owned(),
+            repository_root,
+        })
+    }
+
+    /// Convert git-like revisions into a jj revset.
+    fn to_revset<'rev>(&self, revision: &'rev str) -> &'rev str {
+        if revision == "HEAD" {
+            "@"
+        } else {
+            revision
+        }
+    }
+
+    async fn resolve_commit(&self, revset: &str) -> miette::Result<Arc<String>> {
+        self.process
+            .run_with_formatter(
+                [
+                    "log",
+                    "--no-graph",
+                    "--ignore-working-copy",
+                    "--limit",
+                    "1",
+                    "-r",
+                    revset,
+                    "-T",
+                    "commit_id",
+                ],
+                true,
+                |out| out.lines().next().unwrap_or_default().to_owned(),
+            )
+            .await
+    }
+
+    async fn get_merge_base(&self, base: &str, head: &str) -> miette::Result<Option<Arc<String>>> {
+        let mut candidates = vec![];
+
+        // Remote bookmarks use the `name@remote` syntax
+        for candidate in [base.to_owned()].into_iter().chain(
+            self.remote_candidates
+                .iter()
+                .map(|remote| format!("{base}@{remote}")),
+        ) {
+            if self.resolve_commit(&candidate).await.is_ok() {
+                candidates.push(candidate);
+            }
+        }
+
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+
+        let revset = format!("heads(::{head} & ::({}))", candidates.join(" | "));
+
+        match self.resolve_commit(&revset).await {
+            Ok(hash) if !hash.is_empty() => Ok(Some(hash)),
+            _ => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn exec_diff(
+        &self,
+        args: Vec<String>,
+        working_copy: bool,
+    ) -> miette::Result<TouchedFiles> {
+        let output = self
+            .process
+            .run_command(
+                self.process.create_command(
+                    ["--no-pager", "diff", "--summary", "--color", "never"]
+                        .into_iter()
+                        .map(|arg| arg.to_owned())
+                        .chain(args),
+                ),
+                false,
+            )
+            .await?;
+
+        Ok(parse_jj_diff_summary(&output, working_copy))
+    }
+}
+
+#[async_trait]
+impl Vcs for Jujutsu {
+    async fn get_local_branch(&self) -> miette::Result<Arc<String>> {
+        // The working copy is typically a detached commit without a bookmark,
+        // so find the closest ancestor that has a bookmark instead
+        self.process
+            .run_with_formatter(
+                [
+                    "log",
+                    "--no-graph",
+                    "--ignore-working-copy",
+                    "--limit",
+                    "1",
+                    "-r",
+                    "latest(::@ & bookmarks())",
+                    "-T",
+                    r#"local_bookmarks.map(|b| b.name()).join("\n")"#,
+                ],
+                true,
+                |out| out.lines().next().unwrap_or_default().to_owned(),
+            )
+            .await
+    }
+
+    async fn get_local_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.resolve_commit("@").await
+    }
+
+    async fn get_default_branch(&self) -> miette::Result<Arc<String>> {
+        Ok(self.default_branch.clone())
+    }
+
+    async fn get_default_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.resolve_commit(&self.default_branch).await
+    }
+
+    #[instrument(skip_all)]
+    async fn get_file_hashes(
+        &self,
+        files: &[String], // Workspace relative
+        allow_ignored: bool,
+        _batch_size: u16,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        hash_file_contents(&self.process.root, files, |file| {
+            allow_ignored || !self.is_ignored(file)
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_file_tree(&self, dir: &str) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let mut args = vec!["--no-pager", "file", "list"];
+
+        if !dir.is_empty() {
+            args.push(dir);
+        }
+
+        let output = self.process.run(args, false).await?;
+
+        Ok(output
+            .lines()
+            .filter(|file| !file.is_empty() && !file.starts_with("../"))
+            .map(WorkspaceRelativePathBuf::from)
+            .filter(|file| self.process.root.join(file.as_str()).is_file())
+            .collect())
+    }
+
+    async fn get_hooks_dir(&self) -> miette::Result<PathBuf> {
+        // jj does not support hooks, but when colocated,
+        // git commands (and their hooks) may still be used
+        match &self.git_root {
+            Some(git_root) => Ok(git_root.join("hooks")),
+            None => Err(JujutsuError::HooksNotSupported.into()),
+        }
+    }
+
+    async fn get_repository_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.repository_root.clone())
+    }
+
+    async fn get_repository_slug(&self) -> miette::Result<Arc<String>> {
+        use git_url_parse::GitUrl;
+
+        let output = self.process.run(["git", "remote", "list"], true).await?;
+
+        for candidate in &self.remote_candidates {
+            for line in output.lines() {
+                if let Some((name, url)) = line.split_once(' ') {
+                    if name == candidate {
+                        if let Ok(url) = GitUrl::parse(url.trim()) {
+                            return Ok(Arc::new(url.fullname));
+                        }
+                    }
+                }
+            }
+        }
+
+        Err(JujutsuError::ExtractRepoSlugFailed.into())
+    }
+
+    async fn get_touched_files(&self) -> miette::Result<TouchedFiles> {
+        self.exec_diff(vec!["-r".into(), "@".into()], true).await
+    }
+
+    async fn get_touched_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let revision = if self.is_default_branch(revision) {
+            "@-"
+        } else {
+            self.to_revset(revision)
+        };
+
+        self.exec_diff(vec!["-r".into(), revision.into()], false)
+            .await
+    }
+
+    async fn get_touched_files_between_revisions(
+        &self,
+        base_revision: &str,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let revision = self.to_revset(revision);
+        let base = self.get_merge_base(base_revision, revision).await?;
+
+        self.exec_diff(
+            vec![
+                "--from".into(),
+                base.as_ref()
+                    .map(|b| b.as_str())
+                    .unwrap_or(base_revision)
+                    .into(),
+                "--to".into(),
+                revision.into(),
+            ],
+            revision == "@",
+        )
+        .await
+    }
+
+    async fn get_version(&self) -> miette::Result<Version> {
+        let version = self
+            .process
+            .run_with_formatter(["--version"], true, clean_jj_version)
+            .await?;
+
+        Ok(
+            Version::parse(&version).map_err(|error| JujutsuError::InvalidVersion {
+                error: Box::new(error),
+            })?,
+        )
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        let default_branch = &self.default_branch;
+
+        default_branch.as_str() == branch
+            || self
+                .remote_candidates
+                .iter()
+                .any(|remote| format!("{default_branch}@{remote}") == branch)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.jj_root.exists()
+    }
+
+    fn is_ignored(&self, file: &Path) -> bool {
+        if let Some(ignore) = &self.ignore {
+            ignore.matched(file, false).is_ignore()
+        } else {
+            false
+        }
+    }
+
+    async fn is_shallow_checkout(&self) -> miette::Result<bool> {
+        // jj stores shallow information in its backing git repository
+        Ok(self
+            .git_root
+            .as_ref()
+            .unwrap_or(&self.jj_root.join("repo").join("store").join("git"))
+            .join("shallow")
+            .exists())
+    }
+}
@@ -1,12 +1,17 @@
+mod content_hasher;
 mod git;
 mod git_submodule;
 mod git_worktree;
+mod jujutsu;
 mod process_cache;
+mod sapling;
 mod touched_files;
 mod vcs;
 
 pub use git::*;
 pub use git_worktree::*;
+pub use jujutsu::*;
+pub use sapling::*;
 pub use touched_files::*;
 pub use vcs::*;
 
@@ -0,0 +1,471 @@
+use crate::content_hasher::hash_file_contents;
+use crate::git::load_gitignore;
+use crate::process_cache::ProcessCache;
+use crate::touched_files::TouchedFiles;
+use crate::vcs::Vcs;
+use async_trait::async_trait;
+use ignore::gitignore::Gitignore;
+use miette::Diagnostic;
+use moon_common::path::WorkspaceRelativePathBuf;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rustc_hash::FxHashSet;
+use semver::Version;
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+const SAPLING_HOOKS_CONFIG: &str = "moon-hooks.rc";
+
+pub static SAPLING_VERSION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d+)\.(\d+)\.(\d+)").unwrap());
+
+pub fn clean_sapling_version(version: String) -> String {
+    // Sapling 0.2.20240718-145624+f4e9df48
+    SAPLING_VERSION_PATTERN
+        .captures(&version)
+        .map(|caps| {
+            // Strip leading zeros from date based versions
+            let part = |index: usize| match caps[index].trim_start_matches('0') {
+                "" => "0".to_owned(),
+                value => value.to_owned(),
+            };
+
+            format!("{}.{}.{}", part(1), part(2), part(3))
+        })
+        .unwrap_or_else(|| "0.0.0".into())
+}
+
+/// Parse the output of `sl status` into touched files. Since Sapling has no
+/// concept of a staging area, changes will be marked as unstaged when comparing
+/// the working copy, otherwise as staged.
+pub fn parse_sapling_status(output: &str, working_copy: bool) -> TouchedFiles {
+    let mut added = FxHashSet::default();
+    let mut deleted = FxHashSet::default();
+    let mut modified = FxHashSet::default();
+    let mut untracked = FxHashSet::default();
+    let mut changed = FxHashSet::default();
+
+    for line in output.lines() {
+        let Some((status, path)) = line.split_once(' ') else {
+            continue;
+        };
+
+        // Paths are relative from the current directory (workspace root),
+        // so ignore any files that are outside of the workspace
+        if path.starts_with("../") {
+            continue;
+        }
+
+        let file = WorkspaceRelativePathBuf::from(path);
+
+        match status {
+            // Added
+            "A" => {
+                added.insert(file.clone());
+            }
+            // Removed, missing
+            "R" | "!" => {
+                deleted.insert(file.clone());
+            }
+            // Modified
+            "M" => {
+                modified.insert(file.clone());
+            }
+            // Unknown
+            "?" => {
+                untracked.insert(file.clone());
+            }
+            // Ignored, clean
+            _ => {
+                continue;
+            }
+        };
+
+        changed.insert(file);
+    }
+
+    let (staged, unstaged) = if working_copy {
+        (FxHashSet::default(), changed)
+    } else {
+        (changed, FxHashSet::default())
+    };
+
+    TouchedFiles {
+        added,
+        deleted,
+        modified,
+        staged,
+        unstaged,
+        untracked,
+    }
+}
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum SaplingError {
+    #[diagnostic(code(sapling::invalid_version))]
+    #[error("Invalid or unsupported Sapling version.")]
+    InvalidVersion {
+        #[source]
+        error: Box<semver::Error>,
+    },
+
+    #[diagnostic(code(sapling::repository::extract_slug))]
+    #[error("Failed to extract a repository slug from Sapling remote paths.")]
+    ExtractRepoSlugFailed,
+}
+
+#[derive(Debug)]
+pub struct Sapling {
+    /// Ignore rules derived from a root `.gitignore` file.
+    ignore: Option<Gitignore>,
+
+    /// Default branch (bookmark) name.
+    pub default_branch: Arc<String>,
+
+    /// Run and cache `sl` commands.
+    pub process: ProcessCache,
+
+    /// List of remotes to use as merge candidates.
+    pub remote_candidates: Vec<String>,
+
+    /// Root of the repository that contains `.sl`.
+    pub repository_root: PathBuf,
+
+    /// Root of the `.sl` directory.
+    pub sl_root: PathBuf,
+}
+
+impl Sapling {
+    pub fn load<R: AsRef<Path>, B: AsRef<str>>(
+        workspace_root: R,
+        default_branch: B,
+        remote_candidates: &[String],
+    ) -> miette::Result<Sapling> {
+        debug!("Using Sapling as a version control system");
+
+        let workspace_root = workspace_root.as_ref();
+
+        debug!(
+            starting_dir = ?workspace_root,
+            "Attempting to find a .sl directory"
+        );
+
+        let mut current_dir = workspace_root;
+        let repository_root;
+
+        loop {
+            if current_dir.join(".sl").is_dir() {
+                repository_root = current_dir.to_path_buf();
+                break;
+            }
+
+            match current_dir.parent() {
+                Some(parent) => current_dir = parent,
+                None => {
+                    debug!("Unable to find .sl, falling back to workspace root");
+
+                    repository_root = workspace_root.to_path_buf();
+                    break;
+                }
+            };
+        }
+
+        Ok(Sapling {
+            default_branch: Arc::new(default_branch.as_ref().to_owned()),
+            ignore: load_gitignore(&repository_root)?,
+            process: ProcessCache::new("sl", workspace_root),
+            remote_candidates: remote_candidates.to_owned(),
+            sl_root: repository_root.join(".sl"),
+            repository_root,
+        })
+    }
+
+    /// Convert git-like revisions into a Sapling revset.
+    fn to_revset<'rev>(&self, revision: &'rev str) -> &'rev str {
+        if revision == "HEAD" {
+            "."
+        } else {
+            revision
+        }
+    }
+
+    async fn resolve_commit(&self, revset: &str) -> miette::Result<Arc<String>> {
+        self.process
+            .run(["log", "--limit", "1", "-r", revset, "-T", "{node}"], true)
+            .await
+    }
+
+    async fn get_merge_base(&self, base: &str, head: &str) -> miette::Result<Option<Arc<String>>> {
+        // Remote bookmarks use the `remote/name` syntax
+        for candidate in [base.to_owned()].into_iter().chain(
+            self.remote_candidates
+                .iter()
+                .map(|remote| format!("{remote}/{base}")),
+        ) {
+            if let Ok(hash) = self
+                .resolve_commit(&format!("ancestor({candidate}, {head})"))
+                .await
+            {
+                if !hash.is_empty() {
+                    return Ok(Some(hash));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[instrument(skip(self))]
+    async fn exec_status(
+        &self,
+        args: Vec<String>,
+        working_copy: bool,
+    ) -> miette::Result<TouchedFiles> {
+        let output = self
+            .process
+            .run_command(
+                self.process.create_command(
+                    ["status", "--color", "never"]
+                        .into_iter()
+                        .map(|arg| arg.to_owned())
+                        .chain(args),
+                ),
+                false,
+            )
+            .await?;
+
+        Ok(parse_sapling_status(&output, working_copy))
+    }
+}
+
+#[async_trait]
+impl Vcs for Sapling {
+    async fn get_local_branch(&self) -> miette::Result<Arc<String>> {
+        self.process
+            .run(["log", "-r", ".", "-T", "{activebookmark}"], true)
+            .await
+    }
+
+    async fn get_local_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.resolve_commit(".").await
+    }
+
+    async fn get_default_branch(&self) -> miette::Result<Arc<String>> {
+        Ok(self.default_branch.clone())
+    }
+
+    async fn get_default_branch_revision(&self) -> miette::Result<Arc<String>> {
+        self.resolve_commit(&self.default_branch).await
+    }
+
+    #[instrument(skip_all)]
+    async fn get_file_hashes(
+        &self,
+        files: &[String], // Workspace relative
+        allow_ignored: bool,
+        _batch_size: u16,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        hash_file_contents(&self.process.root, files, |file| {
+            allow_ignored || !self.is_ignored(file)
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_file_tree(&self, dir: &str) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let mut args = vec!["files"];
+
+        if !dir.is_empty() {
+            args.push(dir);
+        }
+
+        let output = self.process.run(args, false).await?;
+
+        Ok(output
+            .lines()
+            .filter(|file| !file.is_empty() && !file.starts_with("../"))
+            .map(WorkspaceRelativePathBuf::from)
+            .filter(|file| self.process.root.join(file.as_str()).is_file())
+            .collect())
+    }
+
+    async fn get_hooks_dir(&self) -> miette::Result<PathBuf> {
+        // Sapling configures hooks through its config, but we still
+        // need a location to write moon's hook scripts to
+        Ok(self.sl_root.join("hooks"))
+    }
+
+    async fn get_repository_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.repository_root.clone())
+    }
+
+    async fn get_repository_slug(&self) -> miette::Result<Arc<String>> {
+        use git_url_parse::GitUrl;
+
+        for candidate in &self.remote_candidates {
+            if let Ok(output) = self
+                .process
+                .run_with_formatter(["paths", candidate], true, |out| {
+                    if let Ok(url) = GitUrl::parse(&out) {
+                        url.fullname
+                    } else {
+                        out
+                    }
+                })
+                .await
+            {
+                if !output.is_empty() {
+                    return Ok(output);
+                }
+            }
+        }
+
+        Err(SaplingError::ExtractRepoSlugFailed.into())
+    }
+
+    async fn get_touched_files(&self) -> miette::Result<TouchedFiles> {
+        self.exec_status(vec![], true).await
+    }
+
+    async fn get_touched_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let revision = if self.is_default_branch(revision) {
+            "."
+        } else {
+            self.to_revset(revision)
+        };
+
+        self.exec_status(vec!["--change".into(), revision.into()], false)
+            .await
+    }
+
+    async fn get_touched_files_between_revisions(
+        &self,
+        base_revision: &str,
+        revision: &str,
+    ) -> miette::Result<TouchedFiles> {
+        let revision = self.to_revset(revision);
+        let base = self.get_merge_base(base_revision, revision).await?;
+
+        self.exec_status(
+            vec![
+                "--rev".into(),
+                base.as_ref()
+                    .map(|b| b.as_str())
+                    .unwrap_or(base_revision)
+                    .into(),
+                "--rev".into(),
+                revision.into(),
+            ],
+            false,
+        )
+        .await
+    }
+
+    async fn get_version(&self) -> miette::Result<Version> {
+        let version = self
+            .process
+            .run_with_formatter(["--version"], true, clean_sapling_version)
+            .await?;
+
+        Ok(
+            Version::parse(&version).map_err(|error| SaplingError::InvalidVersion {
+                error: Box::new(error),
+            })?,
+        )
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        let default_branch = &self.default_branch;
+
+        default_branch.as_str() == branch
+            || self
+                .remote_candidates
+                .iter()
+                .any(|remote| format!("{remote}/{default_branch}") == branch)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.sl_root.exists()
+    }
+
+    fn is_ignored(&self, file: &Path) -> bool {
+        if let Some(ignore) = &self.ignore {
+            ignore.matched(file, false).is_ignore()
+        } else {
+            false
+        }
+    }
+
+    async fn is_shallow_checkout(&self) -> miette::Result<bool> {
+        // Sapling lazily fetches history on demand, so is never shallow
+        Ok(false)
+    }
+
+    async fn register_hooks(&self, hooks: &BTreeMap<String, PathBuf>) -> miette::Result<()> {
+        let config_path = self.sl_root.join("config");
+        let hooks_path = self.sl_root.join(SAPLING_HOOKS_CONFIG);
+        let include = format!("%include {SAPLING_HOOKS_CONFIG}");
+
+        let mut config = if config_path.exists() {
+            fs::read_file(&config_path)?
+        } else {
+            String::new()
+        };
+        let has_include = config.lines().any(|line| line.trim() == include);
+
+        if hooks.is_empty() {
+            debug!(config = ?config_path, "Unregistering hooks from Sapling config");
+
+            if has_include {
+                config = config
+                    .lines()
+                    .filter(|line| line.trim() != include)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                config.push('\n');
+
+                fs::write_file(&config_path, config)?;
+            }
+
+            fs::remove_file(hooks_path)?;
+
+            return Ok(());
+        }
+
+        debug!(config = ?config_path, "Registering hooks in Sapling config");
+
+        // Sapling runs hooks from its config, so write them to a separate
+        // file that is included, instead of modifying the user's config
+        let mut contents = vec![
+            "# Automatically generated by moon. DO NOT MODIFY!".to_owned(),
+            "[hooks]".to_owned(),
+        ];
+
+        for (name, path) in hooks {
+            contents.push(format!("{name}.moon = {}", path.display()));
+        }
+
+        contents.push(String::new());
+
+        fs::write_file(hooks_path, contents.join("\n"))?;
+
+        if !has_include {
+            if !config.is_empty() && !config.ends_with('\n') {
+                config.push('\n');
+            }
+
+            config.push_str(&include);
+            config.push('\n');
+
+            fs::write_file(&config_path, config)?;
+        }
+
+        Ok(())
+    }
+}
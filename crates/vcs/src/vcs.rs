@@ -63,6 +63,12 @@ pub trait Vcs: Debug {
     /// Get the version of the current VCS binary
     async fn get_version(&self) -> miette::Result<Version>;
 
+    /// Return true if a shallow checkout will automatically fetch more history
+    /// when comparing revisions.
+    fn can_deepen_shallow_checkout(&self) -> bool {
+        false
+    }
+
     /// Return true if the provided branch matches the default branch.
     fn is_default_branch(&self, branch: &str) -> bool;
 
@@ -81,4 +87,11 @@ pub trait Vcs: Debug {
 
         Ok(VersionReq::parse(req).into_diagnostic()?.matches(&version))
     }
+
+    /// Register the provided hooks (name to script path) with the VCS, for those
+    /// that do not execute scripts in the hooks directory. An empty map will
+    /// unregister all hooks.
+    async fn register_hooks(&self, _hooks: &BTreeMap<String, PathBuf>) -> miette::Result<()> {
+        Ok(())
+    }
 }
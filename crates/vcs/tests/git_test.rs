@@ -1,5 +1,5 @@
 use moon_common::path::{RelativePathBuf, WorkspaceRelativePathBuf};
-use moon_vcs::{clean_git_version, Git, GitWorktree, TouchedFiles, Vcs};
+use moon_vcs::{clean_git_version, get_fetch_refspec, Git, GitWorktree, TouchedFiles, Vcs};
 use rustc_hash::FxHashSet;
 use starbase_sandbox::{create_sandbox, Sandbox};
 use std::collections::BTreeMap;
@@ -750,6 +750,127 @@ mod touched_files_via_diff {
     }
 }
 
+mod shallow_deepening {
+    use super::*;
+    use starbase_sandbox::create_empty_sandbox;
+
+    fn create_shallow_clone() -> (Sandbox, Sandbox) {
+        let (origin, _) = create_git_sandbox("touched");
+
+        origin.run_git(|cmd| {
+            cmd.args(["checkout", "-b", "feature"]);
+        });
+
+        // Commits with the sandbox author, so that a git identity isn't required
+        for index in 0..3 {
+            origin.create_file(format!("file-{index}.txt"), "");
+            origin.enable_git();
+        }
+
+        let clone = create_empty_sandbox();
+        let url = format!("file://{}", origin.path().display());
+
+        clone.run_git(|cmd| {
+            cmd.args(["clone", "--depth", "1", "--branch", "feature", &url, "repo"]);
+        });
+
+        (origin, clone)
+    }
+
+    #[tokio::test]
+    async fn doesnt_find_merge_base_when_disabled() {
+        let (_origin, clone) = create_shallow_clone();
+        let git = Git::load(clone.path().join("repo"), "master", &["origin".into()]).unwrap();
+
+        assert!(git.is_shallow_checkout().await.unwrap());
+        assert!(!git.can_deepen_shallow_checkout());
+        assert!(git
+            .get_touched_files_between_revisions("master", "HEAD")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn deepens_until_merge_base_is_found() {
+        let (_origin, clone) = create_shallow_clone();
+        let mut git = Git::load(clone.path().join("repo"), "master", &["origin".into()]).unwrap();
+        git.deepen_limit = 3;
+
+        assert!(git.can_deepen_shallow_checkout());
+        assert_eq!(
+            git.get_touched_files_between_revisions("master", "HEAD")
+                .await
+                .unwrap(),
+            TouchedFiles {
+                added: create_touched_set(["file-0.txt", "file-1.txt", "file-2.txt"]),
+                staged: create_touched_set(["file-0.txt", "file-1.txt", "file-2.txt"]),
+                ..TouchedFiles::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn deepens_when_base_is_a_remote_branch() {
+        let (_origin, clone) = create_shallow_clone();
+        let mut git = Git::load(clone.path().join("repo"), "master", &["origin".into()]).unwrap();
+        git.deepen_limit = 3;
+
+        assert_eq!(
+            git.get_touched_files_between_revisions("origin/master", "HEAD")
+                .await
+                .unwrap(),
+            TouchedFiles {
+                added: create_touched_set(["file-0.txt", "file-1.txt", "file-2.txt"]),
+                staged: create_touched_set(["file-0.txt", "file-1.txt", "file-2.txt"]),
+                ..TouchedFiles::default()
+            }
+        );
+    }
+
+    #[test]
+    fn maps_base_to_fetch_refspec() {
+        let remotes = vec!["origin".to_string(), "upstream".to_string()];
+
+        assert_eq!(
+            get_fetch_refspec("master", "origin", &remotes).unwrap(),
+            "+refs/heads/master:refs/remotes/origin/master"
+        );
+        assert_eq!(
+            get_fetch_refspec("refs/heads/master", "origin", &remotes).unwrap(),
+            "+refs/heads/master:refs/remotes/origin/master"
+        );
+        assert_eq!(
+            get_fetch_refspec("origin/feature/a", "origin", &remotes).unwrap(),
+            "+refs/heads/feature/a:refs/remotes/origin/feature/a"
+        );
+        assert_eq!(
+            get_fetch_refspec("refs/remotes/upstream/master", "upstream", &remotes).unwrap(),
+            "+refs/heads/master:refs/remotes/upstream/master"
+        );
+        assert_eq!(
+            get_fetch_refspec("refs/tags/v1.0.0", "origin", &remotes).unwrap(),
+            "+refs/tags/v1.0.0:refs/tags/v1.0.0"
+        );
+        assert_eq!(
+            get_fetch_refspec(
+                "3b18e512dba79e4c8300dd08aeb37f8e728b8dad",
+                "origin",
+                &remotes
+            )
+            .unwrap(),
+            "3b18e512dba79e4c8300dd08aeb37f8e728b8dad"
+        );
+        assert_eq!(
+            get_fetch_refspec("upstream/master", "origin", &remotes),
+            None
+        );
+        assert_eq!(
+            get_fetch_refspec("refs/remotes/upstream/master", "origin", &remotes),
+            None
+        );
+    }
+}
+
 mod version_cleaning {
     use super::*;
 
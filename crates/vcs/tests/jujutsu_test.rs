@@ -0,0 +1,244 @@
+use moon_common::path::WorkspaceRelativePathBuf;
+use moon_vcs::{clean_jj_version, expand_jj_renamed_path, parse_jj_diff_summary, Jujutsu, Vcs};
+use rustc_hash::FxHashSet;
+use starbase_sandbox::{create_empty_sandbox, Sandbox};
+use std::collections::BTreeMap;
+use std::process::Command;
+
+fn create_jj_sandbox() -> (Sandbox, Jujutsu) {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(".jj/repo/.keep", "");
+    sandbox.create_file(".gitignore", "foo/*.txt");
+    sandbox.create_file("foo/file.js", "foo");
+    sandbox.create_file("foo/file.txt", "foo");
+
+    let jj = Jujutsu::load(sandbox.path(), "main", &["origin".into()]).unwrap();
+
+    (sandbox, jj)
+}
+
+fn create_touched_set<I: IntoIterator<Item = V>, V: AsRef<str>>(
+    files: I,
+) -> FxHashSet<WorkspaceRelativePathBuf> {
+    FxHashSet::from_iter(
+        files
+            .into_iter()
+            .map(|file| WorkspaceRelativePathBuf::from(file.as_ref())),
+    )
+}
+
+#[tokio::test]
+async fn is_enabled() {
+    let (_sandbox, jj) = create_jj_sandbox();
+
+    assert!(jj.is_enabled());
+}
+
+#[tokio::test]
+async fn matches_default_branch_and_remote_bookmarks() {
+    let (_sandbox, jj) = create_jj_sandbox();
+
+    assert!(jj.is_default_branch("main"));
+    assert!(jj.is_default_branch("main@origin"));
+    assert!(!jj.is_default_branch("main@upstream"));
+    assert!(!jj.is_default_branch("feature"));
+}
+
+#[tokio::test]
+async fn uses_git_hooks_when_colocated() {
+    let (sandbox, _) = create_jj_sandbox();
+    sandbox.create_file(".git/HEAD", "");
+
+    let jj = Jujutsu::load(sandbox.path(), "main", &[]).unwrap();
+
+    assert_eq!(
+        jj.get_hooks_dir().await.unwrap(),
+        sandbox.path().join(".git/hooks")
+    );
+}
+
+#[tokio::test]
+async fn errors_for_hooks_when_not_colocated() {
+    let (_sandbox, jj) = create_jj_sandbox();
+
+    assert!(jj.get_hooks_dir().await.is_err());
+}
+
+#[tokio::test]
+async fn respects_gitignore() {
+    let (sandbox, jj) = create_jj_sandbox();
+
+    assert!(jj.is_ignored(&sandbox.path().join("foo/file.txt")));
+    assert!(!jj.is_ignored(&sandbox.path().join("foo/file.js")));
+}
+
+#[tokio::test]
+async fn hashes_file_contents() {
+    let (_sandbox, jj) = create_jj_sandbox();
+
+    let hashes = jj
+        .get_file_hashes(
+            &[
+                "foo/file.js".into(),
+                "foo/file.txt".into(),
+                "missing.js".into(),
+            ],
+            false,
+            100,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        hashes,
+        BTreeMap::from([(
+            WorkspaceRelativePathBuf::from("foo/file.js"),
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae".to_owned()
+        )])
+    );
+}
+
+// These require the `jj` binary, so are skipped when not installed
+mod touched_files {
+    use super::*;
+
+    fn run_jj(sandbox: &Sandbox, args: &[&str]) {
+        let output = Command::new("jj")
+            .args(args)
+            .env("JJ_USER", "Sandbox")
+            .env("JJ_EMAIL", "fakeemail@somedomain.dev")
+            .current_dir(sandbox.path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    fn create_jj_repo() -> Option<(Sandbox, Jujutsu)> {
+        if Command::new("jj").arg("--version").output().is_err() {
+            return None;
+        }
+
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("existing.txt", "");
+        sandbox.create_file("removed.txt", "");
+
+        run_jj(&sandbox, &["git", "init"]);
+        run_jj(&sandbox, &["commit", "-m", "Initial"]);
+
+        let jj = Jujutsu::load(sandbox.path(), "main", &["origin".into()]).unwrap();
+
+        Some((sandbox, jj))
+    }
+
+    #[tokio::test]
+    async fn detects_working_copy_changes() {
+        let Some((sandbox, jj)) = create_jj_repo() else {
+            return;
+        };
+
+        sandbox.create_file("existing.txt", "modified");
+        sandbox.create_file("added.txt", "");
+        std::fs::remove_file(sandbox.path().join("removed.txt")).unwrap();
+
+        let touched = jj.get_touched_files().await.unwrap();
+
+        assert_eq!(touched.added, create_touched_set(["added.txt"]));
+        assert_eq!(touched.deleted, create_touched_set(["removed.txt"]));
+        assert_eq!(touched.modified, create_touched_set(["existing.txt"]));
+        assert_eq!(
+            touched.unstaged,
+            create_touched_set(["added.txt", "existing.txt", "removed.txt"])
+        );
+    }
+
+    #[tokio::test]
+    async fn detects_changes_in_previous_revision() {
+        let Some((sandbox, jj)) = create_jj_repo() else {
+            return;
+        };
+
+        sandbox.create_file("existing.txt", "modified");
+        run_jj(&sandbox, &["commit", "-m", "Modify"]);
+
+        let touched = jj
+            .get_touched_files_against_previous_revision("@-")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["existing.txt"]));
+        assert_eq!(touched.staged, create_touched_set(["existing.txt"]));
+    }
+}
+
+mod diff_summary {
+    use super::*;
+
+    #[test]
+    fn parses_statuses() {
+        let touched = parse_jj_diff_summary(
+            "M src/index.ts\nA src/new.ts\nD src/old.ts\nM ../outside.ts\n",
+            false,
+        );
+
+        assert_eq!(touched.added, create_touched_set(["src/new.ts"]));
+        assert_eq!(touched.deleted, create_touched_set(["src/old.ts"]));
+        assert_eq!(touched.modified, create_touched_set(["src/index.ts"]));
+        assert_eq!(
+            touched.staged,
+            create_touched_set(["src/index.ts", "src/new.ts", "src/old.ts"])
+        );
+        assert!(touched.unstaged.is_empty());
+    }
+
+    #[test]
+    fn marks_working_copy_as_unstaged() {
+        let touched = parse_jj_diff_summary("M src/index.ts\n", true);
+
+        assert!(touched.staged.is_empty());
+        assert_eq!(touched.unstaged, create_touched_set(["src/index.ts"]));
+    }
+
+    #[test]
+    fn parses_renames_and_copies() {
+        let touched =
+            parse_jj_diff_summary("R src/{old.ts => new.ts}\nC {a => b}/file.ts\n", false);
+
+        assert_eq!(
+            touched.added,
+            create_touched_set(["src/new.ts", "b/file.ts"])
+        );
+        assert_eq!(touched.deleted, create_touched_set(["src/old.ts"]));
+    }
+
+    #[test]
+    fn expands_renamed_paths() {
+        assert_eq!(
+            expand_jj_renamed_path("src/{old.ts => new.ts}"),
+            ("src/old.ts".into(), "src/new.ts".into())
+        );
+        assert_eq!(
+            expand_jj_renamed_path("{a => b}/file.ts"),
+            ("a/file.ts".into(), "b/file.ts".into())
+        );
+        assert_eq!(
+            expand_jj_renamed_path("src/{ => nested}/file.ts"),
+            ("src/file.ts".into(), "src/nested/file.ts".into())
+        );
+        assert_eq!(
+            expand_jj_renamed_path("file.ts"),
+            ("file.ts".into(), "file.ts".into())
+        );
+    }
+}
+
+mod version_cleaning {
+    use super::*;
+
+    #[test]
+    fn cleans() {
+        assert_eq!(clean_jj_version("jj 0.24.0".into()), "0.24.0");
+        assert_eq!(clean_jj_version("jj 0.24.0-3d7a0a3f9e8d".into()), "0.24.0");
+        assert_eq!(clean_jj_version("unknown".into()), "0.0.0");
+    }
+}
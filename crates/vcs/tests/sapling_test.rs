@@ -0,0 +1,236 @@
+use moon_common::path::WorkspaceRelativePathBuf;
+use moon_vcs::{clean_sapling_version, parse_sapling_status, Sapling, Vcs};
+use rustc_hash::FxHashSet;
+use starbase_sandbox::{create_empty_sandbox, Sandbox};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn create_sl_sandbox() -> (Sandbox, Sapling) {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(".sl/requires", "");
+    sandbox.create_file(".gitignore", "*.log");
+
+    let sl = Sapling::load(sandbox.path(), "main", &["remote".into()]).unwrap();
+
+    (sandbox, sl)
+}
+
+fn create_touched_set<I: IntoIterator<Item = V>, V: AsRef<str>>(
+    files: I,
+) -> FxHashSet<WorkspaceRelativePathBuf> {
+    FxHashSet::from_iter(
+        files
+            .into_iter()
+            .map(|file| WorkspaceRelativePathBuf::from(file.as_ref())),
+    )
+}
+
+#[tokio::test]
+async fn is_enabled() {
+    let (_sandbox, sl) = create_sl_sandbox();
+
+    assert!(sl.is_enabled());
+}
+
+#[tokio::test]
+async fn matches_default_branch_and_remote_bookmarks() {
+    let (_sandbox, sl) = create_sl_sandbox();
+
+    assert!(sl.is_default_branch("main"));
+    assert!(sl.is_default_branch("remote/main"));
+    assert!(!sl.is_default_branch("feature"));
+}
+
+#[tokio::test]
+async fn respects_gitignore() {
+    let (sandbox, sl) = create_sl_sandbox();
+
+    assert!(sl.is_ignored(&sandbox.path().join("debug.log")));
+    assert!(!sl.is_ignored(&sandbox.path().join("index.js")));
+}
+
+#[tokio::test]
+async fn is_never_shallow() {
+    let (_sandbox, sl) = create_sl_sandbox();
+
+    assert!(!sl.is_shallow_checkout().await.unwrap());
+}
+
+mod hooks {
+    use super::*;
+
+    #[tokio::test]
+    async fn registers_hooks_in_config() {
+        let (sandbox, sl) = create_sl_sandbox();
+        sandbox.create_file(".sl/config", "[ui]\nusername = Sandbox");
+
+        let hook_path = sl.get_hooks_dir().await.unwrap().join("pre-commit");
+
+        sl.register_hooks(&BTreeMap::from_iter([(
+            "pre-commit".to_string(),
+            hook_path.clone(),
+        )]))
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join(".sl/config")).unwrap(),
+            "[ui]\nusername = Sandbox\n%include moon-hooks.rc\n"
+        );
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join(".sl/moon-hooks.rc")).unwrap(),
+            format!(
+                "# Automatically generated by moon. DO NOT MODIFY!\n[hooks]\npre-commit.moon = {}\n",
+                hook_path.display()
+            )
+        );
+
+        // Doesn't include multiple times
+        sl.register_hooks(&BTreeMap::from_iter([(
+            "pre-push".to_string(),
+            PathBuf::from("pre-push"),
+        )]))
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join(".sl/config")).unwrap(),
+            "[ui]\nusername = Sandbox\n%include moon-hooks.rc\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn unregisters_hooks_from_config() {
+        let (sandbox, sl) = create_sl_sandbox();
+        sandbox.create_file(".sl/config", "[ui]\nusername = Sandbox\n");
+
+        sl.register_hooks(&BTreeMap::from_iter([(
+            "pre-commit".to_string(),
+            PathBuf::from("pre-commit"),
+        )]))
+        .await
+        .unwrap();
+
+        assert!(sandbox.path().join(".sl/moon-hooks.rc").exists());
+
+        sl.register_hooks(&BTreeMap::new()).await.unwrap();
+
+        assert!(!sandbox.path().join(".sl/moon-hooks.rc").exists());
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join(".sl/config")).unwrap(),
+            "[ui]\nusername = Sandbox\n"
+        );
+    }
+}
+
+// These require the `sl` binary, so are skipped when not installed
+mod touched_files {
+    use super::*;
+
+    fn run_sl(sandbox: &Sandbox, args: &[&str]) {
+        let output = Command::new("sl")
+            .args(args)
+            .args(["--config", "ui.username=Sandbox <fakeemail@somedomain.dev>"])
+            .current_dir(sandbox.path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    fn create_sl_repo() -> Option<(Sandbox, Sapling)> {
+        if Command::new("sl").arg("--version").output().is_err() {
+            return None;
+        }
+
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("existing.txt", "");
+
+        run_sl(&sandbox, &["init", "--git"]);
+        run_sl(&sandbox, &["commit", "--addremove", "-m", "Initial"]);
+
+        let sl = Sapling::load(sandbox.path(), "main", &["remote".into()]).unwrap();
+
+        Some((sandbox, sl))
+    }
+
+    #[tokio::test]
+    async fn detects_working_copy_changes() {
+        let Some((sandbox, sl)) = create_sl_repo() else {
+            return;
+        };
+
+        sandbox.create_file("existing.txt", "modified");
+        sandbox.create_file("new.txt", "");
+
+        let touched = sl.get_touched_files().await.unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["existing.txt"]));
+        assert_eq!(touched.untracked, create_touched_set(["new.txt"]));
+        assert!(touched.staged.is_empty());
+    }
+
+    #[tokio::test]
+    async fn detects_changes_in_previous_revision() {
+        let Some((sandbox, sl)) = create_sl_repo() else {
+            return;
+        };
+
+        sandbox.create_file("existing.txt", "modified");
+        run_sl(&sandbox, &["commit", "-m", "Modify"]);
+
+        let touched = sl
+            .get_touched_files_against_previous_revision("main")
+            .await
+            .unwrap();
+
+        assert_eq!(touched.modified, create_touched_set(["existing.txt"]));
+        assert_eq!(touched.staged, create_touched_set(["existing.txt"]));
+    }
+}
+
+mod status {
+    use super::*;
+
+    #[test]
+    fn parses_statuses() {
+        let touched = parse_sapling_status(
+            "M src/index.ts\nA src/new.ts\nR src/old.ts\n! src/missing.ts\n? src/unknown.ts\nI src/ignored.ts\nM ../outside.ts\n",
+            true,
+        );
+
+        assert_eq!(touched.added, create_touched_set(["src/new.ts"]));
+        assert_eq!(
+            touched.deleted,
+            create_touched_set(["src/old.ts", "src/missing.ts"])
+        );
+        assert_eq!(touched.modified, create_touched_set(["src/index.ts"]));
+        assert_eq!(touched.untracked, create_touched_set(["src/unknown.ts"]));
+        assert_eq!(touched.unstaged.len(), 5);
+        assert!(touched.staged.is_empty());
+    }
+
+    #[test]
+    fn marks_revisions_as_staged() {
+        let touched = parse_sapling_status("M src/index.ts\n", false);
+
+        assert_eq!(touched.staged, create_touched_set(["src/index.ts"]));
+        assert!(touched.unstaged.is_empty());
+    }
+}
+
+mod version_cleaning {
+    use super::*;
+
+    #[test]
+    fn cleans() {
+        assert_eq!(
+            clean_sapling_version("Sapling 0.2.20240718-145624+f4e9df48".into()),
+            "0.2.20240718"
+        );
+        assert_eq!(clean_sapling_version("Sapling 1.0.0".into()), "1.0.0");
+        assert_eq!(clean_sapling_version("unknown".into()), "0.0.0");
+    }
+}
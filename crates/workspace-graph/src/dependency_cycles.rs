@@ -0,0 +1,242 @@
+use crate::locate_declaration::locate_declaration_line;
+use crate::WorkspaceGraph;
+use moon_common::Id;
+use moon_config::ConfigFinder;
+use moon_graph_utils::find_elementary_cycles;
+use moon_project_graph::Project;
+use moon_task_graph::Target;
+use petgraph::graph::{DiGraph, NodeIndex};
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use tracing::{debug, instrument};
+
+/// Maximum number of cycles to report, as the number of
+/// elementary cycles can grow exponentially in dense graphs.
+pub const MAX_DEPENDENCY_CYCLES: usize = 100;
+
+/// An edge within a cycle, and where the dependency was declared.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DependencyCycleEdge {
+    /// Workspace relative path to the config file that declared the dependency.
+    pub file: Option<String>,
+
+    /// Line number (1-based) of the declaration within the config file.
+    pub line: Option<usize>,
+
+    pub source: String,
+    pub target: String,
+}
+
+/// A cycle of dependencies, where the last edge points back to the first node.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DependencyCycle {
+    pub edges: Vec<DependencyCycleEdge>,
+}
+
+impl DependencyCycle {
+    /// Return the cycle as a path of node labels, ending with the starting node.
+    pub fn get_path(&self) -> Vec<&str> {
+        let mut path = self
+            .edges
+            .iter()
+            .map(|edge| edge.source.as_str())
+            .collect::<Vec<_>>();
+
+        if let Some(first) = self.edges.first() {
+            path.push(first.source.as_str());
+        }
+
+        path
+    }
+}
+
+struct DeclaredGraph<K> {
+    graph: DiGraph<K, ()>,
+    indices: FxHashMap<K, NodeIndex>,
+}
+
+impl<K: Clone + Eq + Hash> DeclaredGraph<K> {
+    fn new() -> Self {
+        Self {
+            graph: DiGraph::new(),
+            indices: FxHashMap::default(),
+        }
+    }
+
+    fn get_or_insert(&mut self, key: &K) -> NodeIndex {
+        if let Some(index) = self.indices.get(key) {
+            return *index;
+        }
+
+        let index = self.graph.add_node(key.clone());
+        self.indices.insert(key.clone(), index);
+        index
+    }
+
+    fn add_edge(&mut self, source: &K, target: &K) {
+        let source = self.get_or_insert(source);
+        let target = self.get_or_insert(target);
+
+        self.graph.update_edge(source, target, ());
+    }
+
+    fn find_cycles(&self) -> Vec<Vec<K>> {
+        find_elementary_cycles(&self.graph, MAX_DEPENDENCY_CYCLES)
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|index| self.graph[index].clone())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl WorkspaceGraph {
+    /// Find all dependency cycles between projects. The project graph disconnects
+    /// cycles while building, so the graph is re-created from the declared dependencies.
+    #[instrument(skip(self))]
+    pub fn find_project_cycles(&self) -> miette::Result<Vec<DependencyCycle>> {
+        let mut declared = DeclaredGraph::<Id>::new();
+
+        for project in self.get_projects()? {
+            declared.get_or_insert(&project.id);
+
+            for dep_config in &project.dependencies {
+                if !dep_config.is_root_scope() {
+                    declared.add_edge(&project.id, &dep_config.id);
+                }
+            }
+        }
+
+        let mut cycles = vec![];
+
+        for cycle in declared.find_cycles() {
+            let mut edges = vec![];
+
+            for (i, id) in cycle.iter().enumerate() {
+                let dep_id = &cycle[(i + 1) % cycle.len()];
+                let project = self.get_project(id)?;
+                let mut edge = DependencyCycleEdge {
+                    source: id.to_string(),
+                    target: dep_id.to_string(),
+                    ..Default::default()
+                };
+
+                if let Some((file, content)) = read_project_config(&project) {
+                    edge.line = locate_declaration_line(
+                        Path::new(&file),
+                        &content,
+                        &["dependsOn"],
+                        &|value: &str| value == dep_id.as_str(),
+                    );
+                    edge.file = Some(file);
+                }
+
+                edges.push(edge);
+            }
+
+            cycles.push(DependencyCycle { edges });
+        }
+
+        debug!("Found {} project dependency cycles", cycles.len());
+
+        Ok(cycles)
+    }
+
+    /// Find all dependency cycles between tasks. The task graph disconnects
+    /// cycles while building, so the graph is re-created from the declared dependencies.
+    #[instrument(skip(self))]
+    pub fn find_task_cycles(&self) -> miette::Result<Vec<DependencyCycle>> {
+        let mut declared = DeclaredGraph::<Target>::new();
+
+        for task in self.get_tasks_with_internal()? {
+            declared.get_or_insert(&task.target);
+
+            for dep_config in &task.deps {
+                declared.add_edge(&task.target, &dep_config.target);
+            }
+        }
+
+        let mut cycles = vec![];
+
+        for cycle in declared.find_cycles() {
+            let mut edges = vec![];
+
+            for (i, target) in cycle.iter().enumerate() {
+                edges.push(self.locate_task_dependency(target, &cycle[(i + 1) % cycle.len()])?);
+            }
+
+            cycles.push(DependencyCycle { edges });
+        }
+
+        debug!("Found {} task dependency cycles", cycles.len());
+
+        Ok(cycles)
+    }
+
+    /// Locate where a task dependency was declared, within the config
+    /// of the project that owns the task. Inherited dependencies are not
+    /// declared in the project config, so will not have a location.
+    pub fn locate_task_dependency(
+        &self,
+        target: &Target,
+        dep_target: &Target,
+    ) -> miette::Result<DependencyCycleEdge> {
+        let mut edge = DependencyCycleEdge {
+            source: target.to_string(),
+            target: dep_target.to_string(),
+            ..Default::default()
+        };
+
+        let Some(project_id) = target.get_project_id() else {
+            return Ok(edge);
+        };
+
+        let project = self.get_project(project_id)?;
+
+        if let Some((file, content)) = read_project_config(&project) {
+            let dep_task_id = dep_target.task_id.as_str();
+            let same_project = dep_target.get_project_id() == Some(project_id);
+
+            edge.line = locate_declaration_line(
+                Path::new(&file),
+                &content,
+                &["tasks", target.task_id.as_str(), "deps"],
+                &|value: &str| {
+                    if value == dep_target.as_str() {
+                        return true;
+                    }
+
+                    match value.split_once(':') {
+                        Some(("~", id)) => same_project && id == dep_task_id,
+                        Some(("^", id)) => !same_project && id == dep_task_id,
+                        Some(_) => false,
+                        None => same_project && value == dep_task_id,
+                    }
+                },
+            );
+
+            if edge.line.is_some() {
+                edge.file = Some(file);
+            }
+        }
+
+        Ok(edge)
+    }
+}
+
+fn read_project_config(project: &Project) -> Option<(String, String)> {
+    let file = ConfigFinder::with_pkl()
+        .get_project_files(&project.root)
+        .into_iter()
+        .find(|file| file.exists())?;
+
+    let content = fs::read_to_string(&file).ok()?;
+    let name = file.file_name()?.to_str()?;
+
+    Some((project.source.join(name).to_string(), content))
+}
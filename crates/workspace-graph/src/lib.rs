@@ -1,3 +1,5 @@
+mod dependency_cycles;
+mod locate_declaration;
 mod query_projects;
 mod query_tasks;
 
@@ -7,6 +9,8 @@ use moon_task_graph::{Target, Task, TaskGraph};
 use scc::HashMap;
 use std::{path::Path, sync::Arc};
 
+pub use dependency_cycles::*;
+pub use locate_declaration::*;
 pub use moon_graph_utils::*;
 pub use moon_project_graph as projects;
 pub use moon_task_graph as tasks;
@@ -0,0 +1,232 @@
+use serde::de::{DeserializeSeed, Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+use std::path::Path;
+
+// Returned as an error to stop deserializing once the value has been found,
+// so that the parser reports the location of the value.
+const FOUND: &str = "__moon_declaration_found__";
+
+type Matcher<'a> = &'a dyn Fn(&str) -> bool;
+
+/// Locate the line (1-based) of a value declared within a config file, by parsing
+/// the file and walking the provided keys (for example `tasks`, a task ID, and
+/// then `deps`), until an item in the list matches.
+///
+/// Items may be strings, or maps with an `id` or `target` field. Formats that
+/// can't be parsed with location information (Pkl) will return `None`.
+pub fn locate_declaration_line(
+    path: &Path,
+    content: &str,
+    keys: &[&str],
+    matcher: &dyn Fn(&str) -> bool,
+) -> Option<usize> {
+    let seed = PathSeed { keys, matcher };
+
+    match path.extension().and_then(|ext| ext.to_str())? {
+        "yml" | "yaml" => {
+            let error = seed
+                .deserialize(serde_yaml::Deserializer::from_str(content))
+                .err()?;
+
+            if error.to_string().contains(FOUND) {
+                return error.location().map(|loc| loc.line());
+            }
+        }
+        "json" | "jsonc" => {
+            // Comments are replaced with whitespace, so positions are preserved
+            let content = starbase_utils::json::clean(content).ok()?;
+            let error = seed
+                .deserialize(&mut serde_json::Deserializer::from_str(&content))
+                .err()?;
+
+            if error.to_string().contains(FOUND) {
+                return Some(error.line());
+            }
+        }
+        "toml" => {
+            let error = seed.deserialize(toml::Deserializer::new(content)).err()?;
+
+            if error.message() == FOUND {
+                return error
+                    .span()
+                    .map(|span| content[..span.start].matches('\n').count() + 1);
+            }
+        }
+        _ => {}
+    }
+
+    None
+}
+
+struct PathSeed<'a> {
+    keys: &'a [&'a str],
+    matcher: Matcher<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for PathSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PathSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map or list")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Some((key, rest)) = self.keys.split_first() else {
+            return Err(A::Error::custom("expected a list"));
+        };
+
+        while let Some(next_key) = map.next_key::<String>()? {
+            if next_key == *key {
+                map.next_value_seed(PathSeed {
+                    keys: rest,
+                    matcher: self.matcher,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        if !self.keys.is_empty() {
+            return Err(A::Error::custom("expected a map"));
+        }
+
+        while seq
+            .next_element_seed(ItemSeed {
+                matcher: self.matcher,
+            })?
+            .is_some()
+        {}
+
+        Ok(())
+    }
+}
+
+struct ItemSeed<'a> {
+    matcher: Matcher<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for ItemSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ItemSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or map")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<(), E> {
+        if (self.matcher)(value) {
+            return Err(E::custom(FOUND));
+        }
+
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "id" || key == "target" {
+                map.next_value_seed(ItemSeed {
+                    matcher: self.matcher,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locate(file: &str, content: &str, keys: &[&str], needle: &str) -> Option<usize> {
+        locate_declaration_line(Path::new(file), content, keys, &|value: &str| {
+            value == needle
+        })
+    }
+
+    #[test]
+    fn locates_in_yaml() {
+        let content =
+            "language: 'rust'\ndependsOn:\n  - 'foo'\n  - id: 'bar'\n    scope: 'build'\n";
+
+        assert_eq!(locate("moon.yml", content, &["dependsOn"], "foo"), Some(3));
+        assert_eq!(locate("moon.yml", content, &["dependsOn"], "bar"), Some(4));
+        assert_eq!(locate("moon.yml", content, &["dependsOn"], "baz"), None);
+    }
+
+    #[test]
+    fn locates_in_nested_yaml() {
+        let content = "tasks:\n  lint:\n    deps: ['^:build']\n  build:\n    command: 'x'\n    deps:\n      - 'lint'\n";
+
+        assert_eq!(
+            locate("moon.yml", content, &["tasks", "build", "deps"], "lint"),
+            Some(7)
+        );
+        assert_eq!(
+            locate("moon.yml", content, &["tasks", "lint", "deps"], "^:build"),
+            Some(3)
+        );
+        // Keys must match exactly, not by substring
+        assert_eq!(
+            locate("moon.yml", content, &["tasks", "buil", "deps"], "lint"),
+            None
+        );
+    }
+
+    #[test]
+    fn locates_in_json() {
+        let content =
+            "{\n  // comment\n  \"dependsOn\": [\n    \"foo-bar\",\n    \"foo\"\n  ]\n}\n";
+
+        assert_eq!(locate("moon.json", content, &["dependsOn"], "foo"), Some(5));
+        assert_eq!(
+            locate("moon.json", content, &["dependsOn"], "foo-bar"),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn locates_in_toml() {
+        let content = "language = \"rust\"\ndependsOn = [\"foo-bar\", \"foo\"]\n\n[tasks.build]\ncommand = \"x\"\ndeps = [\n  { target = \"lint\" },\n]\n";
+
+        assert_eq!(locate("moon.toml", content, &["dependsOn"], "foo"), Some(2));
+        assert_eq!(
+            locate("moon.toml", content, &["tasks", "build", "deps"], "lint"),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn ignores_unsupported_formats() {
+        assert_eq!(
+            locate(
+                "moon.pkl",
+                "dependsOn = List(\"foo\")",
+                &["dependsOn"],
+                "foo"
+            ),
+            None
+        );
+    }
+}
@@ -79,6 +79,9 @@ impl WorkspaceGraph {
                 Condition::Field { field, .. } => {
                     let result = match field {
                         Field::Language(langs) => condition.matches_enum(langs, &project.language),
+                        Field::Owner(owners) => {
+                            condition.matches_list(owners, &project.config.owners.get_owners())
+                        }
                         Field::Project(ids) => {
                             if condition.matches(ids, &project.id)? {
                                 Ok(true)
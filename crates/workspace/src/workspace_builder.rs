@@ -16,7 +16,9 @@ use moon_config::{
 };
 use moon_project::Project;
 use moon_project_builder::{ProjectBuilder, ProjectBuilderContext};
-use moon_project_constraints::{enforce_project_type_relationships, enforce_tag_relationships};
+use moon_project_constraints::{
+    enforce_project_type_relationships, enforce_tag_relationships, ProjectGraphConstraints,
+};
 use moon_project_graph::{ProjectGraph, ProjectGraphError, ProjectGraphType, ProjectMetadata};
 use moon_task::Target;
 use moon_task_builder::TaskDepsBuilder;
@@ -572,7 +574,7 @@ impl<'app> WorkspaceBuilder<'app> {
         let tag_relationships = &context.workspace_config.constraints.tag_relationships;
 
         if !type_relationships && tag_relationships.is_empty() {
-            return Ok(());
+            return self.enforce_graph_constraints();
         }
 
         let default_scope = DependencyScope::Build;
@@ -606,7 +608,20 @@ impl<'app> WorkspaceBuilder<'app> {
             }
         }
 
-        Ok(())
+        self.enforce_graph_constraints()
+    }
+
+    /// Enforce constraints that span the entire graph, like layers and deny rules,
+    /// which report all violations at once instead of failing on the first.
+    fn enforce_graph_constraints(&self) -> miette::Result<()> {
+        let context = self.context();
+        let constraints = &context.workspace_config.constraints;
+
+        if !constraints.has_graph_constraints() {
+            return Ok(());
+        }
+
+        ProjectGraphConstraints::new(constraints, self.project_graph.node_weights()).enforce()
     }
 
     /// When caching the graph, we must hash all project and workspace
@@ -628,7 +643,7 @@ impl<'app> WorkspaceBuilder<'app> {
         // Hash all workspace-level config files
         for file in glob::walk(
             context.workspace_root.join(consts::CONFIG_DIRNAME),
-            ["*.pkl", "tasks/**/*.pkl", "*.yml", "tasks/**/*.yml"],
+            &context.config_loader.get_workspace_globs(),
         )? {
             configs.push(to_virtual_string(
                 file.strip_prefix(context.workspace_root).unwrap(),
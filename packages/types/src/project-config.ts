@@ -45,10 +45,26 @@ export type ProjectDependsOn = string | DependencyConfig;
 export interface ProjectDockerFileConfig {
 	/** A task within the current project for building the project. */
 	buildTask: string | null;
+	/**
+	 * List of absolute paths within the image to mount as BuildKit
+	 * caches when installing dependencies, in addition to the
+	 * package manager caches of the project's toolchains.
+	 */
+	cacheMounts: string[];
 	/** The base Docker image. */
 	image: string | null;
+	/**
+	 * A slim Docker image for the start stage. When defined, only the
+	 * outputs of the build task are copied into the final image.
+	 */
+	runtimeImage: string | null;
 	/** A task within the current project for starting the project. */
 	startTask: string | null;
+	/**
+	 * A non-root user (or UID) to run the start stage as, when using
+	 * a runtime image. The user must exist in the runtime image.
+	 */
+	user: string | null;
 }
 
 /** Configures aspects of the Docker scaffolding process. */
@@ -104,12 +120,22 @@ export interface OwnersConfig {
 	 * When an object, the key is a path, and the value is a list of owners.
 	 */
 	paths: OwnersPaths;
+	/**
+	 * Bitbucket only. The number of reviewers to randomly assign from
+	 * the owners of each path, using the `Random()` condition.
+	 */
+	randomReviewers: number | null;
 	/**
 	 * Bitbucket and GitLab only. The number of approvals required for the
 	 * request to be satisfied. For Bitbucket, utilizes the `Check()` condition.
 	 * For GitLab, marks the code owners section as required.
 	 */
 	requiredApprovals: number | null;
+	/**
+	 * GitLab only. Name of the code owners section.
+	 * Defaults to the project ID.
+	 */
+	section: string | null;
 }
 
 /** Expanded information about the project. */
@@ -311,10 +337,26 @@ export type PartialProjectDependsOn = string | PartialDependencyConfig;
 export interface PartialProjectDockerFileConfig {
 	/** A task within the current project for building the project. */
 	buildTask?: string | null;
+	/**
+	 * List of absolute paths within the image to mount as BuildKit
+	 * caches when installing dependencies, in addition to the
+	 * package manager caches of the project's toolchains.
+	 */
+	cacheMounts?: string[] | null;
 	/** The base Docker image. */
 	image?: string | null;
+	/**
+	 * A slim Docker image for the start stage. When defined, only the
+	 * outputs of the build task are copied into the final image.
+	 */
+	runtimeImage?: string | null;
 	/** A task within the current project for starting the project. */
 	startTask?: string | null;
+	/**
+	 * A non-root user (or UID) to run the start stage as, when using
+	 * a runtime image. The user must exist in the runtime image.
+	 */
+	user?: string | null;
 }
 
 /** Configures aspects of the Docker scaffolding process. */
@@ -356,12 +398,22 @@ export interface PartialOwnersConfig {
 	 * When an object, the key is a path, and the value is a list of owners.
 	 */
 	paths?: PartialOwnersPaths | null;
+	/**
+	 * Bitbucket only. The number of reviewers to randomly assign from
+	 * the owners of each path, using the `Random()` condition.
+	 */
+	randomReviewers?: number | null;
 	/**
 	 * Bitbucket and GitLab only. The number of approvals required for the
 	 * request to be satisfied. For Bitbucket, utilizes the `Check()` condition.
 	 * For GitLab, marks the code owners section as required.
 	 */
 	requiredApprovals?: number | null;
+	/**
+	 * GitLab only. Name of the code owners section.
+	 * Defaults to the project ID.
+	 */
+	section?: string | null;
 }
 
 /** Expanded information about the project. */
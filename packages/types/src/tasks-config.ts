@@ -29,7 +29,7 @@ export type TaskMergeStrategy = 'append' | 'prepend' | 'preserve' | 'replace';
 export type TaskOperatingSystem = 'linux' | 'macos' | 'windows';
 
 /** The style in which task output will be printed to the console. */
-export type TaskOutputStyle = 'buffer' | 'buffer-only-failure' | 'hash' | 'none' | 'stream';
+export type TaskOutputStyle = 'buffer' | 'buffer-only-failure' | 'group' | 'hash' | 'none' | 'stream';
 
 /** A list of available shells on Unix. */
 export type TaskUnixShell =
@@ -178,6 +178,50 @@ export type TaskPreset = 'server' | 'watcher';
 /** The type of task. */
 export type TaskType = 'build' | 'run' | 'test';
 
+/** Configures an OCI image to assemble from the outputs of a task. */
+export interface TaskImageConfig {
+	/** The base image to build on top of, pinned by digest. */
+	base: string;
+	/** Default arguments for the entrypoint of the image. */
+	cmd: string[];
+	/** The entrypoint of the image. */
+	entrypoint: string[];
+	/**
+	 * A mapping of environment variables to set in the image,
+	 * in addition to those inherited from the base image.
+	 */
+	env: Record<string, string>;
+	/**
+	 * File path, relative from the project root, in which to write
+	 * the OCI image layout tarball to. Is automatically added as an output.
+	 *
+	 * @default 'image.tar'
+	 */
+	file: string;
+	/** A mapping of labels to set in the image. */
+	labels: Record<string, string>;
+	/**
+	 * The platform to select when the base image supports multiple platforms.
+	 *
+	 * @default 'linux/amd64'
+	 */
+	platform: string;
+	/**
+	 * An image reference (registry/name:tag) to push the image to after
+	 * it has been built.
+	 */
+	push: string | null;
+	/** The user (or UID) to run the image as. */
+	user: string | null;
+	/**
+	 * The directory within the image in which outputs are copied to,
+	 * and is used as the working directory.
+	 *
+	 * @default '/app'
+	 */
+	workdir: string;
+}
+
 /** Configures a task to be ran within the action pipeline. */
 export interface TaskConfig {
 	/**
@@ -206,6 +250,11 @@ export interface TaskConfig {
 	env: Record<string, string> | null;
 	/** Extends settings from a sibling task by ID. */
 	extends: string | null;
+	/**
+	 * Assembles an OCI image from the outputs of the task, on top of
+	 * a base image, instead of running a command.
+	 */
+	image: TaskImageConfig | null;
 	/**
 	 * Inputs and sources that will mark the task as affected when comparing
 	 * against touched files. When not provided, all files within the project
@@ -432,6 +481,50 @@ export interface PartialTaskOptionsConfig {
 	windowsShell?: TaskWindowsShell | null;
 }
 
+/** Configures an OCI image to assemble from the outputs of a task. */
+export interface PartialTaskImageConfig {
+	/** The base image to build on top of, pinned by digest. */
+	base?: string | null;
+	/** Default arguments for the entrypoint of the image. */
+	cmd?: string[] | null;
+	/** The entrypoint of the image. */
+	entrypoint?: string[] | null;
+	/**
+	 * A mapping of environment variables to set in the image,
+	 * in addition to those inherited from the base image.
+	 */
+	env?: Record<string, string> | null;
+	/**
+	 * File path, relative from the project root, in which to write
+	 * the OCI image layout tarball to. Is automatically added as an output.
+	 *
+	 * @default 'image.tar'
+	 */
+	file?: string | null;
+	/** A mapping of labels to set in the image. */
+	labels?: Record<string, string> | null;
+	/**
+	 * The platform to select when the base image supports multiple platforms.
+	 *
+	 * @default 'linux/amd64'
+	 */
+	platform?: string | null;
+	/**
+	 * An image reference (registry/name:tag) to push the image to after
+	 * it has been built.
+	 */
+	push?: string | null;
+	/** The user (or UID) to run the image as. */
+	user?: string | null;
+	/**
+	 * The directory within the image in which outputs are copied to,
+	 * and is used as the working directory.
+	 *
+	 * @default '/app'
+	 */
+	workdir?: string | null;
+}
+
 /** Configures a task to be ran within the action pipeline. */
 export interface PartialTaskConfig {
 	/**
@@ -460,6 +553,11 @@ export interface PartialTaskConfig {
 	env?: Record<string, string> | null;
 	/** Extends settings from a sibling task by ID. */
 	extends?: string | null;
+	/**
+	 * Assembles an OCI image from the outputs of the task, on top of
+	 * a base image, instead of running a command.
+	 */
+	image?: PartialTaskImageConfig | null;
 	/**
 	 * Inputs and sources that will mark the task as affected when comparing
 	 * against touched files. When not provided, all files within the project
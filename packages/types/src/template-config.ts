@@ -2,26 +2,65 @@
 
 /* eslint-disable */
 
+/**
+ * Injects the rendered content into an existing file, at an anchor,
+ * instead of overwriting the file.
+ */
+export interface TemplateFrontmatterInjectConfig {
+	/** Inserts the content after the first line that matches this regex. */
+	after: string | null;
+	/** Inserts the content before the first line that matches this regex. */
+	before: string | null;
+	/**
+	 * Inserts the content before the first line that contains this marker,
+	 * typically a comment, so that content can be injected repeatedly.
+	 */
+	marker: string | null;
+}
+
 /** Docs: https://moonrepo.dev/docs/config/template#frontmatter */
 export interface TemplateFrontmatterConfig {
 	/** @default 'https://moonrepo.dev/schemas/template-frontmatter.json' */
 	$schema?: string;
 	force: boolean;
+	inject: TemplateFrontmatterInjectConfig | null;
 	skip: boolean;
 	to: string | null;
 }
 
+/**
+ * Injects the rendered content into an existing file, at an anchor,
+ * instead of overwriting the file.
+ */
+export interface PartialTemplateFrontmatterInjectConfig {
+	/** Inserts the content after the first line that matches this regex. */
+	after?: string | null;
+	/** Inserts the content before the first line that matches this regex. */
+	before?: string | null;
+	/**
+	 * Inserts the content before the first line that contains this marker,
+	 * typically a comment, so that content can be injected repeatedly.
+	 */
+	marker?: string | null;
+}
+
 /** Docs: https://moonrepo.dev/docs/config/template#frontmatter */
 export interface PartialTemplateFrontmatterConfig {
 	/** @default 'https://moonrepo.dev/schemas/template-frontmatter.json' */
 	$schema?: string | null;
 	force?: boolean | null;
+	inject?: PartialTemplateFrontmatterInjectConfig | null;
 	skip?: boolean | null;
 	to?: string | null;
 }
 
 /** Configuration for a template variable. */
 export interface TemplateVariableBoolSetting {
+	/**
+	 * A Tera expression that computes the value of the variable from
+	 * other variables. Computed variables are never prompted for.
+	 */
+	computed: string | null;
 	/** The default value of the variable if none was provided. */
 	default: boolean;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
@@ -33,6 +72,11 @@ export interface TemplateVariableBoolSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'boolean';
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when: string | null;
 }
 
 export type TemplateVariableEnumDefault = string | string[];
@@ -60,14 +104,28 @@ export interface TemplateVariableEnumSetting {
 	type: 'enum';
 	/** List of acceptable values for this variable. */
 	values: TemplateVariableEnumValue[];
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when: string | null;
 }
 
 /** Configuration for a template variable. */
 export interface TemplateVariableNumberSetting {
+	/**
+	 * A Tera expression that computes the value of the variable from
+	 * other variables. Computed variables are never prompted for.
+	 */
+	computed: string | null;
 	/** The default value of the variable if none was provided. */
 	default: number;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal: boolean;
+	/** The maximum value allowed (inclusive). */
+	max: number | null;
+	/** The minimum value allowed (inclusive). */
+	min: number | null;
 	/** The order in which variables should be prompted for. */
 	order: number | null;
 	/** Prompt the user for a value when the generator is running. */
@@ -75,21 +133,38 @@ export interface TemplateVariableNumberSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'number';
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when: string | null;
 }
 
 /** Configuration for a template variable. */
 export interface TemplateVariableStringSetting {
+	/**
+	 * A Tera expression that computes the value of the variable from
+	 * other variables. Computed variables are never prompted for.
+	 */
+	computed: string | null;
 	/** The default value of the variable if none was provided. */
 	default: string;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal: boolean;
 	/** The order in which variables should be prompted for. */
 	order: number | null;
+	/** A regex pattern that the value must match. */
+	pattern: string | null;
 	/** Prompt the user for a value when the generator is running. */
 	prompt: string | null;
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'string';
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when: string | null;
 }
 
 export type TemplateVariable =
@@ -98,6 +173,48 @@ export type TemplateVariable =
 	| TemplateVariableNumberSetting
 	| TemplateVariableStringSetting;
 
+/** Configures a command or target to run as a hook. */
+export interface TemplateHookConfig {
+	/** A command to run within the destination directory. */
+	command: string | null;
+	/** A moon target to run. */
+	target: string | null;
+}
+
+/** A command, or a command or target object, to run as a hook. */
+export type TemplateHook = string | TemplateHookConfig;
+
+/** Configures hooks to run during the generation process. */
+export interface TemplateHooksConfig {
+	/**
+	 * A list of commands and targets to run, in order, after files
+	 * have been generated. Variables are rendered into each hook.
+	 */
+	afterGenerate: TemplateHook[];
+	/**
+	 * Sync all projects after files have been generated, and before
+	 * running hooks, so that new projects are immediately runnable.
+	 */
+	syncProjects: boolean;
+}
+
+/**
+ * Configures a test case for a template, which renders the template
+ * and compares the output against a snapshot.
+ */
+export interface TemplateTestConfig {
+	/**
+	 * A task target to run after the rendered output has been compared
+	 * against the snapshot. The output directory is passed as an argument.
+	 */
+	task: string | null;
+	/**
+	 * A mapping of variable values to render with. Variables that
+	 * are not defined will use their default value.
+	 */
+	variables: Record<string, unknown>;
+}
+
 /**
  * Configures a template and its files to be scaffolded.
  * Docs: https://moonrepo.dev/docs/config/template
@@ -114,8 +231,15 @@ export interface TemplateConfig {
 	destination: string | null;
 	/** Extends one or many other templates. */
 	extends: string | string[];
+	/** Hooks to run during the generation process. */
+	hooks: TemplateHooksConfig;
 	/** Overrides the ID of the template, instead of using the folder name. */
 	id: string | null;
+	/**
+	 * A mapping of test cases, keyed by name, to render and compare
+	 * against snapshots with `moon templates test`.
+	 */
+	tests: Record<string, TemplateTestConfig>;
 	/** A human-readable title for the template. */
 	title: string;
 	/**
@@ -127,6 +251,11 @@ export interface TemplateConfig {
 
 /** Configuration for a template variable. */
 export interface PartialTemplateVariableBoolSetting {
+	/**
+	 * A Tera expression that computes the value of the variable from
+	 * other variables. Computed variables are never prompted for.
+	 */
+	computed?: string | null;
 	/** The default value of the variable if none was provided. */
 	default?: boolean | null;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
@@ -138,6 +267,11 @@ export interface PartialTemplateVariableBoolSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'boolean' | null;
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when?: string | null;
 }
 
 export type PartialTemplateVariableEnumDefault = string | string[];
@@ -165,14 +299,28 @@ export interface PartialTemplateVariableEnumSetting {
 	type?: 'enum' | null;
 	/** List of acceptable values for this variable. */
 	values?: PartialTemplateVariableEnumValue[] | null;
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when?: string | null;
 }
 
 /** Configuration for a template variable. */
 export interface PartialTemplateVariableNumberSetting {
+	/**
+	 * A Tera expression that computes the value of the variable from
+	 * other variables. Computed variables are never prompted for.
+	 */
+	computed?: string | null;
 	/** The default value of the variable if none was provided. */
 	default?: number | null;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal?: boolean | null;
+	/** The maximum value allowed (inclusive). */
+	max?: number | null;
+	/** The minimum value allowed (inclusive). */
+	min?: number | null;
 	/** The order in which variables should be prompted for. */
 	order?: number | null;
 	/** Prompt the user for a value when the generator is running. */
@@ -180,21 +328,38 @@ export interface PartialTemplateVariableNumberSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'number' | null;
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when?: string | null;
 }
 
 /** Configuration for a template variable. */
 export interface PartialTemplateVariableStringSetting {
+	/**
+	 * A Tera expression that computes the value of the variable from
+	 * other variables. Computed variables are never prompted for.
+	 */
+	computed?: string | null;
 	/** The default value of the variable if none was provided. */
 	default?: string | null;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal?: boolean | null;
 	/** The order in which variables should be prompted for. */
 	order?: number | null;
+	/** A regex pattern that the value must match. */
+	pattern?: string | null;
 	/** Prompt the user for a value when the generator is running. */
 	prompt?: string | null;
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'string' | null;
+	/**
+	 * A Tera expression that must be truthy for the user to be prompted.
+	 * When falsy, the default value will be used.
+	 */
+	when?: string | null;
 }
 
 export type PartialTemplateVariable =
@@ -203,6 +368,48 @@ export type PartialTemplateVariable =
 	| PartialTemplateVariableNumberSetting
 	| PartialTemplateVariableStringSetting;
 
+/** Configures a command or target to run as a hook. */
+export interface PartialTemplateHookConfig {
+	/** A command to run within the destination directory. */
+	command?: string | null;
+	/** A moon target to run. */
+	target?: string | null;
+}
+
+/** A command, or a command or target object, to run as a hook. */
+export type PartialTemplateHook = string | PartialTemplateHookConfig;
+
+/** Configures hooks to run during the generation process. */
+export interface PartialTemplateHooksConfig {
+	/**
+	 * A list of commands and targets to run, in order, after files
+	 * have been generated. Variables are rendered into each hook.
+	 */
+	afterGenerate?: PartialTemplateHook[] | null;
+	/**
+	 * Sync all projects after files have been generated, and before
+	 * running hooks, so that new projects are immediately runnable.
+	 */
+	syncProjects?: boolean | null;
+}
+
+/**
+ * Configures a test case for a template, which renders the template
+ * and compares the output against a snapshot.
+ */
+export interface PartialTemplateTestConfig {
+	/**
+	 * A task target to run after the rendered output has been compared
+	 * against the snapshot. The output directory is passed as an argument.
+	 */
+	task?: string | null;
+	/**
+	 * A mapping of variable values to render with. Variables that
+	 * are not defined will use their default value.
+	 */
+	variables?: Record<string, unknown> | null;
+}
+
 /**
  * Configures a template and its files to be scaffolded.
  * Docs: https://moonrepo.dev/docs/config/template
@@ -219,8 +426,15 @@ export interface PartialTemplateConfig {
 	destination?: string | null;
 	/** Extends one or many other templates. */
 	extends?: string | string[] | null;
+	/** Hooks to run during the generation process. */
+	hooks?: PartialTemplateHooksConfig | null;
 	/** Overrides the ID of the template, instead of using the folder name. */
 	id?: string | null;
+	/**
+	 * A mapping of test cases, keyed by name, to render and compare
+	 * against snapshots with `moon templates test`.
+	 */
+	tests?: Record<string, PartialTemplateTestConfig> | null;
 	/** A human-readable title for the template. */
 	title?: string | null;
 	/**
@@ -3,6 +3,7 @@
 /* eslint-disable */
 
 import type { ExtendsFrom } from './common';
+import type { StackType } from './project-config';
 import type { PluginLocator } from './toolchain-config';
 
 /** How to order ownership rules within the generated file. */
@@ -10,6 +11,11 @@ export type CodeownersOrderBy = 'file-source' | 'project-name';
 
 /** Configures code ownership rules for generating a `CODEOWNERS` file. */
 export interface CodeownersConfig {
+	/**
+	 * Bitbucket only. A mapping of custom groups (prefixed with `@@@`),
+	 * to a list of user and normal groups. Available to all projects.
+	 */
+	customGroups: Record<string, string[]>;
 	/**
 	 * Paths that are applied globally to all projects. Can be relative
 	 * from the workspace root, or a wildcard match for any depth.
@@ -22,6 +28,11 @@ export interface CodeownersConfig {
 	 * @type {'file-source' | 'project-name'}
 	 */
 	orderBy: CodeownersOrderBy;
+	/**
+	 * Bitbucket only. The number of reviewers to randomly assign from
+	 * the owners of each path. This will be applied to all paths.
+	 */
+	randomReviewers: number | null;
 	/**
 	 * Bitbucket and GitLab only. The number of approvals required for the
 	 * request to be satisfied. This will be applied to all paths.
@@ -34,8 +45,44 @@ export interface CodeownersConfig {
 	syncOnRun: boolean;
 }
 
+/**
+ * Enforces a single version policy for external dependencies,
+ * as declared in each project's manifest (`package.json`,
+ * `Cargo.toml`, `requirements.txt`).
+ */
+export interface ConstraintsDependencyVersionsConfig {
+	/**
+	 * Enforces that each external dependency is declared
+	 * with the same version across all projects.
+	 */
+	enabled: boolean;
+	/**
+	 * A mapping of dependency names, to additional versions that are
+	 * allowed to differ. When the list is empty, all versions are allowed.
+	 */
+	exceptions: Record<string, string[]>;
+}
+
+/**
+ * Denies dependencies between projects. Projects are matched by ID
+ * or source path glob, or by tag when prefixed with `#`.
+ */
+export interface ConstraintsDenyRule {
+	/** Projects that must not depend on the `to` projects. */
+	from: string;
+	/**
+	 * Projects that must not be depended on, either directly
+	 * or transitively, by the `from` projects.
+	 */
+	to: string;
+}
+
 /** Configures boundaries and constraints between projects. */
 export interface ConstraintsConfig {
+	/** Enforces a single version policy for external dependencies. */
+	dependencyVersions: ConstraintsDependencyVersionsConfig;
+	/** A list of rules that deny dependencies between projects. */
+	deny: ConstraintsDenyRule[];
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `type` setting.
@@ -43,6 +90,21 @@ export interface ConstraintsConfig {
 	 * @default true
 	 */
 	enforceProjectTypeRelationships?: boolean;
+	/**
+	 * An ordered list of tags that represent architectural layers, from
+	 * the highest to the lowest. A project may only depend on projects
+	 * within the same or a lower layer.
+	 */
+	layers: string[];
+	/** The maximum number of projects that a project can depend on. */
+	maxDependencies: number | null;
+	/** The maximum number of projects that can depend on a project. */
+	maxDependents: number | null;
+	/**
+	 * Denies dependencies on projects of specific stacks, based on each
+	 * project's `tags` setting. Requires a mapping of tags, to denied stacks.
+	 */
+	tagDeniedStacks: Record<string, StackType[]>;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `tags` setting. Requires a mapping of tags, to acceptable tags.
@@ -131,6 +193,11 @@ export interface ExperimentsConfig {
 export interface ExtensionConfig {
 	/** Arbitrary configuration that'll be passed to the WASM plugin. */
 	config: Record<string, unknown>;
+	/**
+	 * Subscribe the extension to action pipeline events, which will
+	 * be passed to the plugin's `on_pipeline_event` function.
+	 */
+	pipelineEvents: boolean;
 	/** Location of the WASM plugin to use. */
 	plugin: PluginLocator | null;
 }
@@ -192,10 +259,42 @@ export interface HasherConfig {
 	warnOnMissingInputs?: boolean;
 }
 
+/** Configures an individual webhook endpoint. */
+export interface NotifierWebhookConfig {
+	/**
+	 * List of event types (e.g. `task.ran`) to send to the endpoint.
+	 * If empty, all events will be sent.
+	 */
+	events: string[];
+	/** Only send events that contain an error, like a failed task. */
+	failuresOnly: boolean;
+	/** Custom headers to include in every request. */
+	headers: Record<string, string>;
+	/**
+	 * Maximum number of times to retry a failed request,
+	 * using an exponential backoff between attempts. Cannot exceed 10.
+	 *
+	 * @default 3
+	 */
+	retryCount?: number;
+	/**
+	 * Name of an environment variable that contains a secret, which will
+	 * be used to sign the request body with HMAC-SHA256.
+	 */
+	secretEnv: string | null;
+	/** A secure URL in which to send webhooks to. */
+	url: string | null;
+}
+
 /** Configures how and where notifications are sent. */
 export interface NotifierConfig {
 	/** A secure URL in which to send webhooks to. */
 	webhookUrl: string | null;
+	/**
+	 * A list of webhook endpoints, each with their own
+	 * filtering, signing, and retry settings.
+	 */
+	webhooks: NotifierWebhookConfig[];
 }
 
 /** Configures projects in the workspace, using both globs and explicit source paths. */
@@ -323,7 +422,7 @@ export interface RemoteConfig {
 export type VcsHookFormat = 'bash' | 'native';
 
 /** The VCS being utilized by the repository. */
-export type VcsManager = 'git';
+export type VcsManager = 'git' | 'jujutsu' | 'sapling';
 
 /**
  * The upstream version control provider, where the repository
@@ -333,6 +432,18 @@ export type VcsProvider = 'bitbucket' | 'github' | 'gitlab' | 'other';
 
 /** Configures the version control system (VCS). */
 export interface VcsConfig {
+	/**
+	 * Automatically fetch more history in a shallow checkout when the
+	 * merge base of the compared revisions cannot be found.
+	 */
+	autoDeepen: boolean;
+	/**
+	 * The maximum number of times to deepen a shallow checkout,
+	 * doubling the fetched depth on each attempt. Must be between 1 and 20.
+	 *
+	 * @default 5
+	 */
+	autoDeepenLimit?: number;
 	/**
 	 * The default branch / base.
 	 *
@@ -352,7 +463,7 @@ export interface VcsConfig {
 	 * The VCS client being utilized by the repository.
 	 *
 	 * @default 'git'
-	 * @type {'git'}
+	 * @type {'git' | 'jujutsu' | 'sapling'}
 	 */
 	manager: VcsManager;
 	/**
@@ -421,6 +532,11 @@ export interface WorkspaceConfig {
 
 /** Configures code ownership rules for generating a `CODEOWNERS` file. */
 export interface PartialCodeownersConfig {
+	/**
+	 * Bitbucket only. A mapping of custom groups (prefixed with `@@@`),
+	 * to a list of user and normal groups. Available to all projects.
+	 */
+	customGroups?: Record<string, string[]> | null;
 	/**
 	 * Paths that are applied globally to all projects. Can be relative
 	 * from the workspace root, or a wildcard match for any depth.
@@ -432,6 +548,11 @@ export interface PartialCodeownersConfig {
 	 * @default 'file-source'
 	 */
 	orderBy?: CodeownersOrderBy | null;
+	/**
+	 * Bitbucket only. The number of reviewers to randomly assign from
+	 * the owners of each path. This will be applied to all paths.
+	 */
+	randomReviewers?: number | null;
 	/**
 	 * Bitbucket and GitLab only. The number of approvals required for the
 	 * request to be satisfied. This will be applied to all paths.
@@ -444,8 +565,44 @@ export interface PartialCodeownersConfig {
 	syncOnRun?: boolean | null;
 }
 
+/**
+ * Enforces a single version policy for external dependencies,
+ * as declared in each project's manifest (`package.json`,
+ * `Cargo.toml`, `requirements.txt`).
+ */
+export interface PartialConstraintsDependencyVersionsConfig {
+	/**
+	 * Enforces that each external dependency is declared
+	 * with the same version across all projects.
+	 */
+	enabled?: boolean | null;
+	/**
+	 * A mapping of dependency names, to additional versions that are
+	 * allowed to differ. When the list is empty, all versions are allowed.
+	 */
+	exceptions?: Record<string, string[]> | null;
+}
+
+/**
+ * Denies dependencies between projects. Projects are matched by ID
+ * or source path glob, or by tag when prefixed with `#`.
+ */
+export interface PartialConstraintsDenyRule {
+	/** Projects that must not depend on the `to` projects. */
+	from?: string | null;
+	/**
+	 * Projects that must not be depended on, either directly
+	 * or transitively, by the `from` projects.
+	 */
+	to?: string | null;
+}
+
 /** Configures boundaries and constraints between projects. */
 export interface PartialConstraintsConfig {
+	/** Enforces a single version policy for external dependencies. */
+	dependencyVersions?: PartialConstraintsDependencyVersionsConfig | null;
+	/** A list of rules that deny dependencies between projects. */
+	deny?: PartialConstraintsDenyRule[] | null;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `type` setting.
@@ -453,6 +610,21 @@ export interface PartialConstraintsConfig {
 	 * @default true
 	 */
 	enforceProjectTypeRelationships?: boolean | null;
+	/**
+	 * An ordered list of tags that represent architectural layers, from
+	 * the highest to the lowest. A project may only depend on projects
+	 * within the same or a lower layer.
+	 */
+	layers?: string[] | null;
+	/** The maximum number of projects that a project can depend on. */
+	maxDependencies?: number | null;
+	/** The maximum number of projects that can depend on a project. */
+	maxDependents?: number | null;
+	/**
+	 * Denies dependencies on projects of specific stacks, based on each
+	 * project's `tags` setting. Requires a mapping of tags, to denied stacks.
+	 */
+	tagDeniedStacks?: Record<string, StackType[]> | null;
 	/**
 	 * Enforces relationships between projects based on each project's
 	 * `tags` setting. Requires a mapping of tags, to acceptable tags.
@@ -541,6 +713,11 @@ export interface PartialExperimentsConfig {
 export interface PartialExtensionConfig {
 	/** Arbitrary configuration that'll be passed to the WASM plugin. */
 	config?: Record<string, unknown> | null;
+	/**
+	 * Subscribe the extension to action pipeline events, which will
+	 * be passed to the plugin's `on_pipeline_event` function.
+	 */
+	pipelineEvents?: boolean | null;
 	/** Location of the WASM plugin to use. */
 	plugin?: PluginLocator | null;
 }
@@ -594,10 +771,42 @@ export interface PartialHasherConfig {
 	warnOnMissingInputs?: boolean | null;
 }
 
+/** Configures an individual webhook endpoint. */
+export interface PartialNotifierWebhookConfig {
+	/**
+	 * List of event types (e.g. `task.ran`) to send to the endpoint.
+	 * If empty, all events will be sent.
+	 */
+	events?: string[] | null;
+	/** Only send events that contain an error, like a failed task. */
+	failuresOnly?: boolean | null;
+	/** Custom headers to include in every request. */
+	headers?: Record<string, string> | null;
+	/**
+	 * Maximum number of times to retry a failed request,
+	 * using an exponential backoff between attempts. Cannot exceed 10.
+	 *
+	 * @default 3
+	 */
+	retryCount?: number | null;
+	/**
+	 * Name of an environment variable that contains a secret, which will
+	 * be used to sign the request body with HMAC-SHA256.
+	 */
+	secretEnv?: string | null;
+	/** A secure URL in which to send webhooks to. */
+	url?: string | null;
+}
+
 /** Configures how and where notifications are sent. */
 export interface PartialNotifierConfig {
 	/** A secure URL in which to send webhooks to. */
 	webhookUrl?: string | null;
+	/**
+	 * A list of webhook endpoints, each with their own
+	 * filtering, signing, and retry settings.
+	 */
+	webhooks?: PartialNotifierWebhookConfig[] | null;
 }
 
 /** Configures projects in the workspace, using both globs and explicit source paths. */
@@ -723,6 +932,18 @@ export interface PartialRemoteConfig {
 
 /** Configures the version control system (VCS). */
 export interface PartialVcsConfig {
+	/**
+	 * Automatically fetch more history in a shallow checkout when the
+	 * merge base of the compared revisions cannot be found.
+	 */
+	autoDeepen?: boolean | null;
+	/**
+	 * The maximum number of times to deepen a shallow checkout,
+	 * doubling the fetched depth on each attempt. Must be between 1 and 20.
+	 *
+	 * @default 5
+	 */
+	autoDeepenLimit?: number | null;
 	/**
 	 * The default branch / base.
 	 *
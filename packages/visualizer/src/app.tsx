@@ -1,8 +1,11 @@
 /* eslint-disable node/no-unsupported-features/node-builtins */
 
 import './app.css';
-import { useState } from 'preact/hooks';
+import { useCallback, useEffect, useState } from 'preact/hooks';
 import { Graph } from './components/Graph';
+import { NodeDetails } from './components/NodeDetails';
+import { loadGraph, loadNodeDetails } from './helpers/api';
+import type { GraphFilters, GraphInfo, NodeDetails as Details } from './helpers/types';
 
 const SUPPORTED_LAYOUTS = ['dagre', 'klay', 'breadthfirst', 'grid'];
 
@@ -24,10 +27,60 @@ function setLayoutIntoQuery(layout: string) {
 	window.history.pushState(null, '', `${window.location.pathname}?${query}`);
 }
 
+const INPUT_CLASS = 'border border-slate-400 rounded bg-slate-600 text-slate-50 p-1';
+
 export function App() {
 	const [layout, setLayout] = useState(getLayoutFromQuery());
+	const [data, setData] = useState<GraphInfo>(() => JSON.parse(window.GRAPH_DATA) as GraphInfo);
+	const [error, setError] = useState('');
+	const [query, setQuery] = useState('');
+	const [filters, setFilters] = useState<GraphFilters>({
+		affected: false,
+		depth: 1,
+		focus: null,
+		query: '',
+	});
+	const [selected, setSelected] = useState<number | null>(null);
+	const [details, setDetails] = useState<Details | null>(null);
+
+	// Filter the graph on the server whenever the filters change
+	useEffect(() => {
+		if (!filters.affected && filters.focus === null && !filters.query) {
+			setData(JSON.parse(window.GRAPH_DATA) as GraphInfo);
+			setError('');
+
+			return;
+		}
+
+		loadGraph(filters)
+			.then((result) => {
+				setData(result);
+				setError('');
+			})
+			.catch((error_: Error) => {
+				setError(error_.message);
+			});
+	}, [filters]);
+
+	useEffect(() => {
+		if (selected === null) {
+			setDetails(null);
 
-	function handleChange(event: Event) {
+			return;
+		}
+
+		loadNodeDetails(selected)
+			.then(setDetails)
+			.catch(() => {
+				setDetails(null);
+			});
+	}, [selected]);
+
+	const handleSelect = useCallback((id: number) => {
+		setSelected(id);
+	}, []);
+
+	function handleLayoutChange(event: Event) {
 		const target = event.target as HTMLSelectElement;
 		const newLayout = target.value;
 
@@ -35,27 +88,95 @@ export function App() {
 		setLayoutIntoQuery(newLayout);
 	}
 
+	function handleQuerySubmit(event: Event) {
+		event.preventDefault();
+		setFilters((prev) => ({ ...prev, query }));
+	}
+
 	return (
-		<main>
-			<div className="p-4 flex items-center float-right">
-				<span className="inline-block mr-1">Layout:</span>
-
-				<select
-					className="border border-slate-400 rounded bg-slate-600 text-slate-50 p-1"
-					value={layout}
-					onChange={handleChange}
-				>
-					{SUPPORTED_LAYOUTS.map((value) => (
-						<option key={value} value={value}>
-							{value}
-						</option>
-					))}
-				</select>
+		<main className="relative">
+			<div className="p-4 flex items-center float-right gap-4">
+				<form className="flex items-center" onSubmit={handleQuerySubmit}>
+					<input
+						className={`${INPUT_CLASS} w-80`}
+						placeholder="Filter with MQL, e.g. language=rust"
+						value={query}
+						onInput={(event) => {
+							setQuery((event.target as HTMLInputElement).value);
+						}}
+					/>
+				</form>
+
+				{filters.focus !== null && (
+					<label className="flex items-center">
+						<span className="inline-block mr-1">Depth:</span>
+						<input
+							className={`${INPUT_CLASS} w-16`}
+							type="number"
+							min={1}
+							value={filters.depth}
+							onChange={(event) => {
+								const depth = Number((event.target as HTMLInputElement).value) || 1;
+
+								setFilters((prev) => ({ ...prev, depth }));
+							}}
+						/>
+						<button
+							type="button"
+							className="ml-2 rounded bg-slate-600 px-2 py-1"
+							onClick={() => {
+								setFilters((prev) => ({ ...prev, focus: null }));
+							}}
+						>
+							Unfocus
+						</button>
+					</label>
+				)}
+
+				<label className="flex items-center">
+					<input
+						className="mr-1"
+						type="checkbox"
+						checked={filters.affected}
+						onChange={(event) => {
+							const affected = (event.target as HTMLInputElement).checked;
+
+							setFilters((prev) => ({ ...prev, affected }));
+						}}
+					/>
+					<span>Highlight affected</span>
+				</label>
+
+				<label className="flex items-center">
+					<span className="inline-block mr-1">Layout:</span>
+
+					<select className={INPUT_CLASS} value={layout} onChange={handleLayoutChange}>
+						{SUPPORTED_LAYOUTS.map((value) => (
+							<option key={value} value={value}>
+								{value}
+							</option>
+						))}
+					</select>
+				</label>
 			</div>
 
 			<h2 className="m-0 p-4 text-3xl font-extrabold sm:text-4xl">{window.PAGE_TITLE}</h2>
 
-			<Graph layout={layout} />
+			{error && <p className="px-4 text-red-400">{error}</p>}
+
+			{details && selected !== null && (
+				<NodeDetails
+					details={details}
+					onClose={() => {
+						setSelected(null);
+					}}
+					onFocus={() => {
+						setFilters((prev) => ({ ...prev, focus: selected }));
+					}}
+				/>
+			)}
+
+			<Graph data={data} layout={layout} onSelect={handleSelect} />
 		</main>
 	);
 }
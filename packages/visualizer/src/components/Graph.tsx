@@ -3,17 +3,29 @@ import { render } from '../helpers/render';
 import type { GraphInfo } from '../helpers/types';
 
 export interface GraphProps {
+	data: GraphInfo;
 	layout: string;
+	onSelect: (id: number) => void;
 }
 
-export function Graph({ layout }: GraphProps) {
+export function Graph({ data, layout, onSelect }: GraphProps) {
 	const graphRef = useRef<HTMLDivElement>(null);
 
 	useEffect(() => {
-		if (graphRef.current) {
-			render(graphRef.current, JSON.parse(window.GRAPH_DATA) as GraphInfo, layout);
+		if (!graphRef.current) {
+			return undefined;
 		}
-	}, [layout]);
+
+		const cy = render(graphRef.current, data, layout);
+
+		cy.on('tap', 'node', (event) => {
+			onSelect(Number(event.target.id()));
+		});
+
+		return () => {
+			cy.destroy();
+		};
+	}, [data, layout, onSelect]);
 
 	return <div id="graph" ref={graphRef} style={{ height: '80vh', width: '100%' }} />;
 }
@@ -0,0 +1,86 @@
+import type { NodeDetails as Details, ProjectDetails, TaskDetails } from '../helpers/types';
+
+function Field({ label, value }: { label: string; value: unknown }) {
+	if (value === null || value === undefined || (Array.isArray(value) && value.length === 0)) {
+		return null;
+	}
+
+	return (
+		<div className="mb-2">
+			<div className="text-xs uppercase text-slate-400">{label}</div>
+			<div className="break-all font-mono text-sm">
+				{Array.isArray(value)
+					? value.map((item) => <div key={String(item)}>{String(item)}</div>)
+					: typeof value === 'object'
+						? JSON.stringify(value, null, 2)
+						: String(value)}
+			</div>
+		</div>
+	);
+}
+
+function ProjectFields({ project }: { project: ProjectDetails }) {
+	return (
+		<>
+			<Field label="Project" value={project.id} />
+			<Field label="Alias" value={project.alias} />
+			<Field label="Source" value={project.source} />
+			<Field label="Type" value={project.type} />
+			<Field label="Language" value={project.language} />
+			<Field label="Stack" value={project.stack} />
+			<Field label="Tags" value={project.tags} />
+			<Field label="Dependencies" value={project.dependencies} />
+			<Field label="Tasks" value={project.tasks} />
+			<Field label="Metadata" value={project.metadata} />
+		</>
+	);
+}
+
+function TaskFields({ task }: { task: TaskDetails }) {
+	return (
+		<>
+			<Field label="Target" value={task.target} />
+			<Field label="Description" value={task.description} />
+			<Field label="Type" value={task.type} />
+			<Field
+				label="Command"
+				value={task.script ?? [task.command, ...task.args].join(' ')}
+			/>
+			<Field label="Toolchains" value={task.toolchains} />
+			<Field label="Dependencies" value={task.deps} />
+			<Field label="Inputs" value={task.inputs} />
+			<Field label="Outputs" value={task.outputs} />
+		</>
+	);
+}
+
+export interface NodeDetailsProps {
+	details: Details;
+	onClose: () => void;
+	onFocus: () => void;
+}
+
+export function NodeDetails({ details, onClose, onFocus }: NodeDetailsProps) {
+	return (
+		<aside className="absolute right-4 top-20 z-10 max-h-[75vh] w-96 overflow-auto rounded border border-slate-600 bg-slate-700 p-4">
+			<div className="mb-3 flex items-center justify-between">
+				<button type="button" className="rounded bg-slate-600 px-2 py-1" onClick={onFocus}>
+					Focus
+				</button>
+				<button type="button" className="px-2 py-1" onClick={onClose}>
+					✕
+				</button>
+			</div>
+
+			{details.kind === 'action' && (
+				<>
+					<Field label="Action" value={details.label} />
+					{details.task && <TaskFields task={details.task} />}
+					{details.project && <ProjectFields project={details.project} />}
+				</>
+			)}
+			{details.kind === 'project' && <ProjectFields project={details} />}
+			{details.kind === 'task' && <TaskFields task={details} />}
+		</aside>
+	);
+}
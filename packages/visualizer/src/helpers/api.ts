@@ -0,0 +1,35 @@
+import type { GraphFilters, GraphInfo, NodeDetails } from './types';
+
+async function request<T>(url: string): Promise<T> {
+	const response = await fetch(url);
+	const data = (await response.json()) as T | { error: string };
+
+	if (!response.ok || (data && typeof data === 'object' && 'error' in data)) {
+		throw new Error((data as { error: string }).error);
+	}
+
+	return data as T;
+}
+
+export function loadGraph(filters: GraphFilters): Promise<GraphInfo> {
+	const params = new URLSearchParams();
+
+	if (filters.query) {
+		params.set('query', filters.query);
+	}
+
+	if (filters.focus !== null) {
+		params.set('focus', String(filters.focus));
+		params.set('depth', String(filters.depth));
+	}
+
+	if (filters.affected) {
+		params.set('affected', 'true');
+	}
+
+	return request(`/graph-data?${params}`);
+}
+
+export function loadNodeDetails(id: number): Promise<NodeDetails> {
+	return request(`/node-details?id=${id}`);
+}
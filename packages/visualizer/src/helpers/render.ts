@@ -44,7 +44,12 @@ function getShortDepLabel(label: string) {
 
 export function render(element: HTMLElement, data: GraphInfo, layout: string) {
 	const nodes = data.nodes.map((n) => ({
-		data: { id: n.id.toString(), label: n.label, type: getActionType(n.label) },
+		data: {
+			affected: n.affected ?? false,
+			id: n.id.toString(),
+			label: n.label,
+			type: getActionType(n.label),
+		},
 	}));
 
 	const edges = data.edges.map((e) => ({
@@ -149,6 +154,13 @@ export function render(element: HTMLElement, data: GraphInfo, layout: string) {
 					width: 120,
 				},
 			},
+			{
+				selector: 'node[?affected]',
+				style: {
+					'border-color': '#facc15',
+					'border-width': 6,
+				},
+			},
 		],
 	});
 }
@@ -1,6 +1,7 @@
 export interface GraphNode {
 	id: number;
 	label: string;
+	affected?: boolean;
 }
 
 export interface GraphEdge {
@@ -14,3 +15,41 @@ export interface GraphInfo {
 	nodes: GraphNode[];
 	edges: GraphEdge[];
 }
+
+export interface GraphFilters {
+	affected: boolean;
+	depth: number;
+	focus: number | null;
+	query: string;
+}
+
+export interface ProjectDetails {
+	alias: string | null;
+	dependencies: string[];
+	id: string;
+	language: string;
+	metadata: Record<string, unknown> | null;
+	source: string;
+	stack: string;
+	tags: string[];
+	tasks: string[];
+	type: string;
+}
+
+export interface TaskDetails {
+	args: string[];
+	command: string;
+	deps: string[];
+	description: string | null;
+	inputs: string[];
+	outputs: string[];
+	script: string | null;
+	target: string;
+	toolchains: string[];
+	type: string;
+}
+
+export type NodeDetails =
+	| { kind: 'action'; label: string; project: ProjectDetails | null; task: TaskDetails | null }
+	| ({ kind: 'project' } & ProjectDetails)
+	| ({ kind: 'task' } & TaskDetails);
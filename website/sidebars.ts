@@ -149,6 +149,7 @@ const sidebars: SidebarsConfig = {
 					items: [
 						'commands/query/hash',
 						'commands/query/hash-diff',
+						'commands/query/owners',
 						'commands/query/projects',
 						'commands/query/tasks',
 						'commands/query/touched-files',